@@ -4,11 +4,14 @@ svm_extern::include_buffer_vmcalls!();
 svm_extern::include_host_ctx_vmcalls!();
 svm_extern::include_register_vmcalls!();
 
-// Host imports
+#[link(wasm_import_module = "svm")]
 extern "C" {
-    /// Returns the current app's balance.
-    fn host_get_my_balance() -> u64;
+    /// Returns the amount of coins transferred along with the running transaction.
+    fn svm_value() -> u64;
+}
 
+// Host imports
+extern "C" {
     /// Transferring `amount` coins from app's account
     /// into address given via register `{reg_bits}:{reg_idx}`
     fn host_transfer(amount: u64, reg_bits: u32, reg_idx: u32);
@@ -42,9 +42,9 @@ static buf_idx: u32 = 0;
 /// called as part of app's spawning.
 #[no_mangle]
 pub extern "C" fn init(is_multisig: u32, coins: u32, period_sec: u32, lockup_sec: u32) {
-    /// We need to make sure that `coins` have been locked into tha app.
+    /// We need to make sure that `coins` have been transferred along with the spawning transaction.
     /// Otherwise, spawning the wallet should fail.
-    assert_eq!(coins as u64, unsafe { host_get_my_balance() });
+    assert_eq!(coins as u64, unsafe { svm_value() });
 
     write_pub_keys(is_multisig);
     write_first_layer();
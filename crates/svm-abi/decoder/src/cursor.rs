@@ -71,9 +71,9 @@ impl Cursor {
     ///
     /// In case there are less then `nbytes` left bytes - returns `None`.
     pub fn read_bytes(&mut self, nbytes: usize) -> Option<*const u8> {
-        let last_byte_off = self.offset + nbytes - 1;
+        let end = self.offset.checked_add(nbytes)?;
 
-        if last_byte_off >= self.len() {
+        if end > self.len() {
             return None;
         }
 
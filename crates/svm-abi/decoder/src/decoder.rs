@@ -1,13 +1,14 @@
 use svm_abi_layout::layout;
 
 use svm_sdk_types::value::{self, Primitive, Value};
-use svm_sdk_types::{Address, Amount};
+use svm_sdk_types::{Address, Amount, U128, U256};
 
 use crate::Cursor;
 
 extern crate alloc;
 
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::vec::Vec;
 
 #[derive(Debug)]
@@ -15,6 +16,9 @@ pub enum TypeError {
     MissingTypeKind,
 
     InvalidTypeKind(u8),
+
+    /// Arrays (or tuples) are nested deeper than `Decoder::MAX_DEPTH`.
+    TooDeep,
 }
 
 enum TypeKind {
@@ -30,12 +34,23 @@ enum TypeKind {
     U32,
     I64,
     U64,
+    U128,
+    U256,
     Array,
+    Tuple,
+    Bytes,
+    String,
 }
 
 #[derive(Debug)]
 pub enum ValueError {
     NotEnoughBytes,
+
+    /// A length `varuint` is longer than `MAX_VARUINT_LEN` bytes (or doesn't fit a `u32`).
+    InvalidVarUint,
+
+    /// A `String` isn't valid UTF-8.
+    InvalidUtf8,
 }
 
 /// Denotes a decode error
@@ -72,6 +87,9 @@ macro_rules! decode_fixed_primitive {
 pub struct Decoder;
 
 impl Decoder {
+    /// The maximum nesting level of arrays and tuples.
+    pub const MAX_DEPTH: usize = 32;
+
     /// New instance
     pub fn new() -> Self {
         Self {}
@@ -80,6 +98,14 @@ impl Decoder {
     /// Decodes the next `sdk_types::Value` (primitive or composite) and returns it.
     /// Returns `DecodeError` when decode fails.
     pub fn decode_value<'a>(&self, cursor: &mut Cursor) -> Result<Value<'a>, DecodeError> {
+        self.decode_nested_value(cursor, 0)
+    }
+
+    fn decode_nested_value<'a>(
+        &self,
+        cursor: &mut Cursor,
+        depth: usize,
+    ) -> Result<Value<'a>, DecodeError> {
         assert_no_eof!(cursor);
 
         let kind = self.read_type_kind(cursor)?;
@@ -97,7 +123,12 @@ impl Decoder {
             TypeKind::U32 => self.decode_u32(cursor)?.into(),
             TypeKind::I64 => self.decode_i64(cursor)?.into(),
             TypeKind::U64 => self.decode_u64(cursor)?.into(),
-            TypeKind::Array => self.decode_array(cursor)?,
+            TypeKind::U128 => self.decode_u128(cursor)?.into(),
+            TypeKind::U256 => self.decode_u256(cursor)?.into(),
+            TypeKind::Array => self.decode_array(cursor, depth + 1)?,
+            TypeKind::Tuple => self.decode_tuple(cursor, depth + 1)?,
+            TypeKind::Bytes => self.decode_bytes(cursor)?,
+            TypeKind::String => self.decode_string(cursor)?,
         };
 
         Ok(value)
@@ -228,9 +259,39 @@ impl Decoder {
         Ok(num)
     }
 
-    fn decode_array<'a>(&self, cursor: &mut Cursor) -> Result<Value<'a>, DecodeError> {
+    fn decode_u128(&self, cursor: &mut Cursor) -> Result<U128, DecodeError> {
+        let byte = self.read_byte(cursor)?;
+
+        debug_assert_eq!(byte, layout::U128);
+
+        let mut bytes = [0; U128::BYTES];
+        self.read_fixed(cursor, &mut bytes)?;
+
+        Ok(U128::from_be_bytes(bytes))
+    }
+
+    fn decode_u256(&self, cursor: &mut Cursor) -> Result<U256, DecodeError> {
+        let byte = self.read_byte(cursor)?;
+
+        debug_assert_eq!(byte, layout::U256);
+
+        let mut bytes = [0; U256::BYTES];
+        self.read_fixed(cursor, &mut bytes)?;
+
+        Ok(U256::from_be_bytes(bytes))
+    }
+
+    fn decode_array<'a>(
+        &self,
+        cursor: &mut Cursor,
+        depth: usize,
+    ) -> Result<Value<'a>, DecodeError> {
         assert_no_eof!(cursor);
 
+        if depth > Self::MAX_DEPTH {
+            return Err(DecodeError::Type(TypeError::TooDeep));
+        }
+
         let byte = self.read_byte(cursor)?;
         let nitems = match byte {
             layout::ARR_0 => 0,
@@ -240,21 +301,127 @@ impl Decoder {
             layout::ARR_4 => 4,
             layout::ARR_5 => 5,
             layout::ARR_6 => 6,
-            layout::ARR_0_255 => self.read_byte(cursor)?,
+            layout::ARR_0_255 => self.read_byte(cursor)? as usize,
+            layout::ARR_N => self.read_varuint(cursor)?,
+            _ => unreachable!(),
+        };
+
+        let values = self.decode_items(cursor, nitems, depth)?;
+
+        let values: Value = values.into();
+        Ok(values)
+    }
+
+    fn decode_tuple<'a>(
+        &self,
+        cursor: &mut Cursor,
+        depth: usize,
+    ) -> Result<Value<'a>, DecodeError> {
+        assert_no_eof!(cursor);
+
+        if depth > Self::MAX_DEPTH {
+            return Err(DecodeError::Type(TypeError::TooDeep));
+        }
+
+        let byte = self.read_byte(cursor)?;
+        let nfields = match byte {
+            layout::TUPLE_0 => 0,
+            layout::TUPLE_1 => 1,
+            layout::TUPLE_2 => 2,
+            layout::TUPLE_3 => 3,
+            layout::TUPLE_4 => 4,
+            layout::TUPLE_5 => 5,
+            layout::TUPLE_6 => 6,
+            layout::TUPLE_N => self.read_varuint(cursor)?,
             _ => unreachable!(),
         };
 
-        let mut values: Vec<Value> = Vec::with_capacity(nitems as usize);
+        let fields = self.decode_items(cursor, nfields, depth)?;
+
+        Ok(Value::tuple(fields))
+    }
+
+    fn decode_items<'a>(
+        &self,
+        cursor: &mut Cursor,
+        nitems: usize,
+        depth: usize,
+    ) -> Result<Vec<Value<'a>>, DecodeError> {
+        // each item takes at least one byte, so we don't trust `nitems` for the allocation
+        let remaining = cursor.len() - cursor.offset.min(cursor.len());
+        let mut values: Vec<Value> = Vec::with_capacity(nitems.min(remaining));
 
         for _ in 0..nitems {
-            let value = self.decode_value(cursor)?;
+            let value = self.decode_nested_value(cursor, depth)?;
             values.push(value);
         }
 
-        let values: Value = values.into();
         Ok(values)
     }
 
+    fn decode_bytes<'a>(&self, cursor: &mut Cursor) -> Result<Value<'a>, DecodeError> {
+        let byte = self.read_byte(cursor)?;
+
+        debug_assert_eq!(byte, layout::BYTES);
+
+        let bytes = self.read_blob(cursor)?;
+        let value = Value::Composite(value::Composite::Bytes(bytes));
+
+        Ok(value)
+    }
+
+    fn decode_string<'a>(&self, cursor: &mut Cursor) -> Result<Value<'a>, DecodeError> {
+        let byte = self.read_byte(cursor)?;
+
+        debug_assert_eq!(byte, layout::STRING);
+
+        let bytes = self.read_blob(cursor)?;
+        let s =
+            String::from_utf8(bytes).map_err(|_| DecodeError::Value(ValueError::InvalidUtf8))?;
+
+        Ok(s.into())
+    }
+
+    /// Reads a `varuint` length followed by that many bytes.
+    fn read_blob(&self, cursor: &mut Cursor) -> Result<Vec<u8>, DecodeError> {
+        let len = self.read_varuint(cursor)?;
+        let ptr = self.read_bytes(cursor, len)?;
+
+        let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Fills `buf` with the next `buf.len()` bytes.
+    fn read_fixed(&self, cursor: &mut Cursor, buf: &mut [u8]) -> Result<(), DecodeError> {
+        let ptr = self.read_bytes(cursor, buf.len())?;
+        let bytes = unsafe { core::slice::from_raw_parts(ptr, buf.len()) };
+
+        buf.copy_from_slice(bytes);
+
+        Ok(())
+    }
+
+    fn read_varuint(&self, cursor: &mut Cursor) -> Result<usize, DecodeError> {
+        let mut value: u64 = 0;
+
+        for i in 0..layout::MAX_VARUINT_LEN {
+            let byte = self.read_byte(cursor)?;
+
+            value |= ((byte & 0x7F) as u64) << (7 * i);
+
+            if byte & 0x80 == 0 {
+                if value > core::u32::MAX as u64 {
+                    break;
+                }
+
+                return Ok(value as usize);
+            }
+        }
+
+        Err(DecodeError::Value(ValueError::InvalidVarUint))
+    }
+
     #[inline]
     fn read_byte(&self, cursor: &mut Cursor) -> Result<u8, DecodeError> {
         cursor
@@ -350,6 +517,9 @@ impl Decoder {
             | layout::U64_7B
             | layout::U64_8B => TypeKind::U64,
 
+            layout::U128 => TypeKind::U128,
+            layout::U256 => TypeKind::U256,
+
             layout::ARR_0
             | layout::ARR_1
             | layout::ARR_2
@@ -357,9 +527,22 @@ impl Decoder {
             | layout::ARR_4
             | layout::ARR_5
             | layout::ARR_6
-            | layout::ARR_0_255 => TypeKind::Array,
-
-            _ => unreachable!(),
+            | layout::ARR_0_255
+            | layout::ARR_N => TypeKind::Array,
+
+            layout::TUPLE_0
+            | layout::TUPLE_1
+            | layout::TUPLE_2
+            | layout::TUPLE_3
+            | layout::TUPLE_4
+            | layout::TUPLE_5
+            | layout::TUPLE_6
+            | layout::TUPLE_N => TypeKind::Tuple,
+
+            layout::BYTES => TypeKind::Bytes,
+            layout::STRING => TypeKind::String,
+
+            _ => return Err(DecodeError::Type(TypeError::InvalidTypeKind(byte))),
         };
 
         Ok(kind)
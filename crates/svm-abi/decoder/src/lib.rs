@@ -28,4 +28,4 @@ pub use calldata::CallData;
 pub type ReturnData = CallData;
 
 pub use cursor::Cursor;
-pub use decoder::{DecodeError, Decoder};
+pub use decoder::{DecodeError, Decoder, TypeError, ValueError};
@@ -6,13 +6,11 @@ use svm_abi_layout::layout;
 use svm_sdk_types::types::PrimitiveMarker;
 use svm_sdk_types::value::Value;
 
-use crate::Encoder;
+use crate::{encode_varuint, Encoder};
 
 macro_rules! impl_encode {
     () => {
         fn encode(&self, w: &mut Vec<u8>) {
-            assert!(self.len() < 255);
-
             let marker = match self.len() {
                 0 => layout::ARR_0,
                 1 => layout::ARR_1,
@@ -22,11 +20,17 @@ macro_rules! impl_encode {
                 5 => layout::ARR_5,
                 6 => layout::ARR_6,
                 7..256 => layout::ARR_0_255,
-                _ => unreachable!(),
+                _ => layout::ARR_N,
             };
 
             w.push(marker);
 
+            match marker {
+                layout::ARR_0_255 => w.push(self.len() as u8),
+                layout::ARR_N => encode_varuint(self.len(), w),
+                _ => (),
+            }
+
             for elem in self.iter() {
                 elem.encode(w);
             }
@@ -0,0 +1,28 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use svm_abi_layout::layout;
+use svm_sdk_types::Bytes;
+
+use crate::{encode_varuint, Encoder};
+
+macro_rules! impl_blob_encoder {
+    ($ty:ty, $marker:path, $as_bytes:ident) => {
+        impl Encoder for $ty {
+            /// Encodes `self` (of type `$ty`) and outputs the data into `w`
+            fn encode(&self, w: &mut Vec<u8>) {
+                let bytes = self.$as_bytes();
+
+                w.push($marker);
+                encode_varuint(bytes.len(), w);
+                w.extend_from_slice(bytes);
+            }
+        }
+    };
+}
+
+impl_blob_encoder!(Bytes, layout::BYTES, as_slice);
+impl_blob_encoder!(String, layout::STRING, as_bytes);
+impl_blob_encoder!(&str, layout::STRING, as_bytes);
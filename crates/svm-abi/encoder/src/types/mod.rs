@@ -12,6 +12,9 @@
 //! | type (Marker)  | type value (blob) |
 //! +------------------------------------+
 //!
+//! Integers up to 64-bit are encoded using the minimal number of bytes (the marker holds the length),
+//! whereas `U128` and `U256` are always encoded as 16 and 32 Big-Endian bytes respectively.
+//!
 //!
 //! ## Composite Encoding:
 //!
@@ -22,30 +25,48 @@
 //! +-----------------------------------------------------------------------------------------------------------------+
 //!
 //!
-//! ### Note:
+//! Arrays of more than 255 items start with the `ARR_N` marker followed by the number of items (a `varuint`).
+//!
+//! ### Tuple (and structs)
+//!
+//! +-----------------------------------------------------------------+
+//! | Tuple Marker (1 byte) | Field #1 Encoding | . . . | Field #N Encoding |
+//! +-----------------------------------------------------------------+
+//!
+//! Tuples of more than 6 fields start with the `TUPLE_N` marker followed by the number of fields (a `varuint`).
+//! (Rust tuples are encoded as a sequence of values and not as a `Tuple`, see `encode_tuple`).
 //!
-//! Actually the current `Encoder` code supports encoding also `Array` of `Array`'s but it'll error when decoded
-//! (see the `svm-abi-decoder` crate).
+//! ### Bytes / String
+//!
+//! +-------------------------------------------------------+
+//! | Marker (1 byte) | length (varuint) | bytes (UTF-8 for `String`) |
+//! +-------------------------------------------------------+
 //!
 
 mod address;
 mod amount;
 mod array;
 mod boolean;
+mod bytes;
 mod num_i16;
 mod num_i32;
 mod num_i64;
 mod num_i8;
+mod num_wide;
 mod tuples;
+mod varuint;
 
 pub use address::*;
 pub use amount::*;
 pub use array::*;
 pub use boolean::*;
+pub use bytes::*;
 pub use num_i16::*;
 pub use num_i32::*;
 pub use num_i64::*;
+pub use num_wide::*;
 pub use tuples::*;
+pub use varuint::*;
 
 extern crate alloc;
 use alloc::vec::Vec;
@@ -94,6 +115,8 @@ fn encode_primitive(p: &Primitive, w: &mut Vec<u8>) {
         Primitive::U32(p) => p.encode(w),
         Primitive::I64(p) => p.encode(w),
         Primitive::U64(p) => p.encode(w),
+        Primitive::U128(p) => p.encode(w),
+        Primitive::U256(p) => p.encode(w),
     }
 }
 
@@ -107,6 +130,18 @@ fn encode_composite(c: &Composite, w: &mut Vec<u8>) {
             let values: Vec<&dyn Encoder> = values.iter().map(|v| v as &dyn Encoder).collect();
             values.encode(w);
         }
+        Composite::Tuple(fields) => {
+            let fields: Vec<&dyn Encoder> = fields.iter().map(|v| v as &dyn Encoder).collect();
+            encode_tuple(&fields, w);
+        }
+        Composite::Bytes(bytes) => {
+            use svm_abi_layout::layout;
+
+            w.push(layout::BYTES);
+            encode_varuint(bytes.len(), w);
+            w.extend_from_slice(bytes);
+        }
+        Composite::String(s) => s.encode(w),
     }
 }
 
@@ -0,0 +1,21 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use svm_abi_layout::layout;
+use svm_sdk_types::{U128, U256};
+
+use crate::Encoder;
+
+macro_rules! encode {
+    ($ty:ty, $MARK:expr) => {
+        impl Encoder for $ty {
+            fn encode(&self, w: &mut Vec<u8>) {
+                w.push($MARK);
+                w.extend_from_slice(&self.to_be_bytes());
+            }
+        }
+    };
+}
+
+encode!(U128, layout::U128);
+encode!(U256, layout::U256);
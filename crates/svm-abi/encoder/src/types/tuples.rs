@@ -2,7 +2,42 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
-use crate::Encoder;
+use svm_abi_layout::layout;
+
+use crate::{encode_varuint, Encoder};
+
+/// Encodes `fields` as a single ABI `Tuple` and outputs the data into `w`.
+///
+/// Rust tuples (see below) are encoded as a sequence of values (i.e multiple arguments or returns values),
+/// while `encode_tuple` is used for encoding a first-class `Tuple` (e.g a struct deriving `AbiStruct`).
+pub fn encode_tuple(fields: &[&dyn Encoder], w: &mut Vec<u8>) {
+    let marker = match fields.len() {
+        0 => layout::TUPLE_0,
+        1 => layout::TUPLE_1,
+        2 => layout::TUPLE_2,
+        3 => layout::TUPLE_3,
+        4 => layout::TUPLE_4,
+        5 => layout::TUPLE_5,
+        6 => layout::TUPLE_6,
+        _ => layout::TUPLE_N,
+    };
+
+    w.push(marker);
+
+    if marker == layout::TUPLE_N {
+        encode_varuint(fields.len(), w);
+    }
+
+    for field in fields.iter() {
+        field.encode(w);
+    }
+}
+
+/// The unit type `()` is encoded as nothing (i.e an endpoint returning no value).
+impl Encoder for () {
+    #[inline]
+    fn encode(&self, _w: &mut Vec<u8>) {}
+}
 
 impl<T> Encoder for (T,)
 where
@@ -0,0 +1,24 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+/// Encodes `len` as a `varuint` (LEB128) and outputs the data into `w`
+///
+/// It's used for the length of the variable-length values (`Bytes`, `String`, long `Array` and `Tuple`).
+pub fn encode_varuint(len: usize, w: &mut Vec<u8>) {
+    assert!(len <= core::u32::MAX as usize);
+
+    let mut v = len as u32;
+
+    loop {
+        let byte = (v & 0x7F) as u8;
+        v >>= 7;
+
+        if v == 0 {
+            w.push(byte);
+            break;
+        }
+
+        w.push(byte | 0x80);
+    }
+}
@@ -6,9 +6,9 @@
 //! | 0 | 0 0 1 | 0 0 0 0 |  True  (Boolean)          |
 //! | 0 | 0 1 0 | 0 0 0 0 |  None                     |
 //! | 0 | 0 1 1 | 0 0 0 0 |  Address                  |
-//! | 0 | 1 0 0 | 0 0 0 0 |  Reserved                 |
-//! | 0 | 1 0 1 | 0 0 0 0 |  Reserved                 |
-//! | 0 | 1 1 0 | 0 0 0 0 |  Reserved                 |
+//! | 0 | 1 0 0 | 0 0 0 0 |  Bytes                    |
+//! | 0 | 1 0 1 | 0 0 0 0 |  String                   |
+//! | 0 | 1 1 0 | 0 0 0 0 |  Array - any # of items   |
 //! | 0 | 1 1 1 | 0 0 0 0 |  Reserved                 |
 //! +---+-------+---------+---------------------------+
 //! | 0 | 0 0 0 | 0 0 0 1 |  Amount - 1 byte          |
@@ -26,8 +26,8 @@
 //! | 0 | 0 1 1 | 0 0 1 0 |  i16 (signed)   - 2 bytes |
 //! | 0 | 1 0 0 | 0 0 1 0 |  u16 (unsigned) - 1 byte  |
 //! | 0 | 1 0 1 | 0 0 1 0 |  u16 (signed)   - 2 bytes |
-//! | 0 | 1 1 0 | 0 0 1 0 |  Reserved                 |
-//! | 0 | 1 1 1 | 0 0 1 0 |  Reserved                 |
+//! | 0 | 1 1 0 | 0 0 1 0 |  u128 (unsigned) - 16 bytes |
+//! | 0 | 1 1 1 | 0 0 1 0 |  u256 (unsigned) - 32 bytes |
 //! +---+-------+---------+---------------------------+
 //! | 0 | 0 0 0 | 0 0 1 1 |  i32 (signed)   - 1 byte  |
 //! | 0 | 0 0 1 | 0 0 1 1 |  i32 (signed)   - 2 bytes |
@@ -64,8 +64,25 @@
 //! | 0 | 1 0 1 | 0 1 1 0 |  Array - 5 items          |
 //! | 0 | 1 1 0 | 0 1 1 0 |  Array - 6 items          |
 //! | 0 | 1 1 1 | 0 1 1 0 |  Array - 0..255 items     |
-//! +---+----------------+---------------------------+
+//! +---+-------+---------+---------------------------+
+//! | 0 | 0 0 0 | 0 1 1 1 |  Tuple - 0 items          |
+//! | 0 | 0 0 1 | 0 1 1 1 |  Tuple - 1 item           |
+//! | 0 | 0 1 0 | 0 1 1 1 |  Tuple - 2 items          |
+//! | 0 | 0 1 1 | 0 1 1 1 |  Tuple - 3 items          |
+//! | 0 | 1 0 0 | 0 1 1 1 |  Tuple - 4 items          |
+//! | 0 | 1 0 1 | 0 1 1 1 |  Tuple - 5 items          |
+//! | 0 | 1 1 0 | 0 1 1 1 |  Tuple - 6 items          |
+//! | 0 | 1 1 1 | 0 1 1 1 |  Tuple - any # of items   |
+//! +---+-------+---------+---------------------------+
+//!
+//! Variable-length values (`Bytes`, `String`, `Array - any # of items` and `Tuple - any # of items`)
+//! are followed by their length (i.e number of bytes or items) encoded as a `varuint`
+//! (LEB128 - 7 bits per byte, least-significant group first, the high bit marks a continuation byte)
+//! of at most `MAX_VARUINT_LEN` bytes (i.e the length fits a `u32`).
 //!
+//! `u128` and `u256` are followed by their fixed-size Big-Endian representation.
+//!
+//! `Array - 0..255 items` is followed by a single byte holding the number of items.
 //!
 
 #![no_std]
@@ -85,6 +102,12 @@ pub mod layout {
     // Address
     pub const ADDRESS: u8 = 0b_0_011_0000;
 
+    // Bytes
+    pub const BYTES: u8 = 0b_0_100_0000;
+
+    // String (UTF-8)
+    pub const STRING: u8 = 0b_0_101_0000;
+
     /// Amount
     pub const AMOUNT_1B: u8 = 0b_0_000_0001;
     pub const AMOUNT_2B: u8 = 0b_0_001_0001;
@@ -109,6 +132,10 @@ pub mod layout {
     pub const U16_1B: u8 = 0b_0_100_0010;
     pub const U16_2B: u8 = 0b_0_101_0010;
 
+    // Wide integers (fixed-size, Big-Endian)
+    pub const U128: u8 = 0b_0_110_0010;
+    pub const U256: u8 = 0b_0_111_0010;
+
     // i32
     //// signed
     pub const I32_1B: u8 = 0b_0_000_0011;
@@ -150,4 +177,18 @@ pub mod layout {
     pub const ARR_5: u8 = 0b_0_101_0110;
     pub const ARR_6: u8 = 0b_0_110_0110;
     pub const ARR_0_255: u8 = 0b_0_111_0110;
+    pub const ARR_N: u8 = 0b_0_110_0000;
+
+    // Tuple
+    pub const TUPLE_0: u8 = 0b_0_000_0111;
+    pub const TUPLE_1: u8 = 0b_0_001_0111;
+    pub const TUPLE_2: u8 = 0b_0_010_0111;
+    pub const TUPLE_3: u8 = 0b_0_011_0111;
+    pub const TUPLE_4: u8 = 0b_0_100_0111;
+    pub const TUPLE_5: u8 = 0b_0_101_0111;
+    pub const TUPLE_6: u8 = 0b_0_110_0111;
+    pub const TUPLE_N: u8 = 0b_0_111_0111;
+
+    /// The maximum number of bytes of an encoded `varuint` (a `u32` has 5 groups of 7 bits).
+    pub const MAX_VARUINT_LEN: usize = 5;
 }
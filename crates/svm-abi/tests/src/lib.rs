@@ -11,7 +11,7 @@ mod tests {
     use svm_abi_decoder::CallData;
     use svm_abi_encoder::Encoder;
     use svm_sdk::value::{Composite, Primitive, Value};
-    use svm_sdk::{Address, Amount};
+    use svm_sdk::{Address, Amount, U128, U256};
 
     macro_rules! as_static {
         ($bytes:expr) => {
@@ -272,6 +272,38 @@ mod tests {
         test_array!([Amount; 3], [Amount(10), Amount(20), Amount(30)]);
     }
 
+    #[test]
+    fn encode_decode_u128() {
+        test_primitive!(U128, U128(0));
+        test_primitive!(U128, U128(std::u64::MAX as u128 + 1));
+        test_primitive!(U128, U128::MAX);
+
+        test_array!([U128; 2], [U128(5), U128::MAX]);
+
+        let mut bytes = Vec::new();
+        U128(1).encode(&mut bytes);
+
+        assert_eq!(bytes.len(), 1 + 16);
+        assert_eq!(bytes[16], 1);
+    }
+
+    #[test]
+    fn encode_decode_u256() {
+        let big = U256::from(std::u128::MAX) * U256::from(1000u64);
+
+        test_primitive!(U256, U256::ZERO);
+        test_primitive!(U256, big);
+        test_primitive!(U256, U256::MAX);
+
+        test_array!([U256; 3], [U256::from(1u64), big, U256::MAX]);
+
+        let mut bytes = Vec::new();
+        U256::from(1u64).encode(&mut bytes);
+
+        assert_eq!(bytes.len(), 1 + 32);
+        assert_eq!(bytes[32], 1);
+    }
+
     #[test]
     fn encode_decode_addr() {
         let addr: Address = [0x10; Address::len()].into();
@@ -427,4 +459,181 @@ mod tests {
         let fmt = format!("{}", addr);
         assert_eq!(fmt, "102030405060708090a0112233445566778899aa");
     }
+
+    #[test]
+    fn decode_invalid_type_kind() {
+        use svm_abi_decoder::{Cursor, DecodeError, Decoder, TypeError};
+
+        let bytes = [0xFF];
+        let mut cursor = Cursor::new(&bytes);
+
+        let res = Decoder::new().decode_value(&mut cursor);
+        assert!(matches!(
+            res,
+            Err(DecodeError::Type(TypeError::InvalidTypeKind(0xFF)))
+        ));
+    }
+
+    #[test]
+    fn decode_too_deep_array() {
+        use svm_abi_decoder::{Cursor, DecodeError, Decoder, TypeError};
+        use svm_abi_layout::layout;
+
+        let bytes = vec![layout::ARR_1; Decoder::MAX_DEPTH + 2];
+        let mut cursor = Cursor::new(&bytes);
+
+        let res = Decoder::new().decode_value(&mut cursor);
+        assert!(matches!(res, Err(DecodeError::Type(TypeError::TooDeep))));
+    }
+
+    #[test]
+    fn encode_decode_long_array() {
+        test_array!([u8; 7], [0u8, 1, 2, 3, 4, 5, 6]);
+        test_array!([u16; 10], [10u16, 20, 30, 40, 50, 60, 70, 80, 90, 100]);
+    }
+
+    #[test]
+    fn encode_decode_array_over_255_items() {
+        use svm_abi_layout::layout;
+
+        for &len in [255usize, 256, 1000].iter() {
+            let array: Vec<u32> = (0..len as u32).collect();
+
+            let mut bytes = Vec::new();
+            array.encode(&mut bytes);
+
+            let expected = if len == 255 {
+                layout::ARR_0_255
+            } else {
+                layout::ARR_N
+            };
+            assert_eq!(bytes[0], expected);
+
+            let mut calldata = CallData::new(as_static!(&bytes));
+            let decoded: Vec<u32> = calldata.next_1();
+
+            assert_eq!(decoded, array);
+        }
+    }
+
+    #[test]
+    fn encode_decode_bytes() {
+        use svm_abi_layout::layout;
+        use svm_sdk::Bytes;
+
+        test_primitive!(Bytes, Bytes(vec![]));
+        test_primitive!(Bytes, Bytes(vec![0x10, 0x20, 0x30]));
+        test_primitive!(Bytes, Bytes(vec![0xFF; 300]));
+
+        let mut bytes = Vec::new();
+        Bytes(vec![0xAB; 300]).encode(&mut bytes);
+
+        // 300 = 0b10_0101100 (varuint: 0b1_0101100, 0b0_0000010)
+        assert_eq!(&bytes[..3], &[layout::BYTES, 0xAC, 0x02]);
+        assert_eq!(bytes.len(), 3 + 300);
+
+        test_primitive!(Option<Bytes>, None);
+    }
+
+    #[test]
+    fn encode_decode_string() {
+        test_primitive!(String, String::new());
+        test_primitive!(String, "Hello World".to_string());
+        test_primitive!(String, "שלום".to_string());
+
+        let mut buf = Vec::new();
+        "Hello".encode(&mut buf);
+
+        let mut calldata = CallData::new(as_static!(buf));
+        let s: String = calldata.next_1();
+
+        assert_eq!(s, "Hello");
+    }
+
+    #[test]
+    fn encode_decode_tuple() {
+        use svm_abi_layout::layout;
+
+        let fields: Vec<Value> = vec![10u32.into(), true.into(), "Hi".into()];
+        let value = Value::tuple(fields);
+
+        let mut buf = Vec::new();
+        value.encode(&mut buf);
+
+        assert_eq!(buf[0], layout::TUPLE_3);
+
+        let mut calldata = CallData::new(as_static!(buf));
+        let decoded = calldata.next().unwrap();
+
+        assert_eq!(decoded, value);
+
+        // nested and long tuples
+        let inner: Vec<Value> = (0..10u8).map(|i| i.into()).collect();
+        let value = Value::tuple(vec![Value::tuple(inner), Value::tuple(vec![])]);
+
+        let mut buf = Vec::new();
+        value.encode(&mut buf);
+
+        assert_eq!(&buf[..3], &[layout::TUPLE_2, layout::TUPLE_N, 10]);
+
+        let mut calldata = CallData::new(as_static!(buf));
+        let decoded = calldata.next().unwrap();
+
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn rust_tuples_are_encoded_as_values_sequence() {
+        let mut buf = Vec::new();
+        (10u32, true).encode(&mut buf);
+
+        let mut calldata = CallData::new(as_static!(buf));
+        let (a, b): (u32, bool) = calldata.next_2();
+
+        assert_eq!(a, 10);
+        assert_eq!(b, true);
+    }
+
+    #[test]
+    fn decode_invalid_blob() {
+        use svm_abi_decoder::{Cursor, DecodeError, Decoder, ValueError};
+        use svm_abi_layout::layout;
+
+        macro_rules! assert_value_err {
+            ($bytes:expr, $err:pat) => {{
+                let bytes = $bytes;
+                let mut cursor = Cursor::new(&bytes);
+
+                let res = Decoder::new().decode_value(&mut cursor);
+                assert!(matches!(res, Err(DecodeError::Value($err))));
+            }};
+        }
+
+        // length is bigger than the remaining bytes
+        assert_value_err!([layout::BYTES, 3, 0x10], ValueError::NotEnoughBytes);
+
+        // a huge length
+        assert_value_err!(
+            [layout::BYTES, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0x10],
+            ValueError::NotEnoughBytes
+        );
+
+        // varuint longer than 5 bytes
+        assert_value_err!(
+            [layout::ARR_N, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01],
+            ValueError::InvalidVarUint
+        );
+
+        // varuint overflowing a `u32`
+        assert_value_err!(
+            [layout::TUPLE_N, 0xFF, 0xFF, 0xFF, 0xFF, 0x1F],
+            ValueError::InvalidVarUint
+        );
+
+        // invalid UTF-8
+        assert_value_err!([layout::STRING, 2, 0xC3, 0x28], ValueError::InvalidUtf8);
+
+        // a truncated `u128`
+        assert_value_err!([layout::U128, 0, 0, 1], ValueError::NotEnoughBytes);
+    }
 }
@@ -3,6 +3,8 @@ use crate::schema::{Var, VarType};
 use byteorder::{BigEndian, ByteOrder};
 use serde_json::{Number, Value};
 
+use svm_sdk_types::{U128, U256};
+
 pub trait VarRenderer<V> {
     fn render(var: &Var, bytes: &[u8]) -> Option<V>;
 }
@@ -28,8 +30,7 @@ impl JsonVarRenderer {
         let length = var.layout.length;
 
         if length > 8 {
-            /// Currently, an integer can be at most 8 bytes
-            return None;
+            return Self::render_wide_uint(bytes, is_signed);
         }
 
         let nbytes = bytes.len();
@@ -50,8 +51,34 @@ impl JsonVarRenderer {
         Some(Value::Number(num))
     }
 
-    fn render_balance(_var: &Var, _bytes: &[u8]) -> Option<Value> {
-        todo!()
+    /// Renders a `U128` or a `U256` as a decimal string
+    /// (JSON numbers can't represent integers wider than 64-bit precisely).
+    fn render_wide_uint(bytes: &[u8], is_signed: bool) -> Option<Value> {
+        if is_signed {
+            return None;
+        }
+
+        let s = match bytes.len() {
+            U128::BYTES => {
+                let mut buf = [0; U128::BYTES];
+                buf.copy_from_slice(bytes);
+
+                U128::from_be_bytes(buf).to_string()
+            }
+            U256::BYTES => {
+                let mut buf = [0; U256::BYTES];
+                buf.copy_from_slice(bytes);
+
+                U256::from_be_bytes(buf).to_string()
+            }
+            _ => return None,
+        };
+
+        Some(Value::String(s))
+    }
+
+    fn render_balance(var: &Var, bytes: &[u8]) -> Option<Value> {
+        Self::render_int(var, bytes, false)
     }
 
     fn render_bool(_var: &Var, bytes: &[u8]) -> Option<Value> {
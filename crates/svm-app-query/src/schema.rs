@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use svm_layout::{DataLayout, VarId};
+
 ///  Schema's variable representation
 #[derive(Debug, Clone, PartialEq)]
 pub struct Var {
@@ -71,3 +73,48 @@ impl Schema {
         self.0.get(&id).cloned()
     }
 }
+
+impl From<&DataLayout> for Schema {
+    /// Builds a `Schema` out of the typed schema embedded in `layout`.
+    ///
+    /// Each item of an array field is added as a variable of its own, named `name[index]`.
+    /// Returns an empty `Schema` when the layout has no schema.
+    fn from(layout: &DataLayout) -> Self {
+        let mut schema = Schema::new();
+
+        let fields = layout.schema().unwrap_or(&[]);
+
+        for field in fields.iter() {
+            for i in 0..field.count {
+                let var_id = VarId(field.id.0 + i);
+                let (offset, length) = layout.get_var(var_id);
+
+                let name = if field.is_array() {
+                    format!("{}[{}]", field.name, i)
+                } else {
+                    field.name.clone()
+                };
+
+                let ty = match field.ty {
+                    svm_layout::VarType::Bool => VarType::Bool,
+                    svm_layout::VarType::Amount => VarType::Balance,
+                    svm_layout::VarType::Address => VarType::Address,
+                    ty => VarType::Int(ty.is_signed()),
+                };
+
+                schema.add_var(Var {
+                    id: var_id.0 as usize,
+                    layout: VarLayout {
+                        offset: offset as usize,
+                        length: length as usize,
+                    },
+                    ty,
+                    name,
+                    desc: String::new(),
+                });
+            }
+        }
+
+        schema
+    }
+}
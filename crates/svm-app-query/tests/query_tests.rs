@@ -80,6 +80,43 @@ fn query_int_u8_var() {
     );
 }
 
+#[test]
+fn query_int_u128_var() {
+    let layout = VarLayout {
+        offset: 0,
+        length: 16,
+    };
+
+    let mut bytes = vec![0; 16];
+    bytes[7] = 1;
+
+    // 2^64
+    test_var!(
+        bytes,
+        layout,
+        VarType::Int(false),
+        Value::String("18446744073709551616".to_string())
+    );
+}
+
+#[test]
+fn query_int_u256_var() {
+    let layout = VarLayout {
+        offset: 0,
+        length: 32,
+    };
+
+    test_var!(
+        vec![0xFF; 32],
+        layout,
+        VarType::Int(false),
+        Value::String(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+                .to_string()
+        )
+    );
+}
+
 #[test]
 fn query_blob_var() {
     let layout = VarLayout {
@@ -124,3 +161,26 @@ fn query_addr_var() {
         Value::String("0x0A0B0C0D0E".to_string())
     );
 }
+
+#[test]
+fn schema_from_layout() {
+    use svm_layout::{DataLayout, DataLayoutBuilder, VarType as LayoutVarType};
+
+    let mut builder = DataLayoutBuilder::new();
+    builder.extend_from_slice(&[8, 1, 1]);
+    builder.add_symbolic_var("balance", LayoutVarType::Amount, 1);
+    builder.add_symbolic_var("flags", LayoutVarType::Bool, 2);
+
+    let layout: DataLayout = builder.build();
+    let schema = Schema::from(&layout);
+
+    let balance = schema.get_var(0).unwrap();
+    assert_eq!(balance.name, "balance");
+    assert_eq!(balance.ty, VarType::Balance);
+    assert_eq!(balance.layout, VarLayout { offset: 0, length: 8 });
+
+    let flag = schema.get_var(2).unwrap();
+    assert_eq!(flag.name, "flags[1]");
+    assert_eq!(flag.ty, VarType::Bool);
+    assert_eq!(flag.layout, VarLayout { offset: 9, length: 1 });
+}
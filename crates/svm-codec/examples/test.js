@@ -147,6 +147,17 @@ function decodeCallData(instance, encodedData) {
     return json;
 }
 
+function decodeReturns(instance, object) {
+    const buf = wasmNewBuffer(instance, object);
+    const result = instanceCall(instance, 'wasm_decode_returns', buf);
+    const json = loadWasmBufferDataAsJson(instance, result);
+
+    wasmBufferFree(instance, buf);
+    wasmBufferFree(instance, result);
+
+    return json;
+}
+
 function binToString(array) {
     let result = "";
 
@@ -259,6 +270,57 @@ describe('Encode Calldata', function () {
     });
 })
 
+describe('Encode Calldata using a Template ABI', function () {
+    const abi = {
+	endpoints: [
+	    {
+		name: 'transfer',
+		ctor: false,
+		params: [
+		    { name: 'to', type: 'address' },
+		    { name: 'amount', type: 'amount' }
+		],
+		returns: ['bool']
+	    }
+	],
+	storage: []
+    };
+
+    it('Encodes named params', function () {
+	return compileWasmCodec().then(instance => {
+	    const addr = generateAddress('1020304050');
+	    const object = {
+		abi: abi,
+		func_name: 'transfer',
+		params: { amount: 10, to: addr },
+	    };
+
+	    let encoded = encodeCallData(instance, object);
+	    let decoded = decodeCallData(instance, encoded);
+
+	    assert.deepEqual(decoded,
+			     {
+				 abi: ['address', 'amount'],
+				 data: [addr, 10],
+			     });
+	})
+    })
+
+    it('Decodes returns', function () {
+	return compileWasmCodec().then(instance => {
+	    const object = {
+		abi: abi,
+		func_name: 'transfer',
+		returndata: '10',
+	    };
+
+	    let decoded = decodeReturns(instance, object);
+
+	    assert.deepEqual(decoded, { returns: [true] });
+	})
+    })
+})
+
 describe('WASM Buffer', function () {
     it('Allocate & Free', function () {
 	return compileWasmCodec().then(instance => {
@@ -283,7 +345,10 @@ describe('Deploy Template', function () {
 	      "version": 0,
 	      "name": "My Template",
 	      "code": "C0DE",
-	      "data": "0000000100000003"
+	      "data": {
+	        "vars": [[0, 1], [1, 3]],
+	        "schema": []
+	      }
 	    };
 
 	    const buf = wasmNewBuffer(instance, tx);
@@ -310,7 +375,7 @@ describe('Deploy Template', function () {
 	    const result = instanceCall(instance, 'wasm_deploy_template', buf);
 
 	    const error = loadWasmBufferError(instance, result);
-	    assert.equal(error, "InvalidField { field: \"name\", reason: \"value `null` isn\\'t a string\" }");
+	    assert.equal(error, "InvalidJson(\"missing field `name`\")");
 
 	    wasmBufferFree(instance, buf);
 	    wasmBufferFree(instance, result);
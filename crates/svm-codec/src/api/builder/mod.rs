@@ -1,7 +1,9 @@
 mod app_builder;
 mod app_tx_builder;
 mod template_builder;
+mod upgrade_builder;
 
 pub use app_builder::SpawnAppBuilder;
 pub use app_tx_builder::AppTxBuilder;
 pub use template_builder::DeployAppTemplateBuilder;
+pub use upgrade_builder::UpgradeAppBuilder;
@@ -1,6 +1,7 @@
 use svm_nibble::NibbleWriter;
 use svm_types::AppTemplate;
 
+use crate::api::json::template_layout;
 use crate::api::raw::encode_deploy_template;
 
 use svm_layout::DataLayout;
@@ -45,6 +46,10 @@ pub struct DeployAppTemplateBuilder {
 /// assert_eq!(expected, actual);
 /// ```
 ///
+/// When `with_data` isn't called, the `DataLayout` (including its schema) is derived out of
+/// the `#[storage]` records of the code's ABI (see `svm_codec::api::json::template_layout`).
+/// Such a template should be of version `1` (version `0` carries no schema).
+///
 #[allow(missing_docs)]
 impl DeployAppTemplateBuilder {
     #[allow(clippy::new_without_default)]
@@ -81,7 +86,10 @@ impl DeployAppTemplateBuilder {
         let version = self.version.unwrap();
         let name = self.name.unwrap();
         let code = self.code.unwrap();
-        let data = self.data.unwrap();
+        let data = match self.data {
+            Some(data) => data,
+            None => template_layout(&code).expect("Invalid code ABI"),
+        };
 
         let app = AppTemplate {
             version,
@@ -0,0 +1,113 @@
+use svm_nibble::NibbleWriter;
+use svm_types::{AppAddr, TemplateAddr, UpgradeApp};
+
+use crate::api::raw::encode_upgrade_app;
+
+/// Builds a raw representation for `upgrade-app`
+/// Should be used for testing only.
+pub struct UpgradeAppBuilder {
+    version: Option<u32>,
+    app: Option<AppAddr>,
+    template: Option<TemplateAddr>,
+    migration: Option<String>,
+    calldata: Option<Vec<u8>>,
+}
+
+///
+/// # Example
+///
+/// ```rust
+/// use svm_types::{UpgradeApp, Address};
+/// use svm_nibble::NibbleIter;
+/// use svm_codec::api::{raw::decode_upgrade_app, builder::UpgradeAppBuilder};
+///
+/// let app = Address::of("@my-app").into();
+/// let template = Address::of("@my-template-v2").into();
+///
+/// let calldata = vec![0x10, 0x20, 0x30];
+///
+/// let bytes = UpgradeAppBuilder::new()
+///            .with_version(0)
+///            .with_app(&app)
+///            .with_template(&template)
+///            .with_migration("migrate")
+///            .with_calldata(&calldata)
+///            .build();
+///
+/// let mut iter = NibbleIter::new(&bytes[..]);
+/// let actual = decode_upgrade_app(&mut iter).unwrap();
+/// let expected = UpgradeApp {
+///                  version: 0,
+///                  app,
+///                  template,
+///                  migration: Some("migrate".to_string()),
+///                  calldata,
+///                };
+///
+/// assert_eq!(expected, actual);
+/// ```
+///
+#[allow(missing_docs)]
+impl UpgradeAppBuilder {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            version: None,
+            app: None,
+            template: None,
+            migration: None,
+            calldata: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn with_app(mut self, app: &AppAddr) -> Self {
+        self.app = Some(app.clone());
+        self
+    }
+
+    pub fn with_template(mut self, template: &TemplateAddr) -> Self {
+        self.template = Some(template.clone());
+        self
+    }
+
+    pub fn with_migration(mut self, migration: &str) -> Self {
+        self.migration = Some(migration.to_string());
+        self
+    }
+
+    pub fn with_calldata(mut self, calldata: &Vec<u8>) -> Self {
+        self.calldata = Some(calldata.to_vec());
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        let version = self.version.unwrap();
+        let app = self.app.unwrap();
+        let template = self.template.unwrap();
+        let migration = self.migration;
+
+        let calldata = match self.calldata {
+            None => vec![],
+            Some(calldata) => calldata.to_vec(),
+        };
+
+        let upgrade = UpgradeApp {
+            version,
+            app,
+            template,
+            migration,
+            calldata,
+        };
+
+        let mut w = NibbleWriter::new();
+
+        encode_upgrade_app(&upgrade, &mut w);
+
+        w.into_bytes()
+    }
+}
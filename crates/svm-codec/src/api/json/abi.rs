@@ -0,0 +1,446 @@
+use parity_wasm::elements::Module;
+use serde_json::{json, Value as Json};
+
+use std::collections::{HashMap, HashSet};
+
+use svm_layout::{DataLayout, DataLayoutBuilder, VarType};
+use svm_types::AppTemplate;
+
+use crate::api::json::JsonError;
+
+pub use svm_sdk::abi::ABI_SECTION;
+
+///
+/// Extracts the ABI of `template` out of its code's `svm_abi` custom section.
+///
+/// The section consists of JSON lines. Each line is either an `endpoint`, a `struct` or a `storage` record.
+/// (the records are appended to the section by the `svm-sdk` macros).
+///
+/// ```json
+/// {
+///   "endpoints": [
+///     {
+///       "name": "init",
+///       "ctor": true,
+///       "payable": true,
+///       "params": [{ "name": "owner", "type": "address" }],
+///       "returns": []
+///     },
+///     {
+///       "name": "work",
+///       "ctor": false,
+///       "payable": false,
+///       "params": [{ "name": "a", "type": "amount" }, { "name": "to_double", "type": "bool" }],
+///       "returns": ["amount"]
+///     },
+///     {
+///       "name": "move_to",
+///       "ctor": false,
+///       "payable": false,
+///       "params": [{ "name": "point", "type": { "struct": "Point" } }],
+///       "returns": []
+///     }
+///   ],
+///   "structs": [
+///     {
+///       "name": "Point",
+///       "fields": [{ "name": "x", "type": "u32" }, { "name": "y", "type": "u32" }]
+///     }
+///   ],
+///   "storage": [
+///     { "id": 0, "name": "owner", "type": "Address", "count": 1 },
+///     { "id": 1, "name": "scores", "type": "u32", "count": 3 }
+///   ]
+/// }
+/// ```
+///
+/// The `params` types (other than structs) can be used as-is as the `abi` given to `encode_calldata`.
+/// The endpoints and structs are sorted by name (the order of the records in the section depends on the linker).
+///
+/// A `storage` record may have nested storage fields (`{"name": "inner", "type": {"storage": "Inner"}}`).
+/// These are flattened into the variables of the nested storage record (named `inner.<var>`),
+/// laid out after the other variables of the storage (the same way the `#[storage]` macro lays them out).
+///
+pub fn template_abi(template: &AppTemplate) -> Result<Json, JsonError> {
+    let section = abi_section(&template.code)?;
+
+    let section = std::str::from_utf8(&section).map_err(|_| invalid_abi("non UTF-8 section"))?;
+
+    let mut endpoints = Vec::new();
+    let mut structs = Vec::new();
+    let mut storages = Vec::new();
+
+    for line in section.lines().filter(|line| !line.trim().is_empty()) {
+        let record: Json = serde_json::from_str(line).map_err(|e| invalid_abi(&e.to_string()))?;
+
+        if let Some(endpoint) = record.get("endpoint") {
+            endpoints.push(endpoint.clone());
+        } else if let Some(record) = record.get("struct") {
+            structs.push(record.clone());
+        } else if let Some(record) = record.get("storage") {
+            let name = record["name"]
+                .as_str()
+                .ok_or_else(|| invalid_abi("storage `name` must be a string"))?;
+
+            let vars = record["vars"]
+                .as_array()
+                .ok_or_else(|| invalid_abi("storage `vars` must be an array"))?;
+
+            storages.push((name.to_string(), vars.clone()));
+        } else {
+            return Err(invalid_abi(&format!("unknown record: {}", line)));
+        }
+    }
+
+    endpoints.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    structs.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    let storage = flatten_storages(&storages)?;
+
+    let abi = json!({
+        "endpoints": endpoints,
+        "structs": structs,
+        "storage": storage,
+    });
+
+    Ok(abi)
+}
+
+///
+/// Derives the `DataLayout` (including its schema) of a template out of the `storage` records
+/// of its code's ABI (see `template_abi`).
+///
+/// Each storage field becomes a schema field spanning `count` variables,
+/// each variable is sized after the field's type (see `svm_layout::VarType::byte_size`).
+///
+/// The layout should be deployed using a `deploy-template` of version `1` (or later),
+/// since version `0` carries no schema.
+///
+pub fn template_layout(code: &[u8]) -> Result<DataLayout, JsonError> {
+    let template = AppTemplate {
+        version: 0,
+        name: String::new(),
+        code: code.to_vec(),
+        data: DataLayout::empty(),
+    };
+
+    let abi = template_abi(&template)?;
+
+    let mut vars = abi["storage"].as_array().cloned().unwrap_or_default();
+    vars.sort_by_key(|var| var["id"].as_u64());
+
+    let mut builder = DataLayoutBuilder::new();
+    let mut next_id = 0;
+
+    for var in vars.iter() {
+        let id = var["id"].as_u64().unwrap_or_default();
+        let count = var["count"].as_u64().unwrap_or_default();
+        let name = var["name"].as_str().unwrap_or_default();
+
+        if id != next_id {
+            return Err(invalid_abi(&format!(
+                "storage var `{}` isn't laid out right after the previous one",
+                name
+            )));
+        }
+
+        let ty = var["type"]
+            .as_str()
+            .and_then(VarType::from_name)
+            .ok_or_else(|| invalid_abi(&format!("storage var `{}` has an unknown type", name)))?;
+
+        for _ in 0..count {
+            builder.add_var(ty.byte_size());
+        }
+
+        builder.add_symbolic_var(name, ty, count as u32);
+
+        next_id += count;
+    }
+
+    Ok(builder.build())
+}
+
+/// Flattens the `storage` records having no enclosing storage (each laid out starting at variable `0`).
+fn flatten_storages(storages: &[(String, Vec<Json>)]) -> Result<Vec<Json>, JsonError> {
+    let records: HashMap<&str, &[Json]> = storages
+        .iter()
+        .map(|(name, vars)| (name.as_str(), vars.as_slice()))
+        .collect();
+
+    let nested: HashSet<&str> = storages
+        .iter()
+        .flat_map(|(_, vars)| vars.iter())
+        .filter_map(|var| var["type"]["storage"].as_str())
+        .collect();
+
+    let mut flat = Vec::new();
+
+    for (name, _) in storages
+        .iter()
+        .filter(|(name, _)| !nested.contains(name.as_str()))
+    {
+        flatten_storage(name, &records, 0, "", &mut Vec::new(), &mut flat)?;
+    }
+
+    Ok(flat)
+}
+
+/// Appends the variables of storage `name` laid out starting at variable `base` to `flat`.
+///
+/// Returns the number of variables spanned by the storage.
+fn flatten_storage<'a>(
+    name: &'a str,
+    records: &HashMap<&'a str, &'a [Json]>,
+    base: u64,
+    prefix: &str,
+    stack: &mut Vec<&'a str>,
+    flat: &mut Vec<Json>,
+) -> Result<u64, JsonError> {
+    if stack.contains(&name) {
+        return Err(invalid_abi(&format!("storage `{}` nests itself", name)));
+    }
+
+    let vars = records
+        .get(name)
+        .ok_or_else(|| invalid_abi(&format!("unknown storage `{}`", name)))?;
+
+    stack.push(name);
+
+    let mut count = 0;
+
+    for var in vars.iter().filter(|var| !var["type"].is_object()) {
+        let (id, var_count) = match (var["id"].as_u64(), var["count"].as_u64()) {
+            (Some(id), Some(count)) => (id, count),
+            _ => return Err(invalid_abi("storage var `id` and `count` must be integers")),
+        };
+
+        let mut var = var.clone();
+        var["id"] = json!(base + id);
+        var["name"] = json!(format!(
+            "{}{}",
+            prefix,
+            var["name"].as_str().unwrap_or_default()
+        ));
+
+        flat.push(var);
+        count += var_count;
+    }
+
+    for var in vars.iter().filter(|var| var["type"].is_object()) {
+        let nested = var["type"]["storage"].as_str().ok_or_else(|| {
+            invalid_abi("nested storage `type` must be a `{\"storage\": <name>}`")
+        })?;
+
+        let prefix = format!("{}{}.", prefix, var["name"].as_str().unwrap_or_default());
+
+        count += flatten_storage(nested, records, base + count, &prefix, stack, flat)?;
+    }
+
+    stack.pop();
+
+    Ok(count)
+}
+
+/// Returns the concatenated payloads of the `svm_abi` custom sections of `code`.
+fn abi_section(code: &[u8]) -> Result<Vec<u8>, JsonError> {
+    let module =
+        parity_wasm::deserialize_buffer::<Module>(code).map_err(|e| JsonError::InvalidField {
+            field: "code".to_string(),
+            reason: e.to_string(),
+        })?;
+
+    let mut payload = Vec::new();
+    let mut found = false;
+
+    for section in module.custom_sections() {
+        if section.name() == ABI_SECTION {
+            payload.extend_from_slice(section.payload());
+            found = true;
+        }
+    }
+
+    if found {
+        Ok(payload)
+    } else {
+        Err(invalid_abi(&format!(
+            "missing the `{}` custom section",
+            ABI_SECTION
+        )))
+    }
+}
+
+fn invalid_abi(reason: &str) -> JsonError {
+    JsonError::InvalidField {
+        field: "abi".to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use parity_wasm::builder;
+    use parity_wasm::elements::{CustomSection, Section};
+
+    use svm_layout::DataLayout;
+
+    fn template_with(sections: &[&str]) -> AppTemplate {
+        let mut module = builder::module().build();
+
+        for payload in sections.iter() {
+            let section = CustomSection::new(ABI_SECTION.to_string(), payload.as_bytes().to_vec());
+
+            module.sections_mut().push(Section::Custom(section));
+        }
+
+        AppTemplate {
+            version: 0,
+            name: "My Template".to_string(),
+            code: parity_wasm::serialize(module).unwrap(),
+            data: DataLayout::empty(),
+        }
+    }
+
+    #[test]
+    fn template_abi_valid() {
+        let work = r#"{"endpoint":{"name":"work","ctor":false,"params":[{"name":"a","type":"amount"}],"returns":["amount"]}}"#;
+        let init = r#"{"endpoint":{"name":"init","ctor":true,"params":[],"returns":[]}}"#;
+        let storage = r#"{"storage":{"name":"Vars","vars":[{"id":0,"name":"scores","type":"u32","count":3}]}}"#;
+        let point = r#"{"struct":{"name":"Point","fields":[{"name":"x","type":"u32"}]}}"#;
+
+        // the records may be split across a couple of sections
+        let template = template_with(&[
+            &format!("{}\n{}\n", work, storage),
+            &format!("{}\n{}\n", init, point),
+        ]);
+
+        let abi = template_abi(&template).unwrap();
+
+        assert_eq!(
+            abi,
+            json!({
+                "endpoints": [
+                    { "name": "init", "ctor": true, "params": [], "returns": [] },
+                    {
+                        "name": "work",
+                        "ctor": false,
+                        "params": [{ "name": "a", "type": "amount" }],
+                        "returns": ["amount"]
+                    }
+                ],
+                "structs": [
+                    { "name": "Point", "fields": [{ "name": "x", "type": "u32" }] }
+                ],
+                "storage": [
+                    { "id": 0, "name": "scores", "type": "u32", "count": 3 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn template_abi_nested_storage() {
+        let inner = r#"{"storage":{"name":"Inner","vars":[{"id":0,"name":"a","type":"u32","count":1},{"id":1,"name":"b","type":"u64","count":2}]}}"#;
+        let outer = r#"{"storage":{"name":"Outer","vars":[{"id":0,"name":"x","type":"bool","count":1},{"name":"first","type":{"storage":"Inner"}},{"id":1,"name":"y","type":"Amount","count":1},{"name":"second","type":{"storage":"Inner"}}]}}"#;
+
+        let template = template_with(&[&format!("{}\n{}\n", inner, outer)]);
+
+        let abi = template_abi(&template).unwrap();
+
+        assert_eq!(
+            abi["storage"],
+            json!([
+                { "id": 0, "name": "x", "type": "bool", "count": 1 },
+                { "id": 1, "name": "y", "type": "Amount", "count": 1 },
+                { "id": 2, "name": "first.a", "type": "u32", "count": 1 },
+                { "id": 3, "name": "first.b", "type": "u64", "count": 2 },
+                { "id": 5, "name": "second.a", "type": "u32", "count": 1 },
+                { "id": 6, "name": "second.b", "type": "u64", "count": 2 }
+            ])
+        );
+    }
+
+    #[test]
+    fn template_layout_valid() {
+        let inner =
+            r#"{"storage":{"name":"Inner","vars":[{"id":0,"name":"a","type":"u32","count":1}]}}"#;
+        let outer = r#"{"storage":{"name":"Outer","vars":[{"id":0,"name":"owner","type":"Address","count":1},{"name":"inner","type":{"storage":"Inner"}},{"id":1,"name":"scores","type":"u16","count":2}]}}"#;
+
+        let template = template_with(&[&format!("{}\n{}\n", inner, outer)]);
+
+        let layout = template_layout(&template.code).unwrap();
+
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[20, 2, 2, 4]);
+        builder.add_symbolic_var("owner", VarType::Address, 1);
+        builder.add_symbolic_var("scores", VarType::U16, 2);
+        builder.add_symbolic_var("inner.a", VarType::U32, 1);
+
+        assert_eq!(layout, builder.build());
+    }
+
+    #[test]
+    fn template_layout_overlapping_storages() {
+        let first =
+            r#"{"storage":{"name":"First","vars":[{"id":0,"name":"a","type":"u32","count":1}]}}"#;
+        let second =
+            r#"{"storage":{"name":"Second","vars":[{"id":0,"name":"b","type":"u32","count":1}]}}"#;
+
+        let template = template_with(&[&format!("{}\n{}\n", first, second)]);
+
+        assert!(template_layout(&template.code).is_err());
+    }
+
+    #[test]
+    fn template_abi_unknown_nested_storage() {
+        let outer =
+            r#"{"storage":{"name":"Outer","vars":[{"name":"inner","type":{"storage":"Inner"}}]}}"#;
+
+        let template = template_with(&[&format!("{}\n", outer)]);
+
+        assert_eq!(
+            template_abi(&template),
+            Err(JsonError::InvalidField {
+                field: "abi".to_string(),
+                reason: "unknown storage `Inner`".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn template_abi_missing_section() {
+        let template = template_with(&[]);
+
+        assert_eq!(
+            template_abi(&template),
+            Err(JsonError::InvalidField {
+                field: "abi".to_string(),
+                reason: "missing the `svm_abi` custom section".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn template_abi_invalid_record() {
+        let template = template_with(&["{\"unknown\":{}}\n"]);
+
+        assert!(template_abi(&template).is_err());
+
+        let template = template_with(&["not json\n"]);
+
+        assert!(template_abi(&template).is_err());
+    }
+
+    #[test]
+    fn template_abi_invalid_code() {
+        let mut template = template_with(&[]);
+        template.code = vec![0xFF, 0xFF];
+
+        assert!(matches!(
+            template_abi(&template),
+            Err(JsonError::InvalidField { field, .. }) if field == "code"
+        ));
+    }
+}
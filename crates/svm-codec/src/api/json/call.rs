@@ -0,0 +1,607 @@
+use serde_json::Value as Json;
+use serde_json::{json, Map};
+
+use svm_abi_decoder::{Cursor, Decoder};
+use svm_abi_encoder::Encoder;
+use svm_sdk::value::{Composite, Primitive, Value};
+
+use crate::api::json::calldata::{encode_value, value_as_json};
+use crate::api::json::{self, JsonError};
+
+///
+/// Encodes the `calldata` of calling function `func_name` with named `params`.
+///
+/// The function's signature is looked-up in `abi` (a template ABI as returned by `template_abi`).
+/// Each of its parameters must be given exactly once in `params`, i.e:
+///
+/// ```json
+/// { "a": 10, "to_double": true }
+/// ```
+///
+/// A struct parameter (of type `{"struct": "<name>"}`) is given as an object holding each of its fields,
+/// and it's encoded as a `Tuple` (the struct's fields are looked-up in the `structs` of `abi`).
+///
+/// The result is encoded just like `encode_calldata` does, and returned as `{ "calldata": "..." }`.
+///
+pub fn encode_call(abi: &Json, func_name: &str, params: &Json) -> Result<Json, JsonError> {
+    let endpoint = find_endpoint(abi, func_name)?;
+    let decls = json::as_array(endpoint, "params")?;
+
+    let params = params.as_object().ok_or(JsonError::InvalidField {
+        field: "params".to_string(),
+        reason: "value isn't an Object".to_string(),
+    })?;
+
+    if let Some(name) = params
+        .keys()
+        .find(|name| decls.iter().all(|decl| decl["name"] != **name))
+    {
+        return Err(JsonError::InvalidField {
+            field: "params".to_string(),
+            reason: format!("unknown parameter `{}` of function `{}`", name, func_name),
+        });
+    }
+
+    let mut buf = Vec::new();
+
+    let nargs = decls.len() as u8;
+    buf.push(nargs);
+
+    for decl in decls.iter() {
+        let name = json::as_typed::<String>(decl, "name")?;
+
+        let raw = params.get(&name).ok_or(JsonError::InvalidField {
+            field: "params".to_string(),
+            reason: format!("missing parameter `{}` of function `{}`", name, func_name),
+        })?;
+
+        let value = encode_typed(abi, &decl["type"], raw).map_err(|e| param_error(&name, e))?;
+        value.encode(&mut buf);
+    }
+
+    let calldata = json::bytes_to_str(&buf);
+    let json = json!({ "calldata": calldata });
+
+    Ok(json)
+}
+
+///
+/// Decodes the `returndata` of function `func_name`.
+///
+/// The function's return types are looked-up in `abi` (a template ABI as returned by `template_abi`),
+/// and each decoded value must match its declared type. A returned struct is rendered as an object.
+///
+/// Returns the values as `{ "returns": [...] }`.
+///
+pub fn decode_returns(abi: &Json, func_name: &str, returndata: &[u8]) -> Result<Json, JsonError> {
+    let endpoint = find_endpoint(abi, func_name)?;
+    let types = json::as_array(endpoint, "returns")?;
+
+    let decoder = Decoder::new();
+    let mut cursor = Cursor::new(returndata);
+
+    let mut returns = Vec::new();
+
+    for (i, ty) in types.iter().enumerate() {
+        let value = decoder
+            .decode_value(&mut cursor)
+            .map_err(|_| returndata_error(format!("invalid encoding of return value #{}", i)))?;
+
+        let value = typed_as_json(abi, ty, &value).ok_or_else(|| {
+            returndata_error(format!("return value #{} isn't of type `{}`", i, ty))
+        })?;
+
+        returns.push(value);
+    }
+
+    if !cursor.is_eof() {
+        return Err(returndata_error(format!(
+            "function `{}` returns {} value(s) but there are more",
+            func_name,
+            types.len()
+        )));
+    }
+
+    let json = json!({ "returns": returns });
+
+    Ok(json)
+}
+
+///
+/// Decodes `calldata` holding the arguments of function `func_name` (encoded one after the other).
+///
+/// The function's parameters are looked-up in `abi` (a template ABI as returned by `template_abi`),
+/// and each decoded argument must match its parameter's type. A struct argument is rendered as an object.
+///
+/// Returns the arguments by name as `{ "params": {...} }` (the counterpart of `encode_call`'s `params`).
+///
+pub fn decode_params(abi: &Json, func_name: &str, calldata: &[u8]) -> Result<Json, JsonError> {
+    let endpoint = find_endpoint(abi, func_name)?;
+    let decls = json::as_array(endpoint, "params")?;
+
+    let decoder = Decoder::new();
+    let mut cursor = Cursor::new(calldata);
+
+    let mut params = Map::new();
+
+    for (i, decl) in decls.iter().enumerate() {
+        let name = json::as_typed::<String>(decl, "name")?;
+        let ty = &decl["type"];
+
+        let value = decoder
+            .decode_value(&mut cursor)
+            .map_err(|_| calldata_error(format!("missing or invalid argument #{}", i)))?;
+
+        let value = typed_as_json(abi, ty, &value)
+            .ok_or_else(|| calldata_error(format!("argument #{} isn't of type `{}`", i, ty)))?;
+
+        params.insert(name, value);
+    }
+
+    if !cursor.is_eof() {
+        return Err(calldata_error(format!(
+            "function `{}` expects {} argument(s) but there are more",
+            func_name,
+            decls.len()
+        )));
+    }
+
+    let json = json!({ "params": params });
+
+    Ok(json)
+}
+
+fn find_endpoint<'a>(abi: &'a Json, func_name: &str) -> Result<&'a Json, JsonError> {
+    let endpoints = json::as_array(abi, "endpoints")?;
+
+    endpoints
+        .iter()
+        .find(|endpoint| endpoint["name"] == func_name)
+        .ok_or(JsonError::InvalidField {
+            field: "func_name".to_string(),
+            reason: format!("unknown function `{}`", func_name),
+        })
+}
+
+fn find_struct<'a>(abi: &'a Json, name: &Json) -> Result<&'a Vec<Json>, JsonError> {
+    let name = name.as_str().unwrap_or_default();
+
+    let not_found = || JsonError::InvalidField {
+        field: "abi".to_string(),
+        reason: format!("unknown struct `{}`", name),
+    };
+
+    let structs = abi["structs"].as_array().ok_or_else(not_found)?;
+
+    structs
+        .iter()
+        .find(|s| s["name"] == name)
+        .and_then(|s| s["fields"].as_array())
+        .ok_or_else(not_found)
+}
+
+/// Encodes `value` of type `ty`, resolving the structs types using `abi`.
+fn encode_typed(abi: &Json, ty: &Json, value: &Json) -> Result<Value<'static>, JsonError> {
+    let invalid = |reason: String| JsonError::InvalidField {
+        field: "params".to_string(),
+        reason,
+    };
+
+    if let Some(name) = ty.get("struct") {
+        let decls = find_struct(abi, name)?;
+        let name = name.as_str().unwrap_or_default();
+
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid(format!("value `{}` isn't an Object", value)))?;
+
+        if let Some(field) = object
+            .keys()
+            .find(|field| decls.iter().all(|decl| decl["name"] != **field))
+        {
+            return Err(invalid(format!(
+                "unknown field `{}` of struct `{}`",
+                field, name
+            )));
+        }
+
+        let mut fields = Vec::new();
+
+        for decl in decls.iter() {
+            let field = json::as_typed::<String>(decl, "name")?;
+
+            let raw = object.get(&field).ok_or_else(|| {
+                invalid(format!("missing field `{}` of struct `{}`", field, name))
+            })?;
+
+            fields.push(encode_typed(abi, &decl["type"], raw)?);
+        }
+
+        return Ok(Value::tuple(fields));
+    }
+
+    let types = ty
+        .as_array()
+        .or_else(|| ty.get("tuple").and_then(Json::as_array));
+
+    let types = match types {
+        Some(types) => types,
+        None => return encode_value(ty, value),
+    };
+
+    let items = value
+        .as_array()
+        .ok_or_else(|| invalid(format!("value `{}` isn't an Array", value)))?;
+
+    if ty.is_array() {
+        if types.len() != 1 {
+            return Err(invalid(format!("invalid ABI array type: `{}`", ty)));
+        }
+
+        let array = items
+            .iter()
+            .map(|item| encode_typed(abi, &types[0], item))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Value::Composite(Composite::ArrayOwned(array)))
+    } else {
+        if types.len() != items.len() {
+            return Err(invalid(format!(
+                "expected a tuple of {} items",
+                types.len()
+            )));
+        }
+
+        let fields = types
+            .iter()
+            .zip(items)
+            .map(|(ty, item)| encode_typed(abi, ty, item))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Value::tuple(fields))
+    }
+}
+
+/// Renders `value` as JSON given it's of type `ty` (resolving the structs types using `abi`).
+/// Returns `None` when `value` doesn't match `ty`.
+fn typed_as_json(abi: &Json, ty: &Json, value: &Value) -> Option<Json> {
+    match value {
+        Value::Primitive(Primitive::None) => None,
+        Value::Composite(Composite::Array(items)) => array_as_json(abi, ty, items),
+        Value::Composite(Composite::ArrayOwned(items)) => array_as_json(abi, ty, items),
+        Value::Composite(Composite::Tuple(items)) => {
+            if let Some(name) = ty.get("struct") {
+                let decls = find_struct(abi, name).ok()?;
+
+                if decls.len() != items.len() {
+                    return None;
+                }
+
+                let mut object = Map::new();
+
+                for (decl, item) in decls.iter().zip(items) {
+                    let field = decl["name"].as_str()?.to_string();
+                    let value = typed_as_json(abi, &decl["type"], item)?;
+
+                    object.insert(field, value);
+                }
+
+                Some(Json::Object(object))
+            } else {
+                let types = ty.get("tuple")?.as_array()?;
+
+                if types.len() != items.len() {
+                    return None;
+                }
+
+                types
+                    .iter()
+                    .zip(items)
+                    .map(|(ty, item)| typed_as_json(abi, ty, item))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Json::Array)
+            }
+        }
+        _ => {
+            let (actual, json) = value_as_json(value).ok()?;
+
+            if actual == *ty {
+                Some(json)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn array_as_json(abi: &Json, ty: &Json, items: &[Value]) -> Option<Json> {
+    match ty.as_array() {
+        Some(types) if types.len() == 1 => items
+            .iter()
+            .map(|item| typed_as_json(abi, &types[0], item))
+            .collect::<Option<Vec<_>>>()
+            .map(Json::Array),
+        _ => None,
+    }
+}
+
+fn param_error(name: &str, err: JsonError) -> JsonError {
+    let reason = match err {
+        JsonError::InvalidField { reason, .. } => reason,
+        JsonError::InvalidJson(reason) | JsonError::Unknown(reason) => reason,
+    };
+
+    JsonError::InvalidField {
+        field: name.to_string(),
+        reason,
+    }
+}
+
+fn calldata_error(reason: String) -> JsonError {
+    JsonError::InvalidField {
+        field: "calldata".to_string(),
+        reason,
+    }
+}
+
+fn returndata_error(reason: String) -> JsonError {
+    JsonError::InvalidField {
+        field: "returndata".to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_sdk::{Address, Amount};
+
+    use crate::api::json::decode_calldata;
+
+    fn abi() -> Json {
+        json!({
+            "endpoints": [
+                {
+                    "name": "init",
+                    "ctor": true,
+                    "params": [],
+                    "returns": []
+                },
+                {
+                    "name": "transfer",
+                    "ctor": false,
+                    "params": [
+                        { "name": "to", "type": "address" },
+                        { "name": "amount", "type": "amount" },
+                        { "name": "memo", "type": ["u8"] }
+                    ],
+                    "returns": ["bool", ["u32"]]
+                },
+                {
+                    "name": "draw",
+                    "ctor": false,
+                    "params": [{ "name": "shape", "type": { "struct": "Shape" } }],
+                    "returns": [{ "struct": "Point" }, "string"]
+                }
+            ],
+            "structs": [
+                {
+                    "name": "Point",
+                    "fields": [
+                        { "name": "x", "type": "u32" },
+                        { "name": "y", "type": "i16" }
+                    ]
+                },
+                {
+                    "name": "Shape",
+                    "fields": [
+                        { "name": "tag", "type": "bytes" },
+                        { "name": "points", "type": [{ "struct": "Point" }] }
+                    ]
+                }
+            ],
+            "storage": []
+        })
+    }
+
+    const ADDR: &str = "1020304050607080900010203040506070809000";
+
+    #[test]
+    fn encode_call_named_params() {
+        // the params order doesn't matter
+        let params = json!({ "memo": [1, 2], "amount": 100, "to": ADDR });
+        let encoded = encode_call(&abi(), "transfer", &params).unwrap();
+
+        assert_eq!(
+            decode_calldata(&encoded).unwrap(),
+            json!({
+                "abi": ["address", "amount", ["u8"]],
+                "data": [ADDR, 100, [1, 2]]
+            })
+        );
+
+        let encoded = encode_call(&abi(), "init", &json!({})).unwrap();
+        assert_eq!(encoded, json!({ "calldata": "00" }));
+    }
+
+    #[test]
+    fn encode_call_invalid() {
+        let params = json!({ "to": ADDR, "amount": 100, "memo": [] });
+
+        assert_eq!(
+            encode_call(&abi(), "burn", &params),
+            Err(JsonError::InvalidField {
+                field: "func_name".to_string(),
+                reason: "unknown function `burn`".to_string(),
+            })
+        );
+
+        let missing = json!({ "to": ADDR, "amount": 100 });
+        assert_eq!(
+            encode_call(&abi(), "transfer", &missing),
+            Err(JsonError::InvalidField {
+                field: "params".to_string(),
+                reason: "missing parameter `memo` of function `transfer`".to_string(),
+            })
+        );
+
+        let unknown = json!({ "to": ADDR, "amount": 100, "memo": [], "fee": 1 });
+        assert_eq!(
+            encode_call(&abi(), "transfer", &unknown),
+            Err(JsonError::InvalidField {
+                field: "params".to_string(),
+                reason: "unknown parameter `fee` of function `transfer`".to_string(),
+            })
+        );
+
+        let wrong_type = json!({ "to": ADDR, "amount": true, "memo": [] });
+        assert!(matches!(
+            encode_call(&abi(), "transfer", &wrong_type),
+            Err(JsonError::InvalidField { field, .. }) if field == "amount"
+        ));
+
+        assert!(encode_call(&abi(), "transfer", &json!([ADDR, 100, []])).is_err());
+    }
+
+    #[test]
+    fn decode_returns_valid() {
+        let mut returndata = Vec::new();
+        true.encode(&mut returndata);
+        vec![10u32, 20u32].encode(&mut returndata);
+
+        assert_eq!(
+            decode_returns(&abi(), "transfer", &returndata).unwrap(),
+            json!({ "returns": [true, [10, 20]] })
+        );
+
+        assert_eq!(
+            decode_returns(&abi(), "init", &[]).unwrap(),
+            json!({ "returns": [] })
+        );
+    }
+
+    #[test]
+    fn decode_returns_invalid() {
+        // type mismatch
+        let mut returndata = Vec::new();
+        10u32.encode(&mut returndata);
+        vec![10u32].encode(&mut returndata);
+
+        assert!(decode_returns(&abi(), "transfer", &returndata).is_err());
+
+        // missing return value
+        let mut returndata = Vec::new();
+        true.encode(&mut returndata);
+
+        assert!(decode_returns(&abi(), "transfer", &returndata).is_err());
+
+        // too many return values
+        let mut returndata = Vec::new();
+        true.encode(&mut returndata);
+
+        assert!(decode_returns(&abi(), "init", &returndata).is_err());
+
+        // `None` isn't a valid return value
+        let mut returndata = Vec::new();
+        None::<bool>.encode(&mut returndata);
+
+        assert!(decode_returns(&abi(), "transfer", &returndata).is_err());
+    }
+
+    #[test]
+    fn decode_params_valid() {
+        let mut calldata = Vec::new();
+        Address::from([0x10u8; 20]).encode(&mut calldata);
+        Amount(100).encode(&mut calldata);
+        vec![1u8, 2u8].encode(&mut calldata);
+
+        let params = decode_params(&abi(), "transfer", &calldata).unwrap();
+
+        assert_eq!(params["params"]["amount"], json!(100));
+        assert_eq!(params["params"]["memo"], json!([1, 2]));
+
+        assert_eq!(
+            decode_params(&abi(), "init", &[]).unwrap(),
+            json!({ "params": {} })
+        );
+    }
+
+    #[test]
+    fn decode_params_invalid() {
+        // type mismatch
+        let mut calldata = Vec::new();
+        10u32.encode(&mut calldata);
+        Amount(100).encode(&mut calldata);
+        vec![1u8].encode(&mut calldata);
+
+        assert!(decode_params(&abi(), "transfer", &calldata).is_err());
+
+        // missing argument
+        let mut calldata = Vec::new();
+        Address::from([0x10u8; 20]).encode(&mut calldata);
+
+        assert!(decode_params(&abi(), "transfer", &calldata).is_err());
+
+        // too many arguments
+        let mut calldata = Vec::new();
+        true.encode(&mut calldata);
+
+        assert!(decode_params(&abi(), "init", &calldata).is_err());
+    }
+
+    #[test]
+    fn encode_call_struct_params() {
+        let shape = json!({
+            "points": [{ "x": 1, "y": -1 }, { "y": 3, "x": 2 }],
+            "tag": "ABCD"
+        });
+        let encoded = encode_call(&abi(), "draw", &json!({ "shape": shape })).unwrap();
+
+        assert_eq!(
+            decode_calldata(&encoded).unwrap(),
+            json!({
+                "abi": [{ "tuple": ["bytes", [{ "tuple": ["u32", "i16"] }]] }],
+                "data": [["ABCD", [[1, -1], [2, 3]]]]
+            })
+        );
+
+        let missing = json!({ "shape": { "tag": "AB" } });
+        assert_eq!(
+            encode_call(&abi(), "draw", &missing),
+            Err(JsonError::InvalidField {
+                field: "shape".to_string(),
+                reason: "missing field `points` of struct `Shape`".to_string(),
+            })
+        );
+
+        let unknown = json!({ "shape": { "tag": "AB", "points": [], "color": 1 } });
+        assert!(encode_call(&abi(), "draw", &unknown).is_err());
+
+        let not_object = json!({ "shape": ["AB", []] });
+        assert!(encode_call(&abi(), "draw", &not_object).is_err());
+    }
+
+    #[test]
+    fn decode_returns_struct() {
+        let point = Value::tuple(vec![10u32.into(), (-5i16).into()]);
+
+        let mut returndata = Vec::new();
+        point.encode(&mut returndata);
+        "done".encode(&mut returndata);
+
+        assert_eq!(
+            decode_returns(&abi(), "draw", &returndata).unwrap(),
+            json!({ "returns": [{ "x": 10, "y": -5 }, "done"] })
+        );
+
+        // a struct with a missing field
+        let point = Value::tuple(vec![10u32.into()]);
+
+        let mut returndata = Vec::new();
+        point.encode(&mut returndata);
+        "done".encode(&mut returndata);
+
+        assert!(decode_returns(&abi(), "draw", &returndata).is_err());
+    }
+}
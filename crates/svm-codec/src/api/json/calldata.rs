@@ -4,7 +4,7 @@ use serde_json::Value as Json;
 use svm_abi_decoder::{Cursor, Decoder};
 use svm_abi_encoder::Encoder;
 use svm_sdk::value::{Composite, Primitive, Value};
-use svm_sdk::Address;
+use svm_sdk::{Address, Amount, Bytes};
 
 use crate::api::json::{self, JsonError};
 use crate::api::raw;
@@ -52,20 +52,28 @@ pub fn encode_calldata(json: &Json) -> Result<Json, JsonError> {
 }
 
 pub fn decode_calldata(json: &Json) -> Result<Json, JsonError> {
-    let data = json::as_string(json, "calldata")?;
-    let calldata = json::str_to_bytes(&data, "calldata")?;
+    let calldata = json::as_blob(json, "calldata")?;
 
-    let nargs = calldata[0];
+    let (&nargs, calldata) = calldata.split_first().ok_or(JsonError::InvalidField {
+        field: "calldata".to_string(),
+        reason: "missing the number of arguments".to_string(),
+    })?;
 
     let mut decoder = Decoder::new();
-    let mut cursor = Cursor::new(&calldata[1..]);
+    let mut cursor = Cursor::new(calldata);
 
     let mut abi: Vec<Json> = Vec::new();
     let mut data: Vec<Json> = Vec::new();
 
     for _ in 0..nargs {
-        let value: Value = decoder.decode_value(&mut cursor).unwrap();
-        let (ty, item) = value_as_json(&value);
+        let value: Value =
+            decoder
+                .decode_value(&mut cursor)
+                .map_err(|e| JsonError::InvalidField {
+                    field: "calldata".to_string(),
+                    reason: format!("{:?}", e),
+                })?;
+        let (ty, item) = value_as_json(&value)?;
 
         abi.push(ty);
         data.push(item);
@@ -75,15 +83,15 @@ pub fn decode_calldata(json: &Json) -> Result<Json, JsonError> {
     Ok(result)
 }
 
-fn value_as_json(value: &Value) -> (Json, Json) {
+pub(crate) fn value_as_json(value: &Value) -> Result<(Json, Json), JsonError> {
     match value {
         Value::Primitive(p) => primitive_as_json(p),
         Value::Composite(c) => composite_as_json(c),
     }
 }
 
-fn primitive_as_json(p: &Primitive) -> (Json, Json) {
-    match p {
+fn primitive_as_json(p: &Primitive) -> Result<(Json, Json), JsonError> {
+    let json = match p {
         Primitive::Bool(b) => (Json::String("bool".into()), json!(b)),
         Primitive::Amount(a) => (Json::String("amount".into()), json!(a.0)),
         Primitive::I8(n) => (Json::String("i8".into()), json!(n)),
@@ -94,29 +102,44 @@ fn primitive_as_json(p: &Primitive) -> (Json, Json) {
         Primitive::U32(n) => (Json::String("u32".into()), json!(n)),
         Primitive::I64(n) => (Json::String("i64".into()), json!(n)),
         Primitive::U64(n) => (Json::String("u64".into()), json!(n)),
+        Primitive::U128(n) => (Json::String("u128".into()), json!(n.to_string())),
+        Primitive::U256(n) => (Json::String("u256".into()), json!(n.to_string())),
         Primitive::Address(addr) => {
             let s = json::bytes_to_str(addr.as_slice());
             (Json::String("address".into()), json!(s))
         }
-        Primitive::None => unreachable!(),
-    }
+        Primitive::None => {
+            return Err(JsonError::InvalidField {
+                field: "calldata".to_string(),
+                reason: "`None` values are not supported".to_string(),
+            })
+        }
+    };
+
+    Ok(json)
 }
 
-fn composite_as_json(c: &Composite<'_>) -> (Json, Json) {
+fn composite_as_json(c: &Composite<'_>) -> Result<(Json, Json), JsonError> {
     let array: &[Value] = match c {
         Composite::Array(inner) => inner,
         Composite::ArrayOwned(inner) => inner,
+        Composite::Tuple(fields) => return tuple_as_json(fields),
+        Composite::Bytes(bytes) => {
+            let s = json::bytes_to_str(bytes);
+            return Ok((Json::String("bytes".into()), json!(s)));
+        }
+        Composite::String(s) => return Ok((Json::String("string".into()), json!(s))),
     };
 
     if (array.is_empty()) {
-        return (Json::Null, Json::Array(Vec::new()));
+        return Ok((Json::Null, Json::Array(Vec::new())));
     }
 
     let mut types: Vec<Json> = Vec::new();
     let mut values: Vec<Json> = Vec::new();
 
     for elem in array {
-        let (ty, value) = value_as_json(elem);
+        let (ty, value) = value_as_json(elem)?;
 
         types.push(ty);
         values.push(value);
@@ -125,36 +148,59 @@ fn composite_as_json(c: &Composite<'_>) -> (Json, Json) {
     // TODO: assert that all `types` are the same
     let ty = types.pop().unwrap();
 
-    (Json::Array(vec![ty]), Json::Array(values))
+    Ok((Json::Array(vec![ty]), Json::Array(values)))
+}
+
+fn tuple_as_json(fields: &[Value]) -> Result<(Json, Json), JsonError> {
+    let (types, values): (Vec<Json>, Vec<Json>) = fields
+        .iter()
+        .map(value_as_json)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
+
+    Ok((json!({ "tuple": types }), Json::Array(values)))
 }
 
-fn encode_value<'a>(ty: &Json, value: &Json) -> Result<Value<'static>, JsonError> {
+pub(crate) fn encode_value<'a>(ty: &Json, value: &Json) -> Result<Value<'static>, JsonError> {
     if ty.is_array() {
         return encode_array(ty, value);
     }
 
+    if let Some(types) = ty.get("tuple") {
+        return encode_tuple(types, value);
+    }
+
     let ty = as_str!(ty)?;
     let json = json!({ "calldata": value });
 
     macro_rules! encode {
-        ($func:ident) => {{
-            json::$func(&json, "calldata")?.into()
+        ($ty:ty) => {{
+            json::as_typed::<$ty>(&json, "calldata")?.into()
         }};
     }
 
     let value: Value = match ty {
-        "bool" => encode!(as_bool),
-        "i8" => encode!(as_i8),
-        "u8" => encode!(as_u8),
-        "i16" => encode!(as_i16),
-        "u16" => encode!(as_u16),
-        "i32" => encode!(as_i32),
-        "u32" => encode!(as_u32),
-        "i64" => encode!(as_i64),
-        "u64" => encode!(as_u64),
-        "amount" => encode!(as_amount),
+        "bool" => encode!(bool),
+        "i8" => encode!(i8),
+        "u8" => encode!(u8),
+        "i16" => encode!(i16),
+        "u16" => encode!(u16),
+        "i32" => encode!(i32),
+        "u32" => encode!(u32),
+        "i64" => encode!(i64),
+        "u64" => encode!(u64),
+        "u128" => json::as_u128(&json, "calldata")?.into(),
+        "u256" => json::as_u256(&json, "calldata")?.into(),
+        "amount" => Amount(json::as_typed(&json, "calldata")?).into(),
+        "string" => encode!(String),
+        "bytes" => {
+            let bytes = json::as_blob(&json, "calldata")?;
+
+            Bytes(bytes).into()
+        }
         "address" => {
-            let addr: svm_types::Address = json::as_addr(&json, "calldata")?;
+            let addr: svm_types::Address = json::as_typed(&json, "calldata")?;
 
             let bytes = addr.bytes();
             let addr: Address = bytes.into();
@@ -194,6 +240,31 @@ fn encode_array(ty: &Json, value: &Json) -> Result<Value<'static>, JsonError> {
     Ok(Value::Composite(c))
 }
 
+fn encode_tuple(types: &Json, value: &Json) -> Result<Value<'static>, JsonError> {
+    let types = types.as_array().ok_or(JsonError::InvalidField {
+        field: "abi".to_string(),
+        reason: format!("invalid ABI tuple: `{}`", types),
+    })?;
+
+    let json = json!({ "calldata": value });
+    let items = json::as_array(&json, "calldata")?;
+
+    if types.len() != items.len() {
+        return Err(JsonError::InvalidField {
+            field: "calldata".to_string(),
+            reason: format!("expected a tuple of {} items", types.len()),
+        });
+    }
+
+    let fields = types
+        .iter()
+        .zip(items)
+        .map(|(ty, item)| encode_value(ty, item))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Value::tuple(fields))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +315,44 @@ mod tests {
         test!(["u64"], [std::u64::MAX as usize]);
     }
 
+    #[test]
+    pub fn encode_calldata_u128_u256() {
+        let u128_max = "340282366920938463463374607431768211455";
+        let u256_max =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+        test!(["u128", "u256"], ["0", "1"]);
+        test!(["u128", "u256"], [u128_max, u256_max]);
+        test!([["u256"]], [[u128_max, u256_max]]);
+    }
+
+    #[test]
+    pub fn encode_calldata_u128_u256_from_numbers() {
+        let json = json!({"abi": ["u128", "u256"], "data": [10, std::u64::MAX] });
+
+        let encoded = encode_calldata(&json).unwrap();
+        let decoded = decode_calldata(&encoded).unwrap();
+
+        assert_eq!(
+            decoded,
+            json!({"abi": ["u128", "u256"], "data": ["10", std::u64::MAX.to_string()] })
+        );
+    }
+
+    #[test]
+    pub fn encode_calldata_u128_invalid() {
+        let overflow = "340282366920938463463374607431768211456";
+
+        for data in &[json!(-1), json!(1.5), json!("0x10"), json!(overflow)] {
+            let json = json!({"abi": ["u128"], "data": [data] });
+
+            assert!(matches!(
+                encode_calldata(&json),
+                Err(JsonError::InvalidField { .. })
+            ));
+        }
+    }
+
     #[test]
     pub fn encode_calldata_amount() {
         test!(["amount", "amount"], [10 as u64, 20 as u64]);
@@ -262,4 +371,83 @@ mod tests {
         test!([["i8"]], [[-10, 0, 30]]);
         test!([["u32"], ["i8"]], [[10, 20, 30], [-10, 0, 20]]);
     }
+
+    #[test]
+    pub fn encode_calldata_long_array() {
+        let items: Vec<u32> = (0..300).collect();
+        let data = json!([items]);
+
+        test!([["u32"]], data);
+    }
+
+    #[test]
+    pub fn encode_calldata_bytes_and_string() {
+        test!(["bytes", "string"], ["", ""]);
+        test!(["bytes", "string"], ["10203040", "Hello World"]);
+        test!([["bytes"]], [["AB", "CDEF"]]);
+    }
+
+    #[test]
+    pub fn encode_calldata_tuple() {
+        test!(json!([{ "tuple": ["u32", "bool"] }]), json!([[10, true]]));
+        test!(json!([{ "tuple": [] }]), json!([[]]));
+
+        let addr = "1020304050607080900010203040506070809000";
+        let abi = json!([{ "tuple": ["address", { "tuple": ["string", ["u8"]] }] }]);
+        let data = json!([[addr, ["Hi", [1, 2, 3]]]]);
+
+        test!(abi, data);
+    }
+
+    #[test]
+    pub fn encode_calldata_invalid_tuple() {
+        let json = json!({ "abi": [{ "tuple": ["u32", "bool"] }], "data": [[10]] });
+
+        assert!(encode_calldata(&json).is_err());
+
+        let json = json!({ "abi": [{ "tuple": "u32" }], "data": [[10]] });
+
+        assert!(encode_calldata(&json).is_err());
+    }
+
+    #[test]
+    pub fn decode_calldata_empty() {
+        let json = json!({ "calldata": "" });
+
+        assert_eq!(
+            decode_calldata(&json),
+            Err(JsonError::InvalidField {
+                field: "calldata".to_string(),
+                reason: "missing the number of arguments".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    pub fn decode_calldata_missing_values() {
+        let json = json!({"abi": ["bool"], "data": [true] });
+        let encoded = encode_calldata(&json).unwrap();
+
+        // claims `2` arguments while encoding only one
+        let calldata = encoded["calldata"].as_str().unwrap();
+        let json = json!({ "calldata": format!("02{}", &calldata[2..]) });
+
+        assert!(matches!(
+            decode_calldata(&json),
+            Err(JsonError::InvalidField { field, .. }) if field == "calldata"
+        ));
+    }
+
+    #[test]
+    pub fn decode_calldata_none() {
+        let json = json!({ "calldata": "0120" });
+
+        assert_eq!(
+            decode_calldata(&json),
+            Err(JsonError::InvalidField {
+                field: "calldata".to_string(),
+                reason: "`None` values are not supported".to_string(),
+            })
+        );
+    }
 }
@@ -1,6 +1,5 @@
 use svm_nibble::NibbleWriter;
 
-use byteorder::{BigEndian, ByteOrder};
 use serde_json::Value;
 
 use crate::{
@@ -8,31 +7,19 @@ use crate::{
     template,
 };
 
-use svm_layout::{DataLayout, DataLayoutBuilder};
 use svm_types::AppTemplate;
 
 ///
 /// ```json
 /// {
-///   version: 0,   // number
-///   name: '...',  // string
-///   code: '...',  // string (represents a `blob`)
-///   data: '',     // string (represents a `blob`)
+///   version: 0,                       // number
+///   name: '...',                      // string
+///   code: '...',                      // string (represents a `blob`)
+///   data: { vars: [], schema: [] },   // object (the template's `DataLayout`)
 /// }
 /// ```
 pub fn deploy_template(json: &Value) -> Result<Vec<u8>, JsonError> {
-    let version = json::as_u32(json, "version")?;
-    let name = json::as_string(json, "name")?;
-    let code = json::as_blob(json, "code")?;
-    let data = json::as_blob(json, "data")?;
-    let data = to_data_layout(data)?;
-
-    let template = AppTemplate {
-        version,
-        name,
-        code,
-        data,
-    };
+    let template: AppTemplate = json::from_json(json)?;
 
     let mut w = NibbleWriter::new();
     template::encode_deploy_template(&template, &mut w);
@@ -41,26 +28,6 @@ pub fn deploy_template(json: &Value) -> Result<Vec<u8>, JsonError> {
     Ok(bytes)
 }
 
-fn to_data_layout(blob: Vec<u8>) -> Result<DataLayout, JsonError> {
-    if blob.len() % 4 != 0 {
-        return Err(JsonError::InvalidField {
-            field: "data".to_string(),
-            reason: "invalid value".to_string(),
-        });
-    }
-
-    let data: Vec<u32> = blob
-        .chunks_exact(4)
-        .map(|buf| BigEndian::read_u32(&buf))
-        .collect();
-
-    let mut builder = DataLayoutBuilder::new();
-    builder.extend_from_slice(&data);
-    let data = builder.build();
-
-    Ok(data)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,10 +42,7 @@ mod tests {
         let err = deploy_template(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "version".to_string(),
-                reason: "value `null` isn\'t a number".to_string(),
-            }
+            JsonError::InvalidJson("missing field `version`".to_string())
         );
     }
 
@@ -91,10 +55,7 @@ mod tests {
         let err = deploy_template(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "name".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `name`".to_string())
         );
     }
 
@@ -108,10 +69,7 @@ mod tests {
         let err = deploy_template(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "code".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `code`".to_string())
         );
     }
 
@@ -126,10 +84,7 @@ mod tests {
         let err = deploy_template(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "data".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `data`".to_string())
         );
     }
 
@@ -139,7 +94,10 @@ mod tests {
             "version": 0,
             "name": "My Template",
             "code": "C0DE",
-            "data": "0000000100000003"
+            "data": {
+                "vars": [[0, 1], [1, 3]],
+                "schema": []
+            }
         });
 
         let bytes = deploy_template(&json).unwrap();
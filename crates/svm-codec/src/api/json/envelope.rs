@@ -0,0 +1,151 @@
+use svm_nibble::{NibbleIter, NibbleWriter};
+
+use serde_json::Value;
+
+use crate::{
+    api::json::{self, JsonError},
+    api::raw,
+};
+
+use svm_types::{Envelope, PublicKey, Signature};
+
+///
+/// ```json
+/// {
+///   version: 0,               // number
+///   kind: 'exec-app',         // string ('deploy-template' / 'spawn-app' / 'exec-app' / 'upgrade-app')
+///   sender: 'A2FB...',        // string
+///   nonce: 1,                 // number
+///   gas_limit: 1000,          // number
+///   gas_price: 1,             // number
+///   value: 10,                // number
+///   payload: '1C3D...',       // string (the encoded transaction)
+///   public_key: 'E0A1...',    // string (32 bytes)
+///   signature: '90BF...',     // string (64 bytes)
+/// }
+/// ```
+pub fn encode_envelope(json: &Value) -> Result<Vec<u8>, JsonError> {
+    let envelope = json_as_envelope(json, true)?;
+
+    let mut w = NibbleWriter::new();
+    raw::encode_envelope(&envelope, &mut w);
+
+    let bytes = w.into_bytes();
+    Ok(bytes)
+}
+
+/// Returns the bytes to be signed by the `Envelope` sender.
+///
+/// The input is the same as for `encode_envelope`, except that `public_key` and `signature` are ignored.
+pub fn envelope_signing_bytes(json: &Value) -> Result<Vec<u8>, JsonError> {
+    let envelope = json_as_envelope(json, false)?;
+
+    let bytes = raw::envelope_signing_bytes(&envelope);
+    Ok(bytes)
+}
+
+pub fn decode_envelope(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
+
+    let mut iter = NibbleIter::new(&bytes);
+
+    let envelope = raw::decode_envelope(&mut iter).map_err(|e| JsonError::InvalidField {
+        field: "data".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    Ok(json::to_json(&envelope))
+}
+
+fn json_as_envelope(json: &Value, signed: bool) -> Result<Envelope, JsonError> {
+    if signed {
+        return json::from_json(json);
+    }
+
+    // `public_key` and `signature` aren't signed, so they're ignored (and may be missing)
+    let mut json = json.clone();
+
+    if let Some(fields) = json.as_object_mut() {
+        fields.insert(
+            "public_key".to_string(),
+            json::to_json(&PublicKey::from([0; 32])),
+        );
+        fields.insert(
+            "signature".to_string(),
+            json::to_json(&Signature::from([0; 64])),
+        );
+    }
+
+    json::from_json(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn envelope_json() -> Value {
+        json!({
+            "version": 0,
+            "kind": "exec-app",
+            "sender": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "nonce": 1,
+            "gas_limit": 1000,
+            "gas_price": 2,
+            "value": 10,
+            "payload": "A0B0C0",
+            "public_key": "AA".repeat(32),
+            "signature": "BB".repeat(64),
+        })
+    }
+
+    #[test]
+    fn json_envelope_invalid_kind() {
+        let mut json = envelope_json();
+        json["kind"] = json!("transfer");
+
+        let err = encode_envelope(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidJson(
+                "unknown variant `transfer`, expected one of `deploy-template`, `spawn-app`, `exec-app`, `upgrade-app`".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn json_envelope_invalid_signature_length() {
+        let mut json = envelope_json();
+        json["signature"] = json!("BBBB");
+
+        let err = encode_envelope(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidJson("invalid length 2, expected 64 bytes".to_string())
+        );
+    }
+
+    #[test]
+    fn json_envelope_valid() {
+        let json = envelope_json();
+
+        let bytes = encode_envelope(&json).unwrap();
+        let data = json::bytes_to_str(&bytes);
+        let decoded = decode_envelope(&json!({ "data": data })).unwrap();
+
+        assert_eq!(decoded, json);
+    }
+
+    #[test]
+    fn json_envelope_signing_bytes() {
+        let mut json = envelope_json();
+
+        let bytes = encode_envelope(&json).unwrap();
+        let signing_bytes = envelope_signing_bytes(&json).unwrap();
+
+        assert!(bytes.starts_with(&signing_bytes));
+
+        json.as_object_mut().unwrap().remove("signature");
+        assert_eq!(envelope_signing_bytes(&json).unwrap(), signing_bytes);
+    }
+}
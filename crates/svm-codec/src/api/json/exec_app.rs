@@ -20,19 +20,7 @@ use svm_types::{AddressOf, App, AppTransaction, WasmValue};
 /// }
 /// ```
 pub fn encode_exec_app(json: &Value) -> Result<Vec<u8>, JsonError> {
-    let version = json::as_u32(json, "version")?;
-    let app = json::as_addr(json, "app")?.into();
-    let func_name = json::as_string(json, "func_name")?;
-
-    let calldata = json::as_string(json, "calldata")?;
-    let calldata = json::str_to_bytes(&calldata, "calldata")?;
-
-    let tx = AppTransaction {
-        version,
-        app,
-        func_name,
-        calldata,
-    };
+    let tx: AppTransaction = json::from_json(json)?;
 
     let mut w = NibbleWriter::new();
     transaction::encode_exec_app(&tx, &mut w);
@@ -41,26 +29,23 @@ pub fn encode_exec_app(json: &Value) -> Result<Vec<u8>, JsonError> {
     Ok(bytes)
 }
 
+/// Returns the same JSON as given to `encode_exec_app`, except for the `calldata` which is decoded
+/// (see `decode_calldata`).
 pub fn decode_exec_app(json: &Value) -> Result<Value, JsonError> {
-    let data = json::as_string(json, "data")?;
-    let bytes = json::str_to_bytes(&data, "data")?;
+    let bytes = json::as_blob(json, "data")?;
 
     let mut iter = NibbleIter::new(&bytes);
-    let tx = raw::decode_exec_app(&mut iter).unwrap();
 
-    let version = tx.version;
-    let func_name = tx.func_name.clone();
-    let app = json::addr_to_str(&tx.app.inner());
+    let tx = raw::decode_exec_app(&mut iter).map_err(|e| JsonError::InvalidField {
+        field: "data".to_string(),
+        reason: e.to_string(),
+    })?;
 
     let calldata = json::bytes_to_str(&tx.calldata);
     let calldata = json::decode_calldata(&json!({ "calldata": calldata }))?;
 
-    let json = json!({
-        "version": version,
-        "app": app,
-        "func_name": func_name,
-        "calldata": calldata,
-    });
+    let mut json = json::to_json(&tx);
+    json["calldata"] = calldata;
 
     Ok(json)
 }
@@ -80,10 +65,7 @@ mod tests {
         let err = encode_exec_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "version".to_string(),
-                reason: "value `null` isn\'t a number".to_string(),
-            }
+            JsonError::InvalidJson("missing field `version`".to_string())
         );
     }
 
@@ -96,10 +78,7 @@ mod tests {
         let err = encode_exec_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "app".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `app`".to_string())
         );
     }
 
@@ -113,10 +92,7 @@ mod tests {
         let err = encode_exec_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "func_name".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `func_name`".to_string())
         );
     }
 
@@ -131,10 +107,7 @@ mod tests {
         let err = encode_exec_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "calldata".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `calldata`".to_string())
         );
     }
 
@@ -1,21 +1,35 @@
+mod abi;
+mod call;
 mod calldata;
 mod deploy_template;
+mod envelope;
 mod error;
 mod exec_app;
 mod receipt;
+mod snapshot;
 mod spawn_app;
+mod upgrade_app;
 
+pub use abi::{template_abi, template_layout, ABI_SECTION};
+pub use call::{decode_params, decode_returns, encode_call};
 pub use calldata::{decode_calldata, encode_calldata};
 pub use deploy_template::deploy_template;
+pub use envelope::{decode_envelope, encode_envelope, envelope_signing_bytes};
 pub use error::JsonError;
 pub use exec_app::{decode_exec_app, encode_exec_app};
-pub use receipt::decode_receipt;
+pub use receipt::{
+    decode_exec_receipt, decode_receipt, decode_spawn_app_receipt, decode_template_receipt,
+    decode_upgrade_receipt, encode_exec_receipt, encode_receipt, encode_spawn_app_receipt,
+    encode_template_receipt, encode_upgrade_receipt,
+};
+pub use snapshot::{decode_snapshot, render_snapshot};
 pub use spawn_app::{decode_spawn_app, encode_spawn_app};
+pub use upgrade_app::{decode_upgrade_app, encode_upgrade_app};
 
-use serde_json::{json, Value};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 
-use svm_sdk::Amount;
-use svm_types::{gas::MaybeGas, receipt::Log, Address, State};
+use svm_sdk::{U128, U256};
 
 pub(crate) fn to_bytes(json: &Value) -> Result<Vec<u8>, JsonError> {
     match serde_json::to_string(&json) {
@@ -24,176 +38,35 @@ pub(crate) fn to_bytes(json: &Value) -> Result<Vec<u8>, JsonError> {
     }
 }
 
-pub(crate) fn as_bool(json: &Value, field: &str) -> Result<bool, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_bool().ok_or(JsonError::InvalidField {
-        field: field.to_string(),
-        reason: format!("value `{}` isn't a boolean", v),
-    })
-}
-
-pub(crate) fn as_u8(json: &Value, field: &str) -> Result<u8, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_u64()
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        })
-        .and_then(|v| {
-            if v > std::u8::MAX as u64 {
-                Err(JsonError::InvalidField {
-                    field: field.to_string(),
-                    reason: format!("value `{}` doesn't fit into `u8`", v),
-                })
-            } else {
-                Ok(v as u8)
-            }
-        })
-}
-
-pub(crate) fn as_i8(json: &Value, field: &str) -> Result<i8, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_i64()
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        })
-        .and_then(|v| {
-            if v > std::i8::MAX as i64 || v < std::i8::MIN as i64 {
-                Err(JsonError::InvalidField {
-                    field: field.to_string(),
-                    reason: format!("value `{}` doesn't fit into `i8`", v),
-                })
-            } else {
-                Ok(v as i8)
-            }
-        })
-}
-
-pub(crate) fn as_u16(json: &Value, field: &str) -> Result<u16, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_u64()
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        })
-        .and_then(|v| {
-            if v > std::u16::MAX as u64 {
-                Err(JsonError::InvalidField {
-                    field: field.to_string(),
-                    reason: format!("value `{}` doesn't fit into `u16` integer", v),
-                })
-            } else {
-                Ok(v as u16)
-            }
-        })
-}
-
-pub(crate) fn as_i16(json: &Value, field: &str) -> Result<i16, JsonError> {
-    let v: &Value = &json[field];
-
-    match v.as_i64() {
-        None => Err(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        }),
-        Some(v) => {
-            if v > std::i16::MAX as i64 || v < std::i16::MIN as i64 {
-                Err(JsonError::InvalidField {
-                    field: field.to_string(),
-                    reason: format!("value `{}` doesn't fit into `i16` integer", v),
-                })
-            } else {
-                Ok(v as i16)
-            }
-        }
-    }
-}
-
-pub(crate) fn as_u32(json: &Value, field: &str) -> Result<u32, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_u64()
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        })
-        .and_then(|v| {
-            if v > std::u32::MAX as u64 {
-                Err(JsonError::InvalidField {
-                    field: field.to_string(),
-                    reason: format!("value `{}` doesn't fit into `u32` integer", v),
-                })
-            } else {
-                Ok(v as u32)
-            }
-        })
+/// Wide integers are given as decimal strings (a JSON number is accepted too when it fits a `u64`).
+pub(crate) fn as_u128(json: &Value, field: &str) -> Result<U128, JsonError> {
+    as_wide_uint(json, field, "u128", U128::from_dec_str)
 }
 
-pub(crate) fn as_i32(json: &Value, field: &str) -> Result<i32, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_i64()
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        })
-        .and_then(|v| {
-            if v > std::i32::MAX as i64 || v < std::i32::MIN as i64 {
-                Err(JsonError::InvalidField {
-                    field: field.to_string(),
-                    reason: format!("value `{}` doesn't fit into `i32` integer", v),
-                })
-            } else {
-                Ok(v as i32)
-            }
-        })
+pub(crate) fn as_u256(json: &Value, field: &str) -> Result<U256, JsonError> {
+    as_wide_uint(json, field, "u256", U256::from_dec_str)
 }
 
-pub(crate) fn as_u64(json: &Value, field: &str) -> Result<u64, JsonError> {
+fn as_wide_uint<T>(
+    json: &Value,
+    field: &str,
+    ty: &str,
+    from_dec_str: fn(&str) -> Option<T>,
+) -> Result<T, JsonError> {
     let v: &Value = &json[field];
 
-    v.as_u64().ok_or(JsonError::InvalidField {
-        field: field.to_string(),
-        reason: format!("value `{}` isn't a u64 number", v),
-    })
-}
-
-pub(crate) fn as_i64(json: &Value, field: &str) -> Result<i64, JsonError> {
-    let v: &Value = &json[field];
+    let num = match v {
+        Value::String(s) => from_dec_str(s),
+        Value::Number(n) if n.is_u64() => from_dec_str(&n.to_string()),
+        _ => None,
+    };
 
-    v.as_i64().ok_or(JsonError::InvalidField {
+    num.ok_or(JsonError::InvalidField {
         field: field.to_string(),
-        reason: format!("value `{}` isn't a i64 number", v),
+        reason: format!("value `{}` isn't a {} decimal string", v, ty),
     })
 }
 
-pub(crate) fn as_amount(json: &Value, field: &str) -> Result<Amount, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_u64()
-        .map(|v| Amount(v))
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a number", v),
-        })
-}
-
-pub(crate) fn as_string(json: &Value, field: &str) -> Result<String, JsonError> {
-    let v: &Value = &json[field];
-
-    v.as_str()
-        .map(|v| v.to_string())
-        .ok_or(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value `{}` isn't a string", v),
-        })
-}
-
 pub(crate) fn as_array<'a>(json: &'a Value, field: &str) -> Result<&'a Vec<Value>, JsonError> {
     let v: &Value = &json[field];
 
@@ -243,56 +116,26 @@ pub(crate) fn str_to_bytes(v: &str, field: &str) -> Result<Vec<u8>, JsonError> {
 }
 
 pub(crate) fn as_blob(json: &Value, field: &str) -> Result<Vec<u8>, JsonError> {
-    let v = as_string(json, field)?;
+    let v: String = as_typed(json, field)?;
     str_to_bytes(&v, field)
 }
 
-pub(crate) fn as_addr(json: &Value, field: &str) -> Result<Address, JsonError> {
-    let s = as_string(json, field)?;
-    str_as_addr(&s, field)
-}
-
-pub(crate) fn str_as_addr(s: &str, field: &str) -> Result<Address, JsonError> {
-    let bytes = str_to_bytes(s, field)?;
-
-    if bytes.len() != Address::len() {
-        return Err(JsonError::InvalidField {
-            field: field.to_string(),
-            reason: format!("value should be exactly {} hex digits", Address::len() * 2),
-        });
-    }
-
-    let addr: Address = (&bytes[..]).into();
-    Ok(addr)
-}
-
-pub(crate) fn addr_to_str(addr: &Address) -> String {
-    bytes_to_str(addr.as_slice())
-}
-
-pub(crate) fn state_to_str(state: &State) -> String {
-    bytes_to_str(state.as_slice())
+/// Reads `json[field]` using the `serde` representation of `T` (see the `serde` feature of `svm-types`).
+pub(crate) fn as_typed<T: DeserializeOwned>(json: &Value, field: &str) -> Result<T, JsonError> {
+    T::deserialize(&json[field]).map_err(|e| JsonError::InvalidField {
+        field: field.to_string(),
+        reason: e.to_string(),
+    })
 }
 
-pub(crate) fn gas_to_json(gas: &MaybeGas) -> i64 {
-    if gas.is_some() {
-        gas.unwrap() as _
-    } else {
-        (-1)
-    }
+/// Reads the whole `json` using the `serde` representation of `T`.
+pub(crate) fn from_json<T: DeserializeOwned>(json: &Value) -> Result<T, JsonError> {
+    T::deserialize(json).map_err(|e| JsonError::InvalidJson(e.to_string()))
 }
 
-pub(crate) fn logs_to_json(logs: &[Log]) -> Vec<Value> {
-    logs.iter()
-        .map(|log| {
-            let msg = unsafe { String::from_utf8_unchecked(log.msg.clone()) };
-
-            json!({
-                "msg": msg,
-                "code": log.code
-            })
-        })
-        .collect()
+/// Returns the `serde` representation of `value` as JSON.
+pub(crate) fn to_json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("`svm-types` values are always representable as JSON")
 }
 
 #[cfg(test)]
@@ -301,120 +144,60 @@ mod test {
 
     use serde_json::json;
 
-    #[test]
-    fn json_as_u16_valid() {
-        let json = json!({
-            "n": 10
-        });
-
-        let n = as_u16(&json, "n").unwrap();
-        assert_eq!(n, 10u16);
-    }
-
-    #[test]
-    fn json_as_u16_invalid_field() {
-        let json = json!({
-            "n": "NaN"
-        });
-
-        let err = as_u16(&json, "n").unwrap_err();
-        assert_eq!(
-            err,
-            JsonError::InvalidField {
-                field: "n".to_string(),
-                reason: r#"value `"NaN"` isn't a number"#.to_string()
-            }
-        );
-    }
+    use svm_types::Address;
 
     #[test]
-    fn json_as_u32_valid() {
+    fn json_as_typed_valid() {
         let json = json!({
             "n": 10
         });
 
-        let n = as_u32(&json, "n").unwrap();
-        assert_eq!(n, 10u32);
+        let n: u16 = as_typed(&json, "n").unwrap();
+        assert_eq!(n, 10u16);
     }
 
     #[test]
-    fn json_as_u32_invalid_field() {
+    fn json_as_typed_invalid_field() {
         let json = json!({
             "n": "NaN"
         });
 
-        let err = as_u32(&json, "n").unwrap_err();
+        let err = as_typed::<u32>(&json, "n").unwrap_err();
         assert_eq!(
             err,
             JsonError::InvalidField {
                 field: "n".to_string(),
-                reason: r#"value `"NaN"` isn't a number"#.to_string()
-            }
-        );
-    }
-
-    #[test]
-    fn json_as_address_valid() {
-        let json = json!({
-            "addr": "10203040506070809000A0B0C0D0E0F0ABCDEFFF"
-        });
-
-        let addr = as_addr(&json, "addr").unwrap();
-        let actual = addr.bytes();
-
-        let expected = [
-            0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80, 0x90, 0x00, 0xA0, 0xB0, 0xC0, 0xD0,
-            0xE0, 0xF0, 0xAB, 0xCD, 0xEF, 0xFF,
-        ];
-
-        assert_eq!(actual, expected);
-    }
-
-    #[test]
-    fn json_as_address_invalid_type() {
-        let json = json!({
-            "addr": true
-        });
-
-        let err = as_addr(&json, "addr").unwrap_err();
-        assert_eq!(
-            err,
-            JsonError::InvalidField {
-                field: "addr".to_string(),
-                reason: r#"value `true` isn't a string"#.to_string()
+                reason: r#"invalid type: string "NaN", expected u32"#.to_string()
             }
         );
     }
 
     #[test]
-    fn json_as_address_invalid_length() {
+    fn json_as_typed_address_invalid_length() {
         let json = json!({
             "addr": "1020"
         });
 
-        let err = as_addr(&json, "addr").unwrap_err();
+        let err = as_typed::<Address>(&json, "addr").unwrap_err();
         assert_eq!(
             err,
             JsonError::InvalidField {
                 field: "addr".to_string(),
-                reason: "value should be exactly 40 hex digits".to_string(),
+                reason: "invalid length 2, expected 20 bytes".to_string(),
             }
         );
     }
 
     #[test]
-    fn json_as_address_invalid_chars() {
+    fn json_from_json_missing_field() {
         let json = json!({
-            "addr": "XYWZ"
+            "version": 0
         });
 
-        let err = as_addr(&json, "addr").unwrap_err();
+        let err = from_json::<svm_types::AppTransaction>(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "addr".to_string(),
-                reason: "value should have only hex digits".to_string(),
-            }
+            JsonError::InvalidJson("missing field `app`".to_string())
         );
     }
 
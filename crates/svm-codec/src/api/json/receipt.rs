@@ -1,181 +1,239 @@
-use serde_json::{json, Value};
+use serde_json::Value;
 
 use crate::api::json::{self, JsonError};
 use crate::api::raw;
+use crate::receipt as raw_receipt;
+
+use svm_types::receipt::ReceiptOwned;
+
+/// Encodes a JSON receipt (of any type) into a binary receipt.
+///
+/// The JSON layout is the `serde` representation of `ReceiptOwned` (the one returned by `decode_receipt`).
+/// The `bloom` field (if any) is ignored since it's derived from the `logs`.
+///
+/// See also the `schema/receipt.json` JSON Schema.
+pub fn encode_receipt(json: &Value) -> Result<Vec<u8>, JsonError> {
+    let bytes = match as_receipt(json)? {
+        ReceiptOwned::DeployTemplate(receipt) => raw_receipt::encode_template_receipt(&receipt),
+        ReceiptOwned::SpawnApp(receipt) => raw_receipt::encode_app_receipt(&receipt),
+        ReceiptOwned::ExecApp(receipt) => raw_receipt::encode_exec_receipt(&receipt),
+        ReceiptOwned::UpgradeApp(receipt) => raw_receipt::encode_upgrade_receipt(&receipt),
+    };
 
-use svm_types::receipt::{
-    ExecReceipt, Log, ReceiptError, ReceiptOwned, SpawnAppReceipt, TemplateReceipt,
-};
+    Ok(bytes)
+}
 
+/// Decodes a binary receipt (of any type) given as hex string under the `data` field.
+///
+/// ```json
+/// {
+///   type: 'exec-app',    // string ('deploy-template' / 'spawn-app' / 'exec-app' / 'upgrade-app')
+///   success: true,       // boolean
+///   logs: [],            // array (each log is: `{ msg, code, topics }`, its `msg` is hex)
+///                        // (each event is: `{ data, topics }`, its `data` is hex)
+///   bloom: '0000...',    // string
+///   ...                  // receipt type fields (on success) or an `error` object (on failure)
+/// }
+/// ```
 pub fn decode_receipt(json: &Value) -> Result<Value, JsonError> {
-    let data = json::as_string(json, "data")?;
-    let bytes = json::str_to_bytes(&data, "data")?;
+    let bytes = json::as_blob(json, "data")?;
 
-    assert!(bytes.len() > 0);
+    let receipt = raw::decode_receipt(&bytes).map_err(into_data_error)?;
 
-    let receipt = raw::decode_receipt(&bytes);
-    let ty = receipt_type(&receipt);
+    Ok(receipt_to_json(&receipt))
+}
 
-    let json = if receipt.success() {
-        match receipt {
-            ReceiptOwned::DeployTemplate(receipt) => decode_deploy_template(&receipt, ty),
-            ReceiptOwned::SpawnApp(receipt) => decode_spawn_app(&receipt, ty),
-            ReceiptOwned::ExecApp(receipt) => decode_exe_app(&receipt, ty),
-        }
-    } else {
-        let ty = receipt_type(&receipt);
-        let logs = receipt.get_logs();
-        let err = receipt.get_error();
+///
+/// ```json
+/// {
+///   type: 'deploy-template',  // string
+///   success: true,            // boolean
+///   template: 'A2FB...',      // string
+///   gas_used: 10,             // number
+///   logs: [],                 // array
+/// }
+/// ```
+pub fn encode_template_receipt(json: &Value) -> Result<Vec<u8>, JsonError> {
+    expect_type(json, "deploy-template")?;
+
+    encode_receipt(json)
+}
 
-        decode_error(ty, err, logs)
-    };
+pub fn decode_template_receipt(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
 
-    Ok(json)
+    let receipt = raw_receipt::decode_template_receipt(&bytes).map_err(into_data_error)?;
+
+    Ok(receipt_to_json(&ReceiptOwned::DeployTemplate(receipt)))
 }
 
-fn receipt_type(receipt: &ReceiptOwned) -> &'static str {
-    match receipt {
-        ReceiptOwned::DeployTemplate(..) => "deploy-template",
-        ReceiptOwned::SpawnApp(..) => "spawn-app",
-        ReceiptOwned::ExecApp(..) => "exec-app",
-    }
+///
+/// ```json
+/// {
+///   type: 'spawn-app',     // string
+///   success: true,         // boolean
+///   app: 'A2FB...',        // string
+///   state: 'A0A0...',      // string
+///   returndata: '1020',    // string
+///   gas_used: 10,          // number
+///   logs: [],              // array
+/// }
+/// ```
+pub fn encode_spawn_app_receipt(json: &Value) -> Result<Vec<u8>, JsonError> {
+    expect_type(json, "spawn-app")?;
+
+    encode_receipt(json)
 }
 
-fn decode_error(ty: &'static str, err: &ReceiptError, logs: &[Log]) -> Value {
-    let mut json = {
-        match err {
-            ReceiptError::OOG => json!({
-                "err_type": "oog",
-            }),
-            ReceiptError::TemplateNotFound(template_addr) => json!({
-                "err_type": "template-not-found",
-                "template_addr": json::addr_to_str(template_addr.inner()),
-            }),
-            ReceiptError::AppNotFound(app_addr) => json!({
-                "err_type": "app-not-found",
-                "app_addr": json::addr_to_str(app_addr.inner()),
-            }),
-            ReceiptError::CompilationFailed {
-                app_addr,
-                template_addr,
-                msg,
-            } => json!({
-                "err_type": "compilation-failed",
-                "template_addr": json::addr_to_str(template_addr.inner()),
-                "app_addr": json::addr_to_str(app_addr.inner()),
-                "message": msg,
-            }),
-            ReceiptError::InstantiationFailed {
-                app_addr,
-                template_addr,
-                msg,
-            } => json!({
-                "err_type": "instantiation-failed",
-                "template_addr": json::addr_to_str(template_addr.inner()),
-                "app_addr": json::addr_to_str(app_addr.inner()),
-                "message": msg,
-            }),
-            ReceiptError::FuncNotFound {
-                app_addr,
-                template_addr,
-                func,
-            } => json!({
-                "err_type": "function-not-found",
-                "template_addr": json::addr_to_str(template_addr.inner()),
-                "app_addr": json::addr_to_str(app_addr.inner()),
-                "func": func,
-            }),
-            ReceiptError::FuncFailed {
-                app_addr,
-                template_addr,
-                func,
-                msg,
-            } => json!({
-                "err_type": "function-failed",
-                "template_addr": json::addr_to_str(template_addr.inner()),
-                "app_addr": json::addr_to_str(app_addr.inner()),
-                "func": func,
-                "message": msg,
-            }),
-        }
-    };
+pub fn decode_spawn_app_receipt(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
+
+    let receipt = raw_receipt::decode_app_receipt(&bytes).map_err(into_data_error)?;
+
+    Ok(receipt_to_json(&ReceiptOwned::SpawnApp(receipt)))
+}
+
+///
+/// ```json
+/// {
+///   type: 'exec-app',      // string
+///   success: true,         // boolean
+///   new_state: 'A0A0...',  // string
+///   returndata: '1020',    // string
+///   gas_used: 10,          // number
+///   logs: [],              // array
+/// }
+/// ```
+pub fn encode_exec_receipt(json: &Value) -> Result<Vec<u8>, JsonError> {
+    expect_type(json, "exec-app")?;
+
+    encode_receipt(json)
+}
+
+pub fn decode_exec_receipt(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
+
+    let receipt = raw_receipt::decode_exec_receipt(&bytes).map_err(into_data_error)?;
 
-    let logs = json::logs_to_json(logs);
+    Ok(receipt_to_json(&ReceiptOwned::ExecApp(receipt)))
+}
+
+///
+/// ```json
+/// {
+///   type: 'upgrade-app',   // string
+///   success: true,         // boolean
+///   template: 'A2FB...',   // string
+///   new_state: 'A0A0...',  // string
+///   returndata: '1020',    // string
+///   gas_used: 10,          // number
+///   logs: [],              // array
+/// }
+/// ```
+pub fn encode_upgrade_receipt(json: &Value) -> Result<Vec<u8>, JsonError> {
+    expect_type(json, "upgrade-app")?;
+
+    encode_receipt(json)
+}
 
-    let map: &mut serde_json::Map<String, Value> = json.as_object_mut().unwrap();
-    let mut map: serde_json::Map<String, Value> = std::mem::take(map);
+pub fn decode_upgrade_receipt(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
 
-    map.insert("type".into(), Value::String(ty.into()));
-    map.insert("success".into(), Value::Bool(false));
-    map.insert("logs".into(), Value::Array(logs));
+    let receipt = raw_receipt::decode_upgrade_receipt(&bytes).map_err(into_data_error)?;
 
-    map.into()
+    Ok(receipt_to_json(&ReceiptOwned::UpgradeApp(receipt)))
 }
 
-fn decode_deploy_template(receipt: &TemplateReceipt, ty: &'static str) -> Value {
-    debug_assert!(receipt.success);
-    debug_assert!(receipt.error.is_none());
-
-    let TemplateReceipt {
-        addr,
-        gas_used,
-        logs,
-        ..
-    } = receipt;
-
-    json!({
-        "type": ty,
-        "success": true,
-        "addr": json::addr_to_str(addr.as_ref().unwrap().inner()),
-        "gas_used": json::gas_to_json(&gas_used),
-        "logs": json::logs_to_json(&receipt.logs),
-    })
+fn into_data_error(err: crate::error::ParseError) -> JsonError {
+    JsonError::InvalidField {
+        field: "data".to_string(),
+        reason: err.to_string(),
+    }
+}
+
+fn expect_type(json: &Value, expected: &str) -> Result<(), JsonError> {
+    let ty: String = json::as_typed(json, "type")?;
+
+    if ty != expected {
+        return Err(JsonError::InvalidField {
+            field: "type".to_string(),
+            reason: format!("expected `{}` receipt (got `{}`)", expected, ty),
+        });
+    }
+
+    Ok(())
 }
 
-fn decode_spawn_app(receipt: &SpawnAppReceipt, ty: &'static str) -> Value {
-    debug_assert!(receipt.success);
-    debug_assert!(receipt.error.is_none());
-
-    let SpawnAppReceipt {
-        app_addr,
-        init_state,
-        returndata,
-        gas_used,
-        logs,
-        ..
-    } = receipt;
-
-    json!({
-        "type": ty,
-        "success": true,
-        "app": json::addr_to_str(app_addr.as_ref().unwrap().inner()),
-        "state": json::state_to_str(init_state.as_ref().unwrap()),
-        "returndata": json::bytes_to_str(returndata.as_ref().unwrap()),
-        "gas_used": json::gas_to_json(&gas_used),
-        "logs": json::logs_to_json(&receipt.logs),
-    })
+/// Deserializes a `ReceiptOwned`.
+///
+/// Its `serde` representation has the fields of both successful and failed receipts optional,
+/// so the ones required by the receipt `success` are checked here.
+fn as_receipt(json: &Value) -> Result<ReceiptOwned, JsonError> {
+    let receipt: ReceiptOwned = json::from_json(json)?;
+
+    let (error, fields) = match &receipt {
+        ReceiptOwned::DeployTemplate(r) => (
+            r.error.is_some(),
+            vec![
+                ("template", r.addr.is_some()),
+                ("gas_used", r.gas_used.is_some()),
+            ],
+        ),
+        ReceiptOwned::SpawnApp(r) => (
+            r.error.is_some(),
+            vec![
+                ("app", r.app_addr.is_some()),
+                ("state", r.init_state.is_some()),
+                ("returndata", r.returndata.is_some()),
+                ("gas_used", r.gas_used.is_some()),
+            ],
+        ),
+        ReceiptOwned::ExecApp(r) => (
+            r.error.is_some(),
+            vec![
+                ("new_state", r.new_state.is_some()),
+                ("returndata", r.returndata.is_some()),
+                ("gas_used", r.gas_used.is_some()),
+            ],
+        ),
+        ReceiptOwned::UpgradeApp(r) => (
+            r.error.is_some(),
+            vec![
+                ("template", r.template_addr.is_some()),
+                ("new_state", r.new_state.is_some()),
+                ("returndata", r.returndata.is_some()),
+                ("gas_used", r.gas_used.is_some()),
+            ],
+        ),
+    };
+
+    if !receipt.success() {
+        if !error {
+            return Err(JsonError::InvalidJson("missing field `error`".to_string()));
+        }
+
+        return Ok(receipt);
+    }
+
+    if error {
+        return Err(JsonError::InvalidField {
+            field: "error".to_string(),
+            reason: "a successful receipt has no `error`".to_string(),
+        });
+    }
+
+    match fields.iter().find(|(_, present)| !present) {
+        Some((field, _)) => Err(JsonError::InvalidJson(format!("missing field `{}`", field))),
+        None => Ok(receipt),
+    }
 }
 
-fn decode_exe_app(receipt: &ExecReceipt, ty: &'static str) -> Value {
-    debug_assert!(receipt.success);
-    debug_assert!(receipt.error.is_none());
-
-    let ExecReceipt {
-        new_state,
-        returndata,
-        gas_used,
-        logs,
-        ..
-    } = receipt;
-
-    json!({
-        "type": ty,
-        "success": true,
-        "new_state": json::state_to_str(new_state.as_ref().unwrap()),
-        "returndata": json::bytes_to_str(returndata.as_ref().unwrap()),
-        "gas_used": json::gas_to_json(&gas_used),
-        "logs": json::logs_to_json(&receipt.logs),
-    })
+fn receipt_to_json(receipt: &ReceiptOwned) -> Value {
+    let mut json = json::to_json(receipt);
+
+    json["bloom"] = json::to_json(&receipt.bloom());
+
+    json
 }
 
 #[cfg(test)]
@@ -184,20 +242,35 @@ mod tests {
 
     use super::json;
 
-    use svm_types::{gas::MaybeGas, receipt::Log, Address, AppAddr, State, WasmValue};
+    use serde_json::json;
+
+    use svm_types::{
+        gas::MaybeGas,
+        receipt::{
+            Bloom, ExecReceipt, Log, ReceiptError, SpawnAppReceipt, TemplateReceipt,
+            UpgradeAppReceipt,
+        },
+        Address, AppAddr, State,
+    };
+
+    fn empty_bloom() -> String {
+        json::bytes_to_str(Bloom::empty().as_slice())
+    }
 
     #[test]
     fn decode_receipt_deploy_template_receipt_success() {
         let template: Address = [0x10; 20].into();
 
         let logs = vec![
-            Log {
+            Log::Message {
                 msg: b"Log entry #1".to_vec(),
                 code: 100,
+                topics: Vec::new(),
             },
-            Log {
+            Log::Message {
                 msg: b"Log entry #2".to_vec(),
                 code: 200,
+                topics: Vec::new(),
             },
         ];
 
@@ -218,11 +291,12 @@ mod tests {
             json!({
                 "success": true,
                 "type": "deploy-template",
-                "addr": "1010101010101010101010101010101010101010",
+                "template": "1010101010101010101010101010101010101010",
                 "gas_used": 10,
+                "bloom": empty_bloom(),
                 "logs": [
-                    {"msg": "Log entry #1", "code": 100},
-                    {"msg": "Log entry #2", "code": 200}
+                    {"msg": json::bytes_to_str(b"Log entry #1"), "code": 100, "topics": []},
+                    {"msg": json::bytes_to_str(b"Log entry #2"), "code": 200, "topics": []}
                 ]
             })
         );
@@ -234,13 +308,15 @@ mod tests {
         let state: State = [0xA0; 32].into();
 
         let logs = vec![
-            Log {
+            Log::Message {
                 msg: b"Log entry #1".to_vec(),
                 code: 100,
+                topics: Vec::new(),
             },
-            Log {
+            Log::Message {
                 msg: b"Log entry #2".to_vec(),
                 code: 200,
+                topics: Vec::new(),
             },
         ];
 
@@ -267,9 +343,10 @@ mod tests {
                 "gas_used": 10,
                 "returndata": "102030",
                 "state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+                "bloom": empty_bloom(),
                 "logs": [
-                    {"msg": "Log entry #1", "code": 100},
-                    {"msg": "Log entry #2", "code": 200}
+                    {"msg": json::bytes_to_str(b"Log entry #1"), "code": 100, "topics": []},
+                    {"msg": json::bytes_to_str(b"Log entry #2"), "code": 200, "topics": []}
                 ]
             })
         );
@@ -277,9 +354,10 @@ mod tests {
 
     #[test]
     fn decode_receipt_spawn_app_receipt_error() {
-        let logs = vec![Log {
+        let logs = vec![Log::Message {
             msg: b"Reached OOG".to_vec(),
             code: 0,
+            topics: Vec::new(),
         }];
 
         let receipt = SpawnAppReceipt {
@@ -301,8 +379,11 @@ mod tests {
             json!({
                "type": "spawn-app",
                "success": false,
-               "err_type": "oog",
-               "logs": [{"code": 0, "msg": "Reached OOG"}],
+               "error": {
+                   "type": "oog"
+               },
+               "bloom": empty_bloom(),
+               "logs": [{"code": 0, "msg": json::bytes_to_str(b"Reached OOG"), "topics": []}],
             })
         );
     }
@@ -312,14 +393,17 @@ mod tests {
         let state: State = [0xA0; 32].into();
 
         let logs = vec![
-            Log {
+            Log::Message {
                 msg: b"Log entry #1".to_vec(),
                 code: 100,
+                topics: Vec::new(),
             },
-            Log {
+            Log::Message {
                 msg: b"Log entry #2".to_vec(),
                 code: 200,
+                topics: vec![vec![0x10, 0x20], vec![0xAB]],
             },
+            Log::event(vec![vec![0xCD; 32]], vec![0x00, 0xFF, 0xC3]),
         ];
 
         let receipt = ExecReceipt {
@@ -343,11 +427,354 @@ mod tests {
                 "gas_used": 10,
                 "returndata": "1020",
                 "new_state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+                "bloom": json::bytes_to_str(Bloom::from_logs(&receipt.logs).as_slice()),
                 "logs": [
-                    {"msg": "Log entry #1", "code": 100},
-                    {"msg": "Log entry #2", "code": 200}
+                    {"msg": json::bytes_to_str(b"Log entry #1"), "code": 100, "topics": []},
+                    {"msg": json::bytes_to_str(b"Log entry #2"), "code": 200, "topics": ["1020", "AB"]},
+                    {"data": "00FFC3", "topics": ["CD".repeat(32)]}
                 ]
             })
         );
     }
+
+    #[test]
+    fn decode_receipt_upgrade_app_receipt_error() {
+        let app_addr: AppAddr = Address::of("my-app").into();
+
+        let receipt = UpgradeAppReceipt::from_err(
+            ReceiptError::UpgradeNotAuthorized {
+                app_addr: app_addr.clone(),
+            },
+            Vec::new(),
+        );
+
+        let bytes = crate::receipt::encode_upgrade_receipt(&receipt);
+        let data = json::bytes_to_str(&bytes);
+        let json = decode_receipt(&json!({ "data": data })).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+               "type": "upgrade-app",
+               "success": false,
+               "error": {
+                   "type": "upgrade-not-authorized",
+                   "app": json::to_json(&app_addr),
+               },
+               "bloom": empty_bloom(),
+               "logs": [],
+            })
+        );
+    }
+
+    #[test]
+    fn decode_receipt_corrupt_data() {
+        let receipt = ExecReceipt::from_err(ReceiptError::OOG, Vec::new());
+
+        let bytes = crate::receipt::encode_exec_receipt(&receipt);
+        let data = json::bytes_to_str(&bytes[..1]);
+
+        let err = decode_receipt(&json!({ "data": data })).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                field: "data".to_string(),
+                reason: "Field `Version` must not be empty".to_string(),
+            }
+        );
+    }
+
+    fn assert_roundtrip(json: Value) {
+        let bytes = encode_receipt(&json).unwrap();
+        let data = json::bytes_to_str(&bytes);
+        let decoded = decode_receipt(&json!({ "data": data })).unwrap();
+
+        assert_eq!(decoded, json);
+    }
+
+    fn with_logs_and_bloom(mut json: Value, logs: Vec<Log>) -> Value {
+        json["logs"] = json::to_json(&logs);
+        json["bloom"] = Value::String(json::bytes_to_str(Bloom::from_logs(&logs).as_slice()));
+
+        json
+    }
+
+    #[test]
+    fn encode_receipt_roundtrip_success() {
+        let logs = vec![
+            Log::new(b"Hello".to_vec(), 1),
+            Log::event(vec![vec![0xAB; 32]], vec![0x10]),
+            // non-UTF-8 event data
+            Log::event(
+                vec![vec![0xCD; 32], vec![0xEF; 32]],
+                vec![0xFF, 0xFE, 0x00, 0xC3, 0x28],
+            ),
+            // non-UTF-8 log message
+            Log::new(vec![0xC3, 0x28, 0xFF, 0x00], 2),
+        ];
+
+        let receipts = vec![
+            json!({
+                "type": "deploy-template",
+                "success": true,
+                "template": "1010101010101010101010101010101010101010",
+                "gas_used": 10,
+            }),
+            json!({
+                "type": "spawn-app",
+                "success": true,
+                "app": "2020202020202020202020202020202020202020",
+                "state": "A0".repeat(32),
+                "returndata": "1020",
+                "gas_used": 20,
+            }),
+            json!({
+                "type": "exec-app",
+                "success": true,
+                "new_state": "B0".repeat(32),
+                "returndata": "",
+                "gas_used": 30,
+            }),
+            json!({
+                "type": "upgrade-app",
+                "success": true,
+                "template": "3030303030303030303030303030303030303030",
+                "new_state": "C0".repeat(32),
+                "returndata": "FF",
+                "gas_used": 40,
+            }),
+        ];
+
+        // the logs themselves (and not only their JSON) survive the roundtrip
+        let json_logs = json!({ "logs": json::to_json(&logs) });
+        assert_eq!(
+            json::as_typed::<Vec<Log>>(&json_logs, "logs").unwrap(),
+            logs
+        );
+
+        for json in receipts {
+            assert_roundtrip(with_logs_and_bloom(json, logs.clone()));
+        }
+    }
+
+    #[test]
+    fn encode_receipt_roundtrip_errors() {
+        let template = "1010101010101010101010101010101010101010";
+        let app = "2020202020202020202020202020202020202020";
+
+        let errors = vec![
+            json!({ "type": "oog" }),
+            json!({ "type": "template-not-found", "template": template }),
+            json!({ "type": "app-not-found", "app": app }),
+            json!({ "type": "compilation-failed", "template": template, "app": app, "message": "invalid code" }),
+            json!({ "type": "instantiation-failed", "template": template, "app": app, "message": "missing import" }),
+            json!({ "type": "function-not-found", "template": template, "app": app, "func": "run" }),
+            json!({ "type": "function-failed", "template": template, "app": app, "func": "run", "message": "trap" }),
+            json!({ "type": "upgrade-not-authorized", "app": app }),
+            json!({ "type": "incompatible-layout", "template": template, "app": app }),
+            json!({ "type": "reverted", "data": "430750104E6F7420656E6F7567682066756E6473" }),
+            json!({ "type": "storage-failed", "app": app, "message": "host is unavailable" }),
+            json!({ "type": "invalid-transaction", "message": "Unsupported protocol version `1`" }),
+        ];
+
+        let logs = vec![Log::new(b"Failed".to_vec(), 2)];
+
+        for ty in &["deploy-template", "spawn-app", "exec-app", "upgrade-app"] {
+            for error in errors.iter() {
+                let json = json!({
+                    "type": ty,
+                    "success": false,
+                    "error": error,
+                });
+
+                assert_roundtrip(with_logs_and_bloom(json, logs.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn encode_receipt_unknown_type() {
+        let err = encode_receipt(&json!({ "type": "transfer" })).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidJson(
+                "unknown variant `transfer`, expected one of `deploy-template`, `spawn-app`, `exec-app`, `upgrade-app`".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn encode_receipt_unknown_error_type() {
+        let json = json!({
+            "type": "exec-app",
+            "success": false,
+            "error": { "type": "panic" },
+            "logs": [],
+        });
+
+        let err = encode_receipt(&json).unwrap_err();
+
+        match err {
+            JsonError::InvalidJson(reason) => {
+                assert!(reason.starts_with("unknown variant `panic`"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn encode_receipt_success_missing_field() {
+        let json = json!({
+            "type": "exec-app",
+            "success": true,
+            "returndata": "",
+            "gas_used": 10,
+            "logs": [],
+        });
+
+        let err = encode_receipt(&json).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidJson("missing field `new_state`".to_string())
+        );
+    }
+
+    #[test]
+    fn encode_receipt_failure_missing_error() {
+        let json = json!({
+            "type": "spawn-app",
+            "success": false,
+            "logs": [],
+        });
+
+        let err = encode_receipt(&json).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidJson("missing field `error`".to_string())
+        );
+    }
+
+    #[test]
+    fn decode_receipt_of_kind_mismatch() {
+        let receipt = ExecReceipt::from_err(ReceiptError::OOG, Vec::new());
+
+        let bytes = crate::receipt::encode_exec_receipt(&receipt);
+        let data = json::bytes_to_str(&bytes);
+
+        let json = decode_exec_receipt(&json!({ "data": data })).unwrap();
+        assert_eq!(json["type"], "exec-app");
+
+        let err = decode_spawn_app_receipt(&json!({ "data": data })).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                field: "data".to_string(),
+                reason: "Invalid value for field `ReceiptType`".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn encode_receipt_of_kind_mismatch() {
+        let json = json!({
+            "type": "exec-app",
+            "success": false,
+            "error": { "type": "oog" },
+            "logs": [],
+        });
+
+        let err = encode_template_receipt(&json).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                field: "type".to_string(),
+                reason: "expected `deploy-template` receipt (got `exec-app`)".to_string(),
+            }
+        );
+    }
+
+    const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/golden/receipts");
+
+    const SCHEMA: &str = include_str!("../../../schema/receipt.json");
+
+    fn compile_schema() -> jsonschema::JSONSchema {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+
+        jsonschema::JSONSchema::compile(&schema).unwrap()
+    }
+
+    /// Each golden file holds a `receipt` (JSON) and its binary encoding as `data`.
+    ///
+    /// Running the test with `UPDATE_GOLDEN=1` regenerates the golden files
+    /// (the `receipt` is rewritten in its canonical form, i.e including the `bloom`).
+    #[test]
+    fn golden_receipts() {
+        let schema = compile_schema();
+        let update = std::env::var("UPDATE_GOLDEN").is_ok();
+
+        let mut paths: Vec<_> = std::fs::read_dir(GOLDEN_DIR)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+
+        paths.sort();
+        assert!(!paths.is_empty());
+
+        for path in paths {
+            let golden = std::fs::read_to_string(&path).unwrap();
+            let golden: Value = serde_json::from_str(&golden).unwrap();
+
+            let receipt = &golden["receipt"];
+            let bytes = encode_receipt(receipt).unwrap();
+            let data = json::bytes_to_str(&bytes);
+            let decoded = decode_receipt(&json!({ "data": data })).unwrap();
+
+            if update {
+                let golden = json!({ "data": data, "receipt": decoded });
+                let golden = serde_json::to_string_pretty(&golden).unwrap();
+
+                std::fs::write(&path, golden + "\n").unwrap();
+                continue;
+            }
+
+            assert!(
+                schema.is_valid(receipt),
+                "{:?} doesn't match the schema",
+                path
+            );
+            assert_eq!(golden["data"], data, "{:?} encoding has changed", path);
+            assert_eq!(&decoded, receipt, "{:?} decoding has changed", path);
+        }
+    }
+
+    #[test]
+    fn schema_rejects_invalid_receipts() {
+        let schema = compile_schema();
+
+        let valid = json!({
+            "type": "exec-app",
+            "success": false,
+            "error": { "type": "oog" },
+            "logs": [],
+        });
+        assert!(schema.is_valid(&valid));
+
+        let mut missing_logs = valid.clone();
+        missing_logs.as_object_mut().unwrap().remove("logs");
+        assert!(!schema.is_valid(&missing_logs));
+
+        let mut unknown_error = valid.clone();
+        unknown_error["error"] = json!({ "type": "panic" });
+        assert!(!schema.is_valid(&unknown_error));
+
+        let mut invalid_addr = valid.clone();
+        invalid_addr["error"] = json!({ "type": "app-not-found", "app": "1020" });
+        assert!(!schema.is_valid(&invalid_addr));
+    }
 }
@@ -0,0 +1,204 @@
+use serde_json::{json, Value};
+
+use svm_nibble::NibbleIter;
+use svm_types::Snapshot;
+
+use svm_app_query::render::{JsonVarRenderer, VarRenderer};
+use svm_app_query::schema::{Schema, Var, VarLayout, VarType};
+
+use crate::api::json::{self, JsonError};
+use crate::api::raw;
+
+///
+/// ```json
+/// {
+///   data: 'A2FB...',  // string (the raw snapshot as a hex string)
+/// }
+/// ```
+///
+/// The snapshot variables are rendered using the schema embedded in its `DataLayout`.
+/// Variables not covered by a schema are rendered as hex blobs.
+/// (see `render_snapshot` for rendering using an App's `Schema`).
+pub fn decode_snapshot(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
+
+    let mut iter = NibbleIter::new(&bytes);
+
+    let snapshot = raw::decode_snapshot(&mut iter).map_err(|e| JsonError::InvalidField {
+        field: "data".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let schema = Schema::from(&snapshot.layout);
+
+    Ok(render_snapshot(&snapshot, &schema))
+}
+
+/// Renders `snapshot` as JSON, each variable is rendered using its `schema` data.
+///
+/// ```json
+/// {
+///   state: 'A2FB...',  // string
+///   vars: [
+///     { id: 0, name: 'counter', value: 10 },
+///     { id: 1, name: '', value: '1020' }     // a variable not described by `schema`
+///     ...
+///   ]
+/// }
+/// ```
+pub fn render_snapshot(snapshot: &Snapshot, schema: &Schema) -> Value {
+    let vars: Vec<Value> = snapshot
+        .layout
+        .iter()
+        .zip(snapshot.vars.iter())
+        .map(|((var_id, off, len), bytes)| {
+            let id = var_id.0 as usize;
+
+            let var = schema.get_var(id).unwrap_or_else(|| Var {
+                id,
+                layout: VarLayout {
+                    offset: off as usize,
+                    length: len as usize,
+                },
+                ty: VarType::Blob,
+                name: "".to_string(),
+                desc: "".to_string(),
+            });
+
+            let value = JsonVarRenderer::render(&var, bytes)
+                .unwrap_or_else(|| Value::String(json::bytes_to_str(bytes)));
+
+            json!({
+                "id": id,
+                "name": var.name,
+                "value": value,
+            })
+        })
+        .collect();
+
+    json!({
+        "state": json::to_json(&snapshot.state),
+        "vars": vars,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_layout::{DataLayoutBuilder, VarType as LayoutVarType};
+    use svm_nibble::NibbleWriter;
+    use svm_types::State;
+
+    fn encode(snapshot: &Snapshot) -> String {
+        let mut w = NibbleWriter::new();
+        raw::encode_snapshot(snapshot, &mut w).unwrap();
+
+        json::bytes_to_str(&w.into_bytes())
+    }
+
+    #[test]
+    fn json_decode_snapshot() {
+        let snapshot = Snapshot {
+            layout: vec![2, 1].into(),
+            state: [0xA0; 32].into(),
+            vars: vec![vec![0x10, 0x20], vec![0x30]],
+        };
+
+        let json = decode_snapshot(&json!({ "data": encode(&snapshot) })).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+                "vars": [
+                    { "id": 0, "name": "", "value": "1020" },
+                    { "id": 1, "name": "", "value": "30" },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn json_decode_snapshot_with_layout_schema() {
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[2, 1, 1, 1]);
+        builder.add_symbolic_var("counter", LayoutVarType::U16, 1);
+        builder.add_symbolic_var("active", LayoutVarType::Bool, 1);
+        builder.add_symbolic_var("flags", LayoutVarType::U8, 2);
+
+        let snapshot = Snapshot {
+            layout: builder.build(),
+            state: State::empty(),
+            vars: vec![vec![0x01, 0x00], vec![0x01], vec![0x02], vec![0x03]],
+        };
+
+        let json = decode_snapshot(&json!({ "data": encode(&snapshot) })).unwrap();
+
+        assert_eq!(
+            json["vars"],
+            json!([
+                { "id": 0, "name": "counter", "value": 256 },
+                { "id": 1, "name": "active", "value": true },
+                { "id": 2, "name": "flags[0]", "value": 2 },
+                { "id": 3, "name": "flags[1]", "value": 3 },
+            ])
+        );
+    }
+
+    #[test]
+    fn json_render_snapshot_with_schema() {
+        let snapshot = Snapshot {
+            layout: vec![2, 1].into(),
+            state: State::empty(),
+            vars: vec![vec![0x01, 0x00], vec![0x01]],
+        };
+
+        let mut schema = Schema::new();
+
+        schema.add_var(Var {
+            id: 0,
+            layout: VarLayout {
+                offset: 0,
+                length: 2,
+            },
+            ty: VarType::Int(false),
+            name: "counter".to_string(),
+            desc: "".to_string(),
+        });
+
+        schema.add_var(Var {
+            id: 1,
+            layout: VarLayout {
+                offset: 2,
+                length: 1,
+            },
+            ty: VarType::Bool,
+            name: "active".to_string(),
+            desc: "".to_string(),
+        });
+
+        let json = render_snapshot(&snapshot, &schema);
+
+        assert_eq!(
+            json["vars"],
+            json!([
+                { "id": 0, "name": "counter", "value": 256 },
+                { "id": 1, "name": "active", "value": true },
+            ])
+        );
+    }
+
+    #[test]
+    fn json_decode_snapshot_invalid_data() {
+        let err = decode_snapshot(&json!({ "data": "" })).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                field: "data".to_string(),
+                reason: "Field `Version` must not be empty".to_string(),
+            }
+        );
+    }
+}
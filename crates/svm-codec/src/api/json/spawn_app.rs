@@ -20,23 +20,7 @@ use crate::{
 /// }
 /// ```
 pub fn encode_spawn_app(json: &Value) -> Result<Vec<u8>, JsonError> {
-    let version = json::as_u32(json, "version")?;
-    let template = json::as_addr(json, "template")?.into();
-    let name = json::as_string(json, "name")?;
-    let ctor_name = json::as_string(json, "ctor_name")?;
-
-    let calldata = json::as_string(json, "calldata")?;
-    let calldata = json::str_to_bytes(&calldata, "calldata")?;
-
-    let spawn = SpawnApp {
-        app: App {
-            version,
-            name,
-            template,
-        },
-        ctor_name,
-        calldata,
-    };
+    let spawn: SpawnApp = json::from_json(json)?;
 
     let mut w = NibbleWriter::new();
     app::encode_spawn_app(&spawn, &mut w);
@@ -45,29 +29,23 @@ pub fn encode_spawn_app(json: &Value) -> Result<Vec<u8>, JsonError> {
     Ok(bytes)
 }
 
+/// Returns the same JSON as given to `encode_spawn_app`, except for the `calldata` which is decoded
+/// (see `decode_calldata`).
 pub fn decode_spawn_app(json: &Value) -> Result<Value, JsonError> {
-    let data = json::as_string(json, "data")?;
-    let bytes = json::str_to_bytes(&data, "data")?;
+    let bytes = json::as_blob(json, "data")?;
 
     let mut iter = NibbleIter::new(&bytes);
-    let spawn = raw::decode_spawn_app(&mut iter).unwrap();
 
-    let version = spawn.app.version;
-    let ctor_name = spawn.ctor_name;
-    let template = json::addr_to_str(&spawn.app.template.inner());
+    let spawn = raw::decode_spawn_app(&mut iter).map_err(|e| JsonError::InvalidField {
+        field: "data".to_string(),
+        reason: e.to_string(),
+    })?;
 
     let calldata = json::bytes_to_str(&spawn.calldata);
     let calldata = json::decode_calldata(&json!({ "calldata": calldata }))?;
 
-    let name = spawn.app.name;
-
-    let json = json!({
-        "version": version,
-        "template": template,
-        "name": name,
-        "ctor_name": ctor_name,
-        "calldata": calldata,
-    });
+    let mut json = json::to_json(&spawn);
+    json["calldata"] = calldata;
 
     Ok(json)
 }
@@ -82,31 +60,31 @@ mod tests {
 
     #[test]
     fn json_spawn_app_missing_version() {
-        let json = json!({});
+        let json = json!({
+            "ctor_name": "initialize",
+            "calldata": "",
+        });
 
         let err = encode_spawn_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "version".to_string(),
-                reason: "value `null` isn\'t a number".to_string(),
-            }
+            JsonError::InvalidJson("missing field `version`".to_string())
         );
     }
 
     #[test]
     fn json_spawn_app_missing_template_addr() {
         let json = json!({
-            "version": 0
+            "version": 0,
+            "name": "My App",
+            "ctor_name": "initialize",
+            "calldata": "",
         });
 
         let err = encode_spawn_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "template".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `template`".to_string())
         );
     }
 
@@ -114,16 +92,15 @@ mod tests {
     fn json_spawn_app_missing_name() {
         let json = json!({
             "version": 0,
-            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF"
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "ctor_name": "initialize",
+            "calldata": "",
         });
 
         let err = encode_spawn_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "name".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `name`".to_string())
         );
     }
 
@@ -138,10 +115,7 @@ mod tests {
         let err = encode_spawn_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "ctor_name".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `ctor_name`".to_string())
         );
     }
 
@@ -157,10 +131,7 @@ mod tests {
         let err = encode_spawn_app(&json).unwrap_err();
         assert_eq!(
             err,
-            JsonError::InvalidField {
-                field: "calldata".to_string(),
-                reason: "value `null` isn\'t a string".to_string(),
-            }
+            JsonError::InvalidJson("missing field `calldata`".to_string())
         );
     }
 
@@ -175,7 +146,7 @@ mod tests {
         .unwrap();
 
         let json = json!({
-            "version": 1,
+            "version": 0,
             "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
             "name": "My App",
             "ctor_name": "initialize",
@@ -189,7 +160,7 @@ mod tests {
         assert_eq!(
             json,
             json!({
-                "version": 1,
+                "version": 0,
                 "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
                 "name": "My App",
                 "ctor_name": "initialize",
@@ -200,4 +171,33 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn json_spawn_app_unsupported_version() {
+        let calldata = json::encode_calldata(&json!({
+            "abi": ["i32", "i64"],
+            "data": [10, 20]
+        }))
+        .unwrap();
+
+        let json = json!({
+            "version": 1,
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "name": "My App",
+            "ctor_name": "initialize",
+            "calldata": calldata["calldata"],
+        });
+
+        let bytes = encode_spawn_app(&json).unwrap();
+        let data = json::bytes_to_str(&bytes);
+        let err = decode_spawn_app(&json!({ "data": data })).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                field: "data".to_string(),
+                reason: "Unsupported protocol version `1`".to_string(),
+            }
+        );
+    }
 }
@@ -0,0 +1,127 @@
+use svm_nibble::{NibbleIter, NibbleWriter};
+
+use serde_json::{json, Value};
+
+use crate::{
+    api::json::{self, JsonError},
+    api::raw,
+};
+
+use svm_types::UpgradeApp;
+
+///
+/// ```json
+/// {
+///   version: 0,              // number
+///   app: 'A2FB...',          // string
+///   template: '1C3D...',     // string
+///   migration: 'migrate',    // string (optional)
+///   calldata: '',            // string
+/// }
+/// ```
+pub fn encode_upgrade_app(json: &Value) -> Result<Vec<u8>, JsonError> {
+    let upgrade: UpgradeApp = json::from_json(json)?;
+
+    let mut w = NibbleWriter::new();
+    raw::encode_upgrade_app(&upgrade, &mut w);
+
+    let bytes = w.into_bytes();
+    Ok(bytes)
+}
+
+/// Returns the same JSON as given to `encode_upgrade_app`, except for the `calldata` which is decoded
+/// (see `decode_calldata`) or `null` when empty.
+pub fn decode_upgrade_app(json: &Value) -> Result<Value, JsonError> {
+    let bytes = json::as_blob(json, "data")?;
+
+    let mut iter = NibbleIter::new(&bytes);
+
+    let upgrade = raw::decode_upgrade_app(&mut iter).map_err(|e| JsonError::InvalidField {
+        field: "data".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let calldata = if upgrade.calldata.is_empty() {
+        Value::Null
+    } else {
+        let calldata = json::bytes_to_str(&upgrade.calldata);
+        json::decode_calldata(&json!({ "calldata": calldata }))?
+    };
+
+    let mut json = json::to_json(&upgrade);
+    json["calldata"] = calldata;
+
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_upgrade_app_missing_template_addr() {
+        let json = json!({
+            "version": 0,
+            "app": "10203040506070809000A0B0C0D0E0F0ABCDEFFF"
+        });
+
+        let err = encode_upgrade_app(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidJson("missing field `template`".to_string())
+        );
+    }
+
+    #[test]
+    fn json_upgrade_app_valid() {
+        let calldata = json::encode_calldata(&json!({
+            "abi": ["i32"],
+            "data": [10]
+        }))
+        .unwrap();
+
+        let json = json!({
+            "version": 0,
+            "app": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "template": "FFFEDCBA0F0E0D0C0B0A00090807060504030201",
+            "migration": "migrate",
+            "calldata": calldata["calldata"],
+        });
+
+        let bytes = encode_upgrade_app(&json).unwrap();
+        let data = json::bytes_to_str(&bytes);
+        let decoded = decode_upgrade_app(&json!({ "data": data })).unwrap();
+
+        assert_eq!(
+            decoded,
+            json!({
+                "version": 0,
+                "app": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+                "template": "FFFEDCBA0F0E0D0C0B0A00090807060504030201",
+                "migration": "migrate",
+                "calldata": {
+                    "abi": ["i32"],
+                    "data": [10]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn json_upgrade_app_without_migration() {
+        let json = json!({
+            "version": 0,
+            "app": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "template": "FFFEDCBA0F0E0D0C0B0A00090807060504030201",
+            "calldata": "",
+        });
+
+        let bytes = encode_upgrade_app(&json).unwrap();
+        let data = json::bytes_to_str(&bytes);
+        let decoded = decode_upgrade_app(&json!({ "data": data })).unwrap();
+
+        assert_eq!(decoded["migration"], Value::Null);
+        assert_eq!(decoded["calldata"], Value::Null);
+    }
+}
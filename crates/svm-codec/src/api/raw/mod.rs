@@ -1,10 +1,17 @@
 pub use crate::app::{decode_spawn_app, encode_spawn_app};
+pub use crate::envelope::{decode_envelope, encode_envelope, envelope_signing_bytes};
+pub use crate::envelope::{sign_envelope, verify_envelope};
 pub use crate::template::{decode_deploy_template, encode_deploy_template};
+pub use crate::template::{validate_template, HostImport, SVM_IMPORTS, SVM_NAMESPACE};
 pub use crate::transaction::{decode_exec_app, encode_exec_app};
+pub use crate::upgrade::{decode_upgrade_app, encode_upgrade_app};
 
 pub use crate::abi::{decode_abi_data, encode_abi_data};
 pub use crate::field::Field;
 pub use crate::gas::{decode_gas_used, encode_gas_used};
 pub use crate::receipt::decode_receipt;
+pub use crate::snapshot::{decode_snapshot, encode_snapshot};
 pub use crate::varuint14::{decode_varuint14, encode_varuint14};
+pub use crate::varuint32::{decode_varuint32, encode_varuint32};
 pub use crate::version::{decode_version, encode_version};
+pub use crate::version::{is_supported_version, supported_versions, VersionSchedule};
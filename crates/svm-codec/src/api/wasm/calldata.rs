@@ -3,9 +3,17 @@ use serde_json::Value;
 use super::wasm_buf_apply;
 use crate::{api, api::json::JsonError};
 
+/// Encodes `calldata` given either positional `abi` and `data` arrays (see `api::json::encode_calldata`)
+/// or a template ABI, a `func_name` and its named `params` (see `api::json::encode_call`).
 pub fn encode_calldata(ptr: usize) -> Result<usize, JsonError> {
     wasm_buf_apply(ptr, |json: &Value| {
-        let json = api::json::encode_calldata(json)?;
+        let json = if json.get("func_name").is_some() {
+            let func_name = api::json::as_typed::<String>(json, "func_name")?;
+
+            api::json::encode_call(&json["abi"], &func_name, &json["params"])?
+        } else {
+            api::json::encode_calldata(json)?
+        };
 
         api::json::to_bytes(&json)
     })
@@ -19,6 +27,18 @@ pub fn decode_calldata(ptr: usize) -> Result<usize, JsonError> {
     })
 }
 
+/// Decodes the `returndata` of a function given a template ABI (see `api::json::decode_returns`).
+pub fn decode_returns(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, |json: &Value| {
+        let func_name = api::json::as_typed::<String>(json, "func_name")?;
+        let returndata = api::json::as_blob(json, "returndata")?;
+
+        let json = api::json::decode_returns(&json["abi"], &func_name, &returndata)?;
+
+        api::json::to_bytes(&json)
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -71,6 +91,66 @@ mod test {
         free(res_buf);
     }
 
+    #[test]
+    fn wasm_encode_calldata_named() {
+        let json = r#"{
+          "abi": {
+            "endpoints": [
+              {
+                "name": "work",
+                "ctor": false,
+                "params": [{ "name": "a", "type": "i32" }, { "name": "to", "type": "address" }],
+                "returns": ["i32"]
+              }
+            ],
+            "storage": []
+          },
+          "func_name": "work",
+          "params": { "to": "102030405060708090A011121314151617181920", "a": 10 }
+        }"#;
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let calldata = encode_calldata(json_buf).unwrap();
+        let data = wasm_buffer_data(calldata);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let data_buf = to_wasm_buffer(&data[1..]);
+        let res_buf = decode_calldata(data_buf).unwrap();
+
+        assert_eq!(
+            wasm_buf_as_json(res_buf),
+            json!({
+              "abi": ["i32", "address"],
+              "data": [10, "102030405060708090A011121314151617181920"]
+            })
+        );
+
+        free(json_buf);
+        free(calldata);
+        free(data_buf);
+        free(res_buf);
+    }
+
+    #[test]
+    fn wasm_decode_returns_valid() {
+        let json = r#"{
+          "abi": {
+            "endpoints": [{ "name": "work", "ctor": false, "params": [], "returns": ["bool", "u8"] }],
+            "storage": []
+          },
+          "func_name": "work",
+          "returndata": "10120A"
+        }"#;
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let res_buf = decode_returns(json_buf).unwrap();
+
+        assert_eq!(wasm_buf_as_json(res_buf), json!({ "returns": [true, 10] }));
+
+        free(json_buf);
+        free(res_buf);
+    }
+
     #[test]
     fn wasm_encode_calldata_invalid_json() {
         let json = "{";
@@ -30,7 +30,10 @@ mod test {
           "version": 0,
           "name": "My Template",
           "code": "C0DE",
-          "data": "0000000100000003"
+          "data": {
+            "vars": [[0, 1], [1, 3]],
+            "schema": []
+          }
         }"#;
 
         let json_buf = to_wasm_buffer(json.as_bytes());
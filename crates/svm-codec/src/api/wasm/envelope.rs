@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use super::wasm_buf_apply;
+use crate::{api, api::json::JsonError};
+
+/// Encodes an `envelope` json input into SVM binary transaction `Envelope`.
+/// The json input is passed by giving WASM memory start address (`ptr` parameter).
+///
+/// Returns a pointer to a `transaction buffer`.
+pub fn encode_envelope(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, api::json::encode_envelope)
+}
+
+/// Returns a pointer to a buffer holding the bytes to be signed by the `Envelope` sender.
+pub fn envelope_signing_bytes(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, api::json::envelope_signing_bytes)
+}
+
+pub fn decode_envelope(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, |json: &Value| {
+        let json = api::json::decode_envelope(json)?;
+
+        api::json::to_bytes(&json)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::api::json;
+    use crate::api::wasm::{free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER};
+
+    use serde_json::json;
+
+    #[test]
+    fn wasm_encode_envelope_valid() {
+        let json = json!({
+            "version": 0,
+            "kind": "spawn-app",
+            "sender": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "nonce": 5,
+            "gas_limit": 1000,
+            "gas_price": 2,
+            "value": 0,
+            "payload": "A0B0C0",
+            "public_key": "AA".repeat(32),
+            "signature": "BB".repeat(64),
+        });
+
+        let json_str = serde_json::to_string(&json).unwrap();
+        let json_buf = to_wasm_buffer(json_str.as_bytes());
+        let tx_buf = encode_envelope(json_buf).unwrap();
+
+        let data = wasm_buffer_data(tx_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let data = json::bytes_to_str(&data[1..]);
+        let data_json = serde_json::to_string(&json!({ "data": data })).unwrap();
+
+        free(json_buf);
+        free(tx_buf);
+
+        let json_buf = to_wasm_buffer(data_json.as_bytes());
+        let decoded_buf = decode_envelope(json_buf).unwrap();
+
+        let data = wasm_buffer_data(decoded_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let decoded: Value = serde_json::from_slice(&data[1..]).unwrap();
+        assert_eq!(decoded, json);
+
+        free(json_buf);
+        free(decoded_buf);
+    }
+}
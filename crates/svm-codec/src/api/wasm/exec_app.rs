@@ -50,7 +50,7 @@ mod test {
         .unwrap();
 
         let json = json!({
-          "version": 1,
+          "version": 0,
           "app": app_addr,
           "func_name": "do_something",
           "calldata": calldata["calldata"],
@@ -80,7 +80,7 @@ mod test {
         assert_eq!(
             json,
             json!({
-                "version": 1,
+                "version": 0,
                 "app": app_addr,
                 "func_name": "do_something",
                 "calldata": {
@@ -1,16 +1,20 @@
 mod calldata;
 mod deploy_template;
+mod envelope;
 mod error;
 mod exec_app;
 mod receipt;
 mod spawn_app;
+mod upgrade_app;
 
-pub use calldata::{decode_calldata, encode_calldata};
+pub use calldata::{decode_calldata, decode_returns, encode_calldata};
 pub use deploy_template::encode_deploy_template;
+pub use envelope::{decode_envelope, encode_envelope, envelope_signing_bytes};
 pub use error::{error_as_string, into_error_buffer};
 pub use exec_app::{decode_exec_app, encode_exec_app};
-pub use receipt::decode_receipt;
+pub use receipt::{decode_receipt, encode_receipt};
 pub use spawn_app::{decode_spawn_app, encode_spawn_app};
+pub use upgrade_app::{decode_upgrade_app, encode_upgrade_app};
 
 use crate::api::json::JsonError;
 use byteorder::{BigEndian, ByteOrder};
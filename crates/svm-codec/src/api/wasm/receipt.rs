@@ -6,6 +6,14 @@ use crate::{
     api::json::{self, JsonError},
 };
 
+/// Encodes a JSON receipt (of any type) into a binary receipt.
+/// The json input is passed by giving WASM memory start address (`ptr` parameter).
+///
+/// Returns a pointer to a `receipt buffer`.
+pub fn encode_receipt(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, api::json::encode_receipt)
+}
+
 pub fn decode_receipt(ptr: usize) -> Result<usize, JsonError> {
     wasm_buf_apply(ptr, |json: &Value| {
         let json = api::json::decode_receipt(json)?;
@@ -19,7 +27,8 @@ mod test {
     use super::*;
 
     use svm_nibble::NibbleIter;
-    use svm_types::{gas::MaybeGas, receipt::SpawnAppReceipt, Address, State, WasmValue};
+    use svm_types::receipt::{Bloom, SpawnAppReceipt};
+    use svm_types::{gas::MaybeGas, Address, State, WasmValue};
 
     use crate::api::wasm::{
         error_as_string, free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER,
@@ -65,6 +74,7 @@ mod test {
                 "gas_used": 10,
                 "returndata": "1020",
                 "state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+                "bloom": crate::api::json::bytes_to_str(Bloom::empty().as_slice()),
                 "logs": []
             })
         );
@@ -72,4 +82,27 @@ mod test {
         free(json_buf);
         free(receipt_buf);
     }
+
+    #[test]
+    fn wasm_encode_receipt_valid() {
+        let json = json!({
+            "type": "exec-app",
+            "success": false,
+            "error": { "type": "oog" },
+            "logs": [],
+        });
+
+        let json = serde_json::to_string(&json).unwrap();
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let receipt_buf = encode_receipt(json_buf).unwrap();
+
+        let data = wasm_buffer_data(receipt_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let receipt = crate::api::raw::decode_receipt(&data[1..]).unwrap();
+        assert_eq!(receipt.get_error(), &svm_types::receipt::ReceiptError::OOG);
+
+        free(json_buf);
+        free(receipt_buf);
+    }
 }
@@ -46,7 +46,7 @@ mod test {
         .unwrap();
 
         let json = json!({
-          "version": 1,
+          "version": 0,
           "template": template_addr,
           "name": "My App",
           "ctor_name": "initialize",
@@ -77,7 +77,7 @@ mod test {
         assert_eq!(
             json,
             json!({
-                "version": 1,
+                "version": 0,
                 "template": template_addr,
                 "name": "My App",
                 "ctor_name": "initialize",
@@ -0,0 +1,20 @@
+use serde_json::Value;
+
+use super::wasm_buf_apply;
+use crate::{api, api::json::JsonError};
+
+/// Encodes a `upgrade-app` json input into SVM `upgrade-app` binary transaction.
+/// The json input is passed by giving WASM memory start address (`ptr` parameter).
+///
+/// Returns a pointer to a `transaction buffer`.
+pub fn encode_upgrade_app(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, api::json::encode_upgrade_app)
+}
+
+pub fn decode_upgrade_app(ptr: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(ptr, |json: &Value| {
+        let json = api::json::decode_upgrade_app(json)?;
+
+        api::json::to_bytes(&json)
+    })
+}
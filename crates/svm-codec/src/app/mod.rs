@@ -17,3 +17,5 @@ mod wire;
 
 pub use serialize::{DefaultAppDeserializer, DefaultAppSerializer};
 pub use wire::{decode_spawn_app, encode_spawn_app};
+
+pub(crate) use wire::DECODERS;
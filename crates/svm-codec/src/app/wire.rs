@@ -5,6 +5,7 @@ pub use crate::api::raw::{
     decode_abi_data, decode_varuint14, decode_version, encode_abi_data, encode_varuint14, Field,
 };
 
+use crate::version::{dispatch, Decoder};
 use crate::{error::ParseError, helpers};
 
 /// Encodes a raw Spawn-App transaction.
@@ -19,8 +20,18 @@ pub fn encode_spawn_app(spawn: &SpawnApp, w: &mut NibbleWriter) {
 /// Parsing a raw `spawn-app` transaction given as raw bytes.
 /// Returns the parsed transaction as a tuple consisting of an `App` struct and `ctor_name` buffer args.
 /// On failure, returns `ParseError`.
+///
+/// Dispatches to the decoder of the declared version (see `DECODERS`).
 pub fn decode_spawn_app(iter: &mut NibbleIter) -> Result<SpawnApp, ParseError> {
     let version = decode_version(iter)?;
+
+    dispatch(DECODERS, version, iter)
+}
+
+/// The decoder of each supported `spawn-app` wire version.
+pub(crate) const DECODERS: &[(u32, Decoder<SpawnApp>)] = &[(0, decode_v0)];
+
+fn decode_v0(version: u32, iter: &mut NibbleIter) -> Result<SpawnApp, ParseError> {
     let template = decode_template(iter)?;
     let name = decode_name(iter)?;
     let ctor_name = decode_ctor(iter)?;
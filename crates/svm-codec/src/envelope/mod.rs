@@ -0,0 +1,32 @@
+//! Transaction `Envelope` Raw Format Version 0.0
+//!  +-----------------------------------------------------+
+//!  |   proto     |             |                         |
+//!  |  version    |   TxKind    |   Sender (Address)      |
+//!  |  encoding   | (1 nibble)  |                         |
+//!  |_____________|_____________|_________________________|
+//!  |             |             |             |           |
+//!  |    nonce    |  gas limit  |  gas price  |   value   |
+//!  |    (u64)    |    (u64)    |    (u64)    |   (u64)   |
+//!  |_____________|_____________|_____________|___________|
+//!  |            |                                        |
+//!  |  payload   |          payload blob                  |
+//!  |  #length   |   (encoded `TxKind` transaction)       |
+//!  | (varuint32)|                                        |
+//!  |____________|________________________________________|
+//!  |                      |                              |
+//!  |  ed25519 Public Key  |     ed25519 Signature        |
+//!  |     (32 bytes)       |        (64 bytes)            |
+//!  |______________________|______________________________|
+//!
+//!
+//! All the `u64` fields are encoded in Big-Endian.
+//!
+//! The signature is computed over the encoding of all the fields preceding the `Public Key`.
+//! (see `envelope_signing_bytes`).
+//!
+
+mod verify;
+mod wire;
+
+pub use verify::{sign_envelope, verify_envelope};
+pub use wire::{decode_envelope, encode_envelope, envelope_signing_bytes};
@@ -0,0 +1,126 @@
+use std::convert::TryFrom;
+
+use ed25519_dalek::{ExpandedSecretKey, SecretKey};
+
+use svm_types::{Envelope, PublicKey, Signature};
+
+use super::envelope_signing_bytes;
+use crate::error::EnvelopeError;
+
+/// Signs `envelope` using the `ed25519` secret key given as `secret_key` (32 bytes).
+///
+/// Sets the `public_key` and `signature` fields of the envelope.
+/// The `sender` is set too, since it's derived from the `public_key` (see `Envelope::sender_of`).
+pub fn sign_envelope(envelope: &mut Envelope, secret_key: &[u8]) -> Result<(), EnvelopeError> {
+    let secret = SecretKey::from_bytes(secret_key).map_err(|_| EnvelopeError::InvalidSecretKey)?;
+    let public = ed25519_dalek::PublicKey::from(&secret);
+
+    envelope.public_key = PublicKey::from(public.to_bytes());
+    envelope.sender = Envelope::sender_of(&envelope.public_key);
+
+    let msg = envelope_signing_bytes(envelope);
+    let sig = ExpandedSecretKey::from(&secret).sign(&msg, &public);
+
+    envelope.signature = Signature::from(sig.to_bytes());
+
+    Ok(())
+}
+
+/// Verifies that `envelope` has been signed by its `sender`.
+///
+/// * The `public_key` must be a valid `ed25519` public key.
+/// * The `sender` must be the address derived from the `public_key` (see `Envelope::sender_of`).
+/// * The `signature` must be a valid signature over the `envelope_signing_bytes` of `envelope`.
+pub fn verify_envelope(envelope: &Envelope) -> Result<(), EnvelopeError> {
+    let public = ed25519_dalek::PublicKey::from_bytes(envelope.public_key.as_slice())
+        .map_err(|_| EnvelopeError::InvalidPublicKey)?;
+
+    if Envelope::sender_of(&envelope.public_key) != envelope.sender {
+        return Err(EnvelopeError::SenderMismatch);
+    }
+
+    let sig = ed25519_dalek::Signature::try_from(envelope.signature.as_slice())
+        .map_err(|_| EnvelopeError::InvalidSignature)?;
+
+    let msg = envelope_signing_bytes(envelope);
+
+    public
+        .verify_strict(&msg, &sig)
+        .map_err(|_| EnvelopeError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Address, TxKind};
+
+    const SECRET_KEY: [u8; 32] = [0x11; 32];
+
+    fn signed_envelope() -> Envelope {
+        let mut envelope = Envelope {
+            version: 0,
+            kind: TxKind::ExecApp,
+            sender: Address::of("@sender"),
+            nonce: 1,
+            gas_limit: 1000,
+            gas_price: 1,
+            value: 10,
+            payload: vec![0xFF; 10],
+            public_key: PublicKey::from([0; 32]),
+            signature: Signature::from([0; 64]),
+        };
+
+        sign_envelope(&mut envelope, &SECRET_KEY).unwrap();
+
+        envelope
+    }
+
+    #[test]
+    fn verify_envelope_valid() {
+        let envelope = signed_envelope();
+
+        assert_eq!(envelope.sender, Envelope::sender_of(&envelope.public_key));
+        assert_eq!(verify_envelope(&envelope), Ok(()));
+    }
+
+    #[test]
+    fn verify_envelope_tampered() {
+        let mut envelope = signed_envelope();
+        envelope.nonce += 1;
+
+        assert_eq!(
+            verify_envelope(&envelope),
+            Err(EnvelopeError::InvalidSignature)
+        );
+
+        let mut envelope = signed_envelope();
+        envelope.payload[0] = 0;
+
+        assert_eq!(
+            verify_envelope(&envelope),
+            Err(EnvelopeError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn verify_envelope_sender_mismatch() {
+        let mut envelope = signed_envelope();
+        envelope.sender = Address::of("@someone-else");
+
+        assert_eq!(
+            verify_envelope(&envelope),
+            Err(EnvelopeError::SenderMismatch)
+        );
+    }
+
+    #[test]
+    fn sign_envelope_invalid_secret_key() {
+        let mut envelope = signed_envelope();
+
+        assert_eq!(
+            sign_envelope(&mut envelope, &[0; 10]),
+            Err(EnvelopeError::InvalidSecretKey)
+        );
+    }
+}
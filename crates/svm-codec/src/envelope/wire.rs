@@ -0,0 +1,240 @@
+use svm_nibble::{nib, Nibble, NibbleIter, NibbleWriter};
+use svm_types::{Address, Envelope, PublicKey, Signature, TxKind};
+
+use crate::api::raw::{decode_varuint32, decode_version, encode_varuint32, Field};
+
+use crate::{error::ParseError, helpers};
+
+/// Encodes a raw transaction `Envelope`.
+pub fn encode_envelope(envelope: &Envelope, w: &mut NibbleWriter) {
+    encode_unsigned(envelope, w);
+
+    encode_public_key(envelope, w);
+    encode_signature(envelope, w);
+}
+
+/// Returns the bytes signed by the `Envelope` sender.
+///
+/// These are the encoding of all the `Envelope` fields, excluding the `public_key` and `signature`.
+pub fn envelope_signing_bytes(envelope: &Envelope) -> Vec<u8> {
+    let mut w = NibbleWriter::new();
+
+    encode_unsigned(envelope, &mut w);
+
+    w.into_bytes()
+}
+
+/// Parsing a raw transaction `Envelope` given as raw bytes.
+/// Returns the parsed envelope as an `Envelope` struct.
+/// On failure, returns `ParseError`.
+pub fn decode_envelope(iter: &mut NibbleIter) -> Result<Envelope, ParseError> {
+    let version = decode_envelope_version(iter)?;
+    let kind = decode_kind(iter)?;
+    let sender = helpers::decode_address(iter, Field::Sender)?;
+    let nonce = helpers::decode_u64_be(iter, Field::Nonce)?;
+    let gas_limit = helpers::decode_u64_be(iter, Field::GasLimit)?;
+    let gas_price = helpers::decode_u64_be(iter, Field::GasPrice)?;
+    let value = helpers::decode_u64_be(iter, Field::Value)?;
+    let payload = decode_payload(iter)?;
+    let public_key = decode_public_key(iter)?;
+    let signature = decode_signature(iter)?;
+
+    let envelope = Envelope {
+        version,
+        kind,
+        sender,
+        nonce,
+        gas_limit,
+        gas_price,
+        value,
+        payload,
+        public_key,
+        signature,
+    };
+
+    Ok(envelope)
+}
+
+/// Encoders
+
+fn encode_unsigned(envelope: &Envelope, w: &mut NibbleWriter) {
+    crate::api::raw::encode_version(envelope.version, w);
+    w.push(nib!(envelope.kind as u8));
+
+    helpers::encode_address(&envelope.sender, w);
+    helpers::encode_u64_be(envelope.nonce, w);
+    helpers::encode_u64_be(envelope.gas_limit, w);
+    helpers::encode_u64_be(envelope.gas_price, w);
+    helpers::encode_u64_be(envelope.value, w);
+
+    encode_payload(envelope, w);
+}
+
+fn encode_payload(envelope: &Envelope, w: &mut NibbleWriter) {
+    let payload = &envelope.payload;
+
+    assert!(payload.len() <= std::u32::MAX as usize);
+
+    encode_varuint32(payload.len() as u32, w);
+    w.write_bytes(payload);
+}
+
+fn encode_public_key(envelope: &Envelope, w: &mut NibbleWriter) {
+    w.write_bytes(envelope.public_key.as_slice());
+}
+
+fn encode_signature(envelope: &Envelope, w: &mut NibbleWriter) {
+    w.write_bytes(envelope.signature.as_slice());
+}
+
+/// Decoders
+
+fn decode_envelope_version(iter: &mut NibbleIter) -> Result<u32, ParseError> {
+    let version = decode_version(iter)?;
+
+    if version != 0 {
+        return Err(ParseError::InvalidProtocolVersion(version));
+    }
+
+    Ok(version)
+}
+
+fn decode_kind(iter: &mut NibbleIter) -> Result<TxKind, ParseError> {
+    let nib: Option<Nibble> = iter.next();
+
+    match nib {
+        None => Err(ParseError::NotEnoughBytes(Field::TxKind)),
+        Some(nib) => TxKind::from_u8(nib.inner()).ok_or(ParseError::InvalidValue(Field::TxKind)),
+    }
+}
+
+fn decode_payload(iter: &mut NibbleIter) -> Result<Vec<u8>, ParseError> {
+    let length = decode_varuint32(iter, Field::PayloadLength)? as usize;
+
+    if length == 0 {
+        return Err(ParseError::EmptyField(Field::Payload));
+    }
+
+    let payload = iter.read_bytes(length);
+
+    if payload.len() != length {
+        return Err(ParseError::NotEnoughBytes(Field::Payload));
+    }
+
+    Ok(payload)
+}
+
+fn decode_public_key(iter: &mut NibbleIter) -> Result<PublicKey, ParseError> {
+    let bytes = iter.read_bytes(PublicKey::len());
+
+    if bytes.len() != PublicKey::len() {
+        return Err(ParseError::NotEnoughBytes(Field::PublicKey));
+    }
+
+    Ok(PublicKey::from(&bytes[..]))
+}
+
+fn decode_signature(iter: &mut NibbleIter) -> Result<Signature, ParseError> {
+    let bytes = iter.read_bytes(Signature::len());
+
+    if bytes.len() != Signature::len() {
+        return Err(ParseError::NotEnoughBytes(Field::Signature));
+    }
+
+    Ok(Signature::from(&bytes[..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_envelope() -> Envelope {
+        Envelope {
+            version: 0,
+            kind: TxKind::ExecApp,
+            sender: Address::of("@sender"),
+            nonce: 7,
+            gas_limit: 1_000_000,
+            gas_price: 2,
+            value: 100,
+            payload: vec![0x10, 0x20, 0x30],
+            public_key: PublicKey::from([0xAA; 32]),
+            signature: Signature::from([0xBB; 64]),
+        }
+    }
+
+    fn encode(envelope: &Envelope) -> Vec<u8> {
+        let mut w = NibbleWriter::new();
+        encode_envelope(envelope, &mut w);
+
+        w.into_bytes()
+    }
+
+    #[test]
+    fn encode_decode_envelope() {
+        let envelope = build_envelope();
+        let bytes = encode(&envelope);
+
+        let mut iter = NibbleIter::new(&bytes[..]);
+        let decoded = decode_envelope(&mut iter).unwrap();
+
+        assert_eq!(envelope, decoded);
+    }
+
+    #[test]
+    fn envelope_signing_bytes_excludes_signature() {
+        let envelope = build_envelope();
+        let bytes = encode(&envelope);
+        let signing_bytes = envelope_signing_bytes(&envelope);
+
+        let signed_len = bytes.len() - PublicKey::len() - Signature::len();
+        assert_eq!(&bytes[..signed_len], &signing_bytes[..]);
+    }
+
+    #[test]
+    fn decode_envelope_invalid_kind() {
+        let mut bytes = encode(&build_envelope());
+
+        // the version takes the first nibble and the kind the second one
+        bytes[0] = (bytes[0] & 0xF0) | 0x0F;
+
+        let mut iter = NibbleIter::new(&bytes[..]);
+        let err = decode_envelope(&mut iter).unwrap_err();
+
+        assert_eq!(err, ParseError::InvalidValue(Field::TxKind));
+    }
+
+    #[test]
+    fn decode_envelope_empty_payload() {
+        let mut envelope = build_envelope();
+        envelope.payload.clear();
+
+        let bytes = encode(&envelope);
+        let mut iter = NibbleIter::new(&bytes[..]);
+        let err = decode_envelope(&mut iter).unwrap_err();
+
+        assert_eq!(err, ParseError::EmptyField(Field::Payload));
+    }
+
+    #[test]
+    fn decode_envelope_truncated() {
+        let bytes = encode(&build_envelope());
+
+        let expected = [
+            (1, Field::Sender),
+            (25, Field::Nonce),
+            (50, Field::Value),
+            (53, Field::PayloadLength),
+            (55, Field::Payload),
+            (70, Field::PublicKey),
+            (bytes.len() - 1, Field::Signature),
+        ];
+
+        for (len, field) in expected.iter() {
+            let mut iter = NibbleIter::new(&bytes[..*len]);
+            let err = decode_envelope(&mut iter).unwrap_err();
+
+            assert_eq!(err, ParseError::NotEnoughBytes(*field));
+        }
+    }
+}
@@ -13,6 +13,28 @@ pub enum ParseError {
     InvalidUTF8String(Field),
     InvalidProtocolVersion(u32),
     UnexpectedLayout(Field),
+    InvalidSchema(Field),
+    InvalidValue(Field),
+    InvalidWasm(String),
+    MissingWasmExport(String),
+    UnknownImport {
+        namespace: String,
+        name: String,
+    },
+    ImportSignatureMismatch {
+        namespace: String,
+        name: String,
+    },
+    ZeroLengthVar(u32),
+    OverlappingVars(u32, u32),
+    DataLayoutTooLarge {
+        size: u64,
+        max: u32,
+    },
+    NameTooLong {
+        len: usize,
+        max: usize,
+    },
     IncompleteWasmValue {
         expected_nibbles: usize,
         actual_read: usize,
@@ -26,7 +48,9 @@ impl fmt::Display for ParseError {
             ParseError::EmptyField(f) => write!(fmt, "Field `{}` must not be empty", f),
             ParseError::NotEnoughBytes(f) => write!(fmt, "Not enough bytes for field `{}`", f),
             ParseError::TooManyBytes(f) => write!(fmt, "Too many bytes for field `{}`", f),
-            ParseError::InvalidProtocolVersion(msg) => write!(fmt, "{}", msg),
+            ParseError::InvalidProtocolVersion(version) => {
+                write!(fmt, "Unsupported protocol version `{}`", version)
+            }
             ParseError::NotSupported(f) => write!(fmt, "Feature `{}` is not supported yet", f),
             ParseError::InvalidUTF8String(f) => {
                 write!(fmt, "Field `{}` must be a valid UTF-8 string", f)
@@ -37,6 +61,40 @@ impl fmt::Display for ParseError {
             ParseError::UnexpectedLayout(f) => {
                 write!(fmt, "Unexpected Wasm value layout for field `{}`", f)
             }
+            ParseError::InvalidSchema(f) => {
+                write!(fmt, "Invalid storage schema for field `{}`", f)
+            }
+            ParseError::InvalidValue(f) => write!(fmt, "Invalid value for field `{}`", f),
+            ParseError::InvalidWasm(msg) => write!(fmt, "Invalid wasm code: {}", msg),
+            ParseError::MissingWasmExport(name) => {
+                write!(fmt, "Wasm code must export `{}`", name)
+            }
+            ParseError::UnknownImport { namespace, name } => {
+                write!(fmt, "Unknown import `{}.{}`", namespace, name)
+            }
+            ParseError::ImportSignatureMismatch { namespace, name } => {
+                write!(
+                    fmt,
+                    "Import `{}.{}` has a mismatching signature",
+                    namespace, name
+                )
+            }
+            ParseError::ZeroLengthVar(var_id) => {
+                write!(fmt, "Variable #{} must not be of zero length", var_id)
+            }
+            ParseError::OverlappingVars(a, b) => {
+                write!(fmt, "Variables #{} and #{} overlap", a, b)
+            }
+            ParseError::DataLayoutTooLarge { size, max } => write!(
+                fmt,
+                "Data-Layout size ({} bytes) exceeds the maximum ({} bytes)",
+                size, max
+            ),
+            ParseError::NameTooLong { len, max } => write!(
+                fmt,
+                "Name length ({} bytes) exceeds the maximum ({} bytes)",
+                len, max
+            ),
         }
     }
 }
@@ -46,3 +104,32 @@ impl fmt::Debug for ParseError {
         <Self as fmt::Display>::fmt(self, f)
     }
 }
+
+/// Errors raised while signing or verifying a transaction `Envelope`.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum EnvelopeError {
+    InvalidSecretKey,
+    InvalidPublicKey,
+    InvalidSignature,
+    SenderMismatch,
+    NonceMismatch { expected: u64, actual: u64 },
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EnvelopeError::InvalidSecretKey => write!(fmt, "Invalid ed25519 secret key"),
+            EnvelopeError::InvalidPublicKey => write!(fmt, "Invalid ed25519 public key"),
+            EnvelopeError::InvalidSignature => write!(fmt, "Invalid envelope signature"),
+            EnvelopeError::SenderMismatch => {
+                write!(fmt, "Envelope sender doesn't match its public key")
+            }
+            EnvelopeError::NonceMismatch { expected, actual } => write!(
+                fmt,
+                "Invalid envelope nonce (expected: {}, actual: {})",
+                expected, actual
+            ),
+        }
+    }
+}
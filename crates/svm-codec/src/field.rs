@@ -24,6 +24,40 @@ pub enum Field {
     FuncNameLength,
     FuncName,
     FuncIndex,
+    State,
+    VarValue,
+    SchemaVarsCount,
+    SchemaVarNameLength,
+    SchemaVarName,
+    SchemaVarType,
+    SchemaVarCount,
+    Migration,
+    ReceiptType,
+    IsSuccess,
+    ErrorType,
+    RevertDataLength,
+    RevertData,
+    LogsBloom,
+    LogsCount,
+    LogKind,
+    LogMessageLength,
+    LogMessage,
+    LogCode,
+    LogTopicsCount,
+    LogTopicLength,
+    LogTopic,
+    EventDataLength,
+    EventData,
+    TxKind,
+    Sender,
+    Nonce,
+    GasLimit,
+    GasPrice,
+    Value,
+    PayloadLength,
+    Payload,
+    PublicKey,
+    Signature,
 }
 
 impl fmt::Display for Field {
@@ -16,10 +16,9 @@ macro_rules! invalid_layout {
 pub fn decode_gas_used(iter: &mut NibbleIter) -> Result<MaybeGas, ParseError> {
     let nib = iter.next();
 
-    let layout = if let Some(nib) = nib {
-        nib.into()
-    } else {
-        invalid_layout!()
+    let layout = match nib {
+        Some(nib) if nib.inner() != wasm::NO_MORE && nib.inner() != wasm::DO_SKIP => nib.into(),
+        _ => invalid_layout!(),
     };
 
     let value = wasm::decode_wasm_value(&layout, iter)?;
@@ -32,3 +31,29 @@ pub fn decode_gas_used(iter: &mut NibbleIter) -> Result<MaybeGas, ParseError> {
         invalid_layout!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_nibble::{nib, NibbleWriter};
+
+    use crate::api::raw::Field;
+
+    #[test]
+    fn decode_gas_used_rejects_non_value_layouts() {
+        for layout in [wasm::NO_MORE, wasm::DO_SKIP].iter() {
+            let mut w = NibbleWriter::new();
+            w.push(nib!(*layout));
+            w.write_bytes(&[0xFF; 8]);
+
+            let data = w.into_bytes();
+            let mut iter = NibbleIter::new(&data);
+
+            assert_eq!(
+                decode_gas_used(&mut iter),
+                Err(ParseError::UnexpectedLayout(Field::GasUsed))
+            );
+        }
+    }
+}
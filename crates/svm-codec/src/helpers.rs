@@ -31,6 +31,13 @@ pub fn encode_u32_be(n: u32, w: &mut NibbleWriter) {
     w.write_bytes(&buf[..]);
 }
 
+pub fn encode_u64_be(n: u64, w: &mut NibbleWriter) {
+    let mut buf = vec![0; 8];
+    BigEndian::write_u64(&mut buf, n);
+
+    w.write_bytes(&buf[..]);
+}
+
 /// Decoders
 
 #[must_use]
@@ -77,3 +84,15 @@ pub fn decode_u32_be(iter: &mut NibbleIter, field: Field) -> Result<u32, ParseEr
 
     Ok(n)
 }
+
+pub fn decode_u64_be(iter: &mut NibbleIter, field: Field) -> Result<u64, ParseError> {
+    let bytes = iter.read_bytes(8);
+
+    if bytes.len() != 8 {
+        return Err(ParseError::NotEnoughBytes(field));
+    }
+
+    let n = BigEndian::read_u64(&bytes[..]);
+
+    Ok(n)
+}
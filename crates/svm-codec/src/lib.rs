@@ -17,13 +17,17 @@ mod wasm;
 
 mod abi;
 mod app;
+mod envelope;
 mod field;
 mod gas;
 mod helpers;
+mod snapshot;
 mod template;
 mod traits;
 mod transaction;
+mod upgrade;
 mod varuint14;
+mod varuint32;
 mod version;
 
 /// Wraps the exposed APIs under a single place.
@@ -149,6 +153,51 @@ pub extern "C" fn wasm_decode_exec_app(ptr: i32) -> i32 {
     wasm_func_call!(decode_exec_app, ptr)
 }
 
+/// ## WASM Upgrade-App
+///
+/// Reads the WASM buffer given at parameter `ptr` containing a JSON value.
+/// Encodes a `upgrade-app` binary-transaction using that JSON value.
+///
+/// Returns a pointer to a new WASM buffer holding the encoded transaction.
+/// If the encoding failed, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_encode_upgrade_app(ptr: i32) -> i32 {
+    wasm_func_call!(encode_upgrade_app, ptr)
+}
+
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_upgrade_app(ptr: i32) -> i32 {
+    wasm_func_call!(decode_upgrade_app, ptr)
+}
+
+/// ## WASM Envelope
+///
+/// Reads the WASM buffer given at parameter `ptr` containing a JSON value.
+/// Encodes a transaction `Envelope` using that JSON value.
+///
+/// Returns a pointer to a new WASM buffer holding the encoded envelope.
+/// If the encoding failed, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_encode_envelope(ptr: i32) -> i32 {
+    wasm_func_call!(encode_envelope, ptr)
+}
+
+/// Returns a pointer to a new WASM buffer holding the bytes to be signed by the envelope's sender.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_envelope_signing_bytes(ptr: i32) -> i32 {
+    wasm_func_call!(envelope_signing_bytes, ptr)
+}
+
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_envelope(ptr: i32) -> i32 {
+    wasm_func_call!(decode_envelope, ptr)
+}
+
 /// ## WASM Buffer Allocation
 ///
 /// Allocates a new WASM Buffer holding data of `length` bytes.
@@ -195,6 +244,14 @@ pub extern "C" fn wasm_buffer_data(ptr: i32) -> i32 {
     data_ptr as _
 }
 
+/// ## WASM Encode CallData
+///
+/// Reads the WASM buffer given at parameter `ptr` containing a JSON value.
+/// The JSON holds either positional `abi` and `data` arrays,
+/// or a template `abi`, a `func_name` and its named `params`.
+///
+/// Returns a pointer to a new WASM buffer holding the encoded `calldata` (as JSON).
+/// If the encoding failed, the returned WASM buffer will contain a String containing the error message.
 #[no_mangle]
 #[cfg(target_arch = "wasm32")]
 pub extern "C" fn wasm_encode_calldata(ptr: i32) -> i32 {
@@ -207,6 +264,25 @@ pub extern "C" fn wasm_decode_calldata(ptr: i32) -> i32 {
     wasm_func_call!(decode_calldata, ptr)
 }
 
+/// ## WASM Decode Returns
+///
+/// Reads the WASM buffer given at parameter `ptr` containing a JSON value
+/// holding a template `abi`, a `func_name` and the function's `returndata`.
+///
+/// Returns a pointer to a new WASM buffer holding the decoded return values (as JSON).
+/// If the decoding failed, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_returns(ptr: i32) -> i32 {
+    wasm_func_call!(decode_returns, ptr)
+}
+
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_encode_receipt(ptr: i32) -> i32 {
+    wasm_func_call!(encode_receipt, ptr)
+}
+
 #[no_mangle]
 #[cfg(target_arch = "wasm32")]
 pub extern "C" fn wasm_decode_receipt(ptr: i32) -> i32 {
@@ -16,6 +16,8 @@ use svm_types::gas::MaybeGas;
 use svm_types::receipt::{Receipt, TemplateReceipt};
 
 use super::{decode_error, encode_error, helpers, logs};
+use crate::api::raw::Field;
+use crate::error::ParseError;
 
 pub fn encode_template_receipt(receipt: &TemplateReceipt) -> Vec<u8> {
     let mut w = NibbleWriter::new();
@@ -31,46 +33,43 @@ pub fn encode_template_receipt(receipt: &TemplateReceipt) -> Vec<u8> {
         helpers::encode_gas_used(&wrapped_receipt, &mut w);
         logs::encode_logs(&receipt.logs, &mut w);
     } else {
-        let logs = Vec::new();
-        encode_error(receipt.get_error(), &logs, &mut w);
+        let logs = receipt.get_logs();
+
+        encode_error(receipt.get_error(), logs, &mut w);
     };
 
     w.into_bytes()
 }
 
-pub fn decode_template_receipt(bytes: &[u8]) -> TemplateReceipt {
+pub fn decode_template_receipt(bytes: &[u8]) -> Result<TemplateReceipt, ParseError> {
     let mut iter = NibbleIter::new(bytes);
 
-    let ty = helpers::decode_type(&mut iter);
-    debug_assert_eq!(ty, crate::receipt::types::DEPLOY_TEMPLATE);
+    helpers::decode_type(&mut iter, super::types::DEPLOY_TEMPLATE)?;
+    helpers::decode_version(&mut iter)?;
 
-    let version = helpers::decode_version(&mut iter).unwrap();
-    debug_assert_eq!(version, 0);
+    let is_success = helpers::decode_is_success(&mut iter)?;
 
-    let is_success = helpers::decode_is_success(&mut iter);
+    let receipt = if is_success {
+        let addr = helpers::decode_address(&mut iter, Field::TemplateAddr)?;
+        let gas_used = helpers::decode_gas_used(&mut iter)?;
+        let logs = logs::decode_logs(&mut iter)?;
 
-    match is_success {
-        0 => {
-            // error
-            let (err, logs) = decode_error(&mut iter);
-            TemplateReceipt::from_err(err, logs)
+        TemplateReceipt {
+            success: true,
+            error: None,
+            addr: Some(addr.into()),
+            gas_used,
+            logs,
         }
-        1 => {
-            // success
-            let addr = helpers::decode_address(&mut iter);
-            let gas_used = helpers::decode_gas_used(&mut iter);
-            let logs = logs::decode_logs(&mut iter);
+    } else {
+        let (err, logs) = decode_error(&mut iter)?;
 
-            TemplateReceipt {
-                success: true,
-                error: None,
-                addr: Some(addr.into()),
-                gas_used,
-                logs,
-            }
-        }
-        _ => unreachable!(),
-    }
+        TemplateReceipt::from_err(err, logs)
+    };
+
+    iter.ensure_eof(ParseError::ExpectedEOF)?;
+
+    Ok(receipt)
 }
 
 fn encode_template_addr(receipt: &TemplateReceipt, w: &mut NibbleWriter) {
@@ -84,7 +83,8 @@ fn encode_template_addr(receipt: &TemplateReceipt, w: &mut NibbleWriter) {
 mod tests {
     use super::*;
 
-    use svm_types::{gas::MaybeGas, receipt::TemplateReceipt, Address, TemplateAddr};
+    use svm_types::receipt::{ReceiptError, TemplateReceipt};
+    use svm_types::{gas::MaybeGas, Address};
 
     #[test]
     fn encode_decode_deploy_template_receipt() {
@@ -99,8 +99,64 @@ mod tests {
         };
 
         let bytes = encode_template_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
 
         assert_eq!(decoded.into_deploy_template(), receipt);
     }
+
+    #[test]
+    fn encode_decode_deploy_template_receipt_error() {
+        let receipt = TemplateReceipt::from_err(ReceiptError::OOG, Vec::new());
+
+        let bytes = encode_template_receipt(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
+
+        assert_eq!(decoded.into_deploy_template(), receipt);
+    }
+
+    #[test]
+    fn decode_deploy_template_receipt_truncated() {
+        let addr = Address::of("my-template").into();
+
+        let receipts = vec![
+            TemplateReceipt {
+                success: true,
+                error: None,
+                addr: Some(addr),
+                gas_used: MaybeGas::with(100),
+                logs: Vec::new(),
+            },
+            TemplateReceipt::from_err(ReceiptError::OOG, Vec::new()),
+        ];
+
+        for receipt in receipts {
+            let bytes = encode_template_receipt(&receipt);
+
+            for len in 0..bytes.len() {
+                assert!(decode_template_receipt(&bytes[..len]).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn decode_deploy_template_receipt_invalid_version() {
+        let addr = Address::of("my-template").into();
+
+        let receipt = TemplateReceipt {
+            success: true,
+            error: None,
+            addr: Some(addr),
+            gas_used: MaybeGas::with(100),
+            logs: Vec::new(),
+        };
+
+        let mut bytes = encode_template_receipt(&receipt);
+
+        // the `version` is the left nibble of the 2nd byte
+        bytes[1] = (bytes[1] & 0x0F) | 0x10;
+
+        let expected = Err(ParseError::InvalidProtocolVersion(1));
+
+        assert_eq!(decode_template_receipt(&bytes), expected);
+    }
 }
@@ -54,17 +54,51 @@
 //!   |   (20 bytes)      |   (20 bytes)  |  (varuint14) | (UTF-8 String) |
 //!   +-------------------+-----------------------------------------------+
 //!
+//!  * Upgrade Not Authorized
+//!   +---------------------+
+//!   |     App Address     |
+//!   |     (20 bytes)      |
+//!   +---------------------+
+//!
+//!  * Incompatible Layout
+//!   +-------------------+---------------+
+//!   |  Template Address | App Address   |
+//!   |   (20 bytes)      |  (20 bytes)   |
+//!   +-------------------+---------------+
+//!
+//!  * Reverted
+//!   +---------------+-------------------+
+//!   |  Data Length  |    Revert Data    |
+//!   |  (varuint14)  |  (`length` bytes) |
+//!   +---------------+-------------------+
+//!
+//!  * Storage Failed
+//!   +---------------+-----------------+
+//!   |  App Address  |     Error       |
+//!   |  (20 bytes)   |  (UTF-8 String) |
+//!   +---------------+-----------------+
+//!
+//!  * Invalid Transaction
+//!   +-----------------+
+//!   |     Error       |
+//!   |  (UTF-8 String) |
+//!   +-----------------+
+//!
 
-use crate::api::raw::Field;
+use crate::api::raw::{decode_varuint14, encode_varuint14, Field};
+use crate::error::ParseError;
 use crate::helpers;
 
-use svm_nibble::{nib, Nibble, NibbleIter, NibbleWriter};
+use svm_nibble::{nib, NibbleIter, NibbleWriter};
 
 use svm_types::receipt::{Log, ReceiptError, ReceiptError as Err};
 use svm_types::{Address, AppAddr, TemplateAddr};
 
 use super::logs;
 
+/// The maximum byte-length of a `Reverted` error data (it must fit a `varuint14`).
+const MAX_REVERT_DATA_LEN: usize = (1 << 14) - 1;
+
 pub(crate) fn encode_error(err: &ReceiptError, logs: &[Log], w: &mut NibbleWriter) {
     encode_err_type(err, w);
 
@@ -108,9 +142,30 @@ pub(crate) fn encode_error(err: &ReceiptError, logs: &[Log], w: &mut NibbleWrite
             helpers::encode_string(func, w);
             helpers::encode_string(msg, w);
         }
+        Err::UpgradeNotAuthorized { app_addr } => helpers::encode_address(app_addr.inner(), w),
+        Err::IncompatibleLayout {
+            app_addr,
+            template_addr,
+        } => {
+            helpers::encode_address(template_addr.inner(), w);
+            helpers::encode_address(app_addr.inner(), w);
+        }
+        Err::Reverted { data } => encode_revert_data(data, w),
+        Err::StorageFailed { app_addr, msg } => {
+            helpers::encode_address(app_addr.inner(), w);
+            helpers::encode_string(msg, w);
+        }
+        Err::InvalidTransaction { msg } => helpers::encode_string(msg, w),
     };
 }
 
+fn encode_revert_data(data: &[u8], w: &mut NibbleWriter) {
+    assert!(data.len() <= MAX_REVERT_DATA_LEN);
+
+    encode_varuint14(data.len() as u16, w);
+    w.write_bytes(data);
+}
+
 fn encode_err_type(err: &ReceiptError, w: &mut NibbleWriter) {
     let ty = match err {
         Err::OOG => 0,
@@ -120,18 +175,26 @@ fn encode_err_type(err: &ReceiptError, w: &mut NibbleWriter) {
         Err::InstantiationFailed { .. } => 4,
         Err::FuncNotFound { .. } => 5,
         Err::FuncFailed { .. } => 6,
+        Err::UpgradeNotAuthorized { .. } => 7,
+        Err::IncompatibleLayout { .. } => 8,
+        Err::Reverted { .. } => 9,
+        Err::StorageFailed { .. } => 10,
+        Err::InvalidTransaction { .. } => 11,
     };
 
     w.push(nib!(ty));
 }
 
-pub(crate) fn decode_error(iter: &mut NibbleIter) -> (ReceiptError, Vec<Log>) {
-    let err_type: Nibble = iter.next().unwrap();
+pub(crate) fn decode_error(iter: &mut NibbleIter) -> Result<(ReceiptError, Vec<Log>), ParseError> {
+    let err_type = match iter.next() {
+        Some(nib) => nib.inner(),
+        None => return Err(ParseError::NotEnoughBytes(Field::ErrorType)),
+    };
 
-    let logs = logs::decode_logs(iter);
+    let logs = logs::decode_logs(iter)?;
 
     let err = {
-        match err_type.inner() {
+        match err_type {
             0 => decode_oog(iter),
             1 => decode_template_not_found(iter),
             2 => decode_app_not_found(iter),
@@ -139,92 +202,145 @@ pub(crate) fn decode_error(iter: &mut NibbleIter) -> (ReceiptError, Vec<Log>) {
             4 => decode_instantiation_err(iter),
             5 => decode_func_not_found(iter),
             6 => decode_func_err(iter),
-            _ => unreachable!(),
+            7 => decode_upgrade_not_authorized(iter),
+            8 => decode_incompatible_layout(iter),
+            9 => decode_reverted(iter),
+            10 => decode_storage_failed(iter),
+            11 => decode_invalid_transaction(iter),
+            _ => Err(ParseError::InvalidValue(Field::ErrorType)),
         }
-    };
+    }?;
 
-    (err, logs)
+    Ok((err, logs))
 }
 
-fn decode_oog(iter: &mut NibbleIter) -> ReceiptError {
-    ReceiptError::OOG
+fn decode_oog(_iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    Ok(ReceiptError::OOG)
 }
 
-fn decode_template_not_found(iter: &mut NibbleIter) -> ReceiptError {
-    let template_addr = decode_template_addr(iter);
+fn decode_template_not_found(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let template_addr = decode_template_addr(iter)?;
 
-    ReceiptError::TemplateNotFound(template_addr.into())
+    Ok(ReceiptError::TemplateNotFound(template_addr.into()))
 }
 
-fn decode_app_not_found(iter: &mut NibbleIter) -> ReceiptError {
-    let app_addr = decode_app_addr(iter);
+fn decode_app_not_found(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let app_addr = decode_app_addr(iter)?;
 
-    ReceiptError::AppNotFound(app_addr.into())
+    Ok(ReceiptError::AppNotFound(app_addr.into()))
 }
 
-fn decode_compilation_err(iter: &mut NibbleIter) -> ReceiptError {
-    let (template_addr, app_addr) = decode_addrs(iter);
-    let msg = decode_msg(iter);
+fn decode_compilation_err(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let (template_addr, app_addr) = decode_addrs(iter)?;
+    let msg = decode_msg(iter)?;
 
-    ReceiptError::CompilationFailed {
+    Ok(ReceiptError::CompilationFailed {
         template_addr,
         app_addr,
         msg,
-    }
+    })
 }
 
-fn decode_instantiation_err(iter: &mut NibbleIter) -> ReceiptError {
-    let (template_addr, app_addr) = decode_addrs(iter);
-    let msg = decode_msg(iter);
+fn decode_instantiation_err(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let (template_addr, app_addr) = decode_addrs(iter)?;
+    let msg = decode_msg(iter)?;
 
-    ReceiptError::InstantiationFailed {
+    Ok(ReceiptError::InstantiationFailed {
         template_addr,
         app_addr,
         msg,
-    }
+    })
 }
 
-fn decode_func_not_found(iter: &mut NibbleIter) -> ReceiptError {
-    let (template_addr, app_addr) = decode_addrs(iter);
-    let func = helpers::decode_string(iter, Field::FuncNameLength, Field::FuncName).unwrap();
+fn decode_func_not_found(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let (template_addr, app_addr) = decode_addrs(iter)?;
+    let func = decode_func(iter)?;
 
-    ReceiptError::FuncNotFound {
+    Ok(ReceiptError::FuncNotFound {
         template_addr,
         app_addr,
         func,
-    }
+    })
 }
 
-fn decode_func_err(iter: &mut NibbleIter) -> ReceiptError {
-    let (template_addr, app_addr) = decode_addrs(iter);
-    let func = helpers::decode_string(iter, Field::FuncNameLength, Field::FuncName).unwrap();
-    let msg = decode_msg(iter);
+fn decode_func_err(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let (template_addr, app_addr) = decode_addrs(iter)?;
+    let func = decode_func(iter)?;
+    let msg = decode_msg(iter)?;
 
-    ReceiptError::FuncFailed {
+    Ok(ReceiptError::FuncFailed {
         template_addr,
         app_addr,
         func,
         msg,
+    })
+}
+
+fn decode_upgrade_not_authorized(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let app_addr = decode_app_addr(iter)?;
+
+    Ok(ReceiptError::UpgradeNotAuthorized {
+        app_addr: app_addr.into(),
+    })
+}
+
+fn decode_incompatible_layout(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let (template_addr, app_addr) = decode_addrs(iter)?;
+
+    Ok(ReceiptError::IncompatibleLayout {
+        template_addr,
+        app_addr,
+    })
+}
+
+fn decode_reverted(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let len = decode_varuint14(iter, Field::RevertDataLength)? as usize;
+    let data = iter.read_bytes(len);
+
+    if data.len() != len {
+        return Err(ParseError::NotEnoughBytes(Field::RevertData));
     }
+
+    Ok(ReceiptError::Reverted { data })
 }
 
-fn decode_addrs(iter: &mut NibbleIter) -> (TemplateAddr, AppAddr) {
-    let template_addr = decode_template_addr(iter);
-    let app_addr = decode_app_addr(iter);
+fn decode_storage_failed(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let app_addr = decode_app_addr(iter)?;
+    let msg = decode_msg(iter)?;
 
-    (template_addr.into(), app_addr.into())
+    Ok(ReceiptError::StorageFailed {
+        app_addr: app_addr.into(),
+        msg,
+    })
+}
+
+fn decode_invalid_transaction(iter: &mut NibbleIter) -> Result<ReceiptError, ParseError> {
+    let msg = decode_msg(iter)?;
+
+    Ok(ReceiptError::InvalidTransaction { msg })
+}
+
+fn decode_addrs(iter: &mut NibbleIter) -> Result<(TemplateAddr, AppAddr), ParseError> {
+    let template_addr = decode_template_addr(iter)?;
+    let app_addr = decode_app_addr(iter)?;
+
+    Ok((template_addr.into(), app_addr.into()))
 }
 
-fn decode_template_addr(iter: &mut NibbleIter) -> Address {
-    helpers::decode_address(iter, Field::TemplateAddr).unwrap()
+fn decode_template_addr(iter: &mut NibbleIter) -> Result<Address, ParseError> {
+    helpers::decode_address(iter, Field::TemplateAddr)
 }
 
-fn decode_app_addr(iter: &mut NibbleIter) -> Address {
-    helpers::decode_address(iter, Field::AppAddr).unwrap()
+fn decode_app_addr(iter: &mut NibbleIter) -> Result<Address, ParseError> {
+    helpers::decode_address(iter, Field::AppAddr)
 }
 
-fn decode_msg(iter: &mut NibbleIter) -> String {
-    helpers::decode_string(iter, Field::StringLength, Field::String).unwrap()
+fn decode_func(iter: &mut NibbleIter) -> Result<String, ParseError> {
+    helpers::decode_string(iter, Field::FuncNameLength, Field::FuncName)
+}
+
+fn decode_msg(iter: &mut NibbleIter) -> Result<String, ParseError> {
+    helpers::decode_string(iter, Field::StringLength, Field::String)
 }
 
 #[cfg(test)]
@@ -235,134 +351,135 @@ mod tests {
 
     fn test_logs() -> Vec<Log> {
         vec![
-            Log {
+            Log::Message {
                 msg: b"Log entry #1".to_vec(),
                 code: 0,
+                topics: Vec::new(),
             },
-            Log {
+            Log::Message {
                 msg: b"Log entry #2".to_vec(),
                 code: 1,
+                topics: Vec::new(),
             },
         ]
     }
 
-    #[test]
-    fn decode_receipt_oog() {
-        let err = ReceiptError::OOG;
-
-        let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
-
-        let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
-    }
-
-    #[test]
-    fn decode_receipt_template_not_found() {
+    fn test_errors() -> Vec<ReceiptError> {
         let template_addr = Address::of("some-template");
+        let app_addr = Address::of("some-app");
 
-        let err = ReceiptError::TemplateNotFound(template_addr.into());
+        vec![
+            ReceiptError::OOG,
+            ReceiptError::TemplateNotFound(template_addr.clone().into()),
+            ReceiptError::AppNotFound(app_addr.clone().into()),
+            ReceiptError::CompilationFailed {
+                app_addr: app_addr.clone().into(),
+                template_addr: template_addr.clone().into(),
+                msg: "Invalid code".to_string(),
+            },
+            ReceiptError::InstantiationFailed {
+                app_addr: app_addr.clone().into(),
+                template_addr: template_addr.clone().into(),
+                msg: "Invalid input".to_string(),
+            },
+            ReceiptError::FuncNotFound {
+                app_addr: app_addr.clone().into(),
+                template_addr: template_addr.clone().into(),
+                func: "do_something".to_string(),
+            },
+            ReceiptError::FuncFailed {
+                app_addr: app_addr.clone().into(),
+                template_addr: template_addr.clone().into(),
+                func: "do_something".to_string(),
+                msg: "Invalid input".to_string(),
+            },
+            ReceiptError::UpgradeNotAuthorized {
+                app_addr: app_addr.clone().into(),
+            },
+            ReceiptError::IncompatibleLayout {
+                app_addr: app_addr.clone().into(),
+                template_addr: template_addr.clone().into(),
+            },
+            ReceiptError::Reverted {
+                data: b"not enough funds".to_vec(),
+            },
+            ReceiptError::StorageFailed {
+                app_addr: app_addr.clone().into(),
+                msg: "`ExternKV` `get_many` has failed: Host is unavailable".to_string(),
+            },
+            ReceiptError::InvalidTransaction {
+                msg: "Unsupported protocol version `1`".to_string(),
+            },
+        ]
+    }
 
+    fn encode(err: &ReceiptError) -> Vec<u8> {
         let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
+        encode_error(err, &test_logs(), &mut w);
 
-        let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
+        w.into_bytes()
     }
 
     #[test]
-    fn decode_receipt_app_not_found() {
-        let app_addr = Address::of("some-app");
+    fn decode_receipt_error_round_trip() {
+        for err in test_errors() {
+            let bytes = encode(&err);
 
-        let err = ReceiptError::AppNotFound(app_addr.into());
+            let mut iter = NibbleIter::new(&bytes);
+            let decoded = decode_error(&mut iter);
 
-        let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
-
-        let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
+            assert_eq!(decoded, Ok((err, test_logs())));
+        }
     }
 
     #[test]
-    fn decode_receipt_compilation_failed() {
-        let template_addr = Address::of("some-template");
-        let app_addr = Address::of("some-app");
+    fn decode_receipt_error_truncated() {
+        for err in test_errors() {
+            let bytes = encode(&err);
 
-        let err = ReceiptError::CompilationFailed {
-            app_addr: app_addr.into(),
-            template_addr: template_addr.into(),
-            msg: "Invalid code".to_string(),
-        };
+            for len in 0..bytes.len() {
+                let mut iter = NibbleIter::new(&bytes[..len]);
 
-        let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
-
-        let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
+                assert!(decode_error(&mut iter).is_err());
+            }
+        }
     }
 
     #[test]
-    fn decode_receipt_instantiation_failed() {
-        let template_addr = Address::of("some-template");
-        let app_addr = Address::of("some-app");
+    fn decode_receipt_error_empty() {
+        let mut iter = NibbleIter::new(&[]);
 
-        let err = ReceiptError::InstantiationFailed {
-            app_addr: app_addr.into(),
-            template_addr: template_addr.into(),
-            msg: "Invalid input".to_string(),
-        };
+        let expected = Err(ParseError::NotEnoughBytes(Field::ErrorType));
 
-        let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
-
-        let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
+        assert_eq!(decode_error(&mut iter), expected);
     }
 
     #[test]
-    fn decode_receipt_func_not_found() {
-        let template_addr = Address::of("some-template");
-        let app_addr = Address::of("some-app");
-        let func = "do_something".to_string();
+    fn decode_receipt_error_unknown_type() {
+        let mut bytes = encode(&ReceiptError::OOG);
 
-        let err = ReceiptError::FuncNotFound {
-            app_addr: app_addr.into(),
-            template_addr: template_addr.into(),
-            func,
-        };
-
-        let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
+        // the error type is the first nibble
+        bytes[0] = (bytes[0] & 0x0F) | 0xF0;
 
         let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
+        let expected = Err(ParseError::InvalidValue(Field::ErrorType));
+
+        assert_eq!(decode_error(&mut iter), expected);
     }
 
     #[test]
-    fn decode_receipt_func_failed() {
-        let template_addr = Address::of("some-template");
-        let app_addr = Address::of("some-app");
-        let func = "do_something".to_string();
-        let msg = "Invalid input".to_string();
-
-        let err = ReceiptError::FuncFailed {
-            app_addr: app_addr.into(),
-            template_addr: template_addr.into(),
-            func,
-            msg,
+    fn decode_receipt_error_truncated_addr() {
+        let err = ReceiptError::IncompatibleLayout {
+            app_addr: Address::of("some-app").into(),
+            template_addr: Address::of("some-template").into(),
         };
 
-        let mut w = NibbleWriter::new();
-        encode_error(&err, &test_logs(), &mut w);
-        let bytes = w.into_bytes();
+        let bytes = encode(&err);
 
-        let mut iter = NibbleIter::new(&bytes);
-        let decoded = decode_error(&mut iter);
+        // dropping the last 10 bytes cuts the `App Address`
+        let mut iter = NibbleIter::new(&bytes[..bytes.len() - 10]);
+        let expected = Err(ParseError::NotEnoughBytes(Field::AppAddr));
+
+        assert_eq!(decode_error(&mut iter), expected);
     }
 }
@@ -25,6 +25,7 @@ use svm_types::receipt::{ExecReceipt, Log, Receipt};
 
 use super::{decode_error, encode_error, helpers, logs};
 use crate::api::raw;
+use crate::error::ParseError;
 
 pub fn encode_exec_receipt(receipt: &ExecReceipt) -> Vec<u8> {
     let mut w = NibbleWriter::new();
@@ -49,40 +50,37 @@ pub fn encode_exec_receipt(receipt: &ExecReceipt) -> Vec<u8> {
     w.into_bytes()
 }
 
-pub fn decode_exec_receipt(bytes: &[u8]) -> ExecReceipt {
+pub fn decode_exec_receipt(bytes: &[u8]) -> Result<ExecReceipt, ParseError> {
     let mut iter = NibbleIter::new(bytes);
 
-    let ty = helpers::decode_type(&mut iter);
-    debug_assert_eq!(ty, crate::receipt::types::EXEC_APP);
+    helpers::decode_type(&mut iter, super::types::EXEC_APP)?;
+    helpers::decode_version(&mut iter)?;
 
-    let version = helpers::decode_version(&mut iter).unwrap();
-    debug_assert_eq!(0, version);
+    let is_success = helpers::decode_is_success(&mut iter)?;
 
-    let is_success = helpers::decode_is_success(&mut iter);
+    let receipt = if is_success {
+        let new_state = helpers::decode_state(&mut iter)?;
+        let returndata = raw::decode_abi_data(&mut iter)?;
+        let gas_used = helpers::decode_gas_used(&mut iter)?;
+        let logs = logs::decode_logs(&mut iter)?;
 
-    match is_success {
-        0 => {
-            let (err, logs) = decode_error(&mut iter);
-            ExecReceipt::from_err(err, logs)
+        ExecReceipt {
+            success: true,
+            error: None,
+            new_state: Some(new_state),
+            returndata: Some(returndata),
+            gas_used,
+            logs,
         }
-        1 => {
-            // success
-            let new_state = helpers::decode_state(&mut iter);
-            let returndata = raw::decode_abi_data(&mut iter).unwrap();
-            let gas_used = helpers::decode_gas_used(&mut iter);
-            let logs = logs::decode_logs(&mut iter);
+    } else {
+        let (err, logs) = decode_error(&mut iter)?;
 
-            ExecReceipt {
-                success: true,
-                error: None,
-                new_state: Some(new_state),
-                returndata: Some(returndata),
-                gas_used,
-                logs,
-            }
-        }
-        _ => unreachable!(),
-    }
+        ExecReceipt::from_err(err, logs)
+    };
+
+    iter.ensure_eof(ParseError::ExpectedEOF)?;
+
+    Ok(receipt)
 }
 
 fn encode_new_state(receipt: &ExecReceipt, w: &mut NibbleWriter) {
@@ -104,16 +102,19 @@ fn encode_returndata(receipt: &ExecReceipt, w: &mut NibbleWriter) {
 mod tests {
     use super::*;
 
-    use svm_types::{gas::MaybeGas, receipt::ReceiptError, Address, State, WasmValue};
+    use crate::api::raw::Field;
+
+    use svm_types::{gas::MaybeGas, receipt::ReceiptError, Address, State};
 
     #[test]
     fn encode_decode_exec_receipt_error() {
         let app = Address::of("my-app");
         let error = ReceiptError::AppNotFound(app.into());
 
-        let logs = vec![Log {
+        let logs = vec![Log::Message {
             msg: b"something happened".to_vec(),
             code: 200,
+            topics: Vec::new(),
         }];
 
         let receipt = ExecReceipt {
@@ -126,7 +127,7 @@ mod tests {
         };
 
         let bytes = encode_exec_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes[..]);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]).unwrap();
 
         assert_eq!(decoded.into_exec_app(), receipt);
     }
@@ -135,9 +136,10 @@ mod tests {
     fn encode_decode_exec_receipt_success_without_returns() {
         let new_state = State::of("some-state");
 
-        let logs = vec![Log {
+        let logs = vec![Log::Message {
             msg: b"something happened".to_vec(),
             code: 200,
+            topics: Vec::new(),
         }];
 
         let receipt = ExecReceipt {
@@ -150,7 +152,7 @@ mod tests {
         };
 
         let bytes = encode_exec_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes[..]);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]).unwrap();
 
         assert_eq!(decoded.into_exec_app(), receipt);
     }
@@ -160,9 +162,10 @@ mod tests {
         let new_state = State::of("some-state");
         let returndata = vec![0x10, 0x20];
 
-        let logs = vec![Log {
+        let logs = vec![Log::Message {
             msg: b"something happened".to_vec(),
             code: 200,
+            topics: Vec::new(),
         }];
 
         let receipt = ExecReceipt {
@@ -175,8 +178,66 @@ mod tests {
         };
 
         let bytes = encode_exec_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes[..]);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]).unwrap();
 
         assert_eq!(decoded.into_exec_app(), receipt);
     }
+
+    #[test]
+    fn decode_exec_receipt_truncated() {
+        let logs = vec![Log::Message {
+            msg: b"something happened".to_vec(),
+            code: 200,
+            topics: Vec::new(),
+        }];
+
+        let error = ReceiptError::FuncFailed {
+            app_addr: Address::of("my-app").into(),
+            template_addr: Address::of("my-template").into(),
+            func: "run".to_string(),
+            msg: "Invalid input".to_string(),
+        };
+
+        let receipts = vec![
+            ExecReceipt {
+                success: true,
+                error: None,
+                new_state: Some(State::of("some-state")),
+                returndata: Some(vec![0x10, 0x20]),
+                gas_used: MaybeGas::with(100),
+                logs: logs.clone(),
+            },
+            ExecReceipt::from_err(error, logs),
+        ];
+
+        for receipt in receipts {
+            let bytes = encode_exec_receipt(&receipt);
+
+            for len in 0..bytes.len() {
+                assert!(decode_exec_receipt(&bytes[..len]).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn decode_exec_receipt_trailing_bytes() {
+        let receipt = ExecReceipt::from_err(ReceiptError::OOG, Vec::new());
+
+        let mut bytes = encode_exec_receipt(&receipt);
+        bytes.push(0xFF);
+
+        assert_eq!(decode_exec_receipt(&bytes), Err(ParseError::ExpectedEOF));
+    }
+
+    #[test]
+    fn decode_exec_receipt_wrong_type() {
+        let receipt = ExecReceipt::from_err(ReceiptError::OOG, Vec::new());
+
+        let mut bytes = encode_exec_receipt(&receipt);
+        bytes[0] = crate::receipt::types::SPAWN_APP;
+
+        let expected = Err(ParseError::InvalidValue(Field::ReceiptType));
+
+        assert_eq!(decode_exec_receipt(&bytes), expected);
+    }
 }
@@ -52,30 +52,66 @@ pub(crate) fn encode_state(state: &State, w: &mut NibbleWriter) {
 /// Decoders
 
 pub(crate) fn decode_version(iter: &mut NibbleIter) -> Result<u32, ParseError> {
-    raw::decode_version(iter)
+    let version = raw::decode_version(iter)?;
+
+    if version != 0 {
+        return Err(ParseError::InvalidProtocolVersion(version));
+    }
+
+    Ok(version)
 }
 
-pub(crate) fn decode_type(iter: &mut NibbleIter) -> u8 {
-    iter.read_byte()
+pub(crate) fn decode_type(iter: &mut NibbleIter, expected: u8) -> Result<u8, ParseError> {
+    let ty = decode_byte(iter, Field::ReceiptType)?;
+
+    if ty != expected {
+        return Err(ParseError::InvalidValue(Field::ReceiptType));
+    }
+
+    Ok(ty)
 }
 
-pub(crate) fn decode_is_success(iter: &mut NibbleIter) -> u8 {
-    let is_success: Nibble = iter.next().unwrap();
-    is_success.inner()
+pub(crate) fn decode_is_success(iter: &mut NibbleIter) -> Result<bool, ParseError> {
+    let nib = iter.next();
+
+    match nib.map(|nib| nib.inner()) {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        Some(_) => Err(ParseError::InvalidValue(Field::IsSuccess)),
+        None => Err(ParseError::NotEnoughBytes(Field::IsSuccess)),
+    }
 }
 
-pub(crate) fn decode_state(iter: &mut NibbleIter) -> State {
-    let bytes = iter.read_bytes(State::len());
+pub(crate) fn decode_state(iter: &mut NibbleIter) -> Result<State, ParseError> {
+    let bytes = decode_bytes(iter, State::len(), Field::State)?;
 
-    State::from(&bytes[..])
+    Ok(State::from(&bytes[..]))
 }
 
-pub(crate) fn decode_address(iter: &mut NibbleIter) -> Address {
-    let bytes = iter.read_bytes(Address::len());
+pub(crate) fn decode_address(iter: &mut NibbleIter, field: Field) -> Result<Address, ParseError> {
+    crate::helpers::decode_address(iter, field)
+}
 
-    Address::from(&bytes[..])
+pub(crate) fn decode_gas_used(iter: &mut NibbleIter) -> Result<MaybeGas, ParseError> {
+    raw::decode_gas_used(iter)
 }
 
-pub(crate) fn decode_gas_used(iter: &mut NibbleIter) -> MaybeGas {
-    raw::decode_gas_used(iter).unwrap()
+pub(crate) fn decode_byte(iter: &mut NibbleIter, field: Field) -> Result<u8, ParseError> {
+    let bytes = decode_bytes(iter, 1, field)?;
+
+    Ok(bytes[0])
+}
+
+pub(crate) fn decode_bytes(
+    iter: &mut NibbleIter,
+    count: usize,
+    field: Field,
+) -> Result<Vec<u8>, ParseError> {
+    let bytes = iter.read_bytes(count);
+
+    if bytes.len() != count {
+        return Err(ParseError::NotEnoughBytes(field));
+    }
+
+    Ok(bytes)
 }
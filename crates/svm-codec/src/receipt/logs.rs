@@ -1,114 +1,336 @@
+//! Receipt Logs Raw Format Version 1
+//!
+//!  +-------------------------------------+
+//!  |  version   |     Logs Bloom         |
+//!  | (1 nibble) |     (256 bytes)        |
+//!  +____________|________________________+
+//!  |            |           |            |
+//!  |   #logs    |  log #0   |   log #1   |
+//!  | varuint32  |           |            |
+//!  +____________|___________|____________+
+//!  |            |                        |
+//!  |  .  .  .   |        log #N-1        |
+//!  +____________|________________________+
+//!
+//!  The `Logs Bloom` is the bloom filter of all the logs `topics` (see `svm_types::receipt::Bloom`).
+//!
+//!
+//!  Each Log starts with its `kind` (`0` for a message logged by `svm_log`, `1` for an event emitted by `svm_emit`)
+//!
+//!  Log Message
+//!
+//!  +--------------------------------------------------------------+
+//!  |   kind   |  msg length  |  msg (blob)  |   code   | #topics   |
+//!  | (1 byte) |  varuint32   |              | (1 byte) | varuint32 |
+//!  +__________|______________|______________|__________|___________+
+//!  |                |                  |               |
+//!  | topic #0 length| topic #0 (blob)  |   .  .  .     |
+//!  |   varuint32    |                  |               |
+//!  +________________|__________________|_______________+
+//!
+//!
+//!  Event
+//!
+//!  +---------------------------------------------------------------+
+//!  |   kind   | #topics   | topic #0 length | topic #0 (blob) | ... |
+//!  | (1 byte) | varuint32 |   varuint32     |                 |     |
+//!  +__________|___________|_________________|_________________|_____+
+//!  |              |               |
+//!  |  data length |  data (blob)  |
+//!  |  varuint32   |               |
+//!  +______________|_______________+
+//!
+
+use std::convert::TryFrom;
+
 use svm_nibble::{NibbleIter, NibbleWriter};
-use svm_types::receipt::Log;
-
-///                   
-/// +-----------------+
-/// | #logs (1 byte)  |
-/// +-----------------+-------------------------------------------+
-/// |  msg length (1 byte) | msg (blob of bytes) | code (1 byte)  |  ---> log #0
-/// +-------------------------------------------------------------+
-/// |  msg length (1 byte) | msg (blob of bytes) | code (1 byte)  |  ---> log #1
-/// +-------------------------------------------------------------+
-///                            .
-///                            .
-///                            .
-/// +---------------------------------------+---------------------+
-/// |  msg length (1 byte) | msg (blob of bytes) | code (1 byte)  |  ---> log #N
-/// +---------------------------------------+---------------------+
-///
+use svm_types::receipt::{Bloom, Log};
+
+use super::helpers;
+use crate::api::raw::{self, decode_varuint32, encode_varuint32, Field};
+use crate::error::ParseError;
+
+/// The current version of the logs encoding.
+pub const LOGS_VERSION: u32 = 1;
+
+/// The `kind` of a `Log::Message`.
+const MESSAGE_KIND: u8 = 0;
+
+/// The `kind` of a `Log::Event`.
+const EVENT_KIND: u8 = 1;
+
 pub fn encode_logs(logs: &[Log], w: &mut NibbleWriter) {
-    let nlogs = logs.len();
-    assert!(nlogs <= std::u8::MAX as usize);
+    raw::encode_version(LOGS_VERSION, w);
 
-    w.write_bytes(&[nlogs as u8]);
+    let bloom = Bloom::from_logs(logs);
+    w.write_bytes(bloom.as_slice());
+
+    encode_length(logs.len(), w);
 
     for log in logs.iter() {
-        let len = log.msg.len();
-        assert!(log.msg.len() <= std::u8::MAX as usize);
+        match log {
+            Log::Message { msg, code, topics } => {
+                w.write_byte(MESSAGE_KIND);
+
+                encode_blob(msg, w);
+                w.write_byte(*code);
+                encode_topics(topics, w);
+            }
+            Log::Event { topics, data } => {
+                w.write_byte(EVENT_KIND);
 
-        // `msg` length
-        w.write_bytes(&[len as u8]);
+                encode_topics(topics, w);
+                encode_blob(data, w);
+            }
+        }
+    }
+}
 
-        // `msg` blob
-        w.write_bytes(&log.msg);
+pub fn decode_logs(iter: &mut NibbleIter) -> Result<Vec<Log>, ParseError> {
+    let version = raw::decode_version(iter)?;
 
-        // `code`
-        w.write_bytes(&[log.code]);
+    if version != LOGS_VERSION {
+        return Err(ParseError::InvalidProtocolVersion(version));
     }
+
+    let bytes = helpers::decode_bytes(iter, Bloom::BYTES, Field::LogsBloom)?;
+    let bloom = Bloom::from(&bytes[..]);
+
+    let nlogs = decode_varuint32(iter, Field::LogsCount)?;
+
+    let logs = (0..nlogs)
+        .map(|_| decode_log(iter))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if bloom != Bloom::from_logs(&logs) {
+        return Err(ParseError::InvalidValue(Field::LogsBloom));
+    }
+
+    Ok(logs)
 }
 
-pub fn decode_logs(iter: &mut NibbleIter) -> Vec<Log> {
-    let nlogs = iter.read_byte();
+fn decode_log(iter: &mut NibbleIter) -> Result<Log, ParseError> {
+    let kind = helpers::decode_byte(iter, Field::LogKind)?;
+
+    match kind {
+        MESSAGE_KIND => {
+            let msg = decode_blob(iter, Field::LogMessageLength, Field::LogMessage)?;
+            let code = helpers::decode_byte(iter, Field::LogCode)?;
+            let topics = decode_topics(iter)?;
 
-    (0..nlogs)
-        .map(|_| {
-            let len = iter.read_byte();
-            let msg = iter.read_bytes(len as usize);
-            let code = iter.read_byte();
+            Ok(Log::Message { msg, code, topics })
+        }
+        EVENT_KIND => {
+            let topics = decode_topics(iter)?;
+            let data = decode_blob(iter, Field::EventDataLength, Field::EventData)?;
+
+            Ok(Log::Event { topics, data })
+        }
+        _ => Err(ParseError::InvalidValue(Field::LogKind)),
+    }
+}
 
-            Log { msg, code }
-        })
+fn encode_topics(topics: &[Vec<u8>], w: &mut NibbleWriter) {
+    encode_length(topics.len(), w);
+
+    for topic in topics.iter() {
+        encode_blob(topic, w);
+    }
+}
+
+fn decode_topics(iter: &mut NibbleIter) -> Result<Vec<Vec<u8>>, ParseError> {
+    let ntopics = decode_varuint32(iter, Field::LogTopicsCount)?;
+
+    (0..ntopics)
+        .map(|_| decode_blob(iter, Field::LogTopicLength, Field::LogTopic))
         .collect()
 }
 
+fn encode_length(len: usize, w: &mut NibbleWriter) {
+    let len = u32::try_from(len).expect("length exceeds `u32::MAX`");
+
+    encode_varuint32(len, w);
+}
+
+fn encode_blob(blob: &[u8], w: &mut NibbleWriter) {
+    encode_length(blob.len(), w);
+
+    w.write_bytes(blob);
+}
+
+fn decode_blob(
+    iter: &mut NibbleIter,
+    len_field: Field,
+    field: Field,
+) -> Result<Vec<u8>, ParseError> {
+    let len = decode_varuint32(iter, len_field)?;
+
+    helpers::decode_bytes(iter, len as usize, field)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn encode_logs_empty() {
+    fn encode_decode(logs: &[Log]) -> Vec<Log> {
         let mut w = NibbleWriter::new();
 
-        encode_logs(&[], &mut w);
+        encode_logs(logs, &mut w);
 
         let bytes = w.into_bytes();
 
         let mut iter = NibbleIter::new(&bytes);
-        let logs = decode_logs(&mut iter);
+        let logs = decode_logs(&mut iter).unwrap();
+
+        assert!(iter.ensure_eof(ParseError::ExpectedEOF).is_ok());
+
+        logs
+    }
+
+    #[test]
+    fn encode_logs_empty() {
+        let logs = encode_decode(&[]);
 
         assert!(logs.is_empty());
     }
 
     #[test]
     fn encode_logs_single_entry() {
-        let mut w = NibbleWriter::new();
+        let log = Log::new(b"been here".to_vec(), 200);
 
-        let log = Log {
-            msg: b"been here".to_vec(),
-            code: 200,
+        let logs = encode_decode(&[log.clone()]);
+
+        assert_eq!(logs, vec![log]);
+    }
+
+    #[test]
+    fn encode_logs_single_mulitiple_entries() {
+        let log1 = Log::new(b"been here".to_vec(), 200);
+        let log2 = Log::new(b"been there".to_vec(), 201);
+
+        let logs = encode_decode(&[log1.clone(), log2.clone()]);
+
+        assert_eq!(logs, vec![log1, log2]);
+    }
+
+    #[test]
+    fn encode_logs_with_topics() {
+        let log = Log::Message {
+            msg: b"transfer".to_vec(),
+            code: 0,
+            topics: vec![b"Transfer".to_vec(), vec![0xAB; 20], Vec::new()],
         };
 
-        encode_logs(&[log.clone()], &mut w);
+        let logs = encode_decode(&[log.clone()]);
+
+        assert_eq!(logs, vec![log]);
+    }
+
+    #[test]
+    fn encode_logs_exceeding_single_byte_limits() {
+        let logs: Vec<Log> = (0..300)
+            .map(|i| Log::new(vec![(i % 256) as u8; 1000], 1))
+            .collect();
+
+        assert_eq!(encode_decode(&logs), logs);
+    }
+
+    #[test]
+    fn encode_logs_messages_and_events() {
+        let logs = vec![
+            Log::new(b"been here".to_vec(), 255),
+            Log::event(vec![b"Transfer".to_vec(), vec![0xAB; 20]], vec![0x00, 0xFF]),
+            Log::event(Vec::new(), Vec::new()),
+        ];
+
+        assert_eq!(encode_decode(&logs), logs);
+    }
+
+    #[test]
+    fn decode_logs_invalid_kind() {
+        let mut w = NibbleWriter::new();
+
+        raw::encode_version(LOGS_VERSION, &mut w);
+        w.write_bytes(Bloom::empty().as_slice());
+        encode_varuint32(1, &mut w);
+        w.write_byte(2);
 
         let bytes = w.into_bytes();
 
         let mut iter = NibbleIter::new(&bytes);
-        let logs = decode_logs(&mut iter);
+        let expected = Err(ParseError::InvalidValue(Field::LogKind));
 
-        assert_eq!(logs, vec![log]);
+        assert_eq!(decode_logs(&mut iter), expected);
     }
 
     #[test]
-    fn encode_logs_single_mulitiple_entries() {
+    fn decode_logs_bloom_mismatch() {
         let mut w = NibbleWriter::new();
 
-        let log1 = Log {
+        let log = Log::event(vec![b"Transfer".to_vec()], Vec::new());
+        encode_logs(&[log], &mut w);
+
+        let mut bytes = w.into_bytes();
+
+        // clearing the `Logs Bloom`
+        for byte in bytes[1..=Bloom::BYTES].iter_mut() {
+            *byte = 0;
+        }
+
+        let mut iter = NibbleIter::new(&bytes);
+        let expected = Err(ParseError::InvalidValue(Field::LogsBloom));
+
+        assert_eq!(decode_logs(&mut iter), expected);
+    }
+
+    #[test]
+    fn decode_logs_invalid_version() {
+        let mut w = NibbleWriter::new();
+
+        raw::encode_version(0, &mut w);
+        encode_varuint32(0, &mut w);
+
+        let bytes = w.into_bytes();
+
+        let mut iter = NibbleIter::new(&bytes);
+        let expected = Err(ParseError::InvalidProtocolVersion(0));
+
+        assert_eq!(decode_logs(&mut iter), expected);
+    }
+
+    #[test]
+    fn decode_logs_truncated() {
+        let mut w = NibbleWriter::new();
+
+        let log = Log::Message {
             msg: b"been here".to_vec(),
             code: 200,
+            topics: vec![b"topic".to_vec()],
         };
 
-        let log2 = Log {
-            msg: b"been there".to_vec(),
-            code: 201,
-        };
+        encode_logs(&[log], &mut w);
 
-        encode_logs(&[log1.clone(), log2.clone()], &mut w);
+        let bytes = w.into_bytes();
+
+        for len in 0..bytes.len() {
+            let mut iter = NibbleIter::new(&bytes[..len]);
+
+            assert!(decode_logs(&mut iter).is_err());
+        }
+    }
+
+    #[test]
+    fn decode_logs_truncated_msg() {
+        let mut w = NibbleWriter::new();
+
+        encode_logs(&[Log::new(b"been here".to_vec(), 200)], &mut w);
 
         let bytes = w.into_bytes();
 
-        let mut iter = NibbleIter::new(&bytes);
-        let logs = decode_logs(&mut iter);
+        // dropping the `#topics`, `code` and the last `msg` byte
+        let mut iter = NibbleIter::new(&bytes[..bytes.len() - 3]);
+        let expected = Err(ParseError::NotEnoughBytes(Field::LogMessage));
 
-        assert_eq!(logs, vec![log1, log2]);
+        assert_eq!(decode_logs(&mut iter), expected);
     }
 }
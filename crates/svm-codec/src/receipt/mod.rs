@@ -3,6 +3,7 @@ mod error;
 mod exec_app;
 mod helpers;
 mod spawn_app;
+mod upgrade_app;
 
 pub(crate) mod logs;
 
@@ -14,32 +15,77 @@ mod types {
     pub const SPAWN_APP: u8 = 1;
 
     pub const EXEC_APP: u8 = 2;
+
+    pub const UPGRADE_APP: u8 = 3;
 }
 
 pub use deploy_template::{decode_template_receipt, encode_template_receipt};
 pub use exec_app::{decode_exec_receipt, encode_exec_receipt};
 pub use spawn_app::{decode_app_receipt, encode_app_receipt};
+pub use upgrade_app::{decode_upgrade_receipt, encode_upgrade_receipt};
 
-use svm_types::receipt::{ExecReceipt, ReceiptOwned, SpawnAppReceipt, TemplateReceipt};
+use svm_types::receipt::ReceiptOwned;
 
-pub fn decode_receipt(bytes: &[u8]) -> ReceiptOwned {
-    assert!(bytes.len() > 0);
+use crate::api::raw::Field;
+use crate::error::ParseError;
 
-    let ty = bytes[0];
+/// Decodes a binary Receipt of any type (the type is given by the first byte).
+///
+/// Never panics on malformed input. Returns a `ParseError` instead.
+pub fn decode_receipt(bytes: &[u8]) -> Result<ReceiptOwned, ParseError> {
+    let ty = match bytes.first() {
+        Some(ty) => *ty,
+        None => return Err(ParseError::NotEnoughBytes(Field::ReceiptType)),
+    };
 
-    match ty {
+    let receipt = match ty {
         types::DEPLOY_TEMPLATE => {
-            let receipt = decode_template_receipt(bytes);
+            let receipt = decode_template_receipt(bytes)?;
             ReceiptOwned::DeployTemplate(receipt)
         }
         types::SPAWN_APP => {
-            let receipt = decode_app_receipt(bytes);
+            let receipt = decode_app_receipt(bytes)?;
             ReceiptOwned::SpawnApp(receipt)
         }
         types::EXEC_APP => {
-            let receipt = decode_exec_receipt(bytes);
+            let receipt = decode_exec_receipt(bytes)?;
             ReceiptOwned::ExecApp(receipt)
         }
-        _ => unreachable!(),
+        types::UPGRADE_APP => {
+            let receipt = decode_upgrade_receipt(bytes)?;
+            ReceiptOwned::UpgradeApp(receipt)
+        }
+        _ => return Err(ParseError::InvalidValue(Field::ReceiptType)),
+    };
+
+    Ok(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_receipt_empty() {
+        let expected = Err(ParseError::NotEnoughBytes(Field::ReceiptType));
+
+        assert_eq!(decode_receipt(&[]), expected);
+    }
+
+    #[test]
+    fn decode_receipt_unknown_type() {
+        let expected = Err(ParseError::InvalidValue(Field::ReceiptType));
+
+        assert_eq!(decode_receipt(&[0xFF, 0x00]), expected);
+    }
+
+    #[test]
+    fn decode_receipt_invalid_is_success() {
+        // `version = 0` followed by `is_success = 2`
+        let bytes = [types::EXEC_APP, 0b0000_0010];
+
+        let expected = Err(ParseError::InvalidValue(Field::IsSuccess));
+
+        assert_eq!(decode_receipt(&bytes), expected);
     }
 }
@@ -22,7 +22,8 @@
 
 use svm_nibble::{NibbleIter, NibbleWriter};
 
-use crate::api::raw;
+use crate::api::raw::{self, Field};
+use crate::error::ParseError;
 
 use svm_types::gas::MaybeGas;
 use svm_types::receipt::{Receipt, SpawnAppReceipt};
@@ -53,42 +54,39 @@ pub fn encode_app_receipt(receipt: &SpawnAppReceipt) -> Vec<u8> {
     w.into_bytes()
 }
 
-pub fn decode_app_receipt(bytes: &[u8]) -> SpawnAppReceipt {
+pub fn decode_app_receipt(bytes: &[u8]) -> Result<SpawnAppReceipt, ParseError> {
     let mut iter = NibbleIter::new(bytes);
 
-    let ty = helpers::decode_type(&mut iter);
-    debug_assert_eq!(ty, crate::receipt::types::SPAWN_APP);
+    helpers::decode_type(&mut iter, super::types::SPAWN_APP)?;
+    helpers::decode_version(&mut iter)?;
 
-    let version = helpers::decode_version(&mut iter).unwrap();
-    debug_assert_eq!(0, version);
+    let is_success = helpers::decode_is_success(&mut iter)?;
 
-    let is_success = helpers::decode_is_success(&mut iter);
+    let receipt = if is_success {
+        let addr = helpers::decode_address(&mut iter, Field::AppAddr)?;
+        let init_state = helpers::decode_state(&mut iter)?;
+        let returndata = raw::decode_abi_data(&mut iter)?;
+        let gas_used = helpers::decode_gas_used(&mut iter)?;
+        let logs = logs::decode_logs(&mut iter)?;
 
-    match is_success {
-        0 => {
-            let (err, logs) = decode_error(&mut iter);
-            SpawnAppReceipt::from_err(err, logs)
+        SpawnAppReceipt {
+            success: true,
+            error: None,
+            app_addr: Some(addr.into()),
+            init_state: Some(init_state),
+            returndata: Some(returndata),
+            gas_used,
+            logs,
         }
-        1 => {
-            // success
-            let addr = helpers::decode_address(&mut iter);
-            let init_state = helpers::decode_state(&mut iter);
-            let returndata = raw::decode_abi_data(&mut iter).unwrap();
-            let gas_used = helpers::decode_gas_used(&mut iter);
-            let logs = logs::decode_logs(&mut iter);
+    } else {
+        let (err, logs) = decode_error(&mut iter)?;
 
-            SpawnAppReceipt {
-                success: true,
-                error: None,
-                app_addr: Some(addr.into()),
-                init_state: Some(init_state),
-                returndata: Some(returndata),
-                gas_used,
-                logs,
-            }
-        }
-        _ => unreachable!(),
-    }
+        SpawnAppReceipt::from_err(err, logs)
+    };
+
+    iter.ensure_eof(ParseError::ExpectedEOF)?;
+
+    Ok(receipt)
 }
 
 fn encode_app_addr(receipt: &SpawnAppReceipt, w: &mut NibbleWriter) {
@@ -136,9 +134,9 @@ mod tests {
         };
 
         let bytes = encode_app_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
 
-        // assert_eq!(decoded.into_spawn_app(), receipt);
+        assert_eq!(decoded.into_spawn_app(), receipt);
     }
 
     #[test]
@@ -146,9 +144,10 @@ mod tests {
         let addr: AppAddr = Address::of("my-app").into();
         let init_state = State::of("some-state");
 
-        let logs = vec![Log {
+        let logs = vec![Log::Message {
             msg: b"something happened".to_vec(),
             code: 200,
+            topics: Vec::new(),
         }];
 
         let receipt = SpawnAppReceipt {
@@ -162,7 +161,7 @@ mod tests {
         };
 
         let bytes = encode_app_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
 
         assert_eq!(decoded.into_spawn_app(), receipt);
     }
@@ -172,9 +171,10 @@ mod tests {
         let addr: AppAddr = Address::of("my-app").into();
         let init_state = State::of("some-state");
         let returndata = vec![0x10, 0x20];
-        let logs = vec![Log {
+        let logs = vec![Log::Message {
             msg: b"something happened".to_vec(),
             code: 200,
+            topics: Vec::new(),
         }];
 
         let receipt = SpawnAppReceipt {
@@ -188,8 +188,41 @@ mod tests {
         };
 
         let bytes = encode_app_receipt(&receipt);
-        let decoded = crate::receipt::decode_receipt(&bytes);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
 
         assert_eq!(decoded.into_spawn_app(), receipt);
     }
+
+    #[test]
+    fn decode_spawn_app_receipt_truncated() {
+        let addr: AppAddr = Address::of("my-app").into();
+        let template_addr = Address::of("my-template").into();
+
+        let logs = vec![Log::Message {
+            msg: b"something happened".to_vec(),
+            code: 200,
+            topics: Vec::new(),
+        }];
+
+        let receipts = vec![
+            SpawnAppReceipt {
+                success: true,
+                error: None,
+                app_addr: Some(addr),
+                init_state: Some(State::of("some-state")),
+                returndata: Some(vec![0x10, 0x20]),
+                gas_used: MaybeGas::with(100),
+                logs: logs.clone(),
+            },
+            SpawnAppReceipt::from_err(ReceiptError::TemplateNotFound(template_addr), logs),
+        ];
+
+        for receipt in receipts {
+            let bytes = encode_app_receipt(&receipt);
+
+            for len in 0..bytes.len() {
+                assert!(decode_app_receipt(&bytes[..len]).is_err());
+            }
+        }
+    }
 }
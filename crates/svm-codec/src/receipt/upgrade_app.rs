@@ -0,0 +1,196 @@
+//! `Upgrade App` Receipt Raw Format Version 0
+//!
+//!  On success (`is_success = 1`)
+//!  +---------------------------------------------------------+
+//!  |  tx type  |  version   | is_success |  Template Address  |
+//!  | (1 byte)  | (1 nibble) | (1 nibble) |     (20 bytes)     |
+//!  +___________|____________|____________|____________________+
+//!  |              |              |             |              |
+//!  |  new state   | #returndata  | ret #1 type |   ret  #1    |
+//!  |  (32 bytes)  |              |             |              |
+//!  +______________|______________|_____________|______________+
+//!  |          |            |                                  |
+//!  |  ret #2  |   .  .  .  |            gas_used              |
+//!  +__________|____________|__________________________________+
+//!  |          |            |         |                        |
+//!  |  #logs   | log 1 blob |  . . .  |        log #N          |
+//!  +__________|____________|_________|________________________+
+//!
+//!
+//!  On success (`is_success = 0`)
+//!  See [error.rs][./error.rs]
+
+use svm_nibble::{NibbleIter, NibbleWriter};
+
+use crate::api::raw::{self, Field};
+use crate::error::ParseError;
+
+use svm_types::receipt::{Receipt, UpgradeAppReceipt};
+
+use super::{decode_error, encode_error, helpers, logs};
+
+pub fn encode_upgrade_receipt(receipt: &UpgradeAppReceipt) -> Vec<u8> {
+    let mut w = NibbleWriter::new();
+
+    let wrapped_receipt = Receipt::UpgradeApp(receipt);
+
+    helpers::encode_type(super::types::UPGRADE_APP, &mut w);
+    helpers::encode_version(0, &mut w);
+    helpers::encode_is_success(&wrapped_receipt, &mut w);
+
+    if receipt.success {
+        encode_template_addr(receipt, &mut w);
+        encode_new_state(receipt, &mut w);
+        encode_returndata(&receipt, &mut w);
+        helpers::encode_gas_used(&wrapped_receipt, &mut w);
+        logs::encode_logs(&receipt.logs, &mut w);
+    } else {
+        let logs = receipt.get_logs();
+
+        encode_error(receipt.get_error(), logs, &mut w);
+    };
+
+    w.into_bytes()
+}
+
+pub fn decode_upgrade_receipt(bytes: &[u8]) -> Result<UpgradeAppReceipt, ParseError> {
+    let mut iter = NibbleIter::new(bytes);
+
+    helpers::decode_type(&mut iter, super::types::UPGRADE_APP)?;
+    helpers::decode_version(&mut iter)?;
+
+    let is_success = helpers::decode_is_success(&mut iter)?;
+
+    let receipt = if is_success {
+        let addr = helpers::decode_address(&mut iter, Field::TemplateAddr)?;
+        let new_state = helpers::decode_state(&mut iter)?;
+        let returndata = raw::decode_abi_data(&mut iter)?;
+        let gas_used = helpers::decode_gas_used(&mut iter)?;
+        let logs = logs::decode_logs(&mut iter)?;
+
+        UpgradeAppReceipt {
+            success: true,
+            error: None,
+            template_addr: Some(addr.into()),
+            new_state: Some(new_state),
+            returndata: Some(returndata),
+            gas_used,
+            logs,
+        }
+    } else {
+        let (err, logs) = decode_error(&mut iter)?;
+
+        UpgradeAppReceipt::from_err(err, logs)
+    };
+
+    iter.ensure_eof(ParseError::ExpectedEOF)?;
+
+    Ok(receipt)
+}
+
+fn encode_template_addr(receipt: &UpgradeAppReceipt, w: &mut NibbleWriter) {
+    debug_assert!(receipt.success);
+
+    let addr = receipt.get_template_addr();
+    helpers::encode_addr(addr.inner(), w)
+}
+
+fn encode_new_state(receipt: &UpgradeAppReceipt, w: &mut NibbleWriter) {
+    debug_assert!(receipt.success);
+
+    let state = receipt.get_new_state();
+    helpers::encode_state(&state, w);
+}
+
+fn encode_returndata(receipt: &UpgradeAppReceipt, w: &mut NibbleWriter) {
+    debug_assert!(receipt.success);
+
+    let data = receipt.get_returndata();
+    helpers::encode_abi_data(&data, w);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::receipt::{Log, ReceiptError};
+    use svm_types::{gas::MaybeGas, Address, State, TemplateAddr};
+
+    #[test]
+    fn encode_decode_upgrade_app_receipt_success() {
+        let addr: TemplateAddr = Address::of("my-template-v2").into();
+        let new_state = State::of("some-state");
+
+        let logs = vec![Log::Message {
+            msg: b"migrated".to_vec(),
+            code: 200,
+            topics: Vec::new(),
+        }];
+
+        let receipt = UpgradeAppReceipt {
+            success: true,
+            error: None,
+            template_addr: Some(addr),
+            new_state: Some(new_state),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: MaybeGas::with(100),
+            logs,
+        };
+
+        let bytes = encode_upgrade_receipt(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
+
+        assert_eq!(decoded.into_upgrade_app(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_upgrade_app_receipt_error() {
+        let error = ReceiptError::IncompatibleLayout {
+            app_addr: Address::of("my-app").into(),
+            template_addr: Address::of("my-template-v2").into(),
+        };
+
+        let receipt = UpgradeAppReceipt::from_err(error, Vec::new());
+
+        let bytes = encode_upgrade_receipt(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes).unwrap();
+
+        assert_eq!(decoded.into_upgrade_app(), receipt);
+    }
+
+    #[test]
+    fn decode_upgrade_app_receipt_truncated() {
+        let addr: TemplateAddr = Address::of("my-template-v2").into();
+
+        let logs = vec![Log::Message {
+            msg: b"migrated".to_vec(),
+            code: 200,
+            topics: Vec::new(),
+        }];
+
+        let error = ReceiptError::UpgradeNotAuthorized {
+            app_addr: Address::of("my-app").into(),
+        };
+
+        let receipts = vec![
+            UpgradeAppReceipt {
+                success: true,
+                error: None,
+                template_addr: Some(addr),
+                new_state: Some(State::of("some-state")),
+                returndata: Some(vec![0x10, 0x20]),
+                gas_used: MaybeGas::with(100),
+                logs: logs.clone(),
+            },
+            UpgradeAppReceipt::from_err(error, logs),
+        ];
+
+        for receipt in receipts {
+            let bytes = encode_upgrade_receipt(&receipt);
+
+            for len in 0..bytes.len() {
+                assert!(decode_upgrade_receipt(&bytes[..len]).is_err());
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! `Snapshot` Raw Format Version 0
+//!  +------------------------------------------------------+
+//!  |            |                                         |
+//!  |  version   |              State                      |
+//!  |    (a)     |            (32 bytes)                   |
+//!  +____________|_________________________________________+
+//!  |                                                      |
+//!  |           Data-Layout (including Schema)             |
+//!  |                                                      |
+//!  +______________________________________________________+
+//!  |               |               |                      |
+//!  |  var #0 value |  . . .        |    var #N value      |
+//!  | (var #0 len)  |               |    (var #N len)      |
+//!  +_______________|_______________|______________________+
+//!
+//!
+//! (a) Proto Version Encoding
+//! ===========================
+//!
+//! See [template/mod.rs][../template/mod.rs]
+//!
+//!
+//! The Data-Layout is encoded exactly as in a `deploy-template` transaction.
+//!
+
+mod wire;
+
+pub use wire::{decode_snapshot, encode_snapshot};
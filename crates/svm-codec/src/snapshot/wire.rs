@@ -0,0 +1,225 @@
+use svm_layout::DataLayout;
+use svm_nibble::{NibbleIter, NibbleWriter};
+use svm_types::{Snapshot, SnapshotError, State};
+
+use crate::api::raw::{decode_version, Field};
+use crate::error::ParseError;
+use crate::template;
+
+/// The maximum number of variables of a `Snapshot` (the count must fit a `varuint14`).
+const MAX_VARS: usize = (1 << 14) - 1;
+
+/// The maximum byte-length of a `Snapshot` variable (the length must fit a `varuint14`).
+const MAX_VAR_LEN: u32 = (1 << 14) - 1;
+
+/// Encodes a raw `Snapshot`.
+///
+/// Returns an error (without writing anything) when `snapshot` is malformed
+/// or doesn't fit the wire format limits.
+pub fn encode_snapshot(snapshot: &Snapshot, w: &mut NibbleWriter) -> Result<(), SnapshotError> {
+    validate(snapshot)?;
+
+    encode_version(w);
+    encode_state(snapshot, w);
+    encode_layout(snapshot, w);
+    encode_vars(snapshot, w);
+
+    Ok(())
+}
+
+/// Decodes a raw `Snapshot`.
+pub fn decode_snapshot(iter: &mut NibbleIter) -> Result<Snapshot, ParseError> {
+    let version = decode_version(iter)?;
+
+    if version != 0 {
+        return Err(ParseError::InvalidProtocolVersion(version));
+    }
+
+    let state = decode_state(iter)?;
+    let layout = decode_layout(iter)?;
+    let vars = decode_vars(&layout, iter)?;
+
+    let snapshot = Snapshot {
+        layout,
+        state,
+        vars,
+    };
+
+    Ok(snapshot)
+}
+
+fn validate(snapshot: &Snapshot) -> Result<(), SnapshotError> {
+    let layout = &snapshot.layout;
+
+    if layout.len() > MAX_VARS {
+        return Err(SnapshotError::TooManyVars {
+            count: layout.len(),
+            max: MAX_VARS,
+        });
+    }
+
+    if layout.len() != snapshot.vars.len() {
+        return Err(SnapshotError::VarsCountMismatch {
+            expected: layout.len(),
+            actual: snapshot.vars.len(),
+        });
+    }
+
+    for ((var_id, _off, len), value) in layout.iter().zip(snapshot.vars.iter()) {
+        if len > MAX_VAR_LEN {
+            return Err(SnapshotError::VarTooLong {
+                var_id: var_id.0,
+                len,
+                max: MAX_VAR_LEN,
+            });
+        }
+
+        if len as usize != value.len() {
+            return Err(SnapshotError::VarLengthMismatch {
+                var_id: var_id.0,
+                expected: len,
+                actual: value.len(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Encoders
+
+fn encode_version(w: &mut NibbleWriter) {
+    crate::api::raw::encode_version(0, w);
+}
+
+fn encode_state(snapshot: &Snapshot, w: &mut NibbleWriter) {
+    w.write_bytes(snapshot.state.as_slice());
+}
+
+fn encode_layout(snapshot: &Snapshot, w: &mut NibbleWriter) {
+    template::encode_data(&snapshot.layout, w);
+}
+
+fn encode_vars(snapshot: &Snapshot, w: &mut NibbleWriter) {
+    for value in snapshot.vars.iter() {
+        w.write_bytes(value);
+    }
+}
+
+/// Decoders
+
+fn decode_state(iter: &mut NibbleIter) -> Result<State, ParseError> {
+    let bytes = iter.read_bytes(State::len());
+
+    if bytes.len() != State::len() {
+        return Err(ParseError::NotEnoughBytes(Field::State));
+    }
+
+    Ok(State::from(&bytes[..]))
+}
+
+fn decode_layout(iter: &mut NibbleIter) -> Result<DataLayout, ParseError> {
+    template::decode_data(iter)
+}
+
+fn decode_vars(layout: &DataLayout, iter: &mut NibbleIter) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut vars = Vec::with_capacity(layout.len());
+
+    for (_varid, _off, len) in layout.iter() {
+        let value = iter.read_bytes(len as usize);
+
+        if value.len() != len as usize {
+            return Err(ParseError::NotEnoughBytes(Field::VarValue));
+        }
+
+        vars.push(value);
+    }
+
+    Ok(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            layout: vec![4, 2].into(),
+            state: State::of("some-state"),
+            vars: vec![vec![10, 20, 30, 40], vec![50, 60]],
+        }
+    }
+
+    #[test]
+    fn encode_decode_snapshot() {
+        let snapshot = snapshot();
+
+        let mut w = NibbleWriter::new();
+        encode_snapshot(&snapshot, &mut w).unwrap();
+
+        let bytes = w.into_bytes();
+        let mut iter = NibbleIter::new(&bytes[..]);
+
+        let decoded = decode_snapshot(&mut iter).unwrap();
+
+        assert_eq!(snapshot, decoded);
+    }
+
+    #[test]
+    fn encode_snapshot_vars_count_mismatch() {
+        let mut snapshot = snapshot();
+        snapshot.vars.pop();
+
+        let mut w = NibbleWriter::new();
+        let err = encode_snapshot(&snapshot, &mut w).unwrap_err();
+
+        assert_eq!(
+            err,
+            SnapshotError::VarsCountMismatch {
+                expected: 2,
+                actual: 1
+            }
+        );
+        assert!(w.into_bytes().is_empty());
+    }
+
+    #[test]
+    fn encode_snapshot_var_length_mismatch() {
+        let mut snapshot = snapshot();
+        snapshot.vars[1] = vec![50, 60, 70];
+
+        let mut w = NibbleWriter::new();
+        let err = encode_snapshot(&snapshot, &mut w).unwrap_err();
+
+        assert_eq!(
+            err,
+            SnapshotError::VarLengthMismatch {
+                var_id: 1,
+                expected: 2,
+                actual: 3
+            }
+        );
+    }
+
+    #[test]
+    fn encode_snapshot_too_many_vars() {
+        let count = MAX_VARS + 1;
+
+        let snapshot = Snapshot {
+            layout: vec![1; count].into(),
+            state: State::empty(),
+            vars: vec![vec![0]; count],
+        };
+
+        let mut w = NibbleWriter::new();
+        let err = encode_snapshot(&snapshot, &mut w).unwrap_err();
+
+        assert_eq!(
+            err,
+            SnapshotError::TooManyVars {
+                count,
+                max: MAX_VARS
+            }
+        );
+    }
+}
@@ -13,6 +13,13 @@
 //!  |  #variables   |  length     |  . . .  |   length    |
 //!  |  (varuint14)  | (varuint14) |         | (varuint14) |
 //!  +_______________|_____________|_________|_____________+
+//!  |               |                                     |
+//!  |    Schema     |     field #0    . . .   field #M    |
+//!  |   #fields     |                                     |
+//!  |  (varuint14)  |            (see (b))                |
+//!  +_______________|_____________________________________+
+//!
+//!  The Schema is part of version `1` only (version `0` ends right after the Data-Layout).
 //!
 //!
 //!
@@ -29,11 +36,28 @@
 //!  The protocol `#bits` will be a multiplication of 3,
 //!  and is encoded in Little-endian as an unsigned-integer.
 //!
+//!
+//! (b) Schema Field Encoding
+//! ===========================
+//!
+//!  +--------------+--------------+------------+-------------+
+//!  | name length  |    name      |    type    |   #vars     |
+//!  | (varuint14)  |   (UTF-8)    |  (1 byte)  | (varuint14) |
+//!  +--------------+--------------+------------+-------------+
+//!
+//!  The schema is optional. A layout without schema has zero `#fields`.
+//!  Otherwise, the `#vars` of all fields must sum to the Data-Layout `#variables`.
+//!
 
 mod serialize;
 mod validate;
 mod wire;
 
 pub use serialize::{DefaultAppTemplateDeserializer, DefaultAppTemplateSerializer};
-pub use validate::validate_template;
+pub use validate::{
+    validate_template, HostImport, DATA_LAYOUT_MAX_SIZE, SVM_IMPORTS, SVM_NAMESPACE,
+    TEMPLATE_NAME_MAX_LENGTH, TEMPLATE_VERSION,
+};
 pub use wire::{decode_deploy_template, encode_deploy_template};
+
+pub(crate) use wire::{decode_data, encode_data, DECODERS};
@@ -1,10 +1,458 @@
-use svm_types::AppTemplate;
+use parity_wasm::elements::{External, Internal, Module, Type, ValueType};
+
+use svm_layout::DataLayout;
+use svm_types::{AppTemplate, TxKind, WasmType};
 
 use crate::error::ParseError;
 
+/// The latest `deploy-template` version (the first one carrying the storage schema).
+pub const TEMPLATE_VERSION: u32 = 1;
+
+/// The maximum byte-length of a template's name.
+pub const TEMPLATE_NAME_MAX_LENGTH: usize = 128;
+
+/// The maximum total byte-size of a template's `DataLayout` variables.
+pub const DATA_LAYOUT_MAX_SIZE: u32 = 64 * 1024;
+
+/// The namespace of the `SVM` vmcalls.
+pub const SVM_NAMESPACE: &str = "svm";
+
+/// The vmcalls exposed to apps under the `svm` namespace, as `(name, params, returns)`.
+///
+/// `svm_runtime::vmcalls::wasmer_register` registers exactly these vmcalls.
+pub const SVM_IMPORTS: &[(&str, &[WasmType], &[WasmType])] = &[
+    ("svm_calldata_offset", &[], &[WasmType::I32]),
+    ("svm_calldata_len", &[], &[WasmType::I32]),
+    ("svm_set_returndata", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_get32", &[WasmType::I32], &[WasmType::I32]),
+    ("svm_set32", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_get64", &[WasmType::I32], &[WasmType::I64]),
+    ("svm_set64", &[WasmType::I32, WasmType::I64], &[]),
+    ("svm_load160", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_store160", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_load128", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_store128", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_load256", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_store256", &[WasmType::I32, WasmType::I32], &[]),
+    (
+        "svm_log",
+        &[WasmType::I32, WasmType::I32, WasmType::I32],
+        &[],
+    ),
+    (
+        "svm_emit",
+        &[WasmType::I32, WasmType::I32, WasmType::I32, WasmType::I32],
+        &[],
+    ),
+    ("svm_revert", &[WasmType::I32, WasmType::I32], &[]),
+    ("svm_value", &[], &[WasmType::I64]),
+];
+
+/// A function registered by the `Host` to be imported by apps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostImport {
+    /// The import namespace
+    pub namespace: String,
+
+    /// The import name
+    pub name: String,
+
+    /// The function params types
+    pub params: Vec<WasmType>,
+
+    /// The function returns types
+    pub returns: Vec<WasmType>,
+}
+
 /// Validates an app-template.
 /// Should be called right after parsing the raw bytes into `AppTemplate`.
-#[allow(dead_code)]
-pub fn validate_template(_template: &AppTemplate) -> Result<(), ParseError> {
-    todo!()
+///
+/// Checks that:
+/// * The template `version` is supported and its `name` isn't too long.
+/// * The code is a valid wasm module exporting `memory` and `svm_alloc`.
+/// * Each import is either a `svm` vmcall or one of `host_imports`, with a matching signature.
+/// * The `DataLayout` variables are non-empty, don't overlap and don't exceed `DATA_LAYOUT_MAX_SIZE`.
+pub fn validate_template(
+    template: &AppTemplate,
+    host_imports: &[HostImport],
+) -> Result<(), ParseError> {
+    validate_version(template.version)?;
+    validate_name(&template.name)?;
+    validate_code(&template.code, host_imports)?;
+    validate_data(&template.data)
+}
+
+fn validate_version(version: u32) -> Result<(), ParseError> {
+    if crate::version::is_supported_version(TxKind::DeployTemplate, version) {
+        Ok(())
+    } else {
+        Err(ParseError::InvalidProtocolVersion(version))
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), ParseError> {
+    if name.len() > TEMPLATE_NAME_MAX_LENGTH {
+        Err(ParseError::NameTooLong {
+            len: name.len(),
+            max: TEMPLATE_NAME_MAX_LENGTH,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_code(code: &[u8], host_imports: &[HostImport]) -> Result<(), ParseError> {
+    let module = parity_wasm::deserialize_buffer::<Module>(code)
+        .map_err(|e| ParseError::InvalidWasm(e.to_string()))?;
+
+    validate_exports(&module)?;
+    validate_imports(&module, host_imports)
+}
+
+fn validate_exports(module: &Module) -> Result<(), ParseError> {
+    let exports = module
+        .export_section()
+        .map(|section| section.entries())
+        .unwrap_or(&[]);
+
+    let has_export = |name: &str, is_kind: fn(&Internal) -> bool| {
+        exports
+            .iter()
+            .any(|export| export.field() == name && is_kind(export.internal()))
+    };
+
+    if !has_export("memory", |kind| matches!(kind, Internal::Memory(..))) {
+        return Err(ParseError::MissingWasmExport("memory".to_string()));
+    }
+
+    if !has_export("svm_alloc", |kind| matches!(kind, Internal::Function(..))) {
+        return Err(ParseError::MissingWasmExport("svm_alloc".to_string()));
+    }
+
+    Ok(())
+}
+
+fn validate_imports(module: &Module, host_imports: &[HostImport]) -> Result<(), ParseError> {
+    let imports = module
+        .import_section()
+        .map(|section| section.entries())
+        .unwrap_or(&[]);
+
+    let types = module
+        .type_section()
+        .map(|section| section.types())
+        .unwrap_or(&[]);
+
+    for import in imports.iter() {
+        let namespace = import.module();
+        let name = import.field();
+
+        let unknown_import = || ParseError::UnknownImport {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        };
+
+        let (params, returns) = if namespace == SVM_NAMESPACE {
+            SVM_IMPORTS
+                .iter()
+                .find(|(svm_name, _, _)| *svm_name == name)
+                .map(|(_, params, returns)| (*params, *returns))
+                .ok_or_else(unknown_import)?
+        } else {
+            host_imports
+                .iter()
+                .find(|host| host.namespace == namespace && host.name == name)
+                .map(|host| (&host.params[..], &host.returns[..]))
+                .ok_or_else(unknown_import)?
+        };
+
+        let func_type = match import.external() {
+            External::Function(idx) => types.get(*idx as usize),
+            _ => return Err(unknown_import()),
+        };
+
+        let matches = match func_type {
+            Some(Type::Function(func_type)) => {
+                let actual_returns: Vec<ValueType> = func_type.return_type().into_iter().collect();
+
+                types_match(func_type.params(), params) && types_match(&actual_returns, returns)
+            }
+            None => false,
+        };
+
+        if !matches {
+            return Err(ParseError::ImportSignatureMismatch {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn types_match(actual: &[ValueType], expected: &[WasmType]) -> bool {
+    actual.len() == expected.len()
+        && actual.iter().zip(expected.iter()).all(|(a, e)| {
+            matches!(
+                (a, e),
+                (ValueType::I32, WasmType::I32) | (ValueType::I64, WasmType::I64)
+            )
+        })
+}
+
+fn validate_data(layout: &DataLayout) -> Result<(), ParseError> {
+    let mut vars: Vec<(u32, u32, u32)> = layout
+        .iter()
+        .map(|(var_id, off, len)| (off, len, var_id.0))
+        .collect();
+
+    if let Some((_, _, var_id)) = vars.iter().find(|(_, len, _)| *len == 0) {
+        return Err(ParseError::ZeroLengthVar(*var_id));
+    }
+
+    vars.sort();
+
+    for pair in vars.windows(2) {
+        let (off, len, var_id) = pair[0];
+        let (next_off, _, next_var_id) = pair[1];
+
+        if off as u64 + len as u64 > next_off as u64 {
+            return Err(ParseError::OverlappingVars(var_id, next_var_id));
+        }
+    }
+
+    let size: u64 = vars.iter().map(|(_, len, _)| *len as u64).sum();
+
+    if size > DATA_LAYOUT_MAX_SIZE as u64 {
+        return Err(ParseError::DataLayoutTooLarge {
+            size,
+            max: DATA_LAYOUT_MAX_SIZE,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use parity_wasm::builder;
+    use parity_wasm::elements::{Instruction, Instructions};
+
+    fn host_import(namespace: &str, name: &str) -> HostImport {
+        HostImport {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            params: vec![WasmType::I32],
+            returns: vec![WasmType::I64],
+        }
+    }
+
+    /// Builds a wasm module importing `imports` (as `(namespace, name, params, returns)`),
+    /// and exporting `memory` and `svm_alloc` when requested.
+    fn wasm(
+        imports: &[(&str, &str, Vec<ValueType>, Option<ValueType>)],
+        memory: bool,
+        alloc: bool,
+    ) -> Vec<u8> {
+        let mut module = builder::module();
+
+        for (namespace, name, params, ret) in imports.iter() {
+            let sig = module.push_signature(
+                builder::signature()
+                    .with_params(params.clone())
+                    .with_return_type(*ret)
+                    .build_sig(),
+            );
+
+            module = module
+                .import()
+                .path(namespace, name)
+                .external()
+                .func(sig)
+                .build();
+        }
+
+        if memory {
+            module = module
+                .memory()
+                .build()
+                .export()
+                .field("memory")
+                .internal()
+                .memory(0)
+                .build();
+        }
+
+        if alloc {
+            let body = Instructions::new(vec![Instruction::I32Const(0), Instruction::End]);
+
+            module = module
+                .function()
+                .signature()
+                .with_param(ValueType::I32)
+                .with_return_type(Some(ValueType::I32))
+                .build()
+                .body()
+                .with_instructions(body)
+                .build()
+                .build()
+                .export()
+                .field("svm_alloc")
+                .internal()
+                .func(imports.len() as u32)
+                .build();
+        }
+
+        parity_wasm::serialize(module.build()).unwrap()
+    }
+
+    fn new_template(code: Vec<u8>, data: DataLayout) -> AppTemplate {
+        AppTemplate {
+            version: 0,
+            name: "My Template".to_string(),
+            code,
+            data,
+        }
+    }
+
+    #[test]
+    fn validate_template_valid() {
+        let imports = [
+            (
+                "svm",
+                "svm_get64",
+                vec![ValueType::I32],
+                Some(ValueType::I64),
+            ),
+            (
+                "host",
+                "balance",
+                vec![ValueType::I32],
+                Some(ValueType::I64),
+            ),
+        ];
+
+        let template = new_template(wasm(&imports, true, true), vec![4, 8, 20].into());
+        let host_imports = [host_import("host", "balance")];
+
+        assert!(validate_template(&template, &host_imports).is_ok());
+    }
+
+    #[test]
+    fn validate_template_unsupported_version() {
+        let mut template = new_template(wasm(&[], true, true), DataLayout::empty());
+        template.version = TEMPLATE_VERSION;
+
+        assert!(validate_template(&template, &[]).is_ok());
+
+        template.version = TEMPLATE_VERSION + 1;
+
+        let err = validate_template(&template, &[]).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::InvalidProtocolVersion(TEMPLATE_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn validate_template_name_too_long() {
+        let mut template = new_template(wasm(&[], true, true), DataLayout::empty());
+        template.name = "a".repeat(TEMPLATE_NAME_MAX_LENGTH + 1);
+
+        let err = validate_template(&template, &[]).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::NameTooLong {
+                len: TEMPLATE_NAME_MAX_LENGTH + 1,
+                max: TEMPLATE_NAME_MAX_LENGTH
+            }
+        );
+    }
+
+    #[test]
+    fn validate_template_invalid_wasm() {
+        let template = new_template(vec![0x00, 0x61, 0x73], DataLayout::empty());
+
+        let err = validate_template(&template, &[]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidWasm(..)));
+    }
+
+    #[test]
+    fn validate_template_missing_exports() {
+        let template = new_template(wasm(&[], false, true), DataLayout::empty());
+        let err = validate_template(&template, &[]).unwrap_err();
+        assert_eq!(err, ParseError::MissingWasmExport("memory".to_string()));
+
+        let template = new_template(wasm(&[], true, false), DataLayout::empty());
+        let err = validate_template(&template, &[]).unwrap_err();
+        assert_eq!(err, ParseError::MissingWasmExport("svm_alloc".to_string()));
+    }
+
+    #[test]
+    fn validate_template_unknown_import() {
+        let imports = [(
+            "host",
+            "balance",
+            vec![ValueType::I32],
+            Some(ValueType::I64),
+        )];
+        let template = new_template(wasm(&imports, true, true), DataLayout::empty());
+
+        let err = validate_template(&template, &[]).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::UnknownImport {
+                namespace: "host".to_string(),
+                name: "balance".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn validate_template_import_signature_mismatch() {
+        let imports = [(
+            "svm",
+            "svm_get64",
+            vec![ValueType::I32],
+            Some(ValueType::I32),
+        )];
+        let template = new_template(wasm(&imports, true, true), DataLayout::empty());
+
+        let err = validate_template(&template, &[]).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::ImportSignatureMismatch {
+                namespace: "svm".to_string(),
+                name: "svm_get64".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn validate_template_zero_length_var() {
+        let template = new_template(wasm(&[], true, true), vec![4, 0, 8].into());
+
+        let err = validate_template(&template, &[]).unwrap_err();
+        assert_eq!(err, ParseError::ZeroLengthVar(1));
+    }
+
+    #[test]
+    fn validate_template_layout_too_large() {
+        let template = new_template(wasm(&[], true, true), vec![DATA_LAYOUT_MAX_SIZE, 1].into());
+
+        let err = validate_template(&template, &[]).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::DataLayoutTooLarge {
+                size: DATA_LAYOUT_MAX_SIZE as u64 + 1,
+                max: DATA_LAYOUT_MAX_SIZE
+            }
+        );
+    }
 }
@@ -3,21 +3,58 @@ use svm_types::AppTemplate;
 
 use crate::api::raw::{decode_varuint14, decode_version, encode_varuint14, Field};
 
+use crate::version::{dispatch, Decoder};
 use crate::{error::ParseError, helpers};
 
-use svm_layout::{DataLayout, DataLayoutBuilder};
+use svm_layout::{DataLayout, DataLayoutBuilder, VarType};
 
 /// Encodes a raw Deploy-Template.
+///
+/// The storage schema is encoded only by version `1` and later
+/// (a version `0` template is encoded without its `DataLayout` schema).
 pub fn encode_deploy_template(template: &AppTemplate, w: &mut NibbleWriter) {
     encode_version(template, w);
     encode_name(template, w);
     encode_code(template, w);
-    encode_data(template, w);
+
+    if template.version == 0 {
+        encode_layout(&template.data, w);
+    } else {
+        encode_data(&template.data, w);
+    }
 }
 
 /// Decodes a raw Deploy-Template.
+///
+/// Dispatches to the decoder of the declared version (see `DECODERS`).
 pub fn decode_deploy_template(iter: &mut NibbleIter) -> Result<AppTemplate, ParseError> {
     let version = decode_version(iter)?;
+
+    dispatch(DECODERS, version, iter)
+}
+
+/// The decoder of each supported Deploy-Template wire version.
+pub(crate) const DECODERS: &[(u32, Decoder<AppTemplate>)] = &[(0, decode_v0), (1, decode_v1)];
+
+/// Version `0` has no storage schema.
+fn decode_v0(version: u32, iter: &mut NibbleIter) -> Result<AppTemplate, ParseError> {
+    let name = decode_name(iter)?;
+    let code = decode_code(iter)?;
+    let (_nvars, builder) = decode_layout(iter)?;
+    let data = builder.build();
+
+    let template = AppTemplate {
+        version,
+        name,
+        code,
+        data,
+    };
+
+    Ok(template)
+}
+
+/// Version `1` appends the storage schema to the `DataLayout`.
+fn decode_v1(version: u32, iter: &mut NibbleIter) -> Result<AppTemplate, ParseError> {
     let name = decode_name(iter)?;
     let code = decode_code(iter)?;
     let data = decode_data(iter)?;
@@ -43,15 +80,52 @@ fn encode_name(template: &AppTemplate, w: &mut NibbleWriter) {
     helpers::encode_string(&template.name, w);
 }
 
-fn encode_data(template: &AppTemplate, w: &mut NibbleWriter) {
-    let nvars = template.data.len() as u32;
+/// Encodes a `DataLayout` (including its optional schema).
+pub(crate) fn encode_data(layout: &DataLayout, w: &mut NibbleWriter) {
+    encode_layout(layout, w);
+    encode_schema(layout, w);
+}
+
+/// Encodes the `DataLayout` variables (without its schema).
+fn encode_layout(layout: &DataLayout, w: &mut NibbleWriter) {
+    let nvars = layout.len() as u32;
     encode_varuint14(nvars as u16, w);
 
-    for (_varid, _off, len) in template.data.iter() {
+    for (_varid, _off, len) in layout.iter() {
         encode_varuint14(len as u16, w);
     }
 }
 
+fn encode_schema(layout: &DataLayout, w: &mut NibbleWriter) {
+    let schema = layout.schema().unwrap_or(&[]);
+
+    encode_varuint14(schema.len() as u16, w);
+
+    for var in schema.iter() {
+        helpers::encode_string(&var.name, w);
+        w.write_byte(var_type_code(var.ty));
+        encode_varuint14(var.count as u16, w);
+    }
+}
+
+fn var_type_code(ty: VarType) -> u8 {
+    match ty {
+        VarType::Bool => 0,
+        VarType::I8 => 1,
+        VarType::U8 => 2,
+        VarType::I16 => 3,
+        VarType::U16 => 4,
+        VarType::I32 => 5,
+        VarType::U32 => 6,
+        VarType::I64 => 7,
+        VarType::U64 => 8,
+        VarType::Amount => 9,
+        VarType::Address => 10,
+        VarType::U128 => 11,
+        VarType::U256 => 12,
+    }
+}
+
 fn encode_code(template: &AppTemplate, w: &mut NibbleWriter) {
     let code = &template.code;
 
@@ -71,7 +145,21 @@ fn decode_name(iter: &mut NibbleIter) -> Result<String, ParseError> {
     helpers::decode_string(iter, Field::NameLength, Field::Name)
 }
 
-fn decode_data(iter: &mut NibbleIter) -> Result<DataLayout, ParseError> {
+/// Decodes a `DataLayout` (including its optional schema).
+pub(crate) fn decode_data(iter: &mut NibbleIter) -> Result<DataLayout, ParseError> {
+    let (nvars, mut builder) = decode_layout(iter)?;
+
+    decode_schema(iter, nvars, &mut builder)?;
+
+    let layout = builder.build();
+
+    Ok(layout)
+}
+
+/// Decodes the `DataLayout` variables (without its schema).
+///
+/// Returns the number of variables along with the `DataLayoutBuilder` holding them.
+fn decode_layout(iter: &mut NibbleIter) -> Result<(u16, DataLayoutBuilder), ParseError> {
     let nvars = decode_varuint14(iter, Field::DataLayoutVarsCount)?;
 
     let mut builder = DataLayoutBuilder::with_capacity(nvars as usize);
@@ -82,9 +170,68 @@ fn decode_data(iter: &mut NibbleIter) -> Result<DataLayout, ParseError> {
         builder.add_var(len as u32);
     }
 
-    let layout = builder.build();
+    Ok((nvars, builder))
+}
 
-    Ok(layout)
+fn decode_schema(
+    iter: &mut NibbleIter,
+    nvars: u16,
+    builder: &mut DataLayoutBuilder,
+) -> Result<(), ParseError> {
+    let nfields = decode_varuint14(iter, Field::SchemaVarsCount)?;
+
+    if nfields == 0 {
+        return Ok(());
+    }
+
+    let mut total = 0;
+
+    for _ in 0..nfields {
+        let name = helpers::decode_string(iter, Field::SchemaVarNameLength, Field::SchemaVarName)?;
+        let ty = decode_var_type(iter)?;
+        let count = decode_varuint14(iter, Field::SchemaVarCount)?;
+
+        if count == 0 {
+            return Err(ParseError::InvalidSchema(Field::SchemaVarCount));
+        }
+
+        builder.add_symbolic_var(&name, ty, count as u32);
+
+        total += count as u32;
+    }
+
+    if total != nvars as u32 {
+        return Err(ParseError::InvalidSchema(Field::SchemaVarsCount));
+    }
+
+    Ok(())
+}
+
+fn decode_var_type(iter: &mut NibbleIter) -> Result<VarType, ParseError> {
+    let bytes = iter.read_bytes(1);
+
+    if bytes.len() != 1 {
+        return Err(ParseError::NotEnoughBytes(Field::SchemaVarType));
+    }
+
+    let ty = match bytes[0] {
+        0 => VarType::Bool,
+        1 => VarType::I8,
+        2 => VarType::U8,
+        3 => VarType::I16,
+        4 => VarType::U16,
+        5 => VarType::I32,
+        6 => VarType::U32,
+        7 => VarType::I64,
+        8 => VarType::U64,
+        9 => VarType::Amount,
+        10 => VarType::Address,
+        11 => VarType::U128,
+        12 => VarType::U256,
+        _ => return Err(ParseError::InvalidSchema(Field::SchemaVarType)),
+    };
+
+    Ok(ty)
 }
 
 fn decode_code(iter: &mut NibbleIter) -> Result<Vec<u8>, ParseError> {
@@ -117,4 +264,113 @@ mod tests {
 
         assert_eq!(template, decoded);
     }
+
+    #[test]
+    fn encode_decode_deploy_template_with_schema() {
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 20, 20, 32]);
+        builder.add_symbolic_var("balance", VarType::Amount, 1);
+        builder.add_symbolic_var("owners", VarType::Address, 2);
+        builder.add_symbolic_var("supply", VarType::U256, 1);
+
+        let template = AppTemplate {
+            version: 1,
+            name: "My Template".to_string(),
+            code: vec![0x0C, 0x00, 0x0D, 0x0E],
+            data: builder.build(),
+        };
+
+        let mut w = NibbleWriter::new();
+        encode_deploy_template(&template, &mut w);
+
+        let bytes = w.into_bytes();
+        let mut iter = NibbleIter::new(&bytes[..]);
+
+        let decoded = decode_deploy_template(&mut iter).unwrap();
+
+        assert_eq!(template, decoded);
+        assert_eq!(decoded.data.schema().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn decode_deploy_template_schema_not_matching_layout() {
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 20, 20]);
+        builder.add_symbolic_var("balance", VarType::Amount, 1);
+        builder.add_symbolic_var("owner", VarType::Address, 1);
+
+        let template = AppTemplate {
+            version: 1,
+            name: "My Template".to_string(),
+            code: vec![0x0C, 0x00, 0x0D, 0x0E],
+            data: builder.build(),
+        };
+
+        let mut w = NibbleWriter::new();
+        encode_deploy_template(&template, &mut w);
+
+        let bytes = w.into_bytes();
+        let mut iter = NibbleIter::new(&bytes[..]);
+
+        let err = decode_deploy_template(&mut iter).unwrap_err();
+
+        assert_eq!(err, ParseError::InvalidSchema(Field::SchemaVarsCount));
+    }
+
+    #[test]
+    fn decode_deploy_template_v0_has_no_schema() {
+        let mut w = NibbleWriter::new();
+
+        crate::api::raw::encode_version(0, &mut w);
+        helpers::encode_string("My Template", &mut w);
+        helpers::encode_u32_be(4, &mut w);
+        w.write_bytes(&[0x0C, 0x00, 0x0D, 0x0E]);
+
+        // a `DataLayout` of 2 variables (with no trailing schema)
+        encode_varuint14(2, &mut w);
+        encode_varuint14(5, &mut w);
+        encode_varuint14(10, &mut w);
+
+        let bytes = w.into_bytes();
+        let mut iter = NibbleIter::new(&bytes[..]);
+
+        let decoded = decode_deploy_template(&mut iter).unwrap();
+        assert!(iter.ensure_eof(ParseError::ExpectedEOF).is_ok());
+
+        let expected = AppTemplate {
+            version: 0,
+            name: "My Template".to_string(),
+            code: vec![0x0C, 0x00, 0x0D, 0x0E],
+            data: vec![5, 10].into(),
+        };
+
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn encode_deploy_template_v0_drops_schema() {
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8]);
+        builder.add_symbolic_var("balance", VarType::Amount, 1);
+
+        let template = AppTemplate {
+            version: 0,
+            name: "My Template".to_string(),
+            code: vec![0x0C, 0x00, 0x0D, 0x0E],
+            data: builder.build(),
+        };
+
+        let mut w = NibbleWriter::new();
+        encode_deploy_template(&template, &mut w);
+
+        let bytes = w.into_bytes();
+        let mut iter = NibbleIter::new(&bytes[..]);
+
+        let decoded = decode_deploy_template(&mut iter).unwrap();
+
+        let expected: DataLayout = vec![8].into();
+
+        assert_eq!(decoded.data, expected);
+        assert!(decoded.data.schema().is_none());
+    }
 }
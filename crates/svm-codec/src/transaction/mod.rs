@@ -56,3 +56,5 @@
 mod wire;
 
 pub use wire::{decode_exec_app, encode_exec_app};
+
+pub(crate) use wire::DECODERS;
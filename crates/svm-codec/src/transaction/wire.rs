@@ -3,6 +3,7 @@ use svm_types::{AppAddr, AppTransaction};
 
 use crate::api::raw::{decode_abi_data, decode_varuint14, decode_version, encode_abi_data, Field};
 
+use crate::version::{dispatch, Decoder};
 use crate::{error::ParseError, helpers};
 
 /// Encodes a raw App transaction.
@@ -16,8 +17,18 @@ pub fn encode_exec_app(tx: &AppTransaction, w: &mut NibbleWriter) {
 /// Parsing a raw `AppTransaction` transaction given as raw bytes.
 /// Returns the parsed transaction as a `AppTransaction` struct.
 /// On failure, returns `ParseError`.
+///
+/// Dispatches to the decoder of the declared version (see `DECODERS`).
 pub fn decode_exec_app(iter: &mut NibbleIter) -> Result<AppTransaction, ParseError> {
     let version = decode_version(iter)?;
+
+    dispatch(DECODERS, version, iter)
+}
+
+/// The decoder of each supported `exec-app` wire version.
+pub(crate) const DECODERS: &[(u32, Decoder<AppTransaction>)] = &[(0, decode_v0)];
+
+fn decode_v0(version: u32, iter: &mut NibbleIter) -> Result<AppTransaction, ParseError> {
     let app = decode_app(iter)?;
     let func_name = decode_func(iter)?;
     let calldata = decode_abi_data(iter)?;
@@ -0,0 +1,34 @@
+//! Upgrade `App` Raw Format Version 0.0
+//!  +-----------------------------------------------------+
+//!  |   proto     |                                       |
+//!  |  version    |            `AppAddress`               |
+//!  |  encoding   |             (Address)                 |
+//!  |_____________|_______________________________________|
+//!  |                                                     |
+//!  |            `TemplateAddress` (Address)              |
+//!  |_____________________________________________________|
+//!  |                                                     |
+//!  |  has          |                                     |
+//!  |  migration    |  Migration Function Name (String)   |
+//!  |  (1 nibble)   |     (only when `has migration`)     |
+//!  |     (a)       |                                     |
+//!  |_______________|_____________________________________|
+//!  |            |                                        |
+//!  |  calldata  |                                        |
+//!  |  #length   |           calldata blob                |
+//!  |  encoding  |                                        |
+//!  |____________|________________________________________|
+//!
+//!
+//! (a) Migration Function Name
+//! ===========================
+//!
+//!  The `has migration` nibble is `1` when a migration function follows and `0` otherwise.
+//!  An upgrade without a migration is expected to have an empty calldata.
+//!
+
+mod wire;
+
+pub use wire::{decode_upgrade_app, encode_upgrade_app};
+
+pub(crate) use wire::DECODERS;
@@ -0,0 +1,154 @@
+use svm_nibble::{nib, Nibble, NibbleIter, NibbleWriter};
+use svm_types::{AppAddr, TemplateAddr, UpgradeApp};
+
+use crate::api::raw::{decode_abi_data, decode_version, encode_abi_data, Field};
+
+use crate::version::{dispatch, Decoder};
+use crate::{error::ParseError, helpers};
+
+/// Encodes a raw upgrade-app transaction.
+pub fn encode_upgrade_app(upgrade: &UpgradeApp, w: &mut NibbleWriter) {
+    encode_version(upgrade, w);
+    encode_app(upgrade, w);
+    encode_template(upgrade, w);
+    encode_migration(upgrade, w);
+    encode_calldata(upgrade, w);
+}
+
+/// Parsing a raw upgrade-app transaction given as raw bytes.
+/// Returns the parsed transaction as a `UpgradeApp` struct.
+/// On failure, returns `ParseError`.
+///
+/// Dispatches to the decoder of the declared version (see `DECODERS`).
+pub fn decode_upgrade_app(iter: &mut NibbleIter) -> Result<UpgradeApp, ParseError> {
+    let version = decode_version(iter)?;
+
+    dispatch(DECODERS, version, iter)
+}
+
+/// The decoder of each supported `upgrade-app` wire version.
+pub(crate) const DECODERS: &[(u32, Decoder<UpgradeApp>)] = &[(0, decode_v0)];
+
+fn decode_v0(version: u32, iter: &mut NibbleIter) -> Result<UpgradeApp, ParseError> {
+    let app = decode_app(iter)?;
+    let template = decode_template(iter)?;
+    let migration = decode_migration(iter)?;
+    let calldata = decode_abi_data(iter)?;
+
+    let upgrade = UpgradeApp {
+        version,
+        app,
+        template,
+        migration,
+        calldata,
+    };
+
+    Ok(upgrade)
+}
+
+/// Encoders
+
+fn encode_version(upgrade: &UpgradeApp, w: &mut NibbleWriter) {
+    let ver = upgrade.version;
+    crate::api::raw::encode_version(ver, w);
+}
+
+fn encode_app(upgrade: &UpgradeApp, w: &mut NibbleWriter) {
+    let addr = upgrade.app.inner();
+    helpers::encode_address(addr, w);
+}
+
+fn encode_template(upgrade: &UpgradeApp, w: &mut NibbleWriter) {
+    let addr = upgrade.template.inner();
+    helpers::encode_address(addr, w);
+}
+
+fn encode_migration(upgrade: &UpgradeApp, w: &mut NibbleWriter) {
+    match upgrade.migration {
+        None => w.push(nib!(0)),
+        Some(ref func) => {
+            w.push(nib!(1));
+            helpers::encode_string(func, w);
+        }
+    }
+}
+
+fn encode_calldata(upgrade: &UpgradeApp, w: &mut NibbleWriter) {
+    let buf = &upgrade.calldata[..];
+    encode_abi_data(buf, w)
+}
+
+/// Decoders
+
+fn decode_app(iter: &mut NibbleIter) -> Result<AppAddr, ParseError> {
+    let addr = helpers::decode_address(iter, Field::AppAddr)?;
+
+    Ok(addr.into())
+}
+
+fn decode_template(iter: &mut NibbleIter) -> Result<TemplateAddr, ParseError> {
+    let addr = helpers::decode_address(iter, Field::TemplateAddr)?;
+
+    Ok(addr.into())
+}
+
+fn decode_migration(iter: &mut NibbleIter) -> Result<Option<String>, ParseError> {
+    let has_migration: Option<Nibble> = iter.next();
+
+    match has_migration.map(|nib| nib.inner()) {
+        None => Err(ParseError::NotEnoughBytes(Field::Migration)),
+        Some(0) => Ok(None),
+        Some(1) => {
+            let func = helpers::decode_string(iter, Field::FuncNameLength, Field::FuncName)?;
+
+            Ok(Some(func))
+        }
+        Some(..) => Err(ParseError::NotSupported(Field::Migration)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use svm_nibble::{NibbleIter, NibbleWriter};
+    use svm_types::{Address, UpgradeApp};
+
+    use crate::api::raw::{decode_upgrade_app, encode_upgrade_app};
+
+    fn assert_roundtrip(upgrade: UpgradeApp) {
+        let mut w = NibbleWriter::new();
+        encode_upgrade_app(&upgrade, &mut w);
+
+        let bytes = w.into_bytes();
+        let mut iter = NibbleIter::new(&bytes[..]);
+
+        let decoded = decode_upgrade_app(&mut iter).unwrap();
+
+        assert_eq!(upgrade, decoded);
+    }
+
+    #[test]
+    fn encode_decode_upgrade_app_with_migration() {
+        let upgrade = UpgradeApp {
+            version: 0,
+            app: Address::of("my-app").into(),
+            template: Address::of("my-template-v2").into(),
+            migration: Some("migrate".to_string()),
+            calldata: vec![0x10, 0x0, 0x30],
+        };
+
+        assert_roundtrip(upgrade);
+    }
+
+    #[test]
+    fn encode_decode_upgrade_app_without_migration() {
+        let upgrade = UpgradeApp {
+            version: 0,
+            app: Address::of("my-app").into(),
+            template: Address::of("my-template-v2").into(),
+            migration: None,
+            calldata: Vec::new(),
+        };
+
+        assert_roundtrip(upgrade);
+    }
+}
@@ -0,0 +1,75 @@
+use svm_nibble::NibbleIter;
+
+use crate::{api::raw::Field, error::ParseError};
+
+/// Decodes a `varuint32` field into a `u32`.
+pub fn decode_varuint32(iter: &mut NibbleIter, field: Field) -> Result<u32, ParseError> {
+    let mut num: u64 = 0;
+
+    for i in 0..5 {
+        let bytes = iter.read_bytes(1);
+
+        if bytes.is_empty() {
+            return Err(ParseError::NotEnoughBytes(field));
+        }
+
+        let byte = bytes[0];
+        num |= ((byte & 0x7F) as u64) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            if num > std::u32::MAX as u64 {
+                return Err(ParseError::TooManyBytes(field));
+            }
+
+            return Ok(num as u32);
+        }
+    }
+
+    Err(ParseError::TooManyBytes(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_varuint32_empty() {
+        let vec = vec![];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        let expected = Err(ParseError::NotEnoughBytes(Field::LogsCount));
+
+        assert_eq!(expected, decode_varuint32(&mut iter, Field::LogsCount));
+    }
+
+    #[test]
+    fn decode_varuint32_not_enough_bytes() {
+        let vec = vec![0b_1000_0001];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        let expected = Err(ParseError::NotEnoughBytes(Field::LogsCount));
+
+        assert_eq!(expected, decode_varuint32(&mut iter, Field::LogsCount));
+    }
+
+    #[test]
+    fn decode_varuint32_too_many_bytes() {
+        let vec = vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        let expected = Err(ParseError::TooManyBytes(Field::LogsCount));
+
+        assert_eq!(expected, decode_varuint32(&mut iter, Field::LogsCount));
+    }
+
+    #[test]
+    fn decode_varuint32_overflow() {
+        // 5 bytes carrying more than 32 bits
+        let vec = vec![0xFF, 0xFF, 0xFF, 0xFF, 0b_0001_1111];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        let expected = Err(ParseError::TooManyBytes(Field::LogsCount));
+
+        assert_eq!(expected, decode_varuint32(&mut iter, Field::LogsCount));
+    }
+}
@@ -0,0 +1,50 @@
+use svm_nibble::NibbleWriter;
+
+/// Encodes `u32` into `varuint32`.
+///
+/// Each byte holds 7 bits of `num` (least-significant group first).
+/// The byte's most-significant bit is on when there are more bytes to follow.
+/// So a `varuint32` consumes between 1 to 5 bytes inclusive.
+pub fn encode_varuint32(num: u32, w: &mut NibbleWriter) {
+    let mut num = num;
+
+    loop {
+        let byte = (num & 0x7F) as u8;
+        num >>= 7;
+
+        if num == 0 {
+            w.write_byte(byte);
+            break;
+        }
+
+        w.write_byte(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(num: u32) -> Vec<u8> {
+        let mut w = NibbleWriter::new();
+
+        encode_varuint32(num, &mut w);
+
+        w.into_bytes()
+    }
+
+    #[test]
+    fn encode_varuint32_single_byte() {
+        assert_eq!(encode(0), vec![0b_0000_0000]);
+        assert_eq!(encode(0b_0111_1111), vec![0b_0111_1111]);
+    }
+
+    #[test]
+    fn encode_varuint32_multiple_bytes() {
+        assert_eq!(encode(0b_1000_0000), vec![0b_1000_0000, 0b_0000_0001]);
+        assert_eq!(
+            encode(std::u32::MAX),
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0b_0000_1111]
+        );
+    }
+}
@@ -0,0 +1,42 @@
+mod decoder;
+mod encoder;
+
+pub use decoder::decode_varuint32;
+pub use encoder::encode_varuint32;
+
+#[cfg(test)]
+mod tests {
+    use svm_nibble::{NibbleIter, NibbleWriter};
+
+    use crate::api::raw::{decode_varuint32, encode_varuint32, Field};
+    use crate::error::ParseError;
+
+    fn assert_encode_decode(num: u32, expected_bytes: usize) {
+        let mut w = NibbleWriter::new();
+
+        encode_varuint32(num, &mut w);
+
+        let data = w.into_bytes();
+        assert_eq!(data.len(), expected_bytes);
+
+        let mut iter = NibbleIter::new(&data[..]);
+
+        // choosing an arbitrary `varuint32` field.
+        let field = Field::LogMessageLength;
+
+        let decoded = decode_varuint32(&mut iter, field).unwrap();
+        assert_eq!(num, decoded);
+
+        assert!(iter.ensure_eof(ParseError::ExpectedEOF).is_ok());
+    }
+
+    #[test]
+    fn encode_decode_varuint32() {
+        assert_encode_decode(0, 1);
+        assert_encode_decode(0x7F, 1);
+        assert_encode_decode(0x80, 2);
+        assert_encode_decode(0x3FFF, 2);
+        assert_encode_decode(0x4000, 3);
+        assert_encode_decode(std::u32::MAX, 5);
+    }
+}
@@ -1,8 +1,12 @@
 mod decoder;
 mod encoder;
+mod registry;
 
 pub use decoder::decode_version;
 pub use encoder::encode_version;
+pub use registry::{is_supported_version, supported_versions, VersionSchedule};
+
+pub(crate) use registry::{dispatch, Decoder};
 
 #[cfg(test)]
 mod tests {
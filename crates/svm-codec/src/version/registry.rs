@@ -0,0 +1,301 @@
+use svm_nibble::NibbleIter;
+use svm_types::TxKind;
+
+use crate::error::ParseError;
+
+/// Decodes a transaction whose declared wire-format `version` has already been read.
+pub(crate) type Decoder<T> = fn(version: u32, iter: &mut NibbleIter) -> Result<T, ParseError>;
+
+/// Dispatches to the decoder registered for `version` under `decoders`.
+///
+/// Rejects versions having no registered decoder with `ParseError::InvalidProtocolVersion`.
+pub(crate) fn dispatch<T>(
+    decoders: &[(u32, Decoder<T>)],
+    version: u32,
+    iter: &mut NibbleIter,
+) -> Result<T, ParseError> {
+    let decoder = decoders
+        .iter()
+        .find(|(v, _)| *v == version)
+        .map(|(_, decoder)| decoder);
+
+    match decoder {
+        Some(decoder) => decoder(version, iter),
+        None => Err(ParseError::InvalidProtocolVersion(version)),
+    }
+}
+
+/// Returns the wire-format versions of transactions of `kind` this codec knows to decode (in ascending order).
+pub fn supported_versions(kind: TxKind) -> Vec<u32> {
+    match kind {
+        TxKind::DeployTemplate => versions_of(crate::template::DECODERS),
+        TxKind::SpawnApp => versions_of(crate::app::DECODERS),
+        TxKind::ExecApp => versions_of(crate::transaction::DECODERS),
+        TxKind::UpgradeApp => versions_of(crate::upgrade::DECODERS),
+    }
+}
+
+/// Returns whether this codec knows to decode version `version` of transactions of `kind`.
+pub fn is_supported_version(kind: TxKind, version: u32) -> bool {
+    supported_versions(kind).contains(&version)
+}
+
+fn versions_of<T>(decoders: &[(u32, Decoder<T>)]) -> Vec<u32> {
+    let mut versions: Vec<u32> = decoders.iter().map(|(v, _)| *v).collect();
+
+    versions.sort_unstable();
+    versions
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Activation {
+    kind: TxKind,
+    version: u32,
+    from_layer: u64,
+    until_layer: Option<u64>,
+}
+
+impl Activation {
+    fn is_active_at(&self, layer: u64) -> bool {
+        let started = self.from_layer <= layer;
+        let ended = self.until_layer.map_or(false, |until| until <= layer);
+
+        started && !ended
+    }
+}
+
+/// Tells which wire-format versions are active at each layer.
+///
+/// A version is activated at some layer and stays active until it's retired.
+/// Hosts use it to roll out new wire formats in a coordinated manner
+/// (see `svm_runtime::DefaultRuntime::set_version_schedule`).
+///
+/// The default schedule activates all the `supported_versions` since layer `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionSchedule {
+    activations: Vec<Activation>,
+}
+
+impl VersionSchedule {
+    /// Creates a schedule having no active versions.
+    pub fn empty() -> Self {
+        Self {
+            activations: Vec::new(),
+        }
+    }
+
+    /// Activates `version` of transactions of `kind` starting at `layer`.
+    ///
+    /// Fails with `ParseError::InvalidProtocolVersion` when the codec doesn't know to decode that version.
+    pub fn activate(&mut self, kind: TxKind, version: u32, layer: u64) -> Result<(), ParseError> {
+        if !is_supported_version(kind, version) {
+            return Err(ParseError::InvalidProtocolVersion(version));
+        }
+
+        self.retain(kind, version);
+
+        self.activations.push(Activation {
+            kind,
+            version,
+            from_layer: layer,
+            until_layer: None,
+        });
+
+        Ok(())
+    }
+
+    /// Retires `version` of transactions of `kind` starting at `layer` (i.e it's inactive from `layer` and on).
+    ///
+    /// Retiring a version which has never been activated has no effect.
+    pub fn retire(&mut self, kind: TxKind, version: u32, layer: u64) {
+        for activation in self.activations.iter_mut() {
+            if activation.kind == kind && activation.version == version {
+                activation.until_layer = Some(layer);
+            }
+        }
+    }
+
+    /// Returns whether `version` of transactions of `kind` is active at `layer`.
+    pub fn is_active(&self, kind: TxKind, version: u32, layer: u64) -> bool {
+        self.activations
+            .iter()
+            .any(|a| a.kind == kind && a.version == version && a.is_active_at(layer))
+    }
+
+    /// Returns the versions of transactions of `kind` active at `layer` (in ascending order).
+    pub fn active_versions(&self, kind: TxKind, layer: u64) -> Vec<u32> {
+        let mut versions: Vec<u32> = self
+            .activations
+            .iter()
+            .filter(|a| a.kind == kind && a.is_active_at(layer))
+            .map(|a| a.version)
+            .collect();
+
+        versions.sort_unstable();
+        versions
+    }
+
+    /// Fails with `ParseError::InvalidProtocolVersion` unless `version` is active at `layer`.
+    pub fn ensure_active(&self, kind: TxKind, version: u32, layer: u64) -> Result<(), ParseError> {
+        if self.is_active(kind, version, layer) {
+            Ok(())
+        } else {
+            Err(ParseError::InvalidProtocolVersion(version))
+        }
+    }
+
+    fn retain(&mut self, kind: TxKind, version: u32) {
+        self.activations
+            .retain(|a| a.kind != kind || a.version != version);
+    }
+}
+
+impl Default for VersionSchedule {
+    fn default() -> Self {
+        let mut schedule = Self::empty();
+
+        let kinds = [
+            TxKind::DeployTemplate,
+            TxKind::SpawnApp,
+            TxKind::ExecApp,
+            TxKind::UpgradeApp,
+        ];
+
+        for kind in kinds.iter() {
+            for version in supported_versions(*kind) {
+                schedule.activate(*kind, version, 0).unwrap();
+            }
+        }
+
+        schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_nibble::NibbleWriter;
+
+    use crate::api::raw::encode_version;
+
+    fn decode_v0(version: u32, iter: &mut NibbleIter) -> Result<(u32, u8), ParseError> {
+        Ok((version, iter.read_byte()))
+    }
+
+    fn decode_v2(version: u32, _iter: &mut NibbleIter) -> Result<(u32, u8), ParseError> {
+        Ok((version, 0xFF))
+    }
+
+    const DECODERS: &[(u32, Decoder<(u32, u8)>)] = &[(0, decode_v0), (2, decode_v2)];
+
+    #[test]
+    fn dispatch_by_version() {
+        let data = vec![0xAB];
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(dispatch(DECODERS, 0, &mut iter), Ok((0, 0xAB)));
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(dispatch(DECODERS, 2, &mut iter), Ok((2, 0xFF)));
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(
+            dispatch(DECODERS, 1, &mut iter),
+            Err(ParseError::InvalidProtocolVersion(1))
+        );
+    }
+
+    #[test]
+    fn supported_versions_per_kind() {
+        assert_eq!(supported_versions(TxKind::DeployTemplate), vec![0, 1]);
+        assert_eq!(supported_versions(TxKind::SpawnApp), vec![0]);
+        assert_eq!(supported_versions(TxKind::ExecApp), vec![0]);
+        assert_eq!(supported_versions(TxKind::UpgradeApp), vec![0]);
+
+        assert!(is_supported_version(TxKind::ExecApp, 0));
+        assert!(!is_supported_version(TxKind::ExecApp, 1));
+    }
+
+    #[test]
+    fn decoders_reject_unsupported_versions() {
+        let mut w = NibbleWriter::new();
+        encode_version(1, &mut w);
+        let data = w.into_bytes();
+
+        let expected = ParseError::InvalidProtocolVersion(1);
+
+        // version `1` of `deploy-template` is supported (it carries the storage schema)
+        let mut w = NibbleWriter::new();
+        encode_version(2, &mut w);
+        let template_data = w.into_bytes();
+
+        let mut iter = NibbleIter::new(&template_data);
+        assert_eq!(
+            crate::api::raw::decode_deploy_template(&mut iter).unwrap_err(),
+            ParseError::InvalidProtocolVersion(2)
+        );
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(
+            crate::api::raw::decode_spawn_app(&mut iter).unwrap_err(),
+            expected
+        );
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(
+            crate::api::raw::decode_exec_app(&mut iter).unwrap_err(),
+            expected
+        );
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(
+            crate::api::raw::decode_upgrade_app(&mut iter).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn default_schedule() {
+        let schedule = VersionSchedule::default();
+
+        assert!(schedule.is_active(TxKind::SpawnApp, 0, 0));
+        assert!(schedule.is_active(TxKind::SpawnApp, 0, 1000));
+        assert!(!schedule.is_active(TxKind::SpawnApp, 1, 1000));
+
+        assert_eq!(schedule.active_versions(TxKind::ExecApp, 10), vec![0]);
+    }
+
+    #[test]
+    fn schedule_activate_and_retire() {
+        let mut schedule = VersionSchedule::empty();
+        assert_eq!(
+            schedule.active_versions(TxKind::ExecApp, 0),
+            Vec::<u32>::new()
+        );
+
+        schedule.activate(TxKind::ExecApp, 0, 10).unwrap();
+        assert!(!schedule.is_active(TxKind::ExecApp, 0, 9));
+        assert!(schedule.is_active(TxKind::ExecApp, 0, 10));
+        assert!(!schedule.is_active(TxKind::SpawnApp, 0, 10));
+
+        schedule.retire(TxKind::ExecApp, 0, 20);
+        assert!(schedule.is_active(TxKind::ExecApp, 0, 19));
+        assert!(!schedule.is_active(TxKind::ExecApp, 0, 20));
+
+        assert_eq!(
+            schedule.ensure_active(TxKind::ExecApp, 0, 20),
+            Err(ParseError::InvalidProtocolVersion(0))
+        );
+
+        // re-activating overrides the previous activation
+        schedule.activate(TxKind::ExecApp, 0, 30).unwrap();
+        assert!(!schedule.is_active(TxKind::ExecApp, 0, 25));
+        assert!(schedule.is_active(TxKind::ExecApp, 0, 30));
+
+        assert_eq!(
+            schedule.activate(TxKind::ExecApp, 7, 0),
+            Err(ParseError::InvalidProtocolVersion(7))
+        );
+    }
+}
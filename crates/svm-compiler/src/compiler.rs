@@ -1,6 +1,12 @@
 use wasmer::{Cranelift, Module, Store, JIT};
 use wasmer_compiler::CompileError;
 
+/// The name of the exported `i64` global holding the gas metered for the executed wasm code.
+///
+/// It's to be maintained by the `gas-metering` middleware (see `compile`).
+/// The runtime reads it after each execution and adds the gas charged by the vmcalls on top of it.
+pub const GAS_USED_GLOBAL: &str = "svm_gas_used";
+
 /// Compiles the SVM app
 ///
 // TODO:
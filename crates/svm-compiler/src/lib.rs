@@ -8,4 +8,4 @@
 
 mod compiler;
 
-pub use compiler::{compile, new_store};
+pub use compiler::{compile, new_store, GAS_USED_GLOBAL};
@@ -1,10 +1,15 @@
-use crate::layout::DataLayout;
+use crate::layout::{DataLayout, VarId};
+use crate::schema::{SymbolicVar, VarType};
 
 /// Specifies the fixed-sized variables of an application.
 pub struct DataLayoutBuilder {
     vars: Vec<(u32, u32)>,
 
     next_offset: u32,
+
+    schema: Vec<SymbolicVar>,
+
+    next_symbolic_id: u32,
 }
 
 /// `DataLayoutBuilder` represents the fixed-sized variables (storage) of an application.
@@ -19,6 +24,8 @@ impl DataLayoutBuilder {
         Self {
             vars: Vec::with_capacity(capacity),
             next_offset: 0,
+            schema: Vec::new(),
+            next_symbolic_id: 0,
         }
     }
 
@@ -46,8 +53,28 @@ impl DataLayoutBuilder {
         self.next_offset += len;
     }
 
+    /// Adds the next named and typed field to the layout's schema.
+    ///
+    /// The field's first `VarId` is right after where the previous field's variables ended.
+    /// Adding a field doesn't add variables, these should be added using `add_var`.
+    pub fn add_symbolic_var(&mut self, name: &str, ty: VarType, count: u32) {
+        let id = VarId(self.next_symbolic_id);
+
+        self.schema.push(SymbolicVar {
+            id,
+            name: name.to_string(),
+            ty,
+            count,
+        });
+
+        self.next_symbolic_id += count;
+    }
+
     /// Finishes the data-layout building process and outputs the result `DataLayout`.
     pub fn build(self) -> DataLayout {
-        DataLayout { vars: self.vars }
+        DataLayout {
+            vars: self.vars,
+            schema: self.schema,
+        }
     }
 }
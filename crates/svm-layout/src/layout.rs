@@ -1,20 +1,39 @@
 use crate::builder::DataLayoutBuilder;
+use crate::schema::SymbolicVar;
 
 /// Repersents a variable. an unsigned integer.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct VarId(pub u32);
 
 /// In-memory representation of a program's fixed-sized storage variables.
+///
+/// With the `serde` feature, it's represented as `{ vars: [[offset, length], ...], schema: [...] }`.
+/// Deserializing checks that the layout could have been built by a `DataLayoutBuilder`.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawDataLayout")
+)]
 pub struct DataLayout {
     pub(crate) vars: Vec<(u32, u32)>,
+
+    pub(crate) schema: Vec<SymbolicVar>,
 }
 
 impl DataLayout {
     /// For tests that don't care about the `DataLayout`
     pub fn empty() -> Self {
-        Self { vars: Vec::new() }
+        Self {
+            vars: Vec::new(),
+            schema: Vec::new(),
+        }
     }
 
     /// Returns varialbe's layout. i.e: `(offset, length)`
@@ -43,6 +62,51 @@ impl DataLayout {
         self.vars.len()
     }
 
+    /// Returns the layout's typed schema (see `SymbolicVar`).
+    /// Returns `None` when the layout has been built without one.
+    pub fn schema(&self) -> Option<&[SymbolicVar]> {
+        if self.schema.is_empty() {
+            None
+        } else {
+            Some(&self.schema)
+        }
+    }
+
+    /// Returns the schema field spanning variable `var_id` (if there is any).
+    pub fn symbolic_var(&self, var_id: VarId) -> Option<&SymbolicVar> {
+        self.schema
+            .iter()
+            .find(|var| var_id.0 >= var.id.0 && var_id.0 < var.id.0 + var.count)
+    }
+
+    /// Returns whether `new` can replace `self` as an App's storage layout.
+    ///
+    /// An upgrade must not relocate existing variables, so `new` may only append variables:
+    /// * Each variable of `self` has the same `(offset, length)` under `new`.
+    /// * When both layouts have a schema, each field of `self` has a field under `new`
+    ///   with the same `VarId`, `VarType` and count (renaming a field is allowed).
+    pub fn is_upgradable_to(&self, new: &DataLayout) -> bool {
+        if new.len() < self.len() {
+            return false;
+        }
+
+        let same_vars = self.iter().zip(new.iter()).all(|(old, new)| old == new);
+
+        if !same_vars {
+            return false;
+        }
+
+        if self.schema.is_empty() || new.schema.is_empty() {
+            return true;
+        }
+
+        self.schema.iter().all(|old| {
+            new.schema
+                .iter()
+                .any(|new| new.id == old.id && new.ty == old.ty && new.count == old.count)
+        })
+    }
+
     /// Returns the variable index as `usize`.
     ///
     /// # Panics
@@ -76,6 +140,58 @@ impl From<Vec<u32>> for DataLayout {
     }
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawDataLayout {
+    vars: Vec<(u32, u32)>,
+
+    schema: Vec<SymbolicVar>,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<RawDataLayout> for DataLayout {
+    type Error = String;
+
+    /// The variables must be laid out one after the other (starting at offset `0`),
+    /// and so do the schema fields (starting at `VarId(0)`), without spanning missing variables.
+    fn try_from(raw: RawDataLayout) -> Result<Self, Self::Error> {
+        let mut next_offset: u32 = 0;
+
+        for (i, &(offset, len)) in raw.vars.iter().enumerate() {
+            if offset != next_offset {
+                return Err(format!(
+                    "variable #{} should start at offset {} (got {})",
+                    i, next_offset, offset
+                ));
+            }
+
+            next_offset = offset
+                .checked_add(len)
+                .ok_or_else(|| format!("variable #{} overflows the storage", i))?;
+        }
+
+        let mut next_id: u32 = 0;
+
+        for var in raw.schema.iter() {
+            if var.id.0 != next_id || var.count == 0 {
+                return Err(format!("schema field `{}` is misplaced", var.name));
+            }
+
+            next_id = next_id
+                .checked_add(var.count)
+                .filter(|&end| end as usize <= raw.vars.len())
+                .ok_or_else(|| format!("schema field `{}` spans missing variables", var.name))?;
+        }
+
+        let layout = DataLayout {
+            vars: raw.vars,
+            schema: raw.schema,
+        };
+
+        Ok(layout)
+    }
+}
+
 pub struct DataLayoutIter<'iter> {
     cur: usize,
 
@@ -103,6 +219,8 @@ impl<'iter> std::iter::Iterator for DataLayoutIter<'iter> {
 mod tests {
     use super::*;
 
+    use crate::schema::VarType;
+
     #[test]
     fn data_layout_new() {
         let mut builder = DataLayoutBuilder::with_capacity(2);
@@ -141,6 +259,78 @@ mod tests {
         assert_eq!(layout.get_var(VarId(3)), (60, 40));
     }
 
+    #[test]
+    fn data_layout_with_schema() {
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 20, 20, 1]);
+
+        builder.add_symbolic_var("balance", VarType::Amount, 1);
+        builder.add_symbolic_var("owners", VarType::Address, 2);
+        builder.add_symbolic_var("active", VarType::Bool, 1);
+
+        let layout = builder.build();
+        let schema = layout.schema().unwrap();
+
+        assert_eq!(schema.len(), 3);
+        assert_eq!(schema[1].id, VarId(1));
+        assert_eq!(schema[2].id, VarId(3));
+
+        assert_eq!(layout.symbolic_var(VarId(2)).unwrap().name, "owners");
+        assert_eq!(layout.symbolic_var(VarId(3)).unwrap().ty, VarType::Bool);
+        assert_eq!(layout.symbolic_var(VarId(4)), None);
+    }
+
+    #[test]
+    fn data_layout_without_schema() {
+        let layout: DataLayout = vec![10, 20].into();
+
+        assert_eq!(layout.schema(), None);
+        assert_eq!(layout.symbolic_var(VarId(0)), None);
+    }
+
+    #[test]
+    fn data_layout_upgrade_appending_vars() {
+        let old: DataLayout = vec![10, 20].into();
+        let new: DataLayout = vec![10, 20, 4].into();
+
+        assert!(old.is_upgradable_to(&old));
+        assert!(old.is_upgradable_to(&new));
+        assert!(!new.is_upgradable_to(&old));
+    }
+
+    #[test]
+    fn data_layout_upgrade_resizing_var() {
+        let old: DataLayout = vec![10, 20].into();
+        let new: DataLayout = vec![10, 30].into();
+
+        assert!(!old.is_upgradable_to(&new));
+    }
+
+    #[test]
+    fn data_layout_upgrade_with_schema() {
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 1]);
+        builder.add_symbolic_var("balance", VarType::Amount, 1);
+        builder.add_symbolic_var("active", VarType::Bool, 1);
+        let old = builder.build();
+
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 1, 4]);
+        builder.add_symbolic_var("funds", VarType::Amount, 1);
+        builder.add_symbolic_var("active", VarType::Bool, 1);
+        builder.add_symbolic_var("counter", VarType::U32, 1);
+        let renamed = builder.build();
+
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 1]);
+        builder.add_symbolic_var("balance", VarType::U64, 1);
+        builder.add_symbolic_var("active", VarType::U8, 1);
+        let retyped = builder.build();
+
+        assert!(old.is_upgradable_to(&renamed));
+        assert!(!old.is_upgradable_to(&retyped));
+    }
+
     #[test]
     fn data_layout_iter() {
         let mut builder = DataLayoutBuilder::with_capacity(2);
@@ -162,4 +352,63 @@ mod tests {
         assert_eq!(third, None);
         assert_eq!(fourth, None);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn data_layout_serde() {
+        use serde_json::json;
+
+        let mut builder = DataLayoutBuilder::new();
+        builder.extend_from_slice(&[8, 20]);
+        builder.add_symbolic_var("balance", VarType::Amount, 1);
+        builder.add_symbolic_var("owner", VarType::Address, 1);
+
+        let layout = builder.build();
+
+        let json = serde_json::to_value(&layout).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "vars": [[0, 8], [8, 20]],
+                "schema": [
+                    { "id": 0, "name": "balance", "ty": "Amount", "count": 1 },
+                    { "id": 1, "name": "owner", "ty": "Address", "count": 1 }
+                ]
+            })
+        );
+
+        let decoded: DataLayout = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, layout);
+
+        let invalid = json!({
+            "vars": [[0, 8]],
+            "schema": [{ "id": 0, "name": "balance", "ty": "String", "count": 1 }]
+        });
+        assert!(serde_json::from_value::<DataLayout>(invalid).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn data_layout_serde_invalid() {
+        use serde_json::json;
+
+        let invalid_layouts = vec![
+            // overlapping variables
+            json!({ "vars": [[0, 8], [4, 20]], "schema": [] }),
+            // a field spanning a missing variable
+            json!({
+                "vars": [[0, 8]],
+                "schema": [{ "id": 0, "name": "owners", "ty": "Address", "count": 2 }]
+            }),
+            // a field not starting right after the previous one
+            json!({
+                "vars": [[0, 8], [8, 20]],
+                "schema": [{ "id": 1, "name": "owner", "ty": "Address", "count": 1 }]
+            }),
+        ];
+
+        for json in invalid_layouts {
+            assert!(serde_json::from_value::<DataLayout>(json).is_err());
+        }
+    }
 }
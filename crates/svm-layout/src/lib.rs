@@ -7,6 +7,8 @@
 
 mod builder;
 mod layout;
+mod schema;
 
 pub use builder::DataLayoutBuilder;
 pub use layout::{DataLayout, VarId};
+pub use schema::{SymbolicVar, VarType};
@@ -0,0 +1,186 @@
+use crate::layout::VarId;
+
+/// The symbolic type of a storage variable.
+///
+/// Mirrors the field types supported by the `#[storage]` proc-macro of `svm-sdk`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VarType {
+    /// `bool`
+    Bool,
+
+    /// `i8`
+    I8,
+
+    /// `u8`
+    U8,
+
+    /// `i16`
+    I16,
+
+    /// `u16`
+    U16,
+
+    /// `i32`
+    I32,
+
+    /// `u32`
+    U32,
+
+    /// `i64`
+    I64,
+
+    /// `u64`
+    U64,
+
+    /// `U128`
+    U128,
+
+    /// `U256`
+    U256,
+
+    /// `Amount`
+    Amount,
+
+    /// `Address`
+    Address,
+}
+
+impl VarType {
+    /// Parses a type name (as written in a `#[storage]` struct) into a `VarType`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let ty = match name {
+            "bool" => VarType::Bool,
+            "i8" => VarType::I8,
+            "u8" => VarType::U8,
+            "i16" => VarType::I16,
+            "u16" => VarType::U16,
+            "i32" => VarType::I32,
+            "u32" => VarType::U32,
+            "i64" => VarType::I64,
+            "u64" => VarType::U64,
+            "U128" => VarType::U128,
+            "U256" => VarType::U256,
+            "Amount" => VarType::Amount,
+            "Address" => VarType::Address,
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+
+    /// Returns the type name (as written in a `#[storage]` struct).
+    pub fn name(&self) -> &'static str {
+        match self {
+            VarType::Bool => "bool",
+            VarType::I8 => "i8",
+            VarType::U8 => "u8",
+            VarType::I16 => "i16",
+            VarType::U16 => "u16",
+            VarType::I32 => "i32",
+            VarType::U32 => "u32",
+            VarType::I64 => "i64",
+            VarType::U64 => "u64",
+            VarType::U128 => "U128",
+            VarType::U256 => "U256",
+            VarType::Amount => "Amount",
+            VarType::Address => "Address",
+        }
+    }
+
+    /// Returns the byte-length of a variable of the type (as read and written by the `svm-sdk` storage).
+    pub fn byte_size(&self) -> u32 {
+        match self {
+            VarType::Bool | VarType::I8 | VarType::U8 => 1,
+            VarType::I16 | VarType::U16 => 2,
+            VarType::I32 | VarType::U32 => 4,
+            VarType::I64 | VarType::U64 | VarType::Amount => 8,
+            VarType::U128 => 16,
+            VarType::Address => 20,
+            VarType::U256 => 32,
+        }
+    }
+
+    /// Returns whether the type is a signed integer.
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            VarType::I8 | VarType::I16 | VarType::I32 | VarType::I64
+        )
+    }
+}
+
+/// With the `serde` feature, a `VarType` is represented by its type name (see `VarType::name`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for VarType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for VarType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+        VarType::from_name(&name).ok_or_else(|| {
+            let msg = format!("unknown variable type `{}`", name);
+
+            <D::Error as serde::de::Error>::custom(msg)
+        })
+    }
+}
+
+/// A named and typed storage field.
+///
+/// A primitive field maps to a single variable (`count = 1`).
+/// An array field of `N` items maps to `N` consecutive variables starting at `id`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolicVar {
+    /// The `VarId` of the field's first variable
+    pub id: VarId,
+
+    /// Field's name
+    pub name: String,
+
+    /// Field's type (in case of an array, the type of its items)
+    pub ty: VarType,
+
+    /// Number of variables the field spans
+    pub count: u32,
+}
+
+impl SymbolicVar {
+    /// Returns whether the field is an array.
+    pub fn is_array(&self) -> bool {
+        self.count > 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn var_type_from_name() {
+        let types = [
+            VarType::Bool,
+            VarType::I8,
+            VarType::U8,
+            VarType::I16,
+            VarType::U16,
+            VarType::I32,
+            VarType::U32,
+            VarType::I64,
+            VarType::U64,
+            VarType::Amount,
+            VarType::Address,
+        ];
+
+        for ty in types.iter() {
+            assert_eq!(VarType::from_name(ty.name()), Some(*ty));
+        }
+
+        assert_eq!(VarType::from_name("String"), None);
+    }
+}
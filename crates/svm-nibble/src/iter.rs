@@ -55,13 +55,56 @@ impl<'a> NibbleIter<'a> {
     }
 
     /// Reads `count` bytes (i.e `2 * count` nibbles).
+    ///
+    /// In case there are less than `count` bytes left, returns only the complete ones read.
+    /// (So callers should check the returned length).
+    ///
+    /// When the iterator is byte aligned the bytes are copied directly from the underlying data.
     pub fn read_bytes(&mut self, count: usize) -> Vec<u8> {
+        if self.is_byte_aligned() {
+            let count = count.min(self.remaining_bytes());
+
+            return self.advance(count).to_vec();
+        }
+
         // `count` bytes <=> `2 * count` nibbles
         let nibbles = self.take(2 * count).collect::<Vec<Nibble>>();
 
-        let (bytes, rem) = concat_nibbles(&nibbles[..]);
+        let (bytes, _rem) = concat_nibbles(&nibbles[..]);
+
+        bytes
+    }
+
+    /// Reads `count` bytes without copying them, by borrowing them from the underlying data.
+    ///
+    /// Returns `None` (without reading anything) when the iterator isn't byte aligned
+    /// or when there are less than `count` bytes left.
+    /// In the former case, `read_bytes` should be used instead.
+    pub fn read_slice(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.is_byte_aligned() == false || count > self.remaining_bytes() {
+            return None;
+        }
+
+        Some(self.advance(count))
+    }
+
+    /// Returns the number of whole bytes left to read.
+    #[inline]
+    pub fn remaining_bytes(&self) -> usize {
+        // a pending right nibble (of `last_byte`) doesn't complete a byte by itself
+        self.length - self.cursor
+    }
+
+    /// Skips `count` bytes of a byte aligned iterator and returns them.
+    fn advance(&mut self, count: usize) -> &'a [u8] {
+        debug_assert!(self.is_byte_aligned());
+        debug_assert!(self.last_byte.is_none());
+
+        let data: &'a [u8] = self.data;
+        let bytes = &data[self.cursor..self.cursor + count];
 
-        debug_assert!(rem.is_none());
+        self.cursor += count;
+        self.nibbles_read += 2 * count;
 
         bytes
     }
@@ -120,6 +163,38 @@ impl<'a> Iterator for NibbleIter<'a> {
     }
 }
 
+/// Reads the remaining bytes of the nibbles stream.
+///
+/// In case the number of nibbles left is odd, the last nibble isn't returned.
+#[cfg(feature = "std")]
+impl<'a> std::io::Read for NibbleIter<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.is_byte_aligned() {
+            let count = buf.len().min(self.remaining_bytes());
+            let bytes = self.advance(count);
+
+            buf[..count].copy_from_slice(bytes);
+
+            return Ok(count);
+        }
+
+        let mut count = 0;
+
+        while count < buf.len() {
+            let bytes = self.read_bytes(1);
+
+            if bytes.is_empty() {
+                break;
+            }
+
+            buf[count] = bytes[0];
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +253,69 @@ mod tests {
         assert_eq!(4, iter.nibbles_read);
         assert!(iter.is_byte_aligned());
     }
+
+    #[test]
+    fn nibble_iter_read_bytes_aligned() {
+        let vec = vec![0x10, 0x20, 0x30];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        assert_eq!(iter.read_bytes(2), vec![0x10, 0x20]);
+        assert_eq!(iter.remaining_bytes(), 1);
+
+        assert_eq!(iter.read_bytes(2), vec![0x30]);
+        assert_eq!(iter.remaining_bytes(), 0);
+        assert_eq!(None, maybe_read_nibble(&mut iter));
+    }
+
+    #[test]
+    fn nibble_iter_read_bytes_unaligned() {
+        let vec = vec![0x12, 0x34, 0x56];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        assert_eq!(0x1, read_nibble(&mut iter));
+        assert_eq!(iter.remaining_bytes(), 2);
+
+        assert_eq!(iter.read_bytes(2), vec![0x23, 0x45]);
+        assert_eq!(Some(0x6), maybe_read_nibble(&mut iter));
+
+        assert_eq!(iter.read_bytes(1), vec![]);
+        assert_eq!(None, maybe_read_nibble(&mut iter));
+    }
+
+    #[test]
+    fn nibble_iter_read_slice() {
+        let vec = vec![0x12, 0x34, 0x56];
+        let mut iter = NibbleIter::new(&vec[..]);
+
+        assert_eq!(iter.read_slice(1), Some(&vec[0..1]));
+        assert_eq!(iter.read_slice(3), None);
+
+        assert_eq!(0x3, read_nibble(&mut iter));
+        assert_eq!(iter.read_slice(1), None);
+        assert_eq!(0x4, read_nibble(&mut iter));
+
+        assert_eq!(iter.read_slice(1), Some(&vec[2..3]));
+        assert_eq!(iter.read_slice(0), Some(&[][..]));
+        assert!(iter.is_byte_aligned());
+        assert_eq!(None, maybe_read_nibble(&mut iter));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn nibble_iter_io_read() {
+        use std::io::Read;
+
+        let vec = vec![0x12, 0x34, 0x56];
+
+        let mut iter = NibbleIter::new(&vec[..]);
+        let mut buf = Vec::new();
+        iter.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, vec![0x12, 0x34, 0x56]);
+
+        let mut iter = NibbleIter::new(&vec[..]);
+        iter.next();
+        let mut buf = Vec::new();
+        iter.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, vec![0x23, 0x45]);
+    }
 }
@@ -10,14 +10,25 @@
 //! * `svm-codec`
 //! * `svm-abi`
 //! * writing SVM apps.
+//!
+//! Reading byte-aligned data is zero-copy (see `NibbleIter::read_slice`),
+//! and a `NibbleWriter` can output into any `ByteSink` (a `Vec<u8>`, a caller-provided `&mut [u8]`
+//! and, when the `std` feature is on, a `std::io::Write`).
+
+#[cfg(feature = "std")]
+extern crate std;
 
 mod concat;
 mod iter;
 mod macros;
 mod nibble;
+mod sink;
 mod writer;
 
 pub use concat::concat_nibbles;
 pub use iter::NibbleIter;
 pub use nibble::Nibble;
+#[cfg(feature = "std")]
+pub use sink::IoSink;
+pub use sink::{BufferTooSmall, ByteSink, SliceSink};
 pub use writer::NibbleWriter;
@@ -0,0 +1,125 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use core::convert::Infallible;
+use core::fmt;
+
+/// A destination for the bytes completed by a `NibbleWriter`.
+pub trait ByteSink {
+    /// The error returned when the bytes can't be written.
+    type Error;
+
+    /// Appends `bytes` to the sink.
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl ByteSink for Vec<u8> {
+    type Error = Infallible;
+
+    #[inline]
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<(), Infallible> {
+        self.extend_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+/// Returned by a `SliceSink` when its buffer has no room left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The buffer size (in bytes).
+    pub capacity: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the output buffer is too small (capacity = {} bytes)",
+            self.capacity
+        )
+    }
+}
+
+/// Writes bytes into a caller-provided buffer.
+pub struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    written: usize,
+}
+
+impl<'a> SliceSink<'a> {
+    /// Creates a new sink writing into `buf` (starting at its first byte).
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, written: 0 }
+    }
+
+    /// The number of bytes written so far.
+    #[inline]
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Returns the written part of the buffer.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.written]
+    }
+}
+
+impl<'a> ByteSink for SliceSink<'a> {
+    type Error = BufferTooSmall;
+
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<(), BufferTooSmall> {
+        let end = self.written + bytes.len();
+
+        if end > self.buf.len() {
+            return Err(BufferTooSmall {
+                capacity: self.buf.len(),
+            });
+        }
+
+        self.buf[self.written..end].copy_from_slice(bytes);
+        self.written = end;
+
+        Ok(())
+    }
+}
+
+/// Writes bytes into a `std::io::Write`.
+///
+/// Each call to `put_bytes` issues a `write_all`, so wrapping unbuffered writers
+/// with a `std::io::BufWriter` is recommended.
+#[cfg(feature = "std")]
+pub struct IoSink<W>(pub W);
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> ByteSink for IoSink<W> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<(), std::io::Error> {
+        self.0.write_all(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slice_sink_put_bytes() {
+        let mut buf = [0; 4];
+        let mut sink = SliceSink::new(&mut buf);
+
+        assert_eq!(sink.put_bytes(&[0x10, 0x20]), Ok(()));
+        assert_eq!(sink.put_bytes(&[0x30]), Ok(()));
+        assert_eq!(sink.written(), 3);
+
+        assert_eq!(
+            sink.put_bytes(&[0x40, 0x50]),
+            Err(BufferTooSmall { capacity: 4 })
+        );
+
+        assert_eq!(sink.into_written(), &[0x10, 0x20, 0x30]);
+    }
+}
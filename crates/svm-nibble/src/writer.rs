@@ -2,25 +2,64 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
-use crate::{concat_nibbles, nib, Nibble};
+use crate::{nib, ByteSink, Nibble};
 
 /// Nibbles Writer.
-pub struct NibbleWriter {
-    nibbles: Vec<Nibble>,
+///
+/// Each completed byte (i.e two nibbles) is handed to the underlying `ByteSink`
+/// as soon as it's available, so no intermediate nibbles buffer is kept.
+///
+/// By default the bytes are appended to a `Vec<u8>` (see `NibbleWriter::new` and `into_bytes`).
+/// Other sinks (a caller-provided `&mut [u8]`, a `std::io::Write`) are used via `with_sink`.
+pub struct NibbleWriter<S: ByteSink = Vec<u8>> {
+    sink: S,
+    pending: Option<Nibble>,
+    len: usize,
+    error: Option<S::Error>,
 }
 
 impl NibbleWriter {
     /// Creates a new writer.
     pub fn new() -> Self {
+        Self::with_sink(Vec::new())
+    }
+
+    /// Closes the `NibbleWriter` and returns the underlying streams as `Vec<u8>`.
+    /// In case the number of nibbles is odd, pads a zero-nibble. (see also: `is_byte_aligned`).
+    #[must_use]
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self.finish() {
+            Ok(bytes) => bytes,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<S: ByteSink> NibbleWriter<S> {
+    /// Creates a new writer outputting its bytes into `sink`.
+    pub fn with_sink(sink: S) -> Self {
         Self {
-            nibbles: Vec::new(),
+            sink,
+            pending: None,
+            len: 0,
+            error: None,
         }
     }
 
     /// Pushes a new nibble to the end of stream
     #[inline]
     pub fn push(&mut self, nib: Nibble) {
-        self.nibbles.push(nib);
+        self.len += 1;
+
+        match self.pending.take() {
+            None => self.pending = Some(nib),
+            Some(lnib) => {
+                let byte = lnib.inner() << 4 | nib.inner();
+
+                self.put(&[byte]);
+            }
+        }
     }
 
     /// Appends `nibbles` to the underlying stream.
@@ -33,44 +72,57 @@ impl NibbleWriter {
     /// Appends `bytes` to the underlying stream.
     /// (each byte consists of 2 nibbles).
     pub fn write_bytes(&mut self, bytes: &[u8]) {
-        for byte in bytes.iter() {
-            self.write_byte(*byte);
+        if self.is_byte_aligned() {
+            self.len += 2 * bytes.len();
+            self.put(bytes);
+
+            return;
+        }
+
+        // each output byte is made of the pending nibble and the left nibble of the next input byte.
+        // the output is flushed to the sink in chunks.
+        let mut buf = [0u8; 64];
+
+        for chunk in bytes.chunks(buf.len()) {
+            for (i, byte) in chunk.iter().enumerate() {
+                let lnib = self.pending.take().unwrap();
+
+                buf[i] = lnib.inner() << 4 | (byte & 0xF0) >> 4;
+                self.pending = Some(nib!(byte & 0x0F));
+            }
+
+            self.len += 2 * chunk.len();
+            self.put(&buf[..chunk.len()]);
         }
     }
 
     /// Appends a `byte` to the underlying stream.
     #[inline]
     pub fn write_byte(&mut self, byte: u8) {
-        let lnib = nib!((byte & 0xF0) >> 4);
-        let rnib = nib!(byte & 0x0F);
-
-        self.write(&[lnib, rnib]);
+        self.write_bytes(&[byte]);
     }
 
-    /// Closes the `NibbleWriter` and returns the underlying streams as `Vec<u8>`.
+    /// Closes the `NibbleWriter` and returns the underlying sink.
     /// In case the number of nibbles is odd, pads a zero-nibble. (see also: `is_byte_aligned`).
-    #[must_use]
-    #[inline]
-    pub fn into_bytes(mut self) -> Vec<u8> {
-        // before calling `self.bytes()` we must make sure
-        // that its number of nibbles is even. If it's not, we pad it with one extra nibble.
-
+    ///
+    /// Returns the first error raised by the sink (if any).
+    pub fn finish(mut self) -> Result<S, S::Error> {
         if self.is_byte_aligned() == false {
             let padding = nib!(0);
-            self.write(&[padding]);
+            self.push(padding);
         }
 
-        let (bytes, rem) = concat_nibbles(&self.nibbles);
-        debug_assert!(rem.is_none());
-
-        bytes
+        match self.error {
+            None => Ok(self.sink),
+            Some(err) => Err(err),
+        }
     }
 
     /// The returns the number of nibbles written so far.
     #[must_use]
     #[inline]
     pub fn len(&self) -> usize {
-        self.nibbles.len()
+        self.len
     }
 
     /// Returns whether the number of written nibbles so far is even.
@@ -79,4 +131,91 @@ impl NibbleWriter {
     pub fn is_byte_aligned(&self) -> bool {
         self.len() % 2 == 0
     }
+
+    /// Outputs `bytes` into the sink.
+    /// Once the sink has failed, the next writes are discarded. The error is returned by `finish`.
+    fn put(&mut self, bytes: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = self.sink.put_bytes(bytes) {
+            self.error = Some(err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::{BufferTooSmall, NibbleIter, SliceSink};
+
+    #[test]
+    fn nibble_writer_aligned() {
+        let mut w = NibbleWriter::new();
+
+        w.write_byte(0x12);
+        w.write_bytes(&[0x34, 0x56]);
+        w.write(&[nib!(0x7), nib!(0x8)]);
+
+        assert_eq!(w.len(), 8);
+        assert_eq!(w.into_bytes(), vec![0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn nibble_writer_unaligned() {
+        let bytes = (0..200).map(|i| i as u8).collect::<Vec<u8>>();
+
+        let mut w = NibbleWriter::new();
+        w.push(nib!(0xA));
+        w.write_bytes(&bytes);
+        assert!(w.is_byte_aligned() == false);
+
+        let data = w.into_bytes();
+        assert_eq!(data.len(), 201);
+
+        let mut iter = NibbleIter::new(&data);
+        assert_eq!(iter.next(), Some(nib!(0xA)));
+        assert_eq!(iter.read_bytes(200), bytes);
+
+        // padding
+        assert_eq!(iter.next(), Some(nib!(0)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn nibble_writer_slice_sink() {
+        let mut buf = [0; 2];
+
+        let mut w = NibbleWriter::with_sink(SliceSink::new(&mut buf));
+        w.write(&[nib!(0x1), nib!(0x2), nib!(0x3)]);
+
+        let sink = w.finish().unwrap();
+        assert_eq!(sink.into_written(), &[0x12, 0x30]);
+
+        let mut buf = [0; 2];
+
+        let mut w = NibbleWriter::with_sink(SliceSink::new(&mut buf));
+        w.write_bytes(&[0x10, 0x20, 0x30]);
+        w.write_byte(0x40);
+
+        let err = w.finish().err().unwrap();
+        assert_eq!(err, BufferTooSmall { capacity: 2 });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn nibble_writer_io_sink() {
+        use crate::IoSink;
+
+        let mut w = NibbleWriter::with_sink(IoSink(std::io::Cursor::new(Vec::new())));
+        w.write_byte(0xAB);
+        w.push(nib!(0xC));
+
+        let IoSink(cursor) = w.finish().unwrap();
+        assert_eq!(cursor.into_inner(), vec![0xAB, 0xC0]);
+    }
 }
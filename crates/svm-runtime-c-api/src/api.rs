@@ -1,10 +1,9 @@
-use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::ffi::c_void;
 use std::io;
 use std::path::Path;
 use std::ptr::NonNull;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use log::{debug, error};
 
@@ -16,8 +15,8 @@ use svm_layout::DataLayout;
 use svm_runtime::env::default::DefaultSerializerTypes;
 use svm_runtime::{gas::DefaultGasEstimator, Context, ExternImport};
 
-use svm_storage::kv::{ExternKV, StatefulKV};
-use svm_types::{Address, State, WasmType};
+use svm_storage::kv::{CheckpointFn, DiscardFn, ExternKV, GetManyFn, SetManyFn, StatefulKV};
+use svm_types::{Address, HostCtx, State, WasmType};
 
 use crate::RuntimePtr;
 use crate::{helpers, raw_error, raw_io_error, raw_utf8_error, raw_validate_error, svm_result_t};
@@ -272,6 +271,9 @@ pub unsafe extern "C" fn svm_imports_alloc(imports: *mut *mut c_void, count: u32
 /// Builds a new `svm_import` (returned via `import` function parameter).
 /// New built `svm_import_t` is pushed into `imports`
 ///
+/// Since a `Runtime` may run transactions concurrently, `func` may be called with `host_env`
+/// from any thread (including concurrently). `host_env` should remain valid as long as `imports` is alive.
+///
 /// # Example
 ///
 /// ```rust
@@ -408,7 +410,7 @@ macro_rules! box_runtime {
 #[must_use]
 #[no_mangle]
 pub unsafe extern "C" fn svm_memory_state_kv_create(kv: *mut *mut c_void) -> svm_result_t {
-    let state_kv = svm_runtime::testing::memory_state_kv_init();
+    let state_kv = svm_runtime::memory_state_kv_init();
 
     *kv = svm_common::into_raw_mut(state_kv);
 
@@ -418,18 +420,23 @@ pub unsafe extern "C" fn svm_memory_state_kv_create(kv: *mut *mut c_void) -> svm
 /// Creates a new FFI key-value client.
 /// Returns a raw pointer to allocated kv-store via input parameter `kv`.
 ///
+/// The key-value callbacks are batched, and all the buffers passed to them are allocated by SVM.
+/// (see `svm_storage::kv::GetManyFn`, `SetManyFn`, `DiscardFn` and `CheckpointFn`).
+///
 /// # Example
 ///
 /// ```rust
 /// use svm_runtime_c_api::*;
 ///
-/// unsafe extern "C" fn get(key_ptr: *const u8, key_len: u32, value_ptr: *mut u8, value_len: *mut u32) {}
-/// unsafe extern "C" fn set(key_ptr: *const u8, key_len: u32, value_ptr: *const u8, value_len: u32) {}
-/// unsafe extern "C" fn discard() {}
-/// unsafe extern "C" fn checkpoint(state: *mut u8) {}
+/// use svm_ffi::svm_byte_array;
+///
+/// unsafe extern "C" fn get_many(keys: *const svm_byte_array, values: *mut svm_byte_array, nkeys: u32, error: *mut svm_byte_array) -> bool { true }
+/// unsafe extern "C" fn set_many(keys: *const svm_byte_array, values: *const svm_byte_array, nkeys: u32, error: *mut svm_byte_array) -> bool { true }
+/// unsafe extern "C" fn discard(error: *mut svm_byte_array) -> bool { true }
+/// unsafe extern "C" fn checkpoint(state: *mut svm_byte_array, error: *mut svm_byte_array) -> bool { true }
 ///
 /// let mut kv = std::ptr::null_mut();
-/// let res = unsafe { svm_ffi_state_kv_create(&mut kv, get, set, discard, checkpoint) };
+/// let res = unsafe { svm_ffi_state_kv_create(&mut kv, get_many, set_many, discard, checkpoint) };
 /// assert!(res.is_ok());
 /// ```
 ///
@@ -437,20 +444,20 @@ pub unsafe extern "C" fn svm_memory_state_kv_create(kv: *mut *mut c_void) -> svm
 #[no_mangle]
 pub unsafe extern "C" fn svm_ffi_state_kv_create(
     state_kv: *mut *mut c_void,
-    get_fn: unsafe extern "C" fn(*const u8, u32, *mut u8, *mut u32),
-    set_fn: unsafe extern "C" fn(*const u8, u32, *const u8, u32),
-    discard_fn: unsafe extern "C" fn(),
-    checkpoint_fn: unsafe extern "C" fn(*mut u8),
+    get_many_fn: GetManyFn,
+    set_many_fn: SetManyFn,
+    discard_fn: DiscardFn,
+    checkpoint_fn: CheckpointFn,
 ) -> svm_result_t {
     let ffi_kv = ExternKV {
-        get_fn,
-        set_fn,
+        get_many_fn,
+        set_many_fn,
         discard_fn,
         checkpoint_fn,
         head: None,
     };
 
-    let ffi_kv = Rc::new(RefCell::new(ffi_kv));
+    let ffi_kv: Arc<Mutex<dyn StatefulKV + Send>> = Arc::new(Mutex::new(ffi_kv));
 
     *state_kv = svm_common::into_raw_mut(ffi_kv);
 
@@ -475,7 +482,7 @@ pub unsafe extern "C" fn svm_ffi_state_kv_create(
 #[must_use]
 #[no_mangle]
 pub unsafe extern "C" fn svm_state_kv_destroy(kv: *mut c_void) -> svm_result_t {
-    let kv: &mut Rc<RefCell<dyn StatefulKV>> = svm_common::from_raw_mut(kv);
+    let kv: &mut Arc<Mutex<dyn StatefulKV + Send>> = svm_common::from_raw_mut(kv);
 
     let _ = Box::from_raw(kv as *mut _);
 
@@ -485,6 +492,8 @@ pub unsafe extern "C" fn svm_state_kv_destroy(kv: *mut c_void) -> svm_result_t {
 /// Creates a new SVM Runtime instance baced-by an in-memory KV.
 /// Returns it via the `runtime` parameter.
 ///
+/// The runtime keeps its own copy of `imports`, so they may be destroyed right afterwards.
+///
 /// # Example
 ///
 /// ```rust
@@ -514,9 +523,9 @@ pub unsafe extern "C" fn svm_memory_runtime_create(
 ) -> svm_result_t {
     debug!("`svm_memory_runtime_create` start");
 
-    let imports = helpers::cast_to_imports(imports);
+    let imports = helpers::cast_to_imports(imports).clone();
     let state_kv = svm_common::from_raw_mut(state_kv);
-    let mem_runtime = svm_runtime::testing::create_memory_runtime(state_kv, imports);
+    let mem_runtime = svm_runtime::create_memory_runtime(state_kv, imports);
 
     let res = box_runtime!(runtime, mem_runtime);
 
@@ -528,6 +537,8 @@ pub unsafe extern "C" fn svm_memory_runtime_create(
 /// Creates a new SVM Runtime instance.
 /// Returns it via the `runtime` parameter.
 ///
+/// The runtime keeps its own copy of `imports`, so they may be destroyed right afterwards.
+///
 /// # Example
 ///
 /// ```rust, no_run
@@ -562,7 +573,7 @@ pub unsafe extern "C" fn svm_runtime_create(
     }
 
     let kv_path = kv_path.unwrap();
-    let imports = helpers::cast_to_imports(imports);
+    let imports = helpers::cast_to_imports(imports).clone();
 
     let rocksdb_runtime = svm_runtime::create_rocksdb_runtime::<
         &Path,
@@ -660,6 +671,8 @@ pub unsafe extern "C" fn svm_deploy_template(
 
 /// Spawns a new App.
 ///
+/// The `value` is the amount of coins transferred along the transaction (see `HostCtx`).
+///
 /// # Example
 ///
 /// ```rust, no_run
@@ -687,6 +700,7 @@ pub unsafe extern "C" fn svm_deploy_template(
 /// let mut init_state = svm_byte_array::default();
 /// let creator = Address::of("@creator").into();
 /// let app_bytes = svm_byte_array::default();
+/// let value = 0;
 /// let gas_metering = false;
 /// let gas_limit = 0;
 ///
@@ -696,6 +710,7 @@ pub unsafe extern "C" fn svm_deploy_template(
 ///     runtime,
 ///     app_bytes,
 ///     creator,
+///     value,
 ///     gas_metering,
 ///     gas_limit,
 ///     &mut error)
@@ -709,6 +724,7 @@ pub unsafe extern "C" fn svm_spawn_app(
     runtime: *mut c_void,
     bytes: svm_byte_array,
     creator: svm_byte_array,
+    value: u64,
     gas_metering: bool,
     gas_limit: u64,
     error: *mut svm_byte_array,
@@ -725,7 +741,10 @@ pub unsafe extern "C" fn svm_spawn_app(
 
     let gas_limit = maybe_gas!(gas_metering, gas_limit);
 
-    let rust_receipt = runtime.spawn_app(bytes.into(), &creator.unwrap().into(), gas_limit);
+    let host_ctx = HostCtx::with_value(value);
+
+    let rust_receipt =
+        runtime.spawn_app(bytes.into(), &creator.unwrap().into(), &host_ctx, gas_limit);
 
     let mut receipt_bytes = encode_app_receipt(&rust_receipt);
 
@@ -741,6 +760,8 @@ pub unsafe extern "C" fn svm_spawn_app(
 /// Triggers an app-transaction execution of an already deployed app.
 /// Returns the receipt of the execution via the `receipt` parameter.
 ///
+/// The `value` is the amount of coins transferred along the transaction (see `HostCtx`).
+///
 /// # Example
 ///
 /// ```rust, no_run
@@ -769,6 +790,7 @@ pub unsafe extern "C" fn svm_spawn_app(
 /// let mut exec_receipt = svm_byte_array::default();
 /// let bytes = svm_byte_array::default();
 /// let state = State::empty().into();
+/// let value = 0;
 /// let gas_metering = false;
 /// let gas_limit = 0;
 ///
@@ -778,6 +800,7 @@ pub unsafe extern "C" fn svm_spawn_app(
 ///     runtime,
 ///     bytes,
 ///     state,
+///     value,
 ///     gas_metering,
 ///     gas_limit,
 ///     &mut error)
@@ -791,6 +814,7 @@ pub unsafe extern "C" fn svm_exec_app(
     runtime: *mut c_void,
     bytes: svm_byte_array,
     state: svm_byte_array,
+    value: u64,
     gas_metering: bool,
     gas_limit: u64,
     error: *mut svm_byte_array,
@@ -807,7 +831,9 @@ pub unsafe extern "C" fn svm_exec_app(
 
     let gas_limit = maybe_gas!(gas_metering, gas_limit);
 
-    let rust_receipt = runtime.exec_app(bytes.into(), &state.unwrap(), gas_limit);
+    let host_ctx = HostCtx::with_value(value);
+
+    let rust_receipt = runtime.exec_app(bytes.into(), &host_ctx, &state.unwrap(), gas_limit);
     let mut receipt_bytes = encode_exec_receipt(&rust_receipt);
 
     // returning encoded `ExecReceipt` as `svm_byte_array`.
@@ -184,6 +184,7 @@ fn svm_runtime_exec_app() {
 
 unsafe fn test_svm_runtime() {
     let version: u32 = 0;
+    let value = 0;
     let gas_metering = false;
     let gas_limit = 0;
 
@@ -220,7 +221,9 @@ unsafe fn test_svm_runtime() {
     assert!(res.is_ok());
 
     // extract the `template-address` out of theh receipt
-    let receipt = raw::decode_receipt(template_receipt.clone().into()).into_deploy_template();
+    let receipt = raw::decode_receipt(template_receipt.clone().into())
+        .unwrap()
+        .into_deploy_template();
     let template_addr: &Address = receipt.get_template_addr().inner();
     let template_addr: svm_byte_array = template_addr.into();
 
@@ -244,6 +247,7 @@ unsafe fn test_svm_runtime() {
         runtime,
         app_bytes,
         spawner,
+        value,
         gas_metering,
         gas_limit,
         &mut error,
@@ -251,7 +255,9 @@ unsafe fn test_svm_runtime() {
     assert!(res.is_ok());
 
     // extracts the spawned-app `Address` and initial `State`.
-    let receipt = raw::decode_receipt(spawn_receipt.clone().into()).into_spawn_app();
+    let receipt = raw::decode_receipt(spawn_receipt.clone().into())
+        .unwrap()
+        .into_spawn_app();
     assert_eq!(receipt.success, true);
     let app_addr = receipt.get_app_addr().inner();
     let app_addr: svm_byte_array = app_addr.into();
@@ -285,13 +291,16 @@ unsafe fn test_svm_runtime() {
         runtime,
         tx_bytes,
         init_state.clone(),
+        value,
         gas_metering,
         gas_limit,
         &mut error,
     );
     assert!(res.is_ok());
 
-    let receipt = raw::decode_receipt(exec_receipt.clone().into()).into_exec_app();
+    let receipt = raw::decode_receipt(exec_receipt.clone().into())
+        .unwrap()
+        .into_exec_app();
     assert_eq!(receipt.success, true);
 
     let bytes = receipt.get_returndata();
@@ -1,21 +1,31 @@
-use std::cell::{Ref, RefCell, RefMut};
 use std::ffi::c_void;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
 
 use log::debug;
 use wasmer::Memory;
 
-use svm_storage::app::AppStorage;
-use svm_types::{gas::MaybeGas, receipt::Log};
+use svm_storage::{app::AppStorage, kv::ExternKVError};
+use svm_types::{
+    gas::{MaybeGas, OOGError},
+    receipt::Log,
+    HostCtx,
+};
 
 /// `Context` is a container for the accessible data by `wasmer` instances.
 ///
 /// * `storage`      - Instance's `AppStorage`.
 /// * `gas_metering` - Whether gas metering is enabled.
-
+///
+/// Each execution creates its own `Context`, which is shared only by the host functions
+/// imported by that execution's instance. It's `Send` so that executions can run on any thread.
+///
+/// Note that `borrow` and `borrow_mut` both lock the `Context`,
+/// so a borrow must be released before borrowing again.
+/// Since a `Context` is used only by its own execution's thread, a nested borrow
+/// can't wait for another thread. Thus it panics (instead of deadlocking).
 #[derive(Clone)]
 pub struct Context {
-    inner: Rc<RefCell<ContextInner>>,
+    inner: Arc<Mutex<ContextInner>>,
 }
 
 impl Context {
@@ -23,7 +33,7 @@ impl Context {
         let inner = ContextInner::new(gas_limit, storage);
 
         Self {
-            inner: Rc::new(RefCell::new(inner)),
+            inner: Arc::new(Mutex::new(inner)),
         }
     }
 
@@ -35,14 +45,32 @@ impl Context {
         ctx
     }
 
+    /// # Panics
+    ///
+    /// Panics when the `Context` is already borrowed.
     #[inline]
-    pub fn borrow(&self) -> Ref<ContextInner> {
-        self.inner.borrow()
+    pub fn borrow(&self) -> MutexGuard<ContextInner> {
+        self.lock()
     }
 
+    /// # Panics
+    ///
+    /// Panics when the `Context` is already borrowed.
     #[inline]
-    pub fn borrow_mut(&self) -> RefMut<ContextInner> {
-        self.inner.borrow_mut()
+    pub fn borrow_mut(&self) -> MutexGuard<ContextInner> {
+        self.lock()
+    }
+
+    fn lock(&self) -> MutexGuard<ContextInner> {
+        match self.inner.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::WouldBlock) => {
+                panic!("`Context` is already borrowed (the previous borrow must be released first)")
+            }
+            Err(TryLockError::Poisoned(..)) => {
+                panic!("`Context` is poisoned (a previous borrower has panicked)")
+            }
+        }
     }
 }
 
@@ -53,6 +81,12 @@ pub struct ContextInner {
     /// Whether gas metering is enabled or not
     pub gas_metering: bool,
 
+    /// Gas charged so far (relevant only when `gas_metering = true`).
+    ///
+    /// The vmcalls charge gas while running, and the gas metered for the wasm code
+    /// is added once the execution is over.
+    pub gas_used: u64,
+
     /// An accessor to the App's storage
     pub storage: AppStorage,
 
@@ -62,6 +96,15 @@ pub struct ContextInner {
     /// Pointer to `returndata`. Tuple stores `(offset, len)`.
     pub returndata: Option<(usize, usize)>,
 
+    /// App's revert data (set by the `svm_revert` vmcall).
+    pub revert_data: Option<Vec<u8>>,
+
+    /// The transaction's environment (read by the `svm_value` vmcall).
+    pub host_ctx: HostCtx,
+
+    /// The first error of the underlying key-value store (raised while a vmcall accessed the storage).
+    pub storage_error: Option<ExternKVError>,
+
     /// Instance's memory
     memory: Option<Memory>,
 
@@ -79,10 +122,14 @@ impl ContextInner {
             storage,
             gas_metering,
             gas_limit,
+            gas_used: 0,
             logs,
             memory: None,
             calldata: None,
             returndata: None,
+            revert_data: None,
+            host_ctx: HostCtx::default(),
+            storage_error: None,
         }
     }
 
@@ -112,7 +159,54 @@ impl ContextInner {
         self.memory.as_ref().unwrap()
     }
 
+    /// Charges `gas` on behalf of a vmcall (or of the executed wasm code).
+    ///
+    /// Returns `Err(OOGError)` when the charged gas exceeds the `gas_limit`.
+    /// In that case the vmcall should trap without doing its work, and the execution will end with an `OOG` receipt.
+    pub fn use_gas(&mut self, gas: u64) -> Result<(), OOGError> {
+        if self.gas_metering == false {
+            return Ok(());
+        }
+
+        self.gas_used = self.gas_used.saturating_add(gas);
+
+        if self.is_oog() {
+            Err(OOGError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether more gas than the `gas_limit` has been charged.
+    pub fn is_oog(&self) -> bool {
+        self.gas_metering && self.gas_used > self.gas_limit
+    }
+
     pub fn take_logs(&mut self) -> Vec<Log> {
         std::mem::take(&mut self.logs)
     }
+
+    /// Records the App's revert data. When called multiple times the first revert wins.
+    pub fn set_revert_data(&mut self, data: Vec<u8>) {
+        if self.revert_data.is_none() {
+            self.revert_data = Some(data);
+        }
+    }
+
+    pub fn take_revert_data(&mut self) -> Option<Vec<u8>> {
+        self.revert_data.take()
+    }
+
+    /// Records a failure of the underlying key-value store. When called multiple times the first error wins.
+    ///
+    /// The vmcall should skip its work, and the execution will end with a `StorageFailed` receipt.
+    pub fn set_storage_error(&mut self, err: ExternKVError) {
+        if self.storage_error.is_none() {
+            self.storage_error = Some(err);
+        }
+    }
+
+    pub fn take_storage_error(&mut self) -> Option<ExternKVError> {
+        self.storage_error.take()
+    }
 }
@@ -7,9 +7,11 @@ use svm_codec::error::ParseError;
 use svm_codec::serializers::{
     AppDeserializer, AppSerializer, AppTemplateDeserializer, AppTemplateSerializer,
 };
+use svm_layout::SymbolicVar;
 use svm_nibble::NibbleIter;
 use svm_types::{
-    App, AppAddr, AppTemplate, AppTransaction, AuthorAddr, CreatorAddr, SpawnApp, TemplateAddr,
+    App, AppAddr, AppTemplate, AppTransaction, AuthorAddr, CreatorAddr, Envelope, SpawnApp,
+    TemplateAddr, UpgradeApp,
 };
 
 /// `Env` storage serialization types
@@ -115,6 +117,30 @@ pub trait Env {
         Ok(tx)
     }
 
+    /// Parses raw a upgrade-app transaction.
+    /// On success returns `UpgradeApp`,
+    /// On failure returns `ParseError`.
+    fn parse_upgrade_app(&self, bytes: &[u8]) -> Result<UpgradeApp, ParseError> {
+        let mut iter = NibbleIter::new(bytes);
+
+        let upgrade = svm_codec::api::raw::decode_upgrade_app(&mut iter)?;
+        iter.ensure_eof(ParseError::ExpectedEOF)?;
+
+        Ok(upgrade)
+    }
+
+    /// Parses a raw transaction envelope.
+    /// On success returns `Envelope`,
+    /// On failure returns `ParseError`.
+    fn parse_envelope(&self, bytes: &[u8]) -> Result<Envelope, ParseError> {
+        let mut iter = NibbleIter::new(bytes);
+
+        let envelope = svm_codec::api::raw::decode_envelope(&mut iter)?;
+        iter.ensure_eof(ParseError::ExpectedEOF)?;
+
+        Ok(envelope)
+    }
+
     /// Stores the following:
     /// * `TemplateAddress` -> `TemplateHash`
     /// * `TemplateHash` -> `AppTemplate` data
@@ -145,6 +171,22 @@ pub trait Env {
         }
     }
 
+    /// Re-binds `App` of address `addr` to the `AppTemplate` of address `template`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the `App` doesn't exist.
+    fn store_app_template(&mut self, addr: &AppAddr, template: &TemplateAddr) {
+        let (mut app, creator) = self
+            .load_app(addr)
+            .expect("Should have validated upgrade transaction first.");
+
+        app.template = template.clone();
+
+        let store = self.get_app_store_mut();
+        store.store(&app, &creator, addr);
+    }
+
     /// Given an `App` address, loads the `AppTemplate` the app is associated with.
     fn load_template_by_app(
         &self,
@@ -166,6 +208,16 @@ pub trait Env {
         store.load(&addr)
     }
 
+    /// Loads the typed storage schema of an `AppTemplate` given its `Address`.
+    ///
+    /// Returns `None` when the `AppTemplate` doesn't exist or when it has been deployed without a schema.
+    #[must_use]
+    fn load_template_schema(&self, addr: &TemplateAddr) -> Option<Vec<SymbolicVar>> {
+        let (template, _author) = self.load_template(addr)?;
+
+        template.data.schema().map(|schema| schema.to_vec())
+    }
+
     /// Loads an `App` given its `Address`
     #[must_use]
     fn load_app(&self, addr: &AppAddr) -> Option<(App, CreatorAddr)> {
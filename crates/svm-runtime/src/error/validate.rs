@@ -1,6 +1,6 @@
 use std::fmt;
 
-use svm_codec::error::ParseError;
+use svm_codec::error::{EnvelopeError, ParseError};
 use svm_gas::error::ProgramError;
 
 #[allow(missing_docs)]
@@ -8,6 +8,7 @@ use svm_gas::error::ProgramError;
 pub enum ValidateError {
     Parse(ParseError),
     Program(ProgramError),
+    Envelope(EnvelopeError),
 }
 
 impl From<ParseError> for ValidateError {
@@ -22,11 +23,18 @@ impl From<ProgramError> for ValidateError {
     }
 }
 
+impl From<EnvelopeError> for ValidateError {
+    fn from(err: EnvelopeError) -> Self {
+        Self::Envelope(err)
+    }
+}
+
 impl fmt::Display for ValidateError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ValidateError::Program(err) => err.fmt(f),
             ValidateError::Parse(err) => err.fmt(f),
+            ValidateError::Envelope(err) => err.fmt(f),
         }
     }
 }
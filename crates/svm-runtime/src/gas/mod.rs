@@ -4,3 +4,4 @@ mod pricing;
 
 pub use default::DefaultGasEstimator;
 pub use estimator::GasEstimator;
+pub use pricing::{EMIT_TOPIC_GAS, LOG_BASE_GAS, LOG_BYTE_GAS};
@@ -1,3 +1,12 @@
+/// Gas charged for each `svm_log` vmcall (regardless of the message size).
+pub const LOG_BASE_GAS: u64 = 375;
+
+/// Gas charged for each byte of a logged message.
+pub const LOG_BYTE_GAS: u64 = 8;
+
+/// Gas charged for each topic of an event emitted by the `svm_emit` vmcall.
+pub const EMIT_TOPIC_GAS: u64 = 375;
+
 /// Updates current running `App`'s `left gas`.
 #[macro_export]
 macro_rules! use_gas {
@@ -19,6 +28,18 @@ macro_rules! use_gas {
     ("store160", $ctx:expr) => {{
         //
     }};
+    ("load128", $ctx:expr) => {{
+        //
+    }};
+    ("store128", $ctx:expr) => {{
+        //
+    }};
+    ("load256", $ctx:expr) => {{
+        //
+    }};
+    ("store256", $ctx:expr) => {{
+        //
+    }};
     ("host_get64", $ctx:expr) => {{
         //
     }};
@@ -31,9 +52,26 @@ macro_rules! use_gas {
     ("set_returndata", $ctx:expr) => {{
         //
     }};
-    ("log", $ctx:expr) => {{
+    ("value", $ctx:expr) => {{
         //
     }};
+    ("emit", $ctx:expr, $ntopics:expr, $data_len:expr) => {{
+        let gas = $crate::gas::LOG_BASE_GAS
+            + $crate::gas::EMIT_TOPIC_GAS * ($ntopics as u64)
+            + $crate::gas::LOG_BYTE_GAS * ($data_len as u64);
+
+        $ctx.borrow_mut().use_gas(gas)
+    }};
+    ("log", $ctx:expr, $msg_len:expr) => {{
+        let gas = $crate::gas::LOG_BASE_GAS + $crate::gas::LOG_BYTE_GAS * ($msg_len as u64);
+
+        $ctx.borrow_mut().use_gas(gas)
+    }};
+    ("revert", $ctx:expr, $data_len:expr) => {{
+        let gas = $crate::gas::LOG_BYTE_GAS * ($data_len as u64);
+
+        $ctx.borrow_mut().use_gas(gas)
+    }};
 
     ($ctx:expr) => {{
         use crate::Context
@@ -1,6 +1,6 @@
 use std::convert::{TryFrom, TryInto};
 use std::ffi::c_void;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::Context;
 
@@ -9,6 +9,10 @@ use wasmer::{Export, Exportable, Function, FunctionType, RuntimeError, Store, Ty
 use svm_ffi::{svm_byte_array, svm_env_t, svm_func_callback_t};
 use svm_types::{WasmType, WasmValue};
 
+/// A function imported from the `Host`.
+///
+/// `ExternImport` is `Send + Sync`, so that a `Runtime` owning its imports can be shared across threads.
+/// See the safety contract of `ExternImport::new`.
 #[derive(Debug, Clone)]
 pub struct ExternImport {
     name: String,
@@ -17,7 +21,7 @@ pub struct ExternImport {
 
     params: Vec<WasmType>,
 
-    returns: Rc<Vec<WasmType>>,
+    returns: Arc<Vec<WasmType>>,
 
     func: svm_func_callback_t,
 
@@ -25,7 +29,15 @@ pub struct ExternImport {
 }
 
 impl ExternImport {
-    pub fn new(
+    /// Creates a new import calling `func` with `host_env`.
+    ///
+    /// # Safety
+    ///
+    /// `SVM` never dereferences `host_env`, it only passes it to `func`.
+    /// Since executions may run concurrently on different threads, the caller must guarantee that
+    /// `host_env` stays valid as long as the import is alive, and that `func` may be called with it
+    /// from any thread, including concurrently.
+    pub unsafe fn new(
         name: String,
         namespace: String,
         params: Vec<WasmType>,
@@ -37,12 +49,16 @@ impl ExternImport {
             name,
             namespace,
             params,
-            returns: Rc::new(returns),
+            returns: Arc::new(returns),
             func,
             host_env,
         }
     }
 
+    /// Creates the `wasmer` function wrapping this import for a single execution.
+    ///
+    /// The returned `svm_env_t` points to the execution's `ctx` and should be released
+    /// once the execution is over.
     pub fn wasmer_export(&self, store: &Store, ctx: &mut Context) -> (Export, *const svm_env_t) {
         unsafe {
             // The following code has been highly influenced by code here:
@@ -129,6 +145,14 @@ impl ExternImport {
         &self.namespace
     }
 
+    pub fn params(&self) -> &[WasmType] {
+        &self.params
+    }
+
+    pub fn returns(&self) -> &[WasmType] {
+        &self.returns
+    }
+
     fn wasmer_function_ty(&self) -> FunctionType {
         let params = to_wasmer_types(&self.params);
         let returns = to_wasmer_types(&self.returns);
@@ -137,6 +161,15 @@ impl ExternImport {
     }
 }
 
+// Safety: all fields besides `host_env` are `Send + Sync`.
+// `host_env` is never dereferenced by `SVM`, and `ExternImport::new` requires that it may be
+// passed to `func` from any thread (see its safety contract).
+unsafe impl Send for ExternImport {}
+
+// Safety: `ExternImport` has no interior mutability, and `&ExternImport` only hands `host_env`
+// over to `func` (allowed from any thread by the `ExternImport::new` safety contract).
+unsafe impl Sync for ExternImport {}
+
 fn to_wasmer_types(types: &[WasmType]) -> Vec<Type> {
     types
         .iter()
@@ -11,7 +11,10 @@
 
 /// Implements the most high-level API of `SVM`.
 mod runtime;
-pub use runtime::{create_rocksdb_runtime, Config, DefaultRuntime, Runtime};
+pub use runtime::{
+    create_memory_runtime, create_rocksdb_runtime, memory_state_kv_init, Config, DefaultRuntime,
+    Runtime,
+};
 
 /// Gas estimation and metering.
 pub mod gas;
@@ -27,6 +30,7 @@ mod context;
 pub use context::Context;
 
 /// Implements common functionalities to be consnumed by tests.
+#[cfg(feature = "testing")]
 pub mod testing;
 
 /// Implements the `SVM` vmcalls (a.k.a libcalls / hostcalls / syscalls)
@@ -1,10 +1,19 @@
 use std::path::{Path, PathBuf};
 
+use svm_codec::api::raw::VersionSchedule;
+
 /// Runtime configuration
 #[derive(Debug, Clone)]
 pub struct Config {
     /// The path for the key-value store
     pub kv_path: PathBuf,
+
+    /// Whether `validate_envelope` should check the envelope's signature and nonce
+    /// (and not only parse it).
+    pub verify_envelopes: bool,
+
+    /// The transactions wire-format versions active at each layer.
+    pub versions: VersionSchedule,
 }
 
 impl Config {
@@ -12,6 +21,8 @@ impl Config {
     pub fn new<P: AsRef<Path>>(kv_path: P) -> Self {
         Self {
             kv_path: kv_path.as_ref().to_path_buf(),
+            verify_envelopes: false,
+            versions: VersionSchedule::default(),
         }
     }
 }
@@ -14,17 +14,20 @@ use crate::{
     vmcalls, Config, Context, ExternImport, Runtime,
 };
 
-use svm_codec::error::ParseError;
+use svm_codec::api::raw::{HostImport, VersionSchedule, SVM_NAMESPACE};
+use svm_codec::error::{EnvelopeError, ParseError};
 use svm_ffi::svm_env_t;
 use svm_gas::Gas;
 use svm_layout::DataLayout;
-use svm_storage::app::AppStorage;
+use svm_storage::{app::AppStorage, kv::ExternKVError};
 use svm_types::{
-    gas::{MaybeGas, OOGError},
+    gas::MaybeGas,
     receipt::{
-        make_spawn_app_receipt, ExecReceipt, Log, ReceiptError, SpawnAppReceipt, TemplateReceipt,
+        make_spawn_app_receipt, make_upgrade_app_receipt, ExecReceipt, Log, ReceiptError,
+        SpawnAppReceipt, TemplateReceipt, UpgradeAppReceipt,
     },
-    AppAddr, AppTemplate, AppTransaction, AuthorAddr, CreatorAddr, SpawnApp, State, TemplateAddr,
+    AppAddr, AppTemplate, AppTransaction, AuthorAddr, CreatorAddr, Envelope, HostCtx, SpawnApp,
+    State, TemplateAddr, TxKind, UpgradeApp,
 };
 
 use wasmer::{
@@ -33,6 +36,10 @@ use wasmer::{
 };
 
 /// Default `Runtime` implementation based on `Wasmer`.
+///
+/// Each execution compiles, instantiates and runs the app using its own `Store` and `Context`.
+/// Thus, when `ENV` is `Sync`, independent `exec_app` calls can run concurrently
+/// over a shared `DefaultRuntime`.
 pub struct DefaultRuntime<ENV, GE> {
     /// The runtime environment. Used mainly for managing app persistence.
     env: ENV,
@@ -41,11 +48,14 @@ pub struct DefaultRuntime<ENV, GE> {
     config: Config,
 
     /// External imports (living in the so-called `Host` or `Node`) to be consumed by the App.
-    imports: *const Vec<ExternImport>,
+    imports: Vec<ExternImport>,
 
     /// builds a `AppStorage` instance.
     storage_builder: Box<StorageBuilderFn>,
 
+    /// The current layer (used for picking the active transactions versions).
+    layer: u64,
+
     phantom: PhantomData<GE>,
 }
 
@@ -59,7 +69,12 @@ where
         let template = self.parse_deploy_template(bytes)?;
         let code = &template.code;
 
-        svm_gas::validate_code(code).map_err(|e| e.into())
+        svm_gas::validate_code(code)?;
+
+        let host_imports = self.host_imports();
+        svm_codec::api::raw::validate_template(&template, &host_imports)?;
+
+        Ok(())
     }
 
     fn validate_app(&self, bytes: &[u8]) -> Result<(), ValidateError> {
@@ -69,12 +84,36 @@ where
     }
 
     fn validate_tx(&self, bytes: &[u8]) -> Result<AppAddr, ValidateError> {
-        self.env
-            .parse_exec_app(bytes)
+        self.parse_exec_app(bytes)
             .map(|tx| tx.app)
             .map_err(|e| e.into())
     }
 
+    fn validate_upgrade(&self, bytes: &[u8]) -> Result<AppAddr, ValidateError> {
+        self.parse_upgrade_app(bytes)
+            .map(|upgrade| upgrade.app)
+            .map_err(|e| e.into())
+    }
+
+    fn validate_envelope(&self, bytes: &[u8], nonce: u64) -> Result<Envelope, ValidateError> {
+        let envelope = self.env.parse_envelope(bytes)?;
+
+        if self.config.verify_envelopes {
+            if envelope.nonce != nonce {
+                let err = EnvelopeError::NonceMismatch {
+                    expected: nonce,
+                    actual: envelope.nonce,
+                };
+
+                return Err(err.into());
+            }
+
+            svm_codec::api::raw::verify_envelope(&envelope)?;
+        }
+
+        Ok(envelope)
+    }
+
     fn estimate_deploy_template(&self, bytes: &[u8]) -> Result<Gas, ValidateError> {
         self.validate_template(bytes)?;
 
@@ -101,7 +140,10 @@ where
     ) -> TemplateReceipt {
         info!("runtime `deploy_template`");
 
-        let template = self.parse_deploy_template(bytes).unwrap();
+        let template = match self.parse_deploy_template(bytes) {
+            Ok(template) => template,
+            Err(e) => return TemplateReceipt::from_err(invalid_tx(e), Vec::new()),
+        };
         let install_gas = self.compute_install_template_gas(bytes, &template);
 
         if gas_limit >= install_gas {
@@ -118,18 +160,22 @@ where
         &mut self,
         bytes: &[u8],
         creator: &CreatorAddr,
+        host_ctx: &HostCtx,
         gas_limit: MaybeGas,
     ) -> SpawnAppReceipt {
         info!("runtime `spawn_app`");
 
-        let spawn = self.parse_spawn_app(bytes).unwrap();
+        let spawn = match self.parse_spawn_app(bytes) {
+            Ok(spawn) => spawn,
+            Err(e) => return SpawnAppReceipt::from_err(invalid_tx(e), Vec::new()),
+        };
         let install_gas = self.compute_install_app_gas(bytes, &spawn);
 
         let gas_left = gas_limit - install_gas;
 
         match gas_left {
             Err(..) => {
-                let log = Log {
+                let log = Log::Message {
                     msg: format!(
                         "not enough gas (installation_gas = {}) for installation",
                         install_gas
@@ -137,6 +183,7 @@ where
                     .into_bytes(),
 
                     code: 1,
+                    topics: Vec::new(),
                 };
 
                 SpawnAppReceipt::new_oog(vec![log])
@@ -145,16 +192,87 @@ where
                 let addr = self.install_app(&spawn, creator);
                 let gas_used = install_gas.into();
 
-                self.call_ctor(creator, spawn, &addr, gas_used, gas_left)
+                self.call_ctor(creator, spawn, &addr, host_ctx, gas_used, gas_left)
             }
         }
     }
 
-    fn exec_app(&self, bytes: &[u8], state: &State, gas_limit: MaybeGas) -> ExecReceipt {
-        let tx = self.parse_exec_app(bytes).unwrap();
+    fn exec_app(
+        &self,
+        bytes: &[u8],
+        host_ctx: &HostCtx,
+        state: &State,
+        gas_limit: MaybeGas,
+    ) -> ExecReceipt {
+        let tx = match self.parse_exec_app(bytes) {
+            Ok(tx) => tx,
+            Err(e) => return ExecReceipt::from_err(invalid_tx(e), Vec::new()),
+        };
         let gas_used = MaybeGas::with(0);
 
-        self._exec_app(&tx, state, gas_used, gas_limit)
+        self._exec_app(&tx, host_ctx, state, gas_used, gas_limit)
+    }
+
+    fn upgrade_app(
+        &mut self,
+        bytes: &[u8],
+        sender: &CreatorAddr,
+        state: &State,
+        gas_limit: MaybeGas,
+    ) -> UpgradeAppReceipt {
+        info!("runtime `upgrade_app`");
+
+        let upgrade = match self.parse_upgrade_app(bytes) {
+            Ok(upgrade) => upgrade,
+            Err(e) => return UpgradeAppReceipt::from_err(invalid_tx(e), Vec::new()),
+        };
+        let upgrade_gas = self.compute_upgrade_app_gas(bytes, &upgrade);
+
+        let gas_left = match gas_limit - upgrade_gas {
+            Ok(gas_left) => gas_left,
+            Err(..) => return UpgradeAppReceipt::new_oog(Vec::new()),
+        };
+
+        let template = match self.check_upgrade(&upgrade, sender) {
+            Ok(template) => template,
+            Err(e) => return UpgradeAppReceipt::from_err(e, Vec::new()),
+        };
+
+        let gas_used = MaybeGas::with(upgrade_gas);
+
+        let receipt = match upgrade.migration {
+            None => UpgradeAppReceipt {
+                success: true,
+                error: None,
+                template_addr: Some(upgrade.template.clone()),
+                new_state: Some(state.clone()),
+                returndata: Some(Vec::new()),
+                gas_used,
+                logs: Vec::new(),
+            },
+            Some(..) => {
+                let migration = self.build_migration_call(&upgrade);
+
+                // no coins are transferred along an `upgrade-app` transaction
+                let migration_receipt = self.exec_with_template(
+                    &migration,
+                    &HostCtx::default(),
+                    state,
+                    &template,
+                    &upgrade.template,
+                    gas_used,
+                    gas_left,
+                );
+
+                make_upgrade_app_receipt(migration_receipt, &upgrade.template)
+            }
+        };
+
+        if receipt.success {
+            self.env.store_app_template(&upgrade.app, &upgrade.template);
+        }
+
+        receipt
     }
 }
 
@@ -168,21 +286,41 @@ where
     pub fn new<P: AsRef<Path>>(
         env: ENV,
         kv_path: P,
-        imports: &Vec<ExternImport>,
+        imports: Vec<ExternImport>,
         storage_builder: Box<StorageBuilderFn>,
     ) -> Self {
         let config = Config::new(kv_path);
-        let imports = imports as *const _;
 
         Self {
             env,
             config,
             imports,
             storage_builder,
+            layer: 0,
             phantom: PhantomData::<GE>,
         }
     }
 
+    /// Turns on (or off) the signature and nonce checks of `validate_envelope`.
+    pub fn set_envelope_verification(&mut self, enabled: bool) {
+        self.config.verify_envelopes = enabled;
+    }
+
+    /// Sets the current layer.
+    ///
+    /// Transactions whose wire-format version isn't active at that layer are rejected
+    /// with `ParseError::InvalidProtocolVersion`.
+    pub fn set_layer(&mut self, layer: u64) {
+        self.layer = layer;
+    }
+
+    /// Replaces the schedule of the active transactions wire-format versions.
+    ///
+    /// By default, all the versions supported by `svm-codec` are active since layer `0`.
+    pub fn set_version_schedule(&mut self, versions: VersionSchedule) {
+        self.config.versions = versions;
+    }
+
     /// Initialize a new `AppStorage` and returndata it.
     /// This method is of `pub` visibility since it's also helpful for tests that want to
     /// observe that app storage data.
@@ -200,12 +338,13 @@ where
         creator: &CreatorAddr,
         spawn: SpawnApp,
         app_addr: &AppAddr,
+        host_ctx: &HostCtx,
         gas_used: MaybeGas,
         gas_left: MaybeGas,
     ) -> SpawnAppReceipt {
         let ctor = self.build_ctor_call(creator, spawn, app_addr);
 
-        let ctor_receipt = self._exec_app(&ctor, &State::empty(), gas_used, gas_left);
+        let ctor_receipt = self._exec_app(&ctor, host_ctx, &State::empty(), gas_used, gas_left);
 
         make_spawn_app_receipt(ctor_receipt, app_addr)
     }
@@ -240,11 +379,62 @@ where
         }
     }
 
+    fn build_migration_call(&self, upgrade: &UpgradeApp) -> AppTransaction {
+        AppTransaction {
+            version: 0,
+            app: upgrade.app.clone(),
+            func_name: upgrade.migration.clone().unwrap(),
+            calldata: upgrade.calldata.clone(),
+        }
+    }
+
+    /// Checks that `sender` may upgrade the app to the new template, and that the new template's
+    /// `DataLayout` doesn't relocate any of the app's existing variables.
+    /// On success, returns the new `AppTemplate`.
+    fn check_upgrade(
+        &self,
+        upgrade: &UpgradeApp,
+        sender: &CreatorAddr,
+    ) -> Result<AppTemplate, ReceiptError> {
+        let app_addr = &upgrade.app;
+
+        let (app, creator) = self
+            .env
+            .load_app(app_addr)
+            .ok_or_else(|| ReceiptError::AppNotFound(app_addr.clone()))?;
+
+        if &creator != sender {
+            return Err(ReceiptError::UpgradeNotAuthorized {
+                app_addr: app_addr.clone(),
+            });
+        }
+
+        let (current, _author) = self
+            .env
+            .load_template(&app.template)
+            .ok_or_else(|| ReceiptError::TemplateNotFound(app.template.clone()))?;
+
+        let (template, _author) = self
+            .env
+            .load_template(&upgrade.template)
+            .ok_or_else(|| ReceiptError::TemplateNotFound(upgrade.template.clone()))?;
+
+        if current.data.is_upgradable_to(&template.data) {
+            Ok(template)
+        } else {
+            Err(ReceiptError::IncompatibleLayout {
+                app_addr: app_addr.clone(),
+                template_addr: upgrade.template.clone(),
+            })
+        }
+    }
+
     fn _exec_app(
         &self,
         tx: &AppTransaction,
+        host_ctx: &HostCtx,
         state: &State,
-        _gas_used: MaybeGas,
+        gas_used: MaybeGas,
         gas_left: MaybeGas,
     ) -> ExecReceipt {
         info!("runtime `exec_app`");
@@ -254,30 +444,50 @@ where
                 let empty_logs = Vec::new();
                 ExecReceipt::from_err(e, empty_logs)
             }
-            Ok((template, template_addr, _author, _creator)) => {
-                let store = svm_compiler::new_store();
-                let mut ctx = self.create_context(&template, &tx.app, &state, gas_left);
-                let (import_object, funcs_envs) = self.create_import_object(&store, &mut ctx);
-
-                let (result, logs) = self.do_exec_app(
-                    &store,
-                    &ctx,
-                    &tx,
-                    &template,
-                    &template_addr,
-                    &import_object,
-                    gas_left,
-                );
+            Ok((template, template_addr, _author, _creator)) => self.exec_with_template(
+                tx,
+                host_ctx,
+                state,
+                &template,
+                &template_addr,
+                gas_used,
+                gas_left,
+            ),
+        }
+    }
+
+    fn exec_with_template(
+        &self,
+        tx: &AppTransaction,
+        host_ctx: &HostCtx,
+        state: &State,
+        template: &AppTemplate,
+        template_addr: &TemplateAddr,
+        gas_used: MaybeGas,
+        gas_left: MaybeGas,
+    ) -> ExecReceipt {
+        let store = svm_compiler::new_store();
+        let mut ctx = self.create_context(template, &tx.app, host_ctx, &state, gas_left);
+        let (import_object, funcs_envs) = self.create_import_object(&store, &mut ctx);
 
-                self.funcs_envs_destroy(funcs_envs);
+        let (result, logs) = self.do_exec_app(
+            &store,
+            &ctx,
+            &tx,
+            template,
+            template_addr,
+            &import_object,
+            gas_used,
+            gas_left,
+        );
 
-                let receipt = self.make_receipt(result, logs);
+        self.funcs_envs_destroy(funcs_envs);
 
-                info!("receipt: {:?}", receipt);
+        let receipt = self.make_receipt(result, logs);
 
-                receipt
-            }
-        }
+        info!("receipt: {:?}", receipt);
+
+        receipt
     }
 
     fn funcs_envs_destroy(&self, mut funcs_envs: Vec<*const svm_env_t>) {
@@ -296,6 +506,7 @@ where
         template: &AppTemplate,
         template_addr: &TemplateAddr,
         import_object: &ImportObject,
+        gas_used: MaybeGas,
         gas_left: MaybeGas,
     ) -> (
         Result<(Option<State>, Option<Vec<u8>>, MaybeGas), ReceiptError>,
@@ -336,11 +547,29 @@ where
         let func_res = func.call(&[]);
         let logs = self.take_logs(ctx);
 
-        let gas_used = self.instance_gas_used(&instance);
-        if gas_used.is_err() {
+        // the underlying key-value store has failed while the app was running.
+        // The storage changes are discarded since the app may have observed wrong values.
+        if let Some(err) = self.take_storage_error(ctx) {
+            return (Err(self.storage_failed(tx, err)), logs);
+        }
+
+        // the gas metered for the wasm code is charged on top of the gas charged by the vmcalls,
+        // so that the `gas_limit` applies to their total.
+        let wasm_gas = self.instance_gas_used(&instance);
+        let _ = ctx.borrow_mut().use_gas(wasm_gas);
+
+        if ctx.borrow().is_oog() {
             return (Err(ReceiptError::OOG), logs);
         }
 
+        let gas_used = self.total_gas_used(ctx, gas_used);
+
+        // the app has called `svm_revert` (it has trapped right after, so `func_res` is ignored).
+        // Since there is no commit the storage changes are discarded.
+        if let Some(data) = self.take_revert_data(ctx) {
+            return (Err(ReceiptError::Reverted { data }), logs);
+        }
+
         let result = match func_res {
             Err(e) => Err(ReceiptError::FuncFailed {
                 app_addr: tx.app.clone(),
@@ -350,9 +579,11 @@ where
             }),
             Ok(returns) => {
                 let returndata = self.take_returndata(ctx, returns);
-                let new_state = self.commit_chages(ctx);
 
-                Ok((Some(new_state), Some(returndata), gas_used.unwrap()))
+                match self.commit_chages(ctx) {
+                    Ok(new_state) => Ok((Some(new_state), Some(returndata), gas_used)),
+                    Err(err) => Err(self.storage_failed(tx, err)),
+                }
             }
         };
 
@@ -360,11 +591,18 @@ where
     }
 
     #[inline]
-    fn commit_chages(&self, ctx: &Context) -> State {
+    fn commit_chages(&self, ctx: &Context) -> Result<State, ExternKVError> {
         let storage = &mut ctx.borrow_mut().storage;
         storage.commit()
     }
 
+    fn storage_failed(&self, tx: &AppTransaction, err: ExternKVError) -> ReceiptError {
+        ReceiptError::StorageFailed {
+            app_addr: tx.app.clone(),
+            msg: err.to_string(),
+        }
+    }
+
     #[inline]
     fn assert_no_returndata(&self, ctx: &Context) {
         assert!(ctx.borrow().returndata.is_none())
@@ -394,6 +632,14 @@ where
         ctx.borrow_mut().take_logs()
     }
 
+    fn take_revert_data(&self, ctx: &Context) -> Option<Vec<u8>> {
+        ctx.borrow_mut().take_revert_data()
+    }
+
+    fn take_storage_error(&self, ctx: &Context) -> Option<ExternKVError> {
+        ctx.borrow_mut().take_storage_error()
+    }
+
     fn make_receipt(
         &self,
         result: Result<(Option<State>, Option<Vec<u8>>, MaybeGas), ReceiptError>,
@@ -488,10 +734,31 @@ where
         ctx.borrow_mut().set_calldata(offset, len);
     }
 
-    #[inline]
-    fn instance_gas_used(&self, _instance: &Instance) -> Result<MaybeGas, OOGError> {
-        // TODO: read `gas_used` out of `instance`
-        Ok(MaybeGas::new())
+    /// Returns the gas metered for the wasm code executed by `instance`.
+    ///
+    /// The metered gas is read out of the `instance` exported global `svm_compiler::GAS_USED_GLOBAL`.
+    /// When there is no such global, the wasm code is considered free.
+    fn instance_gas_used(&self, instance: &Instance) -> u64 {
+        let global = instance.exports.get_global(svm_compiler::GAS_USED_GLOBAL);
+
+        match global.map(|g| g.get()) {
+            Ok(WasmerValue::I64(gas)) => gas as u64,
+            _ => 0,
+        }
+    }
+
+    /// Returns `gas_used` (gas charged before the execution has started)
+    /// plus the gas charged by the execution (for both the wasm code and the vmcalls).
+    ///
+    /// Returns `MaybeGas::new()` when gas metering is off.
+    fn total_gas_used(&self, ctx: &Context, gas_used: MaybeGas) -> MaybeGas {
+        let borrow = ctx.borrow();
+
+        if borrow.gas_metering {
+            gas_used + borrow.gas_used
+        } else {
+            MaybeGas::new()
+        }
     }
 
     fn instantiate(
@@ -531,13 +798,17 @@ where
         &self,
         template: &AppTemplate,
         app_addr: &AppAddr,
+        host_ctx: &HostCtx,
         state: &State,
         gas_limit: MaybeGas,
     ) -> Context {
         let layout = &template.data;
         let storage = self.open_app_storage(app_addr, state, layout);
 
-        Context::new(gas_limit, storage)
+        let ctx = Context::new(gas_limit, storage);
+        ctx.borrow_mut().host_ctx = host_ctx.clone();
+
+        ctx
     }
 
     fn create_import_object(
@@ -550,9 +821,7 @@ where
 
         let mut exports = HashMap::new();
 
-        let imports: &[ExternImport] = unsafe { &*self.imports as _ };
-
-        for import in imports.iter() {
+        for import in self.imports.iter() {
             let namespace = import.namespace();
             let ns_exports = exports.entry(namespace).or_insert(Exports::new());
 
@@ -570,7 +839,7 @@ where
 
         let mut svm = Exports::new();
         vmcalls::wasmer_register(store, ctx, &mut svm);
-        import_object.register("svm", svm);
+        import_object.register(SVM_NAMESPACE, svm);
 
         (import_object, funcs_envs)
     }
@@ -610,16 +879,51 @@ where
         })
     }
 
+    /// The `imports` signatures (used for validating a template's imports).
+    fn host_imports(&self) -> Vec<HostImport> {
+        self.imports
+            .iter()
+            .map(|import| HostImport {
+                namespace: import.namespace().to_string(),
+                name: import.name().to_string(),
+                params: import.params().to_vec(),
+                returns: import.returns().to_vec(),
+            })
+            .collect()
+    }
+
     fn parse_deploy_template(&self, bytes: &[u8]) -> Result<AppTemplate, ParseError> {
-        self.env.parse_deploy_template(bytes)
+        let template = self.env.parse_deploy_template(bytes)?;
+        self.ensure_active_version(TxKind::DeployTemplate, template.version)?;
+
+        Ok(template)
     }
 
     fn parse_spawn_app(&self, bytes: &[u8]) -> Result<SpawnApp, ParseError> {
-        self.env.parse_spawn_app(bytes)
+        let spawn = self.env.parse_spawn_app(bytes)?;
+        self.ensure_active_version(TxKind::SpawnApp, spawn.app.version)?;
+
+        Ok(spawn)
     }
 
     fn parse_exec_app(&self, bytes: &[u8]) -> Result<AppTransaction, ParseError> {
-        self.env.parse_exec_app(bytes)
+        let tx = self.env.parse_exec_app(bytes)?;
+        self.ensure_active_version(TxKind::ExecApp, tx.version)?;
+
+        Ok(tx)
+    }
+
+    fn parse_upgrade_app(&self, bytes: &[u8]) -> Result<UpgradeApp, ParseError> {
+        let upgrade = self.env.parse_upgrade_app(bytes)?;
+        self.ensure_active_version(TxKind::UpgradeApp, upgrade.version)?;
+
+        Ok(upgrade)
+    }
+
+    fn ensure_active_version(&self, kind: TxKind, version: u32) -> Result<(), ParseError> {
+        self.config
+            .versions
+            .ensure_active(kind, version, self.layer)
     }
 
     /// Gas
@@ -632,4 +936,17 @@ where
         // todo!()
         1000 * (bytes.len() as u64)
     }
+
+    fn compute_upgrade_app_gas(&self, bytes: &[u8], _upgrade: &UpgradeApp) -> u64 {
+        // todo!()
+        1000 * (bytes.len() as u64)
+    }
+}
+
+/// A transaction which can't be parsed (or whose version isn't active at the current layer)
+/// fails with an `InvalidTransaction` receipt error.
+fn invalid_tx(err: ParseError) -> ReceiptError {
+    ReceiptError::InvalidTransaction {
+        msg: err.to_string(),
+    }
 }
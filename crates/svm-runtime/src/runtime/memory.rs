@@ -0,0 +1,59 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::env::memory::{DefaultMemAppStore, DefaultMemAppTemplateStore, DefaultMemoryEnv};
+use crate::{gas::DefaultGasEstimator, storage::StorageBuilderFn};
+use crate::{Config, DefaultRuntime, ExternImport};
+
+use svm_layout::DataLayout;
+use svm_storage::{
+    app::{AppKVStore, AppStorage},
+    kv::{FakeKV, StatefulKV},
+};
+use svm_types::{AppAddr, State};
+
+/// Returns a new in-memory stateful-kv.
+/// It should be used for managing apps' storage.
+pub fn memory_state_kv_init() -> Arc<Mutex<dyn StatefulKV + Send>> {
+    Arc::new(Mutex::new(FakeKV::new()))
+}
+
+/// Creates an in-memory `Runtime` backed by key-value and host vmcalls (`imports`).
+pub fn create_memory_runtime(
+    state_kv: &Arc<Mutex<dyn StatefulKV + Send>>,
+    imports: Vec<ExternImport>,
+) -> DefaultRuntime<DefaultMemoryEnv, DefaultGasEstimator> {
+    let storage_builder = runtime_memory_storage_builder(state_kv);
+
+    let env = runtime_memory_env_builder();
+    let kv_path = Path::new("mem");
+
+    DefaultRuntime::new(env, &kv_path, imports, Box::new(storage_builder))
+}
+
+/// Returns a function (wrapped inside `Box`) that initializes an App's storage client.
+pub fn runtime_memory_storage_builder(
+    state_kv: &Arc<Mutex<dyn StatefulKV + Send>>,
+) -> Box<StorageBuilderFn> {
+    let state_kv = Arc::clone(state_kv);
+
+    let func = move |app_addr: &AppAddr, state: &State, layout: &DataLayout, _config: &Config| {
+        let app_addr = app_addr.inner();
+        let app_kv = AppKVStore::new(app_addr.clone(), &state_kv);
+
+        let mut storage = AppStorage::new(layout.clone(), app_kv);
+        storage.rewind(state);
+
+        storage
+    };
+
+    Box::new(func)
+}
+
+/// Creates a new in-memory runtime environment.
+pub fn runtime_memory_env_builder() -> DefaultMemoryEnv {
+    let template_store = DefaultMemAppTemplateStore::new();
+    let app_store = DefaultMemAppStore::new();
+
+    DefaultMemoryEnv::new(app_store, template_store)
+}
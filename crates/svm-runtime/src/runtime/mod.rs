@@ -1,9 +1,14 @@
 mod config;
 mod default;
+mod memory;
 mod rocksdb;
 mod runtime;
 
 pub use config::Config;
 pub use default::DefaultRuntime;
+pub use memory::{
+    create_memory_runtime, memory_state_kv_init, runtime_memory_env_builder,
+    runtime_memory_storage_builder,
+};
 pub use rocksdb::create_rocksdb_runtime;
 pub use runtime::Runtime;
@@ -14,7 +14,7 @@ use wasmer::Export;
 /// Creates a new `Runtime` backed by `rocksdb` for persistence.
 pub fn create_rocksdb_runtime<P, S, GE>(
     kv_path: P,
-    imports: *const Vec<ExternImport>,
+    imports: Vec<ExternImport>,
 ) -> DefaultRuntime<RocksdbEnv<S>, GE>
 where
     P: AsRef<Path>,
@@ -22,7 +22,6 @@ where
     GE: GasEstimator,
 {
     let env = app_env_build(&kv_path);
-    let imports = unsafe { &*imports };
 
     DefaultRuntime::new(env, kv_path, imports, Box::new(app_storage_build))
 }
@@ -1,12 +1,18 @@
 use crate::error::ValidateError;
 
 use svm_gas::Gas;
-use svm_types::receipt::{ExecReceipt, SpawnAppReceipt, TemplateReceipt};
-use svm_types::{gas::MaybeGas, AppAddr, AuthorAddr, CreatorAddr, State};
+use svm_types::receipt::{ExecReceipt, SpawnAppReceipt, TemplateReceipt, UpgradeAppReceipt};
+use svm_types::{gas::MaybeGas, AppAddr, AuthorAddr, CreatorAddr, Envelope, HostCtx, State};
 
 /// Specifies the interface of a `SVM` Runtime.
 pub trait Runtime {
     /// Validates raw `deploy-template` transaction prior to executing it.
+    ///
+    /// As with all other transactions, its wire-format version must be active at the current layer
+    /// (see `Config#versions`).
+    ///
+    /// Besides parsing, the template code must pass the gas validation,
+    /// and the template must pass the semantic checks of `svm_codec::api::raw::validate_template`.
     fn validate_template(&self, bytes: &[u8]) -> Result<(), ValidateError>;
 
     /// Validates a raw `spawn-app` transaction prior to executing it.
@@ -15,6 +21,16 @@ pub trait Runtime {
     /// Validates a raw `exec-app` transaction prior to executing it.
     fn validate_tx(&self, bytes: &[u8]) -> Result<AppAddr, ValidateError>;
 
+    /// Validates a raw `upgrade-app` transaction prior to executing it.
+    fn validate_upgrade(&self, bytes: &[u8]) -> Result<AppAddr, ValidateError>;
+
+    /// Validates a raw transaction `Envelope` and returns it.
+    ///
+    /// When `Config#verify_envelopes` is on, the envelope must also be signed by its sender
+    /// (see `svm_codec::api::raw::verify_envelope`), and its nonce must equal `nonce`.
+    /// Tracking the expected nonce of each sender is left to the host.
+    fn validate_envelope(&self, bytes: &[u8], nonce: u64) -> Result<Envelope, ValidateError>;
+
     /// Estimates the `Gas` required for deploying template givee as raw `bytes`.
     fn estimate_deploy_template(&self, bytes: &[u8]) -> Result<Gas, ValidateError>;
 
@@ -33,14 +49,20 @@ pub trait Runtime {
     ) -> TemplateReceipt;
 
     /// Spawn a new app out of an existing app-template.
+    ///
+    /// The `host_ctx` is the environment of the transaction (see `HostCtx::from(&Envelope)`),
+    /// it's exposed to the app's constructor.
     fn spawn_app(
         &mut self,
         bytes: &[u8],
         creator: &CreatorAddr,
+        host_ctx: &HostCtx,
         gas_limit: MaybeGas,
     ) -> SpawnAppReceipt;
 
     /// Executes an app-transaction. Returns `ExecReceipt`.
+    /// The `host_ctx` is the environment of the transaction (see `HostCtx::from(&Envelope)`).
+    ///
     /// On success:
     /// * Persists changes to the app's own storage.
     /// * Receipt returns the app's new storage state.
@@ -49,5 +71,35 @@ pub trait Runtime {
     /// On failure:
     /// * Receipt returns the occurred error
     /// * Receipt informs the amount of gas used (transaction gas limit)
-    fn exec_app(&self, bytes: &[u8], state: &State, gas_limit: MaybeGas) -> ExecReceipt;
+    fn exec_app(
+        &self,
+        bytes: &[u8],
+        host_ctx: &HostCtx,
+        state: &State,
+        gas_limit: MaybeGas,
+    ) -> ExecReceipt;
+
+    /// Upgrades an app to a new app-template. Returns `UpgradeAppReceipt`.
+    ///
+    /// Only the app's creator is authorized to upgrade it,
+    /// and the new template's `DataLayout` must be compatible with the current one.
+    /// (see `DataLayout::is_upgradable_to`).
+    ///
+    /// When the transaction specifies a migration function, it's taken from the new template
+    /// and runs against the app storage at `state`.
+    ///
+    /// On success:
+    /// * The app is bound to the new template.
+    /// * Receipt returns the app's new storage state.
+    ///
+    /// On failure:
+    /// * The app stays bound to its current template.
+    /// * Receipt returns the occurred error
+    fn upgrade_app(
+        &mut self,
+        bytes: &[u8],
+        sender: &CreatorAddr,
+        state: &State,
+        gas_limit: MaybeGas,
+    ) -> UpgradeAppReceipt;
 }
@@ -5,4 +5,7 @@ use svm_types::{AppAddr, State};
 use crate::Config;
 
 /// `AppStorage` building function signature.
-pub type StorageBuilderFn = dyn Fn(&AppAddr, &State, &DataLayout, &Config) -> AppStorage;
+///
+/// It's `Send + Sync` since a `Runtime` may build storage for executions running on different threads.
+pub type StorageBuilderFn =
+    dyn Fn(&AppAddr, &State, &DataLayout, &Config) -> AppStorage + Send + Sync;
@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_json::Value as Json;
+
+use svm_abi_decoder::ReturnData;
+use svm_abi_encoder::Encoder;
+use svm_codec::api::json;
+use svm_layout::DataLayout;
+use svm_storage::app::AppStorage;
+use svm_types::gas::MaybeGas;
+use svm_types::receipt::{ExecReceipt, Log, ReceiptError};
+use svm_types::{
+    Address, AppAddr, AppTemplate, AuthorAddr, CreatorAddr, HostCtx, State, TemplateAddr,
+};
+
+use crate::env::memory::DefaultMemoryEnv;
+use crate::gas::DefaultGasEstimator;
+use crate::{DefaultRuntime, ExternImport, Runtime};
+
+use super::{
+    build_app, build_app_tx, build_template, create_memory_runtime, memory_state_kv_init, WasmFile,
+};
+
+/// The transactions wire-format version used by `TestChain`.
+const VERSION: u32 = 0;
+
+/// The `deploy-template` wire-format version used by `TestChain` (the first one carrying the storage schema).
+const TEMPLATE_VERSION: u32 = 1;
+
+/// A deployed template.
+struct TestTemplate {
+    layout: DataLayout,
+
+    /// The template's ABI (see `svm_codec::api::json::template_abi`).
+    /// `None` when the template's code has no `svm_abi` custom section.
+    abi: Option<Json>,
+}
+
+/// A spawned app.
+struct TestApp {
+    template: TemplateAddr,
+
+    /// The app's current storage state.
+    state: State,
+}
+
+/// The receipt of a `TestChain` transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestReceipt {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+
+    /// The error of a failed transaction.
+    pub error: Option<ReceiptError>,
+
+    /// The raw `returndata` (empty when there is no `returndata`).
+    pub returndata: Vec<u8>,
+
+    /// The `returndata` decoded using the template's ABI.
+    /// (empty when the transaction has failed or when the template has no ABI).
+    pub returns: Vec<Json>,
+
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+
+    /// The gas used by the transaction.
+    pub gas_used: MaybeGas,
+}
+
+impl TestReceipt {
+    /// Decodes the data of a `ReceiptError::Reverted` error as an error's `(code, message)`
+    /// (the revert data of `ensure!`, panics and endpoints returning an `Err` - see `svm_sdk::revert::encode_error`).
+    ///
+    /// Returns `None` when the transaction hasn't reverted.
+    /// Panics when the revert data isn't an encoded error.
+    pub fn reverted(&self) -> Option<(u32, String)> {
+        match &self.error {
+            Some(ReceiptError::Reverted { data }) => {
+                let mut data = ReturnData::new(data);
+
+                let code: u32 = data.next_1();
+                let msg: String = data.next_1();
+
+                Some((code, msg))
+            }
+            _ => None,
+        }
+    }
+}
+
+///
+/// An in-memory chain for running templates end-to-end through the `DefaultRuntime`.
+///
+/// `TestChain` takes care of building the raw transactions, tracking the state of each spawned app
+/// and decoding the `returndata` using the template's ABI (when its code has a `svm_abi` custom section).
+///
+/// The endpoints arguments are given as typed values (see `svm_abi_encoder::Encoder`),
+/// a single value or a tuple holding multiple values (`()` for no arguments).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use svm_runtime::testing::TestChain;
+///
+/// let mut chain = TestChain::new();
+///
+/// let template = chain.deploy_file("apps/counter.wasm", vec![4].into());
+/// let app = chain.spawn(&template, "init", 10u32).unwrap();
+///
+/// let receipt = chain.call(&app, "add", 5u32);
+/// assert_eq!(receipt.returns, vec![json!(15)]);
+///
+/// chain.advance_layers(1);
+/// ```
+///
+pub struct TestChain {
+    runtime: DefaultRuntime<DefaultMemoryEnv, DefaultGasEstimator>,
+
+    layer: u64,
+
+    gas_limit: MaybeGas,
+
+    host_ctx: HostCtx,
+
+    author: AuthorAddr,
+
+    creator: CreatorAddr,
+
+    templates: HashMap<TemplateAddr, TestTemplate>,
+
+    apps: HashMap<AppAddr, TestApp>,
+}
+
+impl TestChain {
+    /// Creates a new `TestChain` (starting at layer `0` and without a gas limit).
+    pub fn new() -> Self {
+        Self::with_imports(Vec::new())
+    }
+
+    /// Creates a new `TestChain` whose runtime has the host `imports`.
+    pub fn with_imports(imports: Vec<ExternImport>) -> Self {
+        let state_kv = memory_state_kv_init();
+        let runtime = create_memory_runtime(&state_kv, imports);
+
+        Self {
+            runtime,
+            layer: 0,
+            gas_limit: MaybeGas::new(),
+            host_ctx: HostCtx::default(),
+            author: Address::of("author").into(),
+            creator: Address::of("creator").into(),
+            templates: HashMap::new(),
+            apps: HashMap::new(),
+        }
+    }
+
+    /// The underlying runtime.
+    pub fn runtime(&self) -> &DefaultRuntime<DefaultMemoryEnv, DefaultGasEstimator> {
+        &self.runtime
+    }
+
+    /// The underlying runtime (mutable).
+    pub fn runtime_mut(&mut self) -> &mut DefaultRuntime<DefaultMemoryEnv, DefaultGasEstimator> {
+        &mut self.runtime
+    }
+
+    /// The current layer.
+    pub fn layer(&self) -> u64 {
+        self.layer
+    }
+
+    /// Moves `count` layers forward.
+    pub fn advance_layers(&mut self, count: u64) {
+        self.layer += count;
+
+        self.runtime.set_layer(self.layer);
+    }
+
+    /// Sets the gas limit of the next transactions.
+    pub fn set_gas_limit(&mut self, gas_limit: MaybeGas) {
+        self.gas_limit = gas_limit;
+    }
+
+    /// Sets the amount of coins transferred along the next spawned apps and called functions.
+    pub fn set_value(&mut self, value: u64) {
+        self.host_ctx = HostCtx::with_value(value);
+    }
+
+    /// Sets the author of the next deployed templates.
+    pub fn set_author(&mut self, author: AuthorAddr) {
+        self.author = author;
+    }
+
+    /// Sets the creator of the next spawned apps.
+    pub fn set_creator(&mut self, creator: CreatorAddr) {
+        self.creator = creator;
+    }
+
+    /// Deploys a template named `name` having `wasm` as its code.
+    ///
+    /// Panics when the deployment fails.
+    pub fn deploy(&mut self, name: &str, layout: DataLayout, wasm: WasmFile) -> TemplateAddr {
+        let code = wasm.into_bytes();
+
+        let bytes = build_template(
+            TEMPLATE_VERSION,
+            name,
+            layout.clone(),
+            WasmFile::Binary(&code),
+        );
+
+        let receipt = self
+            .runtime
+            .deploy_template(&bytes, &self.author, self.gas_limit);
+
+        assert!(
+            receipt.success,
+            "deploying template `{}` has failed: {:?}",
+            name, receipt.error
+        );
+
+        let template = AppTemplate {
+            version: TEMPLATE_VERSION,
+            name: name.to_string(),
+            code,
+            data: layout.clone(),
+        };
+
+        let abi = json::template_abi(&template).ok();
+        let addr = receipt.addr.unwrap();
+
+        self.templates
+            .insert(addr.clone(), TestTemplate { layout, abi });
+
+        addr
+    }
+
+    /// Deploys a template built using the `svm-sdk`.
+    ///
+    /// Its `DataLayout` (including the storage schema) is derived out of the `#[storage]` records
+    /// of its ABI (see `svm_codec::api::json::template_layout`).
+    pub fn deploy_sdk(&mut self, name: &str, wasm: WasmFile) -> TemplateAddr {
+        let code = wasm.into_bytes();
+
+        let layout = json::template_layout(&code)
+            .unwrap_or_else(|e| panic!("invalid ABI of template `{}`: {:?}", name, e));
+
+        self.deploy(name, layout, WasmFile::Binary(&code))
+    }
+
+    /// Deploys the template whose code is the file at `path`.
+    ///
+    /// Files having a `.wat` (or `.wast`) extension are expected to be in the Wasm text format,
+    /// and otherwise in the Wasm binary format. The template is named after the file.
+    pub fn deploy_file<P: AsRef<Path>>(&mut self, path: P, layout: DataLayout) -> TemplateAddr {
+        let path = path.as_ref();
+
+        let code = std::fs::read(path)
+            .unwrap_or_else(|e| panic!("failed reading `{}`: {}", path.display(), e));
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("wat") | Some("wast") => {
+                let text = String::from_utf8(code).expect("invalid Wasm text file");
+
+                self.deploy(&name, layout, WasmFile::Text(&text))
+            }
+            _ => self.deploy(&name, layout, WasmFile::Binary(&code)),
+        }
+    }
+
+    /// Returns the `DataLayout` of `template`.
+    pub fn layout(&self, template: &TemplateAddr) -> &DataLayout {
+        &self.template(template).layout
+    }
+
+    /// Returns the ABI of `template` (`None` when its code has no `svm_abi` custom section).
+    pub fn abi(&self, template: &TemplateAddr) -> Option<&Json> {
+        self.template(template).abi.as_ref()
+    }
+
+    /// Spawns an app out of `template`, calling its `ctor` with `args`.
+    ///
+    /// Returns the app's address, or the receipt of the failed spawn.
+    pub fn spawn<T: Encoder>(
+        &mut self,
+        template: &TemplateAddr,
+        ctor: &str,
+        args: T,
+    ) -> Result<AppAddr, TestReceipt> {
+        let abi = self.template(template).abi.as_ref();
+        let calldata = encode_args(abi, ctor, Some(true), args);
+
+        // the app's address is derived from its name
+        let name = format!("App #{}", self.apps.len());
+        let bytes = build_app(VERSION, template, &name, ctor, &calldata);
+
+        let receipt = self
+            .runtime
+            .spawn_app(&bytes, &self.creator, &self.host_ctx, self.gas_limit);
+
+        if receipt.success {
+            let addr = receipt.get_app_addr().clone();
+            let state = receipt.get_init_state().clone();
+
+            let app = TestApp {
+                template: template.clone(),
+                state,
+            };
+
+            self.apps.insert(addr.clone(), app);
+
+            Ok(addr)
+        } else {
+            let receipt = TestReceipt {
+                success: false,
+                error: receipt.error,
+                returndata: Vec::new(),
+                returns: Vec::new(),
+                logs: receipt.logs,
+                gas_used: receipt.gas_used,
+            };
+
+            Err(receipt)
+        }
+    }
+
+    /// Calls function `func` of `app` with `args`.
+    ///
+    /// On success, the app's state is updated (the next calls run against the new state).
+    ///
+    /// When the template has an ABI, `func` must be one of its endpoints, `args` must match the parameters
+    /// it declares and its `returndata` is decoded.
+    pub fn call<T: Encoder>(&mut self, app: &AppAddr, func: &str, args: T) -> TestReceipt {
+        let state = self.app(app).state.clone();
+        let abi = self.template(&self.app(app).template).abi.clone();
+
+        let calldata = encode_args(abi.as_ref(), func, None, args);
+        let bytes = build_app_tx(VERSION, app, func, &calldata);
+
+        let receipt = self
+            .runtime
+            .exec_app(&bytes, &self.host_ctx, &state, self.gas_limit);
+
+        if receipt.success {
+            let state = receipt.get_new_state().clone();
+
+            self.apps.get_mut(app).unwrap().state = state;
+        }
+
+        into_test_receipt(receipt, abi.as_ref(), func)
+    }
+
+    /// Returns the current storage state of `app`.
+    pub fn state(&self, app: &AppAddr) -> &State {
+        &self.app(app).state
+    }
+
+    /// Opens the storage of `app` (at its current state).
+    pub fn storage(&self, addr: &AppAddr) -> AppStorage {
+        let app = self.app(addr);
+        let layout = &self.template(&app.template).layout;
+
+        self.runtime.open_app_storage(addr, &app.state, layout)
+    }
+
+    fn template(&self, addr: &TemplateAddr) -> &TestTemplate {
+        self.templates
+            .get(addr)
+            .unwrap_or_else(|| panic!("template `{:?}` hasn't been deployed", addr))
+    }
+
+    fn app(&self, addr: &AppAddr) -> &TestApp {
+        self.apps
+            .get(addr)
+            .unwrap_or_else(|| panic!("app `{:?}` hasn't been spawned", addr))
+    }
+}
+
+/// Encodes `args` as the `calldata` of `func`.
+///
+/// When there is an `abi`, `func` must be one of its endpoints (a constructor if `ctor` is `Some(true)`),
+/// and `args` must match the parameters it declares (both in number and in types).
+fn encode_args<T: Encoder>(abi: Option<&Json>, func: &str, ctor: Option<bool>, args: T) -> Vec<u8> {
+    let mut calldata = Vec::new();
+    args.encode(&mut calldata);
+
+    if let Some(abi) = abi {
+        let endpoint = abi["endpoints"]
+            .as_array()
+            .and_then(|endpoints| endpoints.iter().find(|e| e["name"] == func))
+            .unwrap_or_else(|| panic!("unknown function `{}`", func));
+
+        if let Some(ctor) = ctor {
+            assert_eq!(
+                endpoint["ctor"], ctor,
+                "function `{}` isn't a constructor",
+                func
+            );
+        }
+
+        if let Err(e) = json::decode_params(abi, func, &calldata) {
+            panic!("invalid arguments of `{}`: {:?}", func, e);
+        }
+    }
+
+    calldata
+}
+
+fn into_test_receipt(receipt: ExecReceipt, abi: Option<&Json>, func: &str) -> TestReceipt {
+    let returndata = receipt.returndata.unwrap_or_default();
+
+    let returns = match abi {
+        Some(abi) if receipt.success => {
+            let returns = json::decode_returns(abi, func, &returndata)
+                .unwrap_or_else(|e| panic!("invalid `returndata` of `{}`: {:?}", func, e));
+
+            returns["returns"].as_array().cloned().unwrap_or_default()
+        }
+        _ => Vec::new(),
+    };
+
+    TestReceipt {
+        success: receipt.success,
+        error: receipt.error,
+        returndata,
+        returns,
+        logs: receipt.logs,
+        gas_used: receipt.gas_used,
+    }
+}
@@ -1,23 +1,25 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ffi::c_void;
-use std::path::Path;
-use std::rc::Rc;
 
-use crate::env::memory::{DefaultMemAppStore, DefaultMemAppTemplateStore, DefaultMemoryEnv};
-use crate::{gas::DefaultGasEstimator, storage::StorageBuilderFn};
-use crate::{Config, Context, DefaultRuntime, ExternImport};
+use crate::Context;
 
-use svm_codec::api::builder::{AppTxBuilder, DeployAppTemplateBuilder, SpawnAppBuilder};
-use svm_layout::DataLayout;
-use svm_storage::{
-    app::{AppKVStore, AppStorage},
-    kv::{FakeKV, StatefulKV},
+use svm_codec::api::builder::{
+    AppTxBuilder, DeployAppTemplateBuilder, SpawnAppBuilder, UpgradeAppBuilder,
 };
-use svm_types::{gas::MaybeGas, receipt::Log, Address, AppAddr, State, TemplateAddr, WasmValue};
+use svm_layout::DataLayout;
+use svm_storage::app::{AppKVStore, AppStorage};
+use svm_types::{gas::MaybeGas, receipt::Log, Address, AppAddr, TemplateAddr, WasmValue};
 
 use wasmer::{Export, ImportObject, Instance, Memory, MemoryType, Module, Pages, Store};
 
+mod chain;
+pub use chain::{TestChain, TestReceipt};
+
+pub use crate::runtime::{
+    create_memory_runtime, memory_state_kv_init, runtime_memory_env_builder,
+    runtime_memory_storage_builder,
+};
+
 pub enum WasmFile<'a> {
     Text(&'a str),
 
@@ -87,52 +89,6 @@ pub fn blank_storage(app_addr: &Address, layout: &DataLayout) -> AppStorage {
     AppStorage::new(layout.clone(), app_kv)
 }
 
-/// Returns a new in-memory stateful-kv.
-/// It should be used for managing apps' storage.
-pub fn memory_state_kv_init() -> Rc<RefCell<dyn StatefulKV>> {
-    Rc::new(RefCell::new(FakeKV::new()))
-}
-
-/// Creates an in-memory `Runtime` backed by key-value and host vmcalls (`imports`).
-pub fn create_memory_runtime(
-    state_kv: &Rc<RefCell<dyn StatefulKV>>,
-    imports: &Vec<ExternImport>,
-) -> DefaultRuntime<DefaultMemoryEnv, DefaultGasEstimator> {
-    let storage_builder = runtime_memory_storage_builder(state_kv);
-
-    let env = runtime_memory_env_builder();
-    let kv_path = Path::new("mem");
-
-    DefaultRuntime::new(env, &kv_path, imports, Box::new(storage_builder))
-}
-
-/// Returns a function (wrapped inside `Box`) that initializes an App's storage client.
-pub fn runtime_memory_storage_builder(
-    state_kv: &Rc<RefCell<dyn StatefulKV>>,
-) -> Box<StorageBuilderFn> {
-    let state_kv = Rc::clone(state_kv);
-
-    let func = move |app_addr: &AppAddr, state: &State, layout: &DataLayout, _config: &Config| {
-        let app_addr = app_addr.inner();
-        let app_kv = AppKVStore::new(app_addr.clone(), &state_kv);
-
-        let mut storage = AppStorage::new(layout.clone(), app_kv);
-        storage.rewind(state);
-
-        storage
-    };
-
-    Box::new(func)
-}
-
-/// Creates a new in-memory runtime environment.
-pub fn runtime_memory_env_builder() -> DefaultMemoryEnv {
-    let template_store = DefaultMemAppTemplateStore::new();
-    let app_store = DefaultMemAppStore::new();
-
-    DefaultMemoryEnv::new(app_store, template_store)
-}
-
 /// Synthesizes a raw deploy-template transaction.
 pub fn build_template(version: u32, name: &str, data: DataLayout, wasm: WasmFile) -> Vec<u8> {
     let wasm = wasm.into_bytes();
@@ -171,3 +127,23 @@ pub fn build_app_tx(version: u32, app_addr: &AppAddr, func: &str, calldata: &Vec
         .with_calldata(calldata)
         .build()
 }
+
+/// Synthesizes a raw upgrade-app transaction.
+pub fn build_upgrade_app(
+    version: u32,
+    app_addr: &AppAddr,
+    template: &TemplateAddr,
+    migration: Option<&str>,
+    calldata: &Vec<u8>,
+) -> Vec<u8> {
+    let builder = UpgradeAppBuilder::new()
+        .with_version(version)
+        .with_app(app_addr)
+        .with_template(template)
+        .with_calldata(calldata);
+
+    match migration {
+        Some(migration) => builder.with_migration(migration).build(),
+        None => builder.build(),
+    }
+}
@@ -1,27 +1,84 @@
+use wasmer::RuntimeError;
+
 use crate::{use_gas, Context};
 
 use svm_types::receipt::Log;
 
-pub fn log(ctx: &mut Context, msg_ptr: u32, msg_len: u32, code: u32) {
-    use_gas!("log", ctx);
+/// The byte-size of each topic of an event emitted by `emit`.
+pub const TOPIC_SIZE: usize = 32;
 
-    let start = msg_ptr as usize;
-    let end = start + msg_len as usize;
+/// Appends a new `Log` to the running App's logs.
+///
+/// The logged message is paid for per-byte, so when there is not enough gas left
+/// the vmcall traps (dropping the `Log`) and the execution ends with an `OOG` receipt.
+///
+/// Only the lowest byte of `code` is kept.
+pub fn log(ctx: &mut Context, msg_ptr: u32, msg_len: u32, code: u32) -> Result<(), RuntimeError> {
+    use_gas!("log", ctx, msg_len).map_err(|e| RuntimeError::new(e.to_string()))?;
 
-    let msg: Vec<u8> = {
-        let borrow = ctx.borrow();
-        let memory = borrow.get_memory();
+    let msg = read_memory(ctx, msg_ptr, msg_len as usize)?;
+    let log = Log::new(msg, code as u8);
 
-        memory.view()[start..end]
-            .iter()
-            .map(|cell| cell.get())
-            .collect()
-    };
+    ctx.borrow_mut().logs.push(log);
 
-    let log = Log {
-        msg,
-        code: code as u8,
-    };
+    Ok(())
+}
 
-    ctx.borrow_mut().logs.push(log);
+/// Appends a new event to the running App's logs.
+///
+/// The `topics_count` topics are laid out in memory one after the other starting at `topics_ptr`,
+/// each one of `TOPIC_SIZE` bytes. The event's ABI-encoded data starts at `data_ptr` (of byte-length `data_len`).
+///
+/// Similarly to `log`, the vmcall traps when there is not enough gas left for the event.
+pub fn emit(
+    ctx: &mut Context,
+    topics_ptr: u32,
+    topics_count: u32,
+    data_ptr: u32,
+    data_len: u32,
+) -> Result<(), RuntimeError> {
+    use_gas!("emit", ctx, topics_count, data_len).map_err(|e| RuntimeError::new(e.to_string()))?;
+
+    let topics_len = (topics_count as usize)
+        .checked_mul(TOPIC_SIZE)
+        .ok_or_else(|| {
+            RuntimeError::new(format!("`svm_emit` has too many topics ({})", topics_count))
+        })?;
+
+    let topics = read_memory(ctx, topics_ptr, topics_len)?
+        .chunks_exact(TOPIC_SIZE)
+        .map(|topic| topic.to_vec())
+        .collect();
+
+    let data = read_memory(ctx, data_ptr, data_len as usize)?;
+    let event = Log::event(topics, data);
+
+    ctx.borrow_mut().logs.push(event);
+
+    Ok(())
+}
+
+/// Copies `len` bytes of the App's memory starting at offset `ptr`.
+///
+/// Traps when the range doesn't fit into the memory (both are controlled by the App).
+pub(super) fn read_memory(ctx: &Context, ptr: u32, len: usize) -> Result<Vec<u8>, RuntimeError> {
+    let borrow = ctx.borrow();
+    let view = borrow.get_memory().view::<u8>();
+
+    let start = ptr as usize;
+    let end = start
+        .checked_add(len)
+        .filter(|end| *end <= view.len())
+        .ok_or_else(|| {
+            let msg = format!(
+                "memory range of {} bytes at offset {} is out of bounds",
+                len, ptr
+            );
+
+            RuntimeError::new(msg)
+        })?;
+
+    let bytes = view[start..end].iter().map(|cell| cell.get()).collect();
+
+    Ok(bytes)
 }
@@ -1,16 +1,24 @@
 use wasmer::{Exports, Function, ImportObject, Store};
 
+use svm_codec::api::raw::SVM_IMPORTS;
+
 use crate::Context;
 
 mod calldata;
 mod logs;
 mod returndata;
+mod revert;
 mod storage;
+mod value;
 
 pub use calldata::{calldata_len, calldata_offset};
-pub use logs::log;
+pub use logs::{emit, log, TOPIC_SIZE};
 pub use returndata::set_returndata;
-pub use storage::{get32, get64, load160, set32, set64, store160};
+pub use revert::{revert, MAX_REVERT_DATA_LEN};
+pub use storage::{
+    get32, get64, load128, load160, load256, set32, set64, store128, store160, store256,
+};
+pub use value::value;
 
 macro_rules! func {
     ($store:ident, $ctx:ident, $f:expr) => {{
@@ -21,19 +29,32 @@ macro_rules! func {
     }};
 }
 
+/// Registers the `SVM` vmcalls listed by `svm_codec::api::raw::SVM_IMPORTS` into `ns`.
 pub fn wasmer_register(store: &Store, ctx: &Context, ns: &mut Exports) {
-    ns.insert("svm_calldata_offset", func!(store, ctx, calldata_offset));
-    ns.insert("svm_calldata_len", func!(store, ctx, calldata_len));
-    ns.insert("svm_set_returndata", func!(store, ctx, set_returndata));
-
-    ns.insert("svm_get32", func!(store, ctx, get32));
-    ns.insert("svm_set32", func!(store, ctx, set32));
-
-    ns.insert("svm_get64", func!(store, ctx, get64));
-    ns.insert("svm_set64", func!(store, ctx, set64));
-
-    ns.insert("svm_load160", func!(store, ctx, load160));
-    ns.insert("svm_store160", func!(store, ctx, store160));
+    for (name, _params, _returns) in SVM_IMPORTS.iter() {
+        ns.insert(*name, vmcall(store, ctx, name));
+    }
+}
 
-    ns.insert("svm_log", func!(store, ctx, log));
+fn vmcall(store: &Store, ctx: &Context, name: &str) -> Function {
+    match name {
+        "svm_calldata_offset" => func!(store, ctx, calldata_offset),
+        "svm_calldata_len" => func!(store, ctx, calldata_len),
+        "svm_set_returndata" => func!(store, ctx, set_returndata),
+        "svm_get32" => func!(store, ctx, get32),
+        "svm_set32" => func!(store, ctx, set32),
+        "svm_get64" => func!(store, ctx, get64),
+        "svm_set64" => func!(store, ctx, set64),
+        "svm_load160" => func!(store, ctx, load160),
+        "svm_store160" => func!(store, ctx, store160),
+        "svm_load128" => func!(store, ctx, load128),
+        "svm_store128" => func!(store, ctx, store128),
+        "svm_load256" => func!(store, ctx, load256),
+        "svm_store256" => func!(store, ctx, store256),
+        "svm_log" => func!(store, ctx, log),
+        "svm_emit" => func!(store, ctx, emit),
+        "svm_revert" => func!(store, ctx, revert),
+        "svm_value" => func!(store, ctx, value),
+        _ => unreachable!("vmcall `{}` has no implementation", name),
+    }
 }
@@ -0,0 +1,38 @@
+use wasmer::RuntimeError;
+
+use crate::{use_gas, Context};
+
+use super::logs::read_memory;
+
+/// The maximum byte-length of the revert data.
+/// Passing longer data to `revert` traps (and the execution ends with a `FuncFailed` receipt).
+pub const MAX_REVERT_DATA_LEN: usize = 1024;
+
+/// Reverts the running transaction with the revert data starting at memory offset `data_ptr`
+/// (of byte-length `data_len`, at most `MAX_REVERT_DATA_LEN` bytes).
+///
+/// The App is expected to halt right after calling `revert` (the SDK traps).
+/// The execution then ends with a `Reverted` receipt and its storage changes are discarded.
+///
+/// The data is paid for per-byte. When there is not enough gas left
+/// the vmcall traps and the execution ends with an `OOG` receipt instead.
+pub fn revert(ctx: &mut Context, data_ptr: u32, data_len: u32) -> Result<(), RuntimeError> {
+    let data_len = data_len as usize;
+
+    if data_len > MAX_REVERT_DATA_LEN {
+        let msg = format!(
+            "`svm_revert` data of {} bytes is too long (at most {} bytes are allowed)",
+            data_len, MAX_REVERT_DATA_LEN
+        );
+
+        return Err(RuntimeError::new(msg));
+    }
+
+    use_gas!("revert", ctx, data_len).map_err(|e| RuntimeError::new(e.to_string()))?;
+
+    let data = read_memory(ctx, data_ptr, data_len)?;
+
+    ctx.borrow_mut().set_revert_data(data);
+
+    Ok(())
+}
@@ -26,11 +26,10 @@ macro_rules! store_n_impl {
 
 macro_rules! load_n_impl {
     ($nbytes:expr, $ctx:ident, $var_id:expr, $mem_ptr:expr) => {{
-        use svm_layout::VarId;
-
-        let storage = &$ctx.borrow().storage;
-
-        let bytes = storage.read_var(VarId($var_id));
+        let bytes = match read_var($ctx, $var_id) {
+            Some(bytes) => bytes,
+            None => return,
+        };
         let nbytes = bytes.len();
         assert_eq!(nbytes, $nbytes);
 
@@ -70,6 +69,54 @@ pub fn load160(ctx: &mut Context, var_id: u32, mem_ptr: u32) {
     load_n_impl!(20, ctx, var_id, mem_ptr);
 }
 
+/// Stores memory cells `[mem_ptr, mem_ptr + 1, ..., mem_ptr + 15]` into variable `var_id`.
+///
+/// Used for `U128` variables (stored as their Big-Endian representation).
+///
+/// # Panics
+///
+/// Panics if variable `var_id`'s length isn't 16 bytes.
+pub fn store128(ctx: &mut Context, mem_ptr: u32, var_id: u32) {
+    use_gas!("store128", ctx);
+
+    store_n_impl!(16, ctx, mem_ptr, var_id);
+}
+
+/// Loads variable `var_id` data into memory cells `[mem_ptr, mem_ptr + 1, ..., mem_ptr + 15]`
+///
+/// # Panics
+///
+/// Panics if variable `var_id`'s length isn't 16 bytes.
+pub fn load128(ctx: &mut Context, var_id: u32, mem_ptr: u32) {
+    use_gas!("load128", ctx);
+
+    load_n_impl!(16, ctx, var_id, mem_ptr);
+}
+
+/// Stores memory cells `[mem_ptr, mem_ptr + 1, ..., mem_ptr + 31]` into variable `var_id`.
+///
+/// Used for `U256` variables (stored as their Big-Endian representation).
+///
+/// # Panics
+///
+/// Panics if variable `var_id`'s length isn't 32 bytes.
+pub fn store256(ctx: &mut Context, mem_ptr: u32, var_id: u32) {
+    use_gas!("store256", ctx);
+
+    store_n_impl!(32, ctx, mem_ptr, var_id);
+}
+
+/// Loads variable `var_id` data into memory cells `[mem_ptr, mem_ptr + 1, ..., mem_ptr + 31]`
+///
+/// # Panics
+///
+/// Panics if variable `var_id`'s length isn't 32 bytes.
+pub fn load256(ctx: &mut Context, var_id: u32, mem_ptr: u32) {
+    use_gas!("load256", ctx);
+
+    load_n_impl!(32, ctx, var_id, mem_ptr);
+}
+
 /// Returns the data stored by variable `var_id` as 32-bit integer.
 ///
 /// # Panics
@@ -78,9 +125,10 @@ pub fn load160(ctx: &mut Context, var_id: u32, mem_ptr: u32) {
 pub fn get32(ctx: &mut Context, var_id: u32) -> u32 {
     use_gas!("get32", ctx);
 
-    let storage = &ctx.borrow().storage;
-
-    let bytes = storage.read_var(VarId(var_id));
+    let bytes = match read_var(ctx, var_id) {
+        Some(bytes) => bytes,
+        None => return 0,
+    };
     let nbytes = bytes.len();
 
     assert!(nbytes <= 4);
@@ -121,9 +169,10 @@ pub fn set32(ctx: &mut Context, var_id: u32, value: u32) {
 pub fn get64(ctx: &mut Context, var_id: u32) -> u64 {
     use_gas!("get64", ctx);
 
-    let storage = &ctx.borrow().storage;
-
-    let bytes = storage.read_var(VarId(var_id));
+    let bytes = match read_var(ctx, var_id) {
+        Some(bytes) => bytes,
+        None => return 0,
+    };
     let nbytes = bytes.len();
 
     assert!(nbytes <= 8);
@@ -151,3 +200,20 @@ pub fn set64(ctx: &mut Context, var_id: u32, value: u64) {
 
     storage.write_var(VarId(var_id), buf);
 }
+
+/// Reads variable `var_id`.
+///
+/// When the underlying key-value store fails, the error is recorded by the `Context`
+/// (the execution will end with a `StorageFailed` receipt) and `None` is returned.
+fn read_var(ctx: &Context, var_id: u32) -> Option<Vec<u8>> {
+    let res = ctx.borrow().storage.read_var(VarId(var_id));
+
+    match res {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            ctx.borrow_mut().set_storage_error(err);
+
+            None
+        }
+    }
+}
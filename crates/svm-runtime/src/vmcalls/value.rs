@@ -0,0 +1,10 @@
+use crate::use_gas;
+use crate::Context;
+
+/// Returns the amount of coins transferred along with the running transaction
+/// (see `svm_types::HostCtx`).
+pub fn value(ctx: &mut Context) -> u64 {
+    use_gas!("value", ctx);
+
+    ctx.borrow().host_ctx.value
+}
@@ -1,17 +1,28 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::json;
+
 use svm_abi_decoder::{Cursor, Decoder};
 use svm_abi_encoder::Encoder;
 
 use svm_sdk::Address as AbiAddr;
 
-use svm_codec::api::raw::Field;
-use svm_codec::error::ParseError;
+use svm_codec::api::raw::{self, Field};
+use svm_codec::error::{EnvelopeError, ParseError};
 
 use svm_gas::error::ProgramError;
-use svm_layout::{DataLayout, VarId};
+use svm_layout::{DataLayout, VarId, VarType};
+use svm_nibble::NibbleWriter;
+use svm_runtime::testing::TestChain;
 use svm_runtime::{error::ValidateError, testing, Runtime};
 
-use svm_types::receipt::{ExecReceipt, Log, SpawnAppReceipt, TemplateReceipt};
-use svm_types::{gas::MaybeGas, Address};
+use svm_types::receipt::{
+    ExecReceipt, Log, ReceiptError, SpawnAppReceipt, TemplateReceipt, UpgradeAppReceipt,
+};
+use svm_types::{gas::MaybeGas, Address, AppAddr, CreatorAddr, State, TemplateAddr};
+use svm_types::{Envelope, HostCtx, PublicKey, Signature, TxKind};
 
 macro_rules! default_runtime {
     () => {{
@@ -20,8 +31,6 @@ macro_rules! default_runtime {
         let state_kv = testing::memory_state_kv_init();
         let imports = Vec::new();
 
-        let imports = Box::leak(Box::new(imports));
-
         testing::create_memory_runtime(&state_kv, imports)
     }};
 }
@@ -29,7 +38,8 @@ macro_rules! default_runtime {
 #[test]
 fn default_runtime_validate_template_invalid_raw_format() {
     let runtime = default_runtime!();
-    let bytes = vec![0xFF, 0xFF];
+    // a valid version (`0`) followed by truncated bytes
+    let bytes = vec![0x0F, 0xFF];
 
     let parse_err = ParseError::NotEnoughBytes(Field::NameLength);
     let expected = Err(ValidateError::Parse(parse_err));
@@ -62,7 +72,8 @@ fn default_runtime_validate_template_invalid_wasm() {
 #[test]
 fn default_runtime_validate_app_invalid_raw_format() {
     let runtime = default_runtime!();
-    let bytes = vec![0xFF, 0xFF];
+    // a valid version (`0`) followed by truncated bytes
+    let bytes = vec![0x0F, 0xFF];
 
     let parse_err = ParseError::NotEnoughBytes(Field::TemplateAddr);
     let expected = Err(ValidateError::Parse(parse_err));
@@ -75,7 +86,8 @@ fn default_runtime_validate_app_invalid_raw_format() {
 fn default_runtime_validate_tx_invalid_raw_format() {
     let runtime = default_runtime!();
 
-    let bytes = vec![0xFF, 0xFF];
+    // a valid version (`0`) followed by truncated bytes
+    let bytes = vec![0x0F, 0xFF];
 
     let parse_err = ParseError::NotEnoughBytes(Field::AppAddr);
     let expected = Err(ValidateError::Parse(parse_err));
@@ -84,6 +96,152 @@ fn default_runtime_validate_tx_invalid_raw_format() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn default_runtime_validate_tx_unsupported_version() {
+    let runtime = default_runtime!();
+
+    // declares version `4095`
+    let bytes = vec![0xFF, 0xFF];
+
+    let parse_err = ParseError::InvalidProtocolVersion(4095);
+    let expected = Err(ValidateError::Parse(parse_err));
+
+    let actual = runtime.validate_tx(&bytes);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn default_runtime_validate_tx_inactive_version() {
+    let mut runtime = default_runtime!();
+
+    let app_addr: AppAddr = Address::of("my-app").into();
+    let bytes = testing::build_app_tx(0, &app_addr, "do_something", &vec![]);
+
+    let mut versions = raw::VersionSchedule::default();
+    versions.retire(TxKind::ExecApp, 0, 10);
+    runtime.set_version_schedule(versions);
+
+    runtime.set_layer(9);
+    assert_eq!(runtime.validate_tx(&bytes), Ok(app_addr));
+
+    runtime.set_layer(10);
+
+    let parse_err = ParseError::InvalidProtocolVersion(0);
+    let expected = Err(ValidateError::Parse(parse_err));
+
+    let actual = runtime.validate_tx(&bytes);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn default_runtime_exec_app_inactive_version() {
+    let mut runtime = default_runtime!();
+
+    let app_addr: AppAddr = Address::of("my-app").into();
+    let bytes = testing::build_app_tx(0, &app_addr, "do_something", &vec![]);
+
+    let mut versions = raw::VersionSchedule::default();
+    versions.retire(TxKind::ExecApp, 0, 10);
+    runtime.set_version_schedule(versions);
+    runtime.set_layer(10);
+
+    let receipt = runtime.exec_app(
+        &bytes,
+        &HostCtx::default(),
+        &State::empty(),
+        MaybeGas::new(),
+    );
+
+    assert!(!receipt.success);
+    assert_eq!(
+        receipt.error,
+        Some(ReceiptError::InvalidTransaction {
+            msg: ParseError::InvalidProtocolVersion(0).to_string()
+        })
+    );
+}
+
+#[test]
+fn default_runtime_deploy_template_invalid_raw_format() {
+    let mut runtime = default_runtime!();
+
+    let author = Address::of("author").into();
+    let bytes = vec![0x0F, 0xFF];
+
+    let receipt = runtime.deploy_template(&bytes, &author, MaybeGas::new());
+
+    assert!(!receipt.success);
+    assert!(matches!(
+        receipt.error,
+        Some(ReceiptError::InvalidTransaction { .. })
+    ));
+}
+
+fn build_signed_envelope(nonce: u64) -> Vec<u8> {
+    let mut envelope = Envelope {
+        version: 0,
+        kind: TxKind::ExecApp,
+        sender: Address::of("@sender"),
+        nonce,
+        gas_limit: 1000,
+        gas_price: 1,
+        value: 0,
+        payload: vec![0xFF; 4],
+        public_key: PublicKey::from([0; 32]),
+        signature: Signature::from([0; 64]),
+    };
+
+    raw::sign_envelope(&mut envelope, &[0x11; 32]).unwrap();
+
+    let mut w = NibbleWriter::new();
+    raw::encode_envelope(&envelope, &mut w);
+
+    w.into_bytes()
+}
+
+#[test]
+fn default_runtime_validate_envelope_without_verification() {
+    let runtime = default_runtime!();
+
+    let mut bytes = build_signed_envelope(1);
+
+    // corrupting the signature
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+
+    let envelope = runtime.validate_envelope(&bytes, 2).unwrap();
+    assert_eq!(envelope.nonce, 1);
+}
+
+#[test]
+fn default_runtime_validate_envelope_with_verification() {
+    let mut runtime = default_runtime!();
+    runtime.set_envelope_verification(true);
+
+    let bytes = build_signed_envelope(1);
+
+    let envelope = runtime.validate_envelope(&bytes, 1).unwrap();
+    assert_eq!(envelope.payload, vec![0xFF; 4]);
+
+    let err = EnvelopeError::NonceMismatch {
+        expected: 2,
+        actual: 1,
+    };
+    let expected = Err(ValidateError::Envelope(err));
+
+    let actual = runtime.validate_envelope(&bytes, 2);
+    assert_eq!(expected, actual);
+
+    let mut bytes = bytes;
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+
+    let expected = Err(ValidateError::Envelope(EnvelopeError::InvalidSignature));
+
+    let actual = runtime.validate_envelope(&bytes, 1);
+    assert_eq!(expected, actual);
+}
+
 #[test]
 fn default_runtime_deploy_template_reaches_oog() {
     let mut runtime = default_runtime!();
@@ -154,13 +312,14 @@ fn default_runtime_spawn_app_with_ctor_reaches_oog() {
     let bytes = testing::build_app(version, &template_addr, name, ctor, &calldata);
     let maybe_gas = MaybeGas::with(0);
 
-    let log = Log {
+    let log = Log::Message {
         msg: b"not enough gas (installation_gas = 33000) for installation".to_vec(),
         code: 1,
+        topics: Vec::new(),
     };
 
     let expected = SpawnAppReceipt::new_oog(vec![log]);
-    let actual = runtime.spawn_app(&bytes, &creator, maybe_gas);
+    let actual = runtime.spawn_app(&bytes, &creator, &HostCtx::default(), maybe_gas);
     assert_eq!(expected, actual);
 }
 
@@ -198,7 +357,7 @@ fn default_runtime_spawn_app_with_ctor_with_enough_gas() {
     let bytes = testing::build_app(version, &template_addr, name, ctor, &calldata);
     let gas_limit = MaybeGas::with(1_000_000);
 
-    let receipt = runtime.spawn_app(&bytes, &creator, gas_limit);
+    let receipt = runtime.spawn_app(&bytes, &creator, &HostCtx::default(), gas_limit);
     assert!(receipt.success);
     assert!(receipt.gas_used.is_some());
 
@@ -206,7 +365,7 @@ fn default_runtime_spawn_app_with_ctor_with_enough_gas() {
     let state = receipt.get_init_state();
     let storage = runtime.open_app_storage(&addr, &state, &layout);
 
-    let var = storage.read_var(VarId(0));
+    let var = storage.read_var(VarId(0)).unwrap();
     assert_eq!(var, 10_20_30_40_50_60_70_80u64.to_le_bytes());
 }
 
@@ -238,7 +397,7 @@ fn default_runtime_calldata_returndata() {
     let calldata = vec![];
     let creator = Address::of("creator").into();
     let bytes = testing::build_app(version, &template_addr, name, ctor, &calldata);
-    let receipt = runtime.spawn_app(&bytes, &creator, maybe_gas);
+    let receipt = runtime.spawn_app(&bytes, &creator, &HostCtx::default(), maybe_gas);
     assert!(receipt.success);
 
     let app_addr = receipt.get_app_addr();
@@ -253,7 +412,7 @@ fn default_runtime_calldata_returndata() {
 
     let bytes = testing::build_app_tx(version, &app_addr, func, &calldata);
 
-    let receipt = runtime.exec_app(&bytes, &init_state, maybe_gas);
+    let receipt = runtime.exec_app(&bytes, &HostCtx::default(), &init_state, maybe_gas);
     assert!(receipt.success);
 
     let state = receipt.get_new_state();
@@ -264,7 +423,7 @@ fn default_runtime_calldata_returndata() {
 
     let bytes = testing::build_app_tx(version, &app_addr, func, &calldata);
 
-    let receipt = runtime.exec_app(&bytes, &state, maybe_gas);
+    let receipt = runtime.exec_app(&bytes, &HostCtx::default(), &state, maybe_gas);
     assert!(receipt.success);
 
     let raw = receipt.returndata.unwrap();
@@ -306,7 +465,7 @@ fn default_runtime_exec_app_reaches_oog() {
     let calldata = vec![];
 
     let bytes = testing::build_app(version, &template_addr, name, ctor, &calldata);
-    let receipt = runtime.spawn_app(&bytes, &creator, maybe_gas);
+    let receipt = runtime.spawn_app(&bytes, &creator, &HostCtx::default(), maybe_gas);
 
     let app_addr = receipt.get_app_addr();
     let init_state = receipt.get_init_state();
@@ -319,7 +478,515 @@ fn default_runtime_exec_app_reaches_oog() {
     let logs = Vec::new();
 
     let expected = ExecReceipt::new_oog(logs);
-    let actual = runtime.exec_app(&bytes, &init_state, maybe_gas);
+    let actual = runtime.exec_app(&bytes, &HostCtx::default(), &init_state, maybe_gas);
 
     assert_eq!(expected, actual)
 }
+
+#[test]
+fn default_runtime_exec_app_reverts() {
+    let mut runtime = default_runtime!();
+
+    // 1) deploying the template
+    let version = 0;
+    let author = Address::of("author").into();
+    let creator = Address::of("creator").into();
+    let maybe_gas = MaybeGas::new();
+    let layout: DataLayout = vec![8].into();
+
+    let bytes = testing::build_template(
+        version,
+        "My Template",
+        layout.clone(),
+        include_str!("wasm/runtime_app_revert.wast").into(),
+    );
+
+    let receipt = runtime.deploy_template(&bytes, &author, maybe_gas);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) spawn app
+    let calldata = vec![];
+    let bytes = testing::build_app(version, &template_addr, "My App", "ctor", &calldata);
+    let receipt = runtime.spawn_app(&bytes, &creator, &HostCtx::default(), maybe_gas);
+    assert!(receipt.success);
+
+    let app_addr = receipt.get_app_addr().clone();
+    let init_state = receipt.get_init_state().clone();
+
+    // 3) executing an app-transaction (calling `svm_revert`)
+    let bytes = testing::build_app_tx(version, &app_addr, "withdraw", &calldata);
+
+    let expected = ExecReceipt::from_err(
+        ReceiptError::Reverted {
+            data: b"not enough funds".to_vec(),
+        },
+        Vec::new(),
+    );
+
+    let actual = runtime.exec_app(&bytes, &HostCtx::default(), &init_state, maybe_gas);
+    assert_eq!(expected, actual);
+
+    // the storage changes have been discarded
+    let storage = runtime.open_app_storage(&app_addr, &init_state, &layout);
+
+    let var = storage.read_var(VarId(0)).unwrap();
+    assert_eq!(var, vec![0; 8]);
+}
+
+/// Deploys `runtime_calldata.wasm` under `layout`.
+///
+/// Since the template `Address` is derived from its code, each `revision` appends
+/// a distinct custom section (named after the `revision`) to the wasm.
+fn deploy_calldata_template<R: Runtime>(
+    runtime: &mut R,
+    layout: DataLayout,
+    revision: u8,
+) -> TemplateAddr {
+    let author = Address::of("author").into();
+
+    let mut wasm = include_bytes!("wasm/runtime_calldata.wasm").to_vec();
+    wasm.extend_from_slice(&[0x00, 0x02, 0x01, b'0' + revision]);
+
+    let bytes = testing::build_template(0, "My Template", layout, (&wasm[..]).into());
+
+    let receipt = runtime.deploy_template(&bytes, &author, MaybeGas::new());
+    assert!(receipt.success);
+
+    receipt.addr.unwrap()
+}
+
+fn spawn_calldata_app<R: Runtime>(
+    runtime: &mut R,
+    template_addr: &TemplateAddr,
+    creator: &CreatorAddr,
+) -> (AppAddr, State) {
+    let calldata = vec![];
+    let bytes = testing::build_app(0, template_addr, "My App", "initialize", &calldata);
+
+    let receipt = runtime.spawn_app(&bytes, creator, &HostCtx::default(), MaybeGas::new());
+    assert!(receipt.success);
+
+    (receipt.get_app_addr().clone(), receipt.get_init_state().clone())
+}
+
+#[test]
+fn default_runtime_upgrade_app_with_migration() {
+    let mut runtime = default_runtime!();
+    let creator = Address::of("creator").into();
+
+    // 1) deploying the template and spawning an app out of it
+    let template_addr = deploy_calldata_template(&mut runtime, vec![20].into(), 1);
+    let (app_addr, init_state) = spawn_calldata_app(&mut runtime, &template_addr, &creator);
+
+    // 2) deploying a new template version (appending a variable)
+    let new_template_addr = deploy_calldata_template(&mut runtime, vec![20, 8].into(), 2);
+
+    // 3) upgrading the app, the migration stores a new address
+    let msg: AbiAddr = [0x20; 20].into();
+
+    let mut calldata = Vec::new();
+    msg.encode(&mut calldata);
+
+    let bytes = testing::build_upgrade_app(
+        0,
+        &app_addr,
+        &new_template_addr,
+        Some("store_addr"),
+        &calldata,
+    );
+
+    let receipt = runtime.upgrade_app(&bytes, &creator, &init_state, MaybeGas::new());
+    assert!(receipt.success);
+    assert_eq!(receipt.get_template_addr(), &new_template_addr);
+
+    let state = receipt.get_new_state();
+
+    // 4) the app now runs the new template against its migrated storage
+    let calldata = vec![];
+    let bytes = testing::build_app_tx(0, &app_addr, "return_addr", &calldata);
+
+    let receipt = runtime.exec_app(&bytes, &HostCtx::default(), &state, MaybeGas::new());
+    assert!(receipt.success);
+
+    let raw = receipt.returndata.unwrap();
+    let mut cursor = Cursor::new(&raw);
+    let decoder = Decoder::new();
+
+    let decoded = decoder.decode_value(&mut cursor).unwrap();
+    let addr: AbiAddr = decoded.into();
+    assert_eq!(addr.as_slice(), &[0x20; 20]);
+}
+
+#[test]
+fn default_runtime_upgrade_app_not_authorized() {
+    let mut runtime = default_runtime!();
+    let creator = Address::of("creator").into();
+    let sender = Address::of("someone-else").into();
+
+    let template_addr = deploy_calldata_template(&mut runtime, vec![20].into(), 1);
+    let (app_addr, init_state) = spawn_calldata_app(&mut runtime, &template_addr, &creator);
+    let new_template_addr = deploy_calldata_template(&mut runtime, vec![20, 8].into(), 2);
+
+    let calldata = vec![];
+    let bytes = testing::build_upgrade_app(0, &app_addr, &new_template_addr, None, &calldata);
+
+    let expected =
+        UpgradeAppReceipt::from_err(ReceiptError::UpgradeNotAuthorized { app_addr }, Vec::new());
+
+    let actual = runtime.upgrade_app(&bytes, &sender, &init_state, MaybeGas::new());
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn default_runtime_upgrade_app_incompatible_layout() {
+    let mut runtime = default_runtime!();
+    let creator = Address::of("creator").into();
+
+    let template_addr = deploy_calldata_template(&mut runtime, vec![20].into(), 1);
+    let (app_addr, init_state) = spawn_calldata_app(&mut runtime, &template_addr, &creator);
+    let new_template_addr = deploy_calldata_template(&mut runtime, vec![8].into(), 2);
+
+    let calldata = vec![];
+    let bytes = testing::build_upgrade_app(0, &app_addr, &new_template_addr, None, &calldata);
+
+    let expected = UpgradeAppReceipt::from_err(
+        ReceiptError::IncompatibleLayout {
+            app_addr,
+            template_addr: new_template_addr,
+        },
+        Vec::new(),
+    );
+
+    let actual = runtime.upgrade_app(&bytes, &creator, &init_state, MaybeGas::new());
+    assert_eq!(expected, actual);
+}
+
+/// Returns a copy of the returned address bytes
+/// (a decoded `AbiAddr` points into the `returndata`, so it can't outlive it).
+fn decode_returned_addr(receipt: ExecReceipt) -> Vec<u8> {
+    let raw = receipt.returndata.unwrap();
+    let mut cursor = Cursor::new(&raw);
+    let decoder = Decoder::new();
+
+    let addr: AbiAddr = decoder.decode_value(&mut cursor).unwrap().into();
+    addr.as_slice().to_vec()
+}
+
+#[test]
+fn default_runtime_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let runtime = default_runtime!();
+
+    assert_send_sync(&runtime);
+}
+
+#[test]
+fn default_runtime_exec_app_concurrently() {
+    const NAPPS: u8 = 8;
+    const NROUNDS: u8 = 4;
+
+    let mut runtime = default_runtime!();
+    let creator = Address::of("creator").into();
+    let layout: DataLayout = vec![20].into();
+
+    // 1) each app is spawned out of its own template (since an app `Address` is derived from its template)
+    let apps: Vec<(AppAddr, State)> = (0..NAPPS)
+        .map(|i| {
+            let template_addr = deploy_calldata_template(&mut runtime, layout.clone(), i);
+
+            spawn_calldata_app(&mut runtime, &template_addr, &creator)
+        })
+        .collect();
+
+    let runtime = Arc::new(runtime);
+
+    // 2) executing transactions against all apps concurrently
+    let handles: Vec<_> = apps
+        .into_iter()
+        .enumerate()
+        .map(|(i, (app_addr, init_state))| {
+            let runtime = Arc::clone(&runtime);
+
+            thread::spawn(move || {
+                let mut state = init_state;
+
+                for round in 0..NROUNDS {
+                    let msg: AbiAddr = [i as u8 * NROUNDS + round; 20].into();
+
+                    let mut calldata = Vec::new();
+                    msg.encode(&mut calldata);
+
+                    let bytes = testing::build_app_tx(0, &app_addr, "store_addr", &calldata);
+                    let host_ctx = HostCtx::default();
+                    let receipt = runtime.exec_app(&bytes, &host_ctx, &state, MaybeGas::new());
+                    assert!(receipt.success);
+
+                    state = receipt.get_new_state().clone();
+                }
+
+                (app_addr, state)
+            })
+        })
+        .collect();
+
+    let results: Vec<(AppAddr, State)> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    // 3) each app has ended at its own `State`
+    let states: HashSet<&State> = results.iter().map(|(_, state)| state).collect();
+    assert_eq!(states.len(), NAPPS as usize);
+
+    // 4) each app's final `State` holds the last address stored by its own transactions
+    for (i, (app_addr, state)) in results.iter().enumerate() {
+        let expected = [i as u8 * NROUNDS + NROUNDS - 1; 20];
+
+        let storage = runtime.open_app_storage(app_addr, state, &layout);
+        assert_eq!(storage.read_var(VarId(0)).unwrap(), expected.to_vec());
+
+        let calldata = vec![];
+        let bytes = testing::build_app_tx(0, app_addr, "return_addr", &calldata);
+
+        let receipt = runtime.exec_app(&bytes, &HostCtx::default(), state, MaybeGas::new());
+        assert!(receipt.success);
+
+        assert_eq!(decode_returned_addr(receipt), expected.to_vec());
+    }
+}
+
+/// Appends a `svm_abi` custom section holding the JSON lines `records` to `wasm`
+/// (as done by the `svm-sdk` macros).
+fn with_abi_section(wasm: &[u8], records: &[serde_json::Value]) -> Vec<u8> {
+    fn push_varuint(mut n: usize, buf: &mut Vec<u8>) {
+        loop {
+            let byte = (n & 0x7F) as u8;
+            n >>= 7;
+
+            if n == 0 {
+                buf.push(byte);
+                break;
+            }
+
+            buf.push(byte | 0x80);
+        }
+    }
+
+    let name = b"svm_abi";
+    let payload: String = records.iter().map(|r| format!("{}\n", r)).collect();
+
+    let mut section = Vec::new();
+    push_varuint(name.len(), &mut section);
+    section.extend_from_slice(name);
+    section.extend_from_slice(payload.as_bytes());
+
+    let mut wasm = wasm.to_vec();
+    wasm.push(0x00);
+    push_varuint(section.len(), &mut wasm);
+    wasm.extend_from_slice(&section);
+
+    wasm
+}
+
+#[test]
+fn default_runtime_test_chain_calls_by_name() {
+    let mut chain = TestChain::new();
+
+    // 1) deploying the template (along with its ABI)
+    let abi = [
+        json!({ "endpoint": { "name": "initialize", "ctor": true, "params": [], "returns": [] } }),
+        json!({ "endpoint": {
+            "name": "store_addr",
+            "ctor": false,
+            "params": [{ "name": "addr", "type": "address" }],
+            "returns": []
+        } }),
+        json!({ "endpoint": { "name": "return_addr", "ctor": false, "params": [], "returns": ["address"] } }),
+    ];
+
+    let wasm = with_abi_section(include_bytes!("wasm/runtime_calldata.wasm"), &abi);
+    let template = chain.deploy("My Template", vec![20].into(), (&wasm[..]).into());
+
+    let endpoints = chain.abi(&template).unwrap()["endpoints"]
+        .as_array()
+        .unwrap();
+    assert_eq!(endpoints.len(), 3);
+
+    // 2) spawning an app
+    let app = chain.spawn(&template, "initialize", ()).unwrap();
+
+    // 3) calling the app, its state is tracked by the chain
+    let addr: AbiAddr = [0x10; 20].into();
+
+    let receipt = chain.call(&app, "store_addr", addr);
+    assert!(receipt.success);
+    assert!(receipt.returns.is_empty());
+
+    let receipt = chain.call(&app, "return_addr", ());
+    assert!(receipt.success);
+    assert_eq!(
+        receipt.returns,
+        vec![json!("1010101010101010101010101010101010101010")]
+    );
+
+    let storage = chain.storage(&app);
+    assert_eq!(storage.read_var(VarId(0)).unwrap(), vec![0x10; 20]);
+}
+
+#[test]
+#[should_panic(expected = "invalid arguments of `store_addr`")]
+fn default_runtime_test_chain_call_args_type_mismatch() {
+    let mut chain = TestChain::new();
+
+    let abi = [
+        json!({ "endpoint": { "name": "initialize", "ctor": true, "params": [], "returns": [] } }),
+        json!({ "endpoint": {
+            "name": "store_addr",
+            "ctor": false,
+            "params": [{ "name": "addr", "type": "address" }],
+            "returns": []
+        } }),
+    ];
+
+    let wasm = with_abi_section(include_bytes!("wasm/runtime_calldata.wasm"), &abi);
+    let template = chain.deploy("My Template", vec![20].into(), (&wasm[..]).into());
+    let app = chain.spawn(&template, "initialize", ()).unwrap();
+
+    // `store_addr` expects an `address`
+    chain.call(&app, "store_addr", 10u32);
+}
+
+#[test]
+fn default_runtime_test_chain_deploy_sdk() {
+    let mut chain = TestChain::new();
+
+    let abi = [
+        json!({ "endpoint": { "name": "initialize", "ctor": true, "params": [], "returns": [] } }),
+        json!({ "endpoint": {
+            "name": "store_addr",
+            "ctor": false,
+            "params": [{ "name": "addr", "type": "address" }],
+            "returns": []
+        } }),
+        json!({ "storage": {
+            "name": "Vars",
+            "vars": [{ "id": 0, "name": "owner", "type": "Address", "count": 1 }]
+        } }),
+    ];
+
+    // the `DataLayout` is derived out of the ABI `storage` record
+    let wasm = with_abi_section(include_bytes!("wasm/runtime_calldata.wasm"), &abi);
+    let template = chain.deploy_sdk("My Template", (&wasm[..]).into());
+
+    let layout = chain.layout(&template);
+    assert_eq!(layout.get_var(VarId(0)), (0, 20));
+
+    let schema = layout.schema().unwrap();
+    assert_eq!(schema.len(), 1);
+    assert_eq!(schema[0].name, "owner");
+    assert_eq!(schema[0].ty, VarType::Address);
+
+    let app = chain.spawn(&template, "initialize", ()).unwrap();
+
+    let addr: AbiAddr = [0x10; 20].into();
+    let receipt = chain.call(&app, "store_addr", addr);
+    assert!(receipt.success);
+
+    let storage = chain.storage(&app);
+    assert_eq!(storage.read_var(VarId(0)).unwrap(), vec![0x10; 20]);
+}
+
+#[test]
+fn default_runtime_test_chain_wat_file_and_layers() {
+    let mut chain = TestChain::new();
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/wasm/runtime_app_revert.wast"
+    );
+    let template = chain.deploy_file(path, vec![8].into());
+
+    // the template has no ABI
+    assert!(chain.abi(&template).is_none());
+
+    let app = chain.spawn(&template, "ctor", ()).unwrap();
+    let state = chain.state(&app).clone();
+
+    chain.advance_layers(2);
+    assert_eq!(chain.layer(), 2);
+
+    let receipt = chain.call(&app, "withdraw", ());
+
+    assert!(!receipt.success);
+    assert_eq!(
+        receipt.error,
+        Some(ReceiptError::Reverted {
+            data: b"not enough funds".to_vec()
+        })
+    );
+
+    // the app's state is left untouched
+    assert_eq!(chain.state(&app), &state);
+
+    let storage = chain.storage(&app);
+    assert_eq!(storage.read_var(VarId(0)).unwrap(), vec![0; 8]);
+}
+
+#[test]
+fn default_runtime_exec_app_charges_wasm_and_vmcalls_gas() {
+    let mut chain = TestChain::new();
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/wasm/runtime_app_gas.wast"
+    );
+    let template = chain.deploy_file(path, DataLayout::empty());
+    let app = chain.spawn(&template, "ctor", ()).unwrap();
+
+    // the wasm code costs `1000` and logging `Hello World` (11 bytes) costs `log_gas`
+    let wasm_gas = 1000;
+    let log_gas = svm_runtime::gas::LOG_BASE_GAS + 11 * svm_runtime::gas::LOG_BYTE_GAS;
+
+    // each of them fits the gas limit, but not their total
+    chain.set_gas_limit(MaybeGas::with(wasm_gas + log_gas - 1));
+
+    let receipt = chain.call(&app, "say_hello", ());
+    assert!(!receipt.success);
+    assert_eq!(receipt.error, Some(ReceiptError::OOG));
+
+    chain.set_gas_limit(MaybeGas::with(wasm_gas + log_gas));
+
+    let receipt = chain.call(&app, "say_hello", ());
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_used, MaybeGas::with(wasm_gas + log_gas));
+}
+
+#[test]
+fn default_runtime_test_chain_value() {
+    let mut chain = TestChain::new();
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/wasm/runtime_app_value.wast"
+    );
+    let template = chain.deploy_file(path, vec![8].into());
+
+    chain.set_value(10);
+    let app = chain.spawn(&template, "ctor", ()).unwrap();
+
+    let storage = chain.storage(&app);
+    assert_eq!(
+        storage.read_var(VarId(0)).unwrap(),
+        10u64.to_le_bytes().to_vec()
+    );
+
+    chain.set_value(5);
+    let receipt = chain.call(&app, "deposit", ());
+    assert!(receipt.success);
+
+    let storage = chain.storage(&app);
+    assert_eq!(
+        storage.read_var(VarId(0)).unwrap(),
+        15u64.to_le_bytes().to_vec()
+    );
+}
@@ -3,11 +3,13 @@ use maplit::hashmap;
 
 use std::ffi::c_void;
 
-use wasmer::{imports, Function, NativeFunc};
+use wasmer::{imports, Exports, Function, NativeFunc, Type};
+
+use svm_codec::api::raw::SVM_IMPORTS;
 
 use svm_layout::{DataLayout, VarId};
 use svm_runtime::{testing, vmcalls, Context};
-use svm_types::{gas::MaybeGas, receipt::Log, Address};
+use svm_types::{gas::MaybeGas, receipt::Log, Address, HostCtx, WasmType};
 
 macro_rules! assert_vars32 {
     ($instance:expr, $( $var_id:expr => $expected:expr), *) => {{
@@ -34,7 +36,7 @@ macro_rules! assert_storage {
         let storage = &$ctx.borrow().storage;
 
         $(
-            let actual = storage.read_var(VarId($var_id));
+            let actual = storage.read_var(VarId($var_id)).unwrap();
             assert_eq!(actual, $expected);
          )*
     }};
@@ -77,6 +79,37 @@ macro_rules! func {
     }};
 }
 
+#[test]
+fn vmcalls_registered_as_svm_imports() {
+    let app_addr = Address::of("my-app");
+    let layout: DataLayout = vec![4].into();
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new(MaybeGas::new(), storage);
+
+    let store = testing::wasmer_store();
+    let mut ns = Exports::new();
+    vmcalls::wasmer_register(&store, &ctx, &mut ns);
+
+    let wasmer_types = |types: &[WasmType]| -> Vec<Type> {
+        types
+            .iter()
+            .map(|ty| match ty {
+                WasmType::I32 => Type::I32,
+                WasmType::I64 => Type::I64,
+            })
+            .collect()
+    };
+
+    assert_eq!(ns.len(), SVM_IMPORTS.len());
+
+    for (name, params, returns) in SVM_IMPORTS.iter() {
+        let func = ns.get_function(name).unwrap();
+
+        assert_eq!(func.ty().params(), &wasmer_types(params)[..], "{}", name);
+        assert_eq!(func.ty().results(), &wasmer_types(returns)[..], "{}", name);
+    }
+}
+
 #[test]
 fn vmcalls_empty_wasm() {
     let wasm = r#"
@@ -92,6 +125,19 @@ fn vmcalls_empty_wasm() {
     testing::wasmer_instantiate(&store, &import_object, wasm, gas_limit);
 }
 
+#[test]
+#[should_panic(expected = "`Context` is already borrowed")]
+fn vmcalls_context_nested_borrow_panics() {
+    let app_addr = Address::of("my-app");
+    let layout: DataLayout = vec![4].into();
+    let storage = testing::blank_storage(&app_addr, &layout);
+
+    let ctx = Context::new(MaybeGas::new(), storage);
+
+    let _borrow = ctx.borrow();
+    let _nested = ctx.borrow_mut();
+}
+
 #[test]
 fn vmcalls_get32_set32() {
     let app_addr = Address::of("my-app");
@@ -242,6 +288,53 @@ fn vmcalls_store160() {
     assert_storage!(ctx, 0 => b"11223344556677889900");
 }
 
+#[test]
+fn vmcalls_load256_store256() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout: DataLayout = vec![32].into();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_load256" => func!(store, ctx, vmcalls::load256),
+            "svm_store256" => func!(store, ctx, vmcalls::store256),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/load256_store256.wast").into(),
+        gas_limit,
+    );
+
+    let value: Vec<u8> = (1..=32).collect();
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(value.iter()) {
+        cell.set(*byte);
+    }
+
+    let func: NativeFunc<(u32, u32)> = instance.exports.get_native_function("store").unwrap();
+    func.call(0, 0).expect("function has failed");
+
+    assert_storage!(ctx, 0 => value);
+
+    let func: NativeFunc<(u32, u32)> = instance.exports.get_native_function("load").unwrap();
+    let ptr = 64;
+    func.call(0, ptr).expect("function has failed");
+
+    let view = &memory.view::<u8>()[ptr as usize..(ptr as usize + 32)];
+    let bytes: Vec<u8> = view.iter().map(|cell| cell.get()).collect();
+
+    assert_eq!(bytes, value);
+}
+
 #[test]
 fn vmcalls_log() {
     let app_addr = Address::of("my-app");
@@ -283,9 +376,394 @@ fn vmcalls_log() {
 
     assert_eq!(
         logs,
-        vec![Log {
+        vec![Log::Message {
             msg: b"Hello World".to_vec(),
-            code: 200
+            code: 200,
+            topics: Vec::new(),
         }]
     );
 }
+
+#[test]
+fn vmcalls_log_truncates_code() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_log" => func!(store, ctx, vmcalls::log),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/log.wast").into(),
+        gas_limit,
+    );
+
+    let data = b"Hello World";
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(data) {
+        cell.set(*byte);
+    }
+
+    let func = instance
+        .exports
+        .get_function("sayHelloWithLargeCode")
+        .unwrap();
+    let _ = func.call(&[]).unwrap();
+
+    let logs = ctx.borrow_mut().take_logs();
+    assert_eq!(logs, vec![Log::new(b"Hello World".to_vec(), 200)]);
+}
+
+#[test]
+fn vmcalls_log_out_of_bounds_traps() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_log" => func!(store, ctx, vmcalls::log),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/log.wast").into(),
+        gas_limit,
+    );
+
+    let func = instance
+        .exports
+        .get_function("sayHelloOutOfBounds")
+        .unwrap();
+    let err = func.call(&[]).unwrap_err();
+
+    assert_eq!(
+        err.message(),
+        "memory range of 11 bytes at offset 65530 is out of bounds"
+    );
+
+    let logs = ctx.borrow_mut().take_logs();
+    assert!(logs.is_empty());
+}
+
+#[test]
+fn vmcalls_log_reaches_oog() {
+    let app_addr = Address::of("my-app");
+    let layout = DataLayout::empty();
+
+    // not enough for logging `Hello World` (11 bytes)
+    let log_gas = svm_runtime::gas::LOG_BASE_GAS + 10 * svm_runtime::gas::LOG_BYTE_GAS;
+    let gas_limit = MaybeGas::with(log_gas);
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_log" => func!(store, ctx, vmcalls::log),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/log.wast").into(),
+        MaybeGas::new(),
+    );
+
+    let func = instance.exports.get_function("sayHello").unwrap();
+    let err = func.call(&[]).unwrap_err();
+
+    assert_eq!(err.message(), "Reached Out of Gas");
+    assert!(ctx.borrow().is_oog());
+
+    let logs = ctx.borrow_mut().take_logs();
+    assert!(logs.is_empty());
+}
+
+#[test]
+fn vmcalls_revert() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_revert" => func!(store, ctx, vmcalls::revert),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/revert.wast").into(),
+        gas_limit,
+    );
+
+    let data = b"Not enough funds";
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(data) {
+        cell.set(*byte);
+    }
+
+    assert_eq!(ctx.borrow_mut().take_revert_data(), None);
+
+    let func = instance.exports.get_function("withdraw").unwrap();
+    assert!(func.call(&[]).is_err());
+
+    let data = ctx.borrow_mut().take_revert_data();
+    assert_eq!(data, Some(b"Not enough funds".to_vec()));
+}
+
+#[test]
+fn vmcalls_revert_reaches_oog() {
+    let app_addr = Address::of("my-app");
+    let layout = DataLayout::empty();
+
+    // not enough for reverting with `Not enough funds` (16 bytes)
+    let gas_limit = MaybeGas::with(15 * svm_runtime::gas::LOG_BYTE_GAS);
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_revert" => func!(store, ctx, vmcalls::revert),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/revert.wast").into(),
+        MaybeGas::new(),
+    );
+
+    let func = instance.exports.get_function("withdraw").unwrap();
+    let err = func.call(&[]).unwrap_err();
+
+    assert_eq!(err.message(), "Reached Out of Gas");
+    assert!(ctx.borrow().is_oog());
+    assert_eq!(ctx.borrow_mut().take_revert_data(), None);
+}
+
+#[test]
+fn vmcalls_revert_out_of_bounds_traps() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_revert" => func!(store, ctx, vmcalls::revert),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/revert.wast").into(),
+        gas_limit,
+    );
+
+    let func = instance
+        .exports
+        .get_function("withdrawOutOfBounds")
+        .unwrap();
+    let err = func.call(&[]).unwrap_err();
+
+    assert_eq!(
+        err.message(),
+        "memory range of 16 bytes at offset 4294967295 is out of bounds"
+    );
+    assert_eq!(ctx.borrow_mut().take_revert_data(), None);
+}
+
+#[test]
+fn vmcalls_revert_data_too_long_traps() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_revert" => func!(store, ctx, vmcalls::revert),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/revert.wast").into(),
+        gas_limit,
+    );
+
+    let func = instance.exports.get_function("withdrawTooLong").unwrap();
+    let err = func.call(&[]).unwrap_err();
+
+    assert!(err.to_string().contains("data of 1025 bytes is too long"));
+    assert_eq!(ctx.borrow_mut().take_revert_data(), None);
+}
+
+#[test]
+fn vmcalls_value() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new(gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "svm_value" => func!(store, ctx, vmcalls::value),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/value.wast").into(),
+        gas_limit,
+    );
+
+    let func: NativeFunc<(), u64> = instance.exports.get_native_function("get_value").unwrap();
+
+    assert_eq!(func.call().unwrap(), 0);
+
+    ctx.borrow_mut().host_ctx = HostCtx::with_value(100);
+
+    assert_eq!(func.call().unwrap(), 100);
+}
+
+#[test]
+fn vmcalls_emit() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_emit" => func!(store, ctx, vmcalls::emit),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/emit.wast").into(),
+        gas_limit,
+    );
+
+    // two topics (of `TOPIC_SIZE` bytes each) followed by the event's data
+    let mut data = vec![0x10; vmcalls::TOPIC_SIZE];
+    data.extend_from_slice(&[0x20; vmcalls::TOPIC_SIZE]);
+    data.extend_from_slice(&[0xA0, 0xB0, 0xC0]);
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(data) {
+        cell.set(byte);
+    }
+
+    let func = instance.exports.get_function("transfer").unwrap();
+    let _ = func.call(&[]).unwrap();
+
+    let logs = ctx.borrow_mut().take_logs();
+
+    assert_eq!(
+        logs,
+        vec![Log::event(
+            vec![
+                vec![0x10; vmcalls::TOPIC_SIZE],
+                vec![0x20; vmcalls::TOPIC_SIZE]
+            ],
+            vec![0xA0, 0xB0, 0xC0]
+        )]
+    );
+}
+
+#[test]
+fn vmcalls_emit_too_many_topics_traps() {
+    let app_addr = Address::of("my-app");
+    let gas_limit = MaybeGas::new();
+    let layout = DataLayout::empty();
+
+    let store = testing::wasmer_store();
+    let memory = testing::wasmer_memory(&store);
+    let storage = testing::blank_storage(&app_addr, &layout);
+    let ctx = Context::new_with_memory(memory.clone(), gas_limit, storage);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_emit" => func!(store, ctx, vmcalls::emit),
+        },
+    };
+
+    let instance = testing::wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/emit.wast").into(),
+        gas_limit,
+    );
+
+    let func = instance
+        .exports
+        .get_function("transferTooManyTopics")
+        .unwrap();
+    let err = func.call(&[]).unwrap_err();
+
+    assert!(err.message().ends_with("is out of bounds"));
+
+    let logs = ctx.borrow_mut().take_logs();
+    assert!(logs.is_empty());
+}
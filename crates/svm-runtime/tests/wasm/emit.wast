@@ -0,0 +1,18 @@
+(module
+  (func $emit (import "svm" "svm_emit") (param $topics_ptr i32) (param $topics_count i32) (param $data_ptr i32) (param $data_len i32))
+
+  (import "svm" "memory" (memory $mem 0))
+
+  (func (export "transfer")
+	i32.const 0   ;; topics_ptr
+	i32.const 2   ;; topics_count
+	i32.const 64  ;; data_ptr
+	i32.const 3   ;; data_len
+	call $emit)
+
+  (func (export "transferTooManyTopics")
+	i32.const 0   ;; topics_ptr
+	i32.const -1  ;; topics_count = u32::MAX
+	i32.const 64  ;; data_ptr
+	i32.const 3   ;; data_len
+	call $emit))
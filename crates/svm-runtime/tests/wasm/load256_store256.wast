@@ -0,0 +1,18 @@
+(module
+  (func $store256 (import "svm" "svm_store256") (param $mem_ptr i32) (param $var_id i32))
+  (func $load256 (import "svm" "svm_load256") (param $var_id i32) (param $mem_ptr i32))
+
+  (import "svm" "memory" (memory 1))
+
+  (func (export "ctor")
+  	nop)
+
+  (func (export "store") (param $var_id i32) (param $mem_ptr i32)
+	get_local $mem_ptr  ;; mem_ptr
+  	get_local $var_id   ;; var_id
+	call $store256)
+
+  (func (export "load") (param $var_id i32) (param $mem_ptr i32)
+  	get_local $var_id   ;; var_id
+  	get_local $mem_ptr  ;; mem_ptr
+	call $load256))
\ No newline at end of file
@@ -7,5 +7,16 @@
   	i32.const 0   ;; msg_ptr
 	i32.const 11  ;; msg_len = len('Hello World')
 	i32.const 200 ;; code
+	call $log)
+
+  (func (export "sayHelloOutOfBounds")
+	i32.const 65530 ;; msg_ptr (the memory has a single page)
+	i32.const 11    ;; msg_len = len('Hello World')
+	i32.const 200   ;; code
+	call $log)
+
+  (func (export "sayHelloWithLargeCode")
+	i32.const 0   ;; msg_ptr
+	i32.const 11  ;; msg_len = len('Hello World')
+	i32.const 456 ;; code (only its lowest byte, 200, is kept)
 	call $log))
-  	
\ No newline at end of file
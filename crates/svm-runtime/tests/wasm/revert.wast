@@ -0,0 +1,25 @@
+(module
+  (func $revert (import "svm" "svm_revert") (param $data_ptr i32) (param $data_len i32))
+
+  (import "svm" "memory" (memory $mem 0))
+
+  (func (export "withdraw")
+    i32.const 0   ;; data_ptr
+    i32.const 16  ;; data_len = len('Not enough funds')
+    call $revert
+
+    unreachable)
+
+  (func (export "withdrawOutOfBounds")
+    i32.const -1  ;; data_ptr = u32::MAX
+    i32.const 16  ;; data_len
+    call $revert
+
+    unreachable)
+
+  (func (export "withdrawTooLong")
+    i32.const 0     ;; data_ptr
+    i32.const 1025  ;; data_len = MAX_REVERT_DATA_LEN + 1
+    call $revert
+
+    unreachable))
@@ -0,0 +1,20 @@
+(module
+  (func $log (import "svm" "svm_log") (param i32 i32 i32))
+
+  (memory (export "memory") 1)
+  (data (i32.const 0) "Hello World")
+
+  ;; the gas metered for the wasm code (see `svm_compiler::GAS_USED_GLOBAL`)
+  (global (export "svm_gas_used") i64 (i64.const 1000))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "ctor")
+    nop)
+
+  (func (export "say_hello")
+    i32.const 0   ;; msg_ptr
+    i32.const 11  ;; msg_len = len('Hello World')
+    i32.const 200 ;; code
+    call $log))
@@ -0,0 +1,24 @@
+(module
+  (func $set64 (import "svm" "svm_set64") (param i32 i64))
+  (func $revert (import "svm" "svm_revert") (param i32 i32))
+
+  (memory (export "memory") 1)
+  (data (i32.const 0) "not enough funds")
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "ctor")
+    nop)
+
+  (func (export "withdraw")
+    ;; var #0 = 10 (discarded since the transaction reverts)
+    i32.const 0
+    i64.const 10
+    call $set64
+
+    i32.const 0   ;; data_ptr
+    i32.const 16  ;; data_len = len('not enough funds')
+    call $revert
+
+    unreachable))
@@ -0,0 +1,24 @@
+(module
+  (func $get64 (import "svm" "svm_get64") (param i32) (result i64))
+  (func $set64 (import "svm" "svm_set64") (param i32 i64))
+  (func $value (import "svm" "svm_value") (result i64))
+
+  (memory (export "memory") 1)
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "ctor")
+    ;; var #0 = value
+    i32.const 0
+    call $value
+    call $set64)
+
+  (func (export "deposit")
+    ;; var #0 += value
+    i32.const 0
+    i32.const 0
+    call $get64
+    call $value
+    i64.add
+    call $set64))
@@ -0,0 +1,5 @@
+(module
+  (func $value (import "svm" "svm_value") (result i64))
+
+  (func (export "get_value") (result i64)
+    call $value))
@@ -1,7 +1,7 @@
 use crate::traits::Host;
 
 use svm_sdk_alloc::Ptr;
-use svm_sdk_types::{Address, Amount, LayerId};
+use svm_sdk_types::{Address, Amount, LayerId, Topic};
 
 extern crate alloc;
 extern crate std;
@@ -42,6 +42,20 @@ extern "C" {
     /// at memory offset `offset` (of byte-length `length`)
     /// and it's associated message code (for signaling errors severity such as `trace/info/error` etc.)
     fn svm_log(offset: u32, length: u32, code: u32);
+
+    /// Sends to SVM an event consisting of `topics_count` topics (each of `TOPIC_SIZE` bytes)
+    /// laid out in memory starting at offset `topics_offset`,
+    /// and the event's ABI-encoded data starting at offset `data_offset` (of byte-length `data_length`).
+    fn svm_emit(topics_offset: u32, topics_count: u32, data_offset: u32, data_length: u32);
+
+    /// Signals to SVM that the current running transaction reverts.
+    /// The revert data starts at memory offset `offset` (of byte-length `length`).
+    ///
+    /// The app should halt right after calling this method.
+    fn svm_revert(offset: u32, length: u32);
+
+    /// Returns the amount of coins transferred along with the current executed transaction.
+    fn svm_value() -> u64;
 }
 
 /// ## Spacemesh Imports
@@ -140,6 +154,13 @@ impl Host for ExtHost {
         host.layer_id()
     }
 
+    #[inline]
+    fn value(&self) -> Amount {
+        let host = Self::instance();
+
+        host.value()
+    }
+
     #[inline]
     fn balance_of(&self, addr: &Address) -> Amount {
         let host = Self::instance();
@@ -160,6 +181,20 @@ impl Host for ExtHost {
 
         host.log(msg, code);
     }
+
+    #[inline]
+    fn emit(&mut self, topics: &[Topic], data: &[u8]) {
+        let host = Self::instance();
+
+        host.emit(topics, data);
+    }
+
+    #[inline]
+    fn revert(&mut self, data: &[u8]) {
+        let host = Self::instance();
+
+        host.revert(data);
+    }
 }
 
 pub struct InnerHost;
@@ -216,6 +251,15 @@ impl Host for InnerHost {
         }
     }
 
+    #[inline]
+    fn value(&self) -> Amount {
+        unsafe {
+            let value = svm_value();
+
+            Amount(value)
+        }
+    }
+
     #[inline]
     fn balance_of(&self, addr: &Address) -> Amount {
         unsafe {
@@ -245,6 +289,35 @@ impl Host for InnerHost {
             svm_log(offset, len, code as u32)
         }
     }
+
+    #[inline]
+    fn emit(&mut self, topics: &[Topic], data: &[u8]) {
+        unsafe {
+            let topics_offset = topics.as_ptr() as u32;
+            let topics_count = topics.len() as u32;
+
+            let data_offset = data.as_ptr() as u32;
+            let data_len = data.len() as u32;
+
+            svm_emit(topics_offset, topics_count, data_offset, data_len)
+        }
+    }
+
+    #[inline]
+    fn revert(&mut self, data: &[u8]) {
+        unsafe {
+            let offset = data.as_ptr() as u32;
+            let len = data.len() as u32;
+
+            svm_revert(offset, len);
+
+            #[cfg(target_arch = "wasm32")]
+            core::arch::wasm32::unreachable();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        unreachable!()
+    }
 }
 
 impl InnerHost {
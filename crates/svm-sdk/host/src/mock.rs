@@ -1,6 +1,6 @@
 use crate::traits::Host;
 
-use svm_sdk_types::{Address, Amount, LayerId};
+use svm_sdk_types::{Address, Amount, LayerId, Topic};
 
 extern crate core;
 
@@ -70,6 +70,12 @@ impl Host for MockHost {
         host.layer_id()
     }
 
+    fn value(&self) -> Amount {
+        let host = Self::instance();
+
+        host.value()
+    }
+
     fn balance_of(&self, addr: &Address) -> Amount {
         let host = Self::instance();
 
@@ -87,6 +93,18 @@ impl Host for MockHost {
 
         host.log(msg, code);
     }
+
+    fn emit(&mut self, topics: &[Topic], data: &[u8]) {
+        let host = Self::instance();
+
+        host.emit(topics, data);
+    }
+
+    fn revert(&mut self, data: &[u8]) {
+        let host = Self::instance();
+
+        host.revert(data);
+    }
 }
 
 pub struct InnerHost {
@@ -102,7 +120,13 @@ pub struct InnerHost {
 
     pub layer_id: Option<LayerId>,
 
+    pub value: Amount,
+
     pub logs: Vec<(String, u8)>,
+
+    pub events: Vec<(Vec<Topic>, Vec<u8>)>,
+
+    pub revert_data: Option<Vec<u8>>,
 }
 
 impl InnerHost {
@@ -114,7 +138,10 @@ impl InnerHost {
             app: None,
             accounts: HashMap::new(),
             layer_id: None,
+            value: Amount(0),
             logs: Vec::new(),
+            events: Vec::new(),
+            revert_data: None,
         }
     }
 
@@ -154,17 +181,32 @@ impl InnerHost {
         self.layer_id = Some(layer_id);
     }
 
+    pub fn set_value(&mut self, value: Amount) {
+        self.value = value;
+    }
+
     pub fn get_logs(&self) -> Vec<(String, u8)> {
         self.logs.clone()
     }
 
+    pub fn get_events(&self) -> Vec<(Vec<Topic>, Vec<u8>)> {
+        self.events.clone()
+    }
+
+    pub fn get_revert_data(&self) -> Option<Vec<u8>> {
+        self.revert_data.clone()
+    }
+
     pub fn reset(&mut self) {
         self.calldata = None;
         self.returndata = None;
         self.sender = None;
         self.app = None;
         self.layer_id = None;
+        self.value = Amount(0);
         self.logs.clear();
+        self.events.clear();
+        self.revert_data = None;
     }
 }
 
@@ -189,6 +231,10 @@ impl Host for InnerHost {
         self.layer_id.unwrap()
     }
 
+    fn value(&self) -> Amount {
+        self.value
+    }
+
     fn balance_of(&self, addr: &Address) -> Amount {
         *self.accounts.get(addr).unwrap_or(&Amount(0))
     }
@@ -214,6 +260,16 @@ impl Host for InnerHost {
 
         self.logs.push(log);
     }
+
+    fn emit(&mut self, topics: &[Topic], data: &[u8]) {
+        let event = (topics.to_vec(), data.to_vec());
+
+        self.events.push(event);
+    }
+
+    fn revert(&mut self, data: &[u8]) {
+        self.revert_data = Some(data.to_vec());
+    }
 }
 
 #[cfg(test)]
@@ -331,6 +387,19 @@ mod tests {
         });
     }
 
+    #[test]
+    fn host_value() {
+        test(|| {
+            let host = MockHost::instance();
+
+            assert_eq!(host.value(), Amount(0));
+
+            host.set_value(Amount(100));
+
+            assert_eq!(host.value(), Amount(100));
+        });
+    }
+
     #[test]
     fn host_logs() {
         test(|| {
@@ -349,4 +418,33 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn host_events() {
+        test(|| {
+            let host = MockHost::instance();
+
+            let events = host.get_events();
+            assert!(events.is_empty());
+
+            let topics = [[0x10; 32], [0x20; 32]];
+            host.emit(&topics, b"data");
+
+            let events = host.get_events();
+            assert_eq!(events, vec![(topics.to_vec(), b"data".to_vec())]);
+        });
+    }
+
+    #[test]
+    fn host_revert() {
+        test(|| {
+            let host = MockHost::instance();
+
+            assert_eq!(host.get_revert_data(), None);
+
+            host.revert(b"Not enough funds");
+
+            assert_eq!(host.get_revert_data(), Some(b"Not enough funds".to_vec()));
+        });
+    }
 }
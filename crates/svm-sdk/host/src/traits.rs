@@ -1,4 +1,4 @@
-use svm_sdk_types::{Address, Amount, LayerId};
+use svm_sdk_types::{Address, Amount, LayerId, Topic};
 
 extern crate alloc;
 
@@ -16,12 +16,22 @@ pub trait Host {
 
     fn layer_id(&self) -> LayerId;
 
+    /// The amount of coins transferred along with the running transaction.
+    fn value(&self) -> Amount;
+
     fn balance_of(&self, addr: &Address) -> Amount;
 
     fn transfer(&mut self, dst: &Address, amount: Amount);
 
     fn log(&mut self, msg: &str, code: u8);
 
+    fn emit(&mut self, topics: &[Topic], data: &[u8]);
+
+    /// Reverts the running transaction with revert data `data`.
+    ///
+    /// The transaction's storage changes are discarded.
+    fn revert(&mut self, data: &[u8]);
+
     #[inline]
     fn sender_balance(&self) -> Amount {
         let sender = self.sender();
@@ -0,0 +1,163 @@
+use proc_macro2::{Ident, Literal, Span, TokenStream, TokenTree};
+use quote::quote;
+
+use serde_json::{json, Value as Json};
+use syn::{Error, GenericArgument, PathArguments, Result, Type};
+
+use svm_sdk::abi::ABI_SECTION;
+
+/// Emits a `static` holding `record` (serialized as a JSON line) under the ABI custom section.
+///
+/// On non-Wasm targets the `static` is a plain byte array (useful for tests).
+pub fn abi_section_ast(static_name: &str, record: Json) -> TokenStream {
+    let line = format!("{}\n", record);
+    let bytes = line.as_bytes();
+
+    let len = bytes.len();
+    let lit = Literal::byte_string(bytes);
+    let name = Ident::new(static_name, Span::call_site());
+    let section = ABI_SECTION;
+
+    quote! {
+        #[cfg_attr(target_arch = "wasm32", link_section = #section)]
+        #[used]
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        static #name: [u8; #len] = *#lit;
+    }
+}
+
+/// Translates a Rust primitive type into its ABI type name
+/// (as expected by `svm_codec::api::json::encode_calldata`).
+///
+/// Returns `None` when `ty` isn't a primitive (nor `Bytes` / `String`).
+pub fn abi_type_name(ty: &Type) -> Option<&'static str> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => path,
+        Type::Paren(paren) => return abi_type_name(&paren.elem),
+        _ => return None,
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if !segment.arguments.is_empty() {
+        return None;
+    }
+
+    let name = match segment.ident.to_string().as_str() {
+        "bool" => "bool",
+        "i8" => "i8",
+        "u8" => "u8",
+        "i16" => "i16",
+        "u16" => "u16",
+        "i32" => "i32",
+        "u32" => "u32",
+        "i64" => "i64",
+        "u64" => "u64",
+        "U128" => "u128",
+        "U256" => "u256",
+        "Amount" => "amount",
+        "Address" => "address",
+        "Bytes" => "bytes",
+        "String" => "string",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Returns the ABI types of an endpoint's return value (given its `-> T` tokens).
+///
+/// * No return type (or `()`) - an empty list.
+/// * A tuple - the ABI type of each of its items.
+/// * A `Result<T, E>` - the ABI types of `T` (the `Err` isn't part of the `returndata`).
+/// * Otherwise - a single ABI type.
+pub fn abi_returns(returns: &TokenStream) -> Result<Vec<Json>> {
+    let ty = match return_type(returns)? {
+        None => return Ok(Vec::new()),
+        Some(ty) => ty,
+    };
+
+    let ty = result_ok_type(&ty).unwrap_or(&ty);
+
+    match ty {
+        Type::Tuple(tuple) => tuple.elems.iter().map(abi_type).collect(),
+        ty => Ok(vec![abi_type(ty)?]),
+    }
+}
+
+/// Parses an endpoint's `-> T` tokens into `T` (`None` when there's no return type).
+pub fn return_type(returns: &TokenStream) -> Result<Option<Type>> {
+    let tokens: Vec<TokenTree> = returns.clone().into_iter().collect();
+
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    // skipping the `->` (i.e the `-` and `>` punctuations)
+    let ty = tokens[2..].iter().cloned().collect();
+    let ty: Type = syn::parse2(ty)?;
+
+    Ok(Some(ty))
+}
+
+/// Returns `T` when `ty` is a `Result<T, E>` (otherwise `None`).
+pub fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) => path,
+        _ => return None,
+    };
+
+    let segment = path.path.segments.last()?;
+
+    match (segment.ident.to_string().as_str(), &segment.arguments) {
+        ("Result", PathArguments::AngleBracketed(args)) if args.args.len() == 2 => {
+            match args.args.first() {
+                Some(GenericArgument::Type(ok)) => Some(ok),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the ABI type of `ty`.
+///
+/// * An array (or a `Vec`) is represented as a list holding its items ABI type.
+/// * Any other non-primitive type is assumed to be a struct deriving `AbiStruct`,
+///   and it's represented as `{"struct": "<name>"}`.
+///
+/// Rust tuples aren't supported (they're encoded as a sequence of values), use a struct instead.
+pub fn abi_type(ty: &Type) -> Result<Json> {
+    if let Some(name) = abi_type_name(ty) {
+        return Ok(json!(name));
+    }
+
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path
+                .path
+                .segments
+                .last()
+                .ok_or_else(|| Error::new_spanned(ty, "Unsupported ABI type"))?;
+
+            match (segment.ident.to_string().as_str(), &segment.arguments) {
+                ("Vec", PathArguments::AngleBracketed(args)) if args.args.len() == 1 => {
+                    match args.args.first() {
+                        Some(GenericArgument::Type(elem)) => Ok(json!([abi_type(elem)?])),
+                        _ => Err(Error::new_spanned(ty, "Unsupported ABI type")),
+                    }
+                }
+                (name, PathArguments::None) => Ok(json!({ "struct": name })),
+                _ => Err(Error::new_spanned(ty, "Unsupported ABI type")),
+            }
+        }
+        Type::Array(array) => Ok(json!([abi_type(&array.elem)?])),
+        Type::Paren(paren) => abi_type(&paren.elem),
+        Type::Tuple(..) => Err(Error::new_spanned(
+            ty,
+            "Unsupported ABI type: tuples (use a struct deriving `AbiStruct`)",
+        )),
+        _ => Err(Error::new_spanned(ty, "Unsupported ABI type")),
+    }
+}
@@ -0,0 +1,132 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+use serde_json::json;
+
+use syn::{Data, DeriveInput, Fields, Index, Member, Result};
+
+use crate::abi;
+
+pub fn derive_abi_struct(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = syn::parse(input).expect("Invalid `#[derive(AbiStruct)]` input");
+
+    if !input.generics.params.is_empty() {
+        panic!("`#[derive(AbiStruct)]` doesn't support generic structs");
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => panic!("`#[derive(AbiStruct)]` supports only structs"),
+    };
+
+    let name = &input.ident;
+    let members = struct_members(fields);
+
+    let encoder = encoder_ast(name, &members);
+    let from_value = from_value_ast(name, fields, &members);
+    let abi = struct_abi(name, fields, &members);
+
+    (quote! {
+        #encoder
+
+        #from_value
+
+        #abi
+    })
+    .into()
+}
+
+fn struct_members(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        })
+        .collect()
+}
+
+fn encoder_ast(name: &Ident, members: &[Member]) -> TokenStream {
+    let nfields = members.len();
+
+    quote! {
+        impl svm_sdk::traits::Encoder for #name {
+            fn encode(&self, w: &mut Vec<u8>) {
+                let fields: [&dyn svm_sdk::traits::Encoder; #nfields] = [#(&self.#members),*];
+
+                svm_sdk::abi::encode_tuple(&fields, w);
+            }
+        }
+    }
+}
+
+fn from_value_ast(name: &Ident, fields: &Fields, members: &[Member]) -> TokenStream {
+    let nfields = members.len();
+
+    let ctor = match fields {
+        Fields::Named(..) => quote! {
+            Self {
+                #(#members: fields.next().unwrap().into()),*
+            }
+        },
+        Fields::Unnamed(..) => {
+            let items = members
+                .iter()
+                .map(|_| quote! { fields.next().unwrap().into() });
+
+            quote! { Self(#(#items),*) }
+        }
+        Fields::Unit => quote! {{ let _ = fields; Self }},
+    };
+
+    quote! {
+        impl<'a> From<svm_sdk::value::Value<'a>> for #name {
+            fn from(value: svm_sdk::value::Value<'a>) -> Self {
+                let fields = value.into_fields();
+                assert_eq!(fields.len(), #nfields);
+
+                let mut fields = fields.into_iter();
+
+                #ctor
+            }
+        }
+    }
+}
+
+/// Emits the struct's ABI record (or a `compile_error!` when a field isn't ABI compatible).
+fn struct_abi(name: &Ident, fields: &Fields, members: &[Member]) -> TokenStream {
+    match struct_abi_record(name, fields, members) {
+        Ok(abi) => abi,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn struct_abi_record(name: &Ident, fields: &Fields, members: &[Member]) -> Result<TokenStream> {
+    let fields = fields
+        .iter()
+        .zip(members)
+        .map(|(field, member)| {
+            let name = match member {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+            let ty = abi::abi_type(&field.ty)?;
+
+            Ok(json!({ "name": name, "type": ty }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let name = name.to_string();
+
+    let record = json!({
+        "struct": {
+            "name": name,
+            "fields": fields,
+        }
+    });
+
+    let static_name = format!("__SVM_ABI_STRUCT_{}", name.to_uppercase());
+
+    Ok(abi::abi_section_ast(&static_name, record))
+}
@@ -5,20 +5,24 @@ use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenS
 
 use quote::{quote, ToTokens};
 
+use serde_json::json;
+
 use syn::parse::ParseStream;
+
+use crate::abi;
 use syn::{
     Data, DataStruct, DeriveInput, Expr, ExprLit, Field, Fields, FieldsNamed, Lit, Path,
     PathArguments, Type, TypeArray, TypePath,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 struct Param {
     name: Ident,
 
-    ty: Ident,
+    ty: Type,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 struct FuncSig {
     name: Ident,
 
@@ -41,45 +45,209 @@ impl FuncSig {
     }
 }
 
-pub fn parse_endpoint(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let (fn_sig, next) = parse_func_sig(input.into());
+/// The `#[endpoint]` (or `#[ctor]`) arguments.
+#[derive(Debug, Default, Clone, Copy)]
+struct Attrs {
+    /// Whether the endpoint is a constructor.
+    ctor: bool,
+
+    /// Whether the endpoint accepts coins transferred along with the transaction.
+    payable: bool,
+}
+
+pub fn parse_endpoint(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match parse_attrs(args.into(), true) {
+        Ok(attrs) => endpoint_ast(attrs, input.into()).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+pub fn parse_ctor(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match parse_attrs(args.into(), false) {
+        Ok(mut attrs) => {
+            attrs.ctor = true;
+
+            endpoint_ast(attrs, input.into()).into()
+        }
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn endpoint_ast(attrs: Attrs, input: TokenStream) -> TokenStream {
+    let (fn_sig, next) = parse_func_sig(input);
     let body = parse_func_body(next);
 
     let name = fn_sig.name();
     let returns = fn_sig.returns();
     let prologue = func_prologue(&fn_sig);
-    let epilogue = func_epilogue();
+    let epilogue = func_epilogue(&fn_sig);
+    let value_check = func_value_check(attrs);
+    let abi = func_abi(&fn_sig, attrs);
 
     let includes = endpoint_includes();
 
-    (quote! {
+    let func_body = quote! {
+        #includes
+
+        #value_check
+
+        fn __inner__() #returns {
+            #prologue
+
+            #body
+        }
+
+        #epilogue
+    };
+
+    // When not compiled to Wasm the endpoint is a plain Rust function
+    // (a panic unwinding out of an `extern "C"` function aborts, see `svm_sdk_testing`).
+    quote! {
+        #abi
+
+        #[cfg(target_arch = "wasm32")]
         #[no_mangle]
         pub extern "C" fn #name() {
-            #includes
+            #func_body
+        }
 
-            fn __inner__() #returns {
-                #prologue
+        #[cfg(not(target_arch = "wasm32"))]
+        pub fn #name() {
+            #func_body
+        }
+    }
+}
 
-                #body
+/// Parses a comma-separated list of `ctor` (only when `allow_ctor = true`) and `payable`.
+///
+/// An invalid argument results in an error spanning the offending token.
+fn parse_attrs(args: TokenStream, allow_ctor: bool) -> syn::Result<Attrs> {
+    let mut attrs = Attrs::default();
+
+    let expected = if allow_ctor {
+        "Invalid `#[endpoint]` arguments (expected nothing, `ctor` and/or `payable`)"
+    } else {
+        "Invalid `#[ctor]` arguments (expected nothing or `payable`)"
+    };
+
+    let mut iter = args.into_iter();
+
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Ident(ident) if ident == "ctor" && allow_ctor && !attrs.ctor => {
+                attrs.ctor = true
             }
+            TokenTree::Ident(ident) if ident == "payable" && !attrs.payable => attrs.payable = true,
+            tt => return Err(syn::Error::new_spanned(tt, expected)),
+        }
 
-            #epilogue
+        match iter.next() {
+            None => break,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => (),
+            Some(tt) => return Err(syn::Error::new_spanned(tt, expected)),
         }
-    })
-    .into()
+    }
+
+    Ok(attrs)
 }
 
-fn func_epilogue() -> TokenStream {
-    quote! {
-        {
-            use svm_sdk::traits::Encoder;
+/// A non-payable endpoint reverts when coins are transferred along with the transaction
+/// (with `svm_sdk::revert::NOT_PAYABLE_CODE`).
+fn func_value_check(attrs: Attrs) -> TokenStream {
+    if attrs.payable {
+        quote! {}
+    } else {
+        quote! {
+            if Node.value() != svm_sdk::Amount(0) {
+                svm_sdk::revert::revert_with(
+                    svm_sdk::revert::NOT_PAYABLE_CODE,
+                    "endpoint isn't payable",
+                );
+            }
+        }
+    }
+}
+
+/// Emits the endpoint's ABI record (or a `compile_error!` when a type isn't ABI compatible).
+fn func_abi(sig: &FuncSig, attrs: Attrs) -> TokenStream {
+    match func_abi_record(sig, attrs) {
+        Ok(abi) => abi,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn func_abi_record(sig: &FuncSig, attrs: Attrs) -> syn::Result<TokenStream> {
+    let params = sig
+        .params()
+        .iter()
+        .map(|param| {
+            let name = param.name.to_string();
+            let ty = abi::abi_type(&param.ty)?;
+
+            Ok(json!({ "name": name, "type": ty }))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = sig.name().to_string();
+    let returns = abi::abi_returns(sig.returns())?;
+
+    let record = json!({
+        "endpoint": {
+            "name": name,
+            "ctor": attrs.ctor,
+            "payable": attrs.payable,
+            "params": params,
+            "returns": returns,
+        }
+    });
+
+    let static_name = format!("__SVM_ABI_ENDPOINT_{}", name.to_uppercase());
+
+    Ok(abi::abi_section_ast(&static_name, record))
+}
+
+fn func_epilogue(sig: &FuncSig) -> TokenStream {
+    // an invalid return type is reported by `func_abi`
+    let is_result = abi::return_type(sig.returns())
+        .ok()
+        .flatten()
+        .map(|ty| abi::result_ok_type(&ty).is_some())
+        .unwrap_or(false);
+
+    if is_result {
+        quote! {
+            {
+                use svm_sdk::traits::{AppError, Encoder};
+
+                match __inner__() {
+                    Ok(rets) => {
+                        let mut bytes = Vec::new();
+                        rets.encode(&mut bytes);
+
+                        Node.set_returndata(&bytes);
+                    }
+                    Err(err) => svm_sdk::revert::revert_with(err.code(), err.message()),
+                }
+            }
+        }
+    } else {
+        quote! {
+            {
+                use svm_sdk::traits::Encoder;
 
-            let mut bytes = Vec::new();
+                let mut bytes = Vec::new();
 
-            let rets = __inner__();
-            rets.encode(&mut bytes);
+                let rets = __inner__();
+                rets.encode(&mut bytes);
 
-            Node.set_returndata(&bytes);
+                Node.set_returndata(&bytes);
+            }
         }
     }
 }
@@ -124,7 +292,6 @@ fn parse_func_params(iter: &mut IntoIter) -> Vec<Param> {
         let mut iter = stream.into_iter();
 
         let colon = Punct::new(':', Spacing::Alone);
-        let comma = Punct::new(',', Spacing::Alone);
 
         loop {
             let name = iter.next();
@@ -135,21 +302,15 @@ fn parse_func_params(iter: &mut IntoIter) -> Vec<Param> {
             let tt = iter.next();
             assert_punct(tt, &colon);
 
-            let ty = iter.next();
-            if ty.is_none() {
-                panic!("Expected function parameter type.");
-            }
+            let (ty, more) = parse_param_type(&mut iter);
 
             let param = Param {
                 name: as_ident(name),
-                ty: as_ident(ty),
+                ty,
             };
             params.push(param);
 
-            let tt = iter.next();
-            if tt.is_some() {
-                assert_punct(tt, &comma);
-            } else {
+            if !more {
                 break;
             }
         }
@@ -160,6 +321,39 @@ fn parse_func_params(iter: &mut IntoIter) -> Vec<Param> {
     }
 }
 
+/// Parses a parameter's type (i.e all the tokens up to the next top-level `,`).
+/// Returns the type and whether there are more parameters.
+fn parse_param_type(iter: &mut IntoIter) -> (Type, bool) {
+    let mut tts: Vec<TokenTree> = Vec::new();
+    let mut depth = 0;
+    let mut more = false;
+
+    for tt in iter {
+        if let TokenTree::Punct(punct) = &tt {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    more = true;
+                    break;
+                }
+                _ => (),
+            }
+        }
+
+        tts.push(tt);
+    }
+
+    if tts.is_empty() {
+        panic!("Expected function parameter type.");
+    }
+
+    let ty = quote! { #(#tts)* };
+    let ty: Type = syn::parse2(ty).expect("Invalid function parameter type");
+
+    (ty, more)
+}
+
 fn parse_func_returns(mut iter: IntoIter) -> (TokenStream, TokenTree) {
     let mut tts: Vec<TokenTree> = Vec::new();
 
@@ -287,10 +481,10 @@ fn endpoint_includes() -> TokenStream {
     quote! {
         use svm_sdk::traits::Host;
 
-        #[cfg(test)]
+        #[cfg(not(target_arch = "wasm32"))]
         use svm_sdk::host::MockHost as Node;
 
-        #[cfg(not(test))]
+        #[cfg(target_arch = "wasm32")]
         use svm_sdk::host::ExtHost as Node;
     }
 }
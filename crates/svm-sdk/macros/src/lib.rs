@@ -6,20 +6,42 @@ cfg_if! {
     if #[cfg(not(windows))] {
         extern crate proc_macro;
 
+        mod abi;
+        mod abi_struct;
         mod storage;
         mod endpoint;
 
+        use abi_struct::derive_abi_struct;
         use storage::parse_storage;
-        use endpoint::parse_endpoint;
+        use endpoint::{parse_ctor, parse_endpoint};
 
         ///
         /// The `#[storage]` proc-macro attribute consumes a struct and translates
         /// its field into more low-level code that interacts against the `svm-sdk` Storage.
-        /// For testing purposes the storage used will be `MockStorage` and `ExtStorage` otherwise.
+        /// When compiled to Wasm the storage used will be `ExtStorage` and `MockStorage` otherwise (for native tests).
         ///
         /// In case the storage field type isn't supported, a compile-time error will be raised.
         /// For each field a corresponding getter and setter methods will be generated.
         ///
+        /// The supported field types are:
+        ///
+        /// * Primitives (`bool`, the integers, `Amount`, `Address`, `U128` and `U256`).
+        /// * Arrays of primitives (e.g `[u32; 3]`), accessed using `get_x(index)` and `set_x(index, value)`.
+        /// * Two-dimensional arrays of primitives (e.g `[[u16; 3]; 2]`), accessed using `get_x(row, col)` and `set_x(row, col, value)`.
+        /// * Byte blobs (`[u8; N]`), read and written as a whole. A blob is stored as `N / 8` (rounded up) `u64` variables.
+        /// * Another `#[storage]` struct, accessed using `x()` which returns a view having `&self` getters and setters.
+        ///   A nested storage is laid out after all the other fields of its enclosing storage.
+        ///
+        /// Additionally, a `SCHEMA` associated const is generated, holding a `(var_id, name, type, count)`
+        /// tuple per field. The same schema is recorded in the template's ABI (see below),
+        /// out of which the deployed template's `DataLayout` is derived (see `svm_codec::api::json::template_layout`).
+        /// The number of variables spanned by the storage (including its nested storage fields) is given by `VARS`.
+        ///
+        /// The storage schema is also appended to the template's ABI (the `svm_abi` Wasm custom section)
+        /// as a `{"storage": {"name": .., "vars": [{"id": .., "name": .., "type": .., "count": ..}]}}` JSON line.
+        /// A nested storage field is recorded as `{"name": .., "type": {"storage": "<struct name>"}}`.
+        /// (see `svm_codec::api::json::template_abi`).
+        ///
         /// Here is a simple example of declaring a storage:
         ///
         /// ```rust
@@ -40,15 +62,21 @@ cfg_if! {
         /// ``` rust
         /// use svm_sdk::{Amount, Address};
         ///
-        /// #[cfg(not(test))]
+        /// #[cfg(target_arch = "wasm32")]
         /// use svm_sdk::storage::ExtStorage as StorageImpl;
         ///
-        /// #[cfg(test)]
+        /// #[cfg(not(target_arch = "wasm32"))]
         /// use svm_sdk::storage::MockStorage as StorageImpl;
         ///
         /// struct MyStorage;
         ///
         /// impl MyStorage {
+        ///   const SCHEMA: &'static [(u32, &'static str, &'static str, u32)] = &[
+        ///       (0, "amount", "Amount", 1),
+        ///       (1, "addr", "Address", 1),
+        ///       (2, "data", "u32", 3),
+        ///   ];
+        ///
         ///   fn get_amount() -> Amount {
         ///       svm_sdk::storage::ops::get_amount::<StorageImpl>(0)
         ///   }
@@ -58,7 +86,7 @@ cfg_if! {
         ///   }
         ///
         ///   fn get_addr() -> Address {
-        ///       svm_sdk::storage::ops::get_addr::<StorageImpl>(0)
+        ///       svm_sdk::storage::ops::get_addr::<StorageImpl>(1)
         ///   }
         ///
         ///   fn set_addr(value: &Address) {
@@ -71,11 +99,36 @@ cfg_if! {
         ///   }
         ///
         ///   fn set_data(index: usize, value: u32) {
-        //        svm_sdk::storage::ops::array_set_addr::<StorageImpl>(2, index, 3, value)
+        ///       svm_sdk::storage::ops::array_set32::<StorageImpl>(2, index, 3, value)
         ///   }
         /// }
         /// ```
         ///
+        /// Storage structs may be nested:
+        ///
+        /// ```rust
+        /// use svm_sdk::{Amount, Address};
+        /// use svm_sdk_macros::storage;
+        ///
+        /// #[storage]
+        /// struct Account {
+        ///   owner: Address,
+        ///   balance: Amount,
+        /// }
+        ///
+        /// #[storage]
+        /// struct Bank {
+        ///   hash: [u8; 20],
+        ///   alice: Account,
+        ///   bob: Account,
+        /// }
+        ///
+        /// fn pay(amount: Amount) {
+        ///   let alice = BankStorage::alice();
+        ///   alice.set_balance(alice.get_balance() + amount);
+        /// }
+        /// ```
+        ///
         #[proc_macro_attribute]
         pub fn storage(
             _args: proc_macro::TokenStream,
@@ -88,6 +141,18 @@ cfg_if! {
         /// The `#[endpoint]` proc-macro attribute facilitates the task of implementing SVM app's endpoint.
         /// Each function annotated with this proc-macro will be transformed into a WASM function export in the compiler's final output.
         ///
+        /// Constructors are marked using `#[endpoint(ctor)]` (or `#[ctor]`).
+        ///
+        /// An endpoint rejects coins transferred along with the transaction (see `Host::value`) unless marked
+        /// using `#[endpoint(payable)]` (or `#[endpoint(ctor, payable)]`). When a non-payable endpoint is called
+        /// along with a non-zero value the transaction reverts with `svm_sdk::revert::NOT_PAYABLE_CODE`.
+        ///
+        /// The endpoint's signature is appended to the template's ABI (the `svm_abi` Wasm custom section)
+        /// as a `{"endpoint": {"name": .., "ctor": .., "payable": .., "params": [{"name": .., "type": ..}], "returns": [..]}}` JSON line.
+        /// The types are named as expected by `svm_codec::api::json::encode_calldata` (e.g `amount`, `address`, `["u32"]`).
+        /// Structs deriving `AbiStruct` are named `{"struct": "<name>"}`.
+        /// (see `svm_codec::api::json::template_abi`).
+        ///
         /// # Example
         ///
         /// Here is an example using `#[endpoint]`:
@@ -116,12 +181,16 @@ cfg_if! {
         /// pub extern "C" fn work() {
         ///     use svm_sdk::traits::Host;
         ///
-        ///     #[cfg(test)]
+        ///     #[cfg(not(target_arch = "wasm32"))]
         ///     use svm_sdk::host::MockHost as Node;
         ///
-        ///     #[cfg(not(test))]
+        ///     #[cfg(target_arch = "wasm32")]
         ///     use svm_sdk::host::ExtHost as Node;
         ///
+        ///     if Node.value() != Amount(0) {
+        ///         svm_sdk::revert::revert_with(svm_sdk::revert::NOT_PAYABLE_CODE, "endpoint isn't payable");
+        ///     }
+        ///
         ///     fn __inner__() -> Amount {
         ///         use svm_sdk::CallData;
         ///
@@ -152,12 +221,137 @@ cfg_if! {
         /// }
         /// ```
         ///
+        /// When not compiled to Wasm, the endpoint is a plain (non `extern "C"`) Rust function instead.
+        /// That's what allows unit-testing an app natively using `svm_sdk_testing::TestApp`.
+        ///
+        /// # Failing an endpoint
+        ///
+        /// An endpoint may return a `Result<T, E>` where `E` implements `svm_sdk::traits::AppError`
+        /// (e.g `svm_sdk::AmountError`, a `u32` error code or a `&str` message).
+        /// The endpoint's ABI `returns` are the ones of `T`.
+        ///
+        /// On `Ok` the value is encoded as the `returndata`. On `Err` the transaction reverts
+        /// with the error's `code` and `message` as revert data (see `svm_sdk::revert::revert_with`),
+        /// and the storage changes are discarded. That's the same flow used by `ensure!` and panics.
+        ///
+        /// A panicking endpoint reverts as well (with `svm_sdk::revert::PANIC_CODE` and the panic message)
+        /// when the Template is compiled with the `svm-sdk` `panic-handler` feature.
+        ///
+        /// ```rust
+        /// use svm_sdk::{Amount, AmountError};
+        /// use svm_sdk_macros::endpoint;
+        ///
+        /// #[endpoint]
+        /// fn add(a: Amount, b: Amount) -> Result<Amount, AmountError> {
+        ///     let c = a.checked_add(b)?;
+        ///
+        ///     Ok(c)
+        /// }
+        /// ```
+        ///
         #[proc_macro_attribute]
         pub fn endpoint(
-            _args: proc_macro::TokenStream,
+            args: proc_macro::TokenStream,
+            input: proc_macro::TokenStream,
+        ) -> proc_macro::TokenStream {
+            parse_endpoint(args, input)
+        }
+
+        ///
+        /// The `#[ctor]` proc-macro attribute is a shorthand for `#[endpoint(ctor)]`.
+        /// A payable constructor is marked using `#[ctor(payable)]`.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use svm_sdk::traits::Host;
+        /// use svm_sdk::Amount;
+        /// use svm_sdk_macros::{ctor, storage};
+        ///
+        /// #[storage]
+        /// struct Vars {
+        ///     deposited: Amount,
+        /// }
+        ///
+        /// #[ctor(payable)]
+        /// fn init() {
+        ///     VarsStorage::set_deposited(Node.value());
+        /// }
+        /// ```
+        ///
+        #[proc_macro_attribute]
+        pub fn ctor(
+            args: proc_macro::TokenStream,
             input: proc_macro::TokenStream,
         ) -> proc_macro::TokenStream {
-            parse_endpoint(input)
+            parse_ctor(args, input)
+        }
+
+        ///
+        /// The `#[derive(AbiStruct)]` proc-macro makes a struct usable as an endpoint's parameter or return value.
+        ///
+        /// The struct is encoded as an ABI `Tuple` holding its fields (in their declaration order).
+        /// Each field's type must be usable as an endpoint's parameter as well (a primitive, `Bytes`, `String`,
+        /// an array, a `Vec` or another struct deriving `AbiStruct`).
+        ///
+        /// The struct's fields are appended to the template's ABI (the `svm_abi` Wasm custom section)
+        /// as a `{"struct": {"name": .., "fields": [{"name": .., "type": ..}]}}` JSON line.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use svm_sdk::{Address, Amount};
+        /// use svm_sdk_macros::{endpoint, AbiStruct};
+        ///
+        /// #[derive(AbiStruct)]
+        /// struct Transfer {
+        ///     to: Address,
+        ///     amount: Amount,
+        /// }
+        ///
+        /// #[endpoint]
+        /// fn transfer(transfer: Transfer) -> Amount {
+        ///     transfer.amount
+        /// }
+        /// ```
+        ///
+        /// A field of an unsupported ABI type (e.g a Rust tuple) is reported as a compile error:
+        ///
+        /// ```compile_fail
+        /// use svm_sdk_macros::AbiStruct;
+        ///
+        /// #[derive(AbiStruct)]
+        /// struct Pair {
+        ///     pair: (u32, u32),
+        /// }
+        /// ```
+        ///
+        /// The `Transfer` struct above gets (roughly) the following implementations:
+        ///
+        /// ```rust,ignore
+        /// impl svm_sdk::traits::Encoder for Transfer {
+        ///     fn encode(&self, w: &mut Vec<u8>) {
+        ///         let fields: [&dyn svm_sdk::traits::Encoder; 2] = [&self.to, &self.amount];
+        ///
+        ///         svm_sdk::abi::encode_tuple(&fields, w);
+        ///     }
+        /// }
+        ///
+        /// impl<'a> From<svm_sdk::value::Value<'a>> for Transfer {
+        ///     fn from(value: svm_sdk::value::Value<'a>) -> Self {
+        ///         let mut fields = value.into_fields().into_iter();
+        ///
+        ///         Self {
+        ///             to: fields.next().unwrap().into(),
+        ///             amount: fields.next().unwrap().into(),
+        ///         }
+        ///     }
+        /// }
+        /// ```
+        ///
+        #[proc_macro_derive(AbiStruct)]
+        pub fn abi_struct(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+            derive_abi_struct(input)
         }
     }
 }
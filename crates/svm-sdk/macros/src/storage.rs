@@ -2,33 +2,72 @@ extern crate proc_macro;
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
+
+use serde_json::json;
 use syn::{
-    parse_macro_input, Data, DataStruct, DeriveInput, Expr, ExprLit, Field, Fields, FieldsNamed,
-    Lit, Path, PathArguments, Type, TypeArray, TypePath,
+    parse_macro_input, Data, DataStruct, DeriveInput, Error, Expr, ExprLit, Field, Fields,
+    FieldsNamed, Lit, PathArguments, Result, Type, TypeArray, TypePath,
 };
 
+use crate::abi;
+
+/// The storage fields primitive types.
+const PRIMITIVES: &[&str] = &[
+    "bool", "Amount", "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "U128", "U256",
+    "Address",
+];
+
 pub fn parse_storage(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = storage_name(&input.ident);
-    let input = as_struct(input);
-    let fields = take_fields(input);
-    let vars = assign_vars(&fields);
+    match storage_ast(input) {
+        Ok(ast) => ast.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn storage_ast(input: DeriveInput) -> Result<TokenStream> {
+    let ident = input.ident.clone();
+    let name = storage_name(&ident);
+    let name_at = storage_at_name(&ident);
+    let input = as_struct(input)?;
+    let fields = take_fields(input)?;
+    let vars = assign_vars(&fields)?;
 
-    let getters = getters_ast(&vars);
-    let setters = setters_ast(&vars);
+    let root = accessors_ast(&vars, Accessor::Root);
+    let nested = accessors_ast(&vars, Accessor::Nested);
+    let schema = schema_ast(&vars);
+    let count = vars_count_ast(&vars);
+    let abi = abi_ast(&ident, &vars);
+
+    let ast = quote! {
+        #abi
 
-    (quote! {
         #[derive(Debug)]
         struct #name;
 
         impl #name {
-            #getters
+            #schema
+
+            /// The number of variables spanned by the storage (including its nested storage fields).
+            #[allow(dead_code)]
+            const VARS: u32 = #count;
+
+            #root
+        }
+
+        /// Accessing the storage when it's nested within another storage.
+        /// (its variables start at the `VarId` held by the struct).
+        #[derive(Debug, Clone, Copy)]
+        struct #name_at(u32);
 
-            #setters
+        #[allow(dead_code)]
+        impl #name_at {
+            #nested
         }
-    })
-    .into()
+    };
+
+    Ok(ast)
 }
 
 #[repr(transparent)]
@@ -60,172 +99,390 @@ enum Var {
         ty: Ident,
         length: u32,
     },
+    Array2 {
+        id: VarId,
+        name: Ident,
+        ty: Ident,
+        rows: u32,
+        cols: u32,
+    },
+    Blob {
+        id: VarId,
+        name: Ident,
+        length: u32,
+    },
+    Nested {
+        name: Ident,
+        ty: Ident,
+        base: TokenStream,
+    },
 }
 
-fn as_struct(input: DeriveInput) -> DataStruct {
-    match input.data {
-        Data::Struct(s) => s,
-        _ => panic!("macro can decorate only a `Struct`"),
+/// A storage field type (before assigning its `VarId`).
+enum FieldType {
+    Primitive(Ident),
+    Array(Ident, u32),
+    Array2(Ident, u32, u32),
+    Blob(u32),
+    Nested(Ident),
+}
+
+/// Whether the accessors are associated functions of the `{Name}Storage` (`Root`),
+/// or methods of the `{Name}StorageAt` (`Nested`), having their `VarId`s relative to `self.0`.
+#[derive(Debug, Copy, Clone)]
+enum Accessor {
+    Root,
+    Nested,
+}
+
+impl Accessor {
+    fn receiver(self) -> TokenStream {
+        match self {
+            Accessor::Root => quote! {},
+            Accessor::Nested => quote! { &self, },
+        }
+    }
+
+    fn var_id(self, id: TokenStream) -> TokenStream {
+        match self {
+            Accessor::Root => id,
+            Accessor::Nested => quote! { (self.0 + #id) },
+        }
     }
 }
 
-fn take_fields(input: DataStruct) -> FieldsNamed {
-    let fields = input.fields;
+fn as_struct(input: DeriveInput) -> Result<DataStruct> {
+    match input.data {
+        Data::Struct(s) => Ok(s),
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            "`#[storage]` can decorate only a struct",
+        )),
+    }
+}
 
-    match fields {
-        Fields::Named(fields) => fields,
-        _ => panic!("Struct fields should be named"),
+fn take_fields(input: DataStruct) -> Result<FieldsNamed> {
+    match input.fields {
+        Fields::Named(fields) => Ok(fields),
+        fields => Err(Error::new_spanned(
+            fields,
+            "the `#[storage]` struct fields should be named",
+        )),
     }
 }
 
-fn assign_vars(fields: &FieldsNamed) -> Vec<Var> {
-    let mut vars = Vec::new();
-    let mut index = 0;
+/// Assigns the `VarId`s of the fields.
+///
+/// The fields are laid out in their declaration order, except for the nested storage fields
+/// which are laid out after all the other fields (since their size isn't known here).
+fn assign_vars(fields: &FieldsNamed) -> Result<Vec<Var>> {
+    let mut types = Vec::new();
 
     for f in fields.named.iter() {
+        types.push((field_ident(f), field_type(f)?));
+    }
+
+    let mut index = 0;
+    let mut vars = Vec::new();
+
+    for (name, ty) in types.iter() {
         let id = VarId(index);
-        let var = field_as_var(id, f);
+        let name = name.clone();
 
-        match &var {
-            Var::Primitive { .. } => {
-                index += 1;
-            }
-            Var::Array { length, .. } => {
-                index += length;
-            }
-        }
+        let var = match ty {
+            FieldType::Primitive(ty) => Var::Primitive {
+                id,
+                name,
+                ty: ty.clone(),
+            },
+            FieldType::Array(ty, length) => Var::Array {
+                id,
+                name,
+                ty: ty.clone(),
+                length: *length,
+            },
+            FieldType::Array2(ty, rows, cols) => Var::Array2 {
+                id,
+                name,
+                ty: ty.clone(),
+                rows: *rows,
+                cols: *cols,
+            },
+            FieldType::Blob(length) => Var::Blob {
+                id,
+                name,
+                length: *length,
+            },
+            FieldType::Nested(..) => continue,
+        };
 
+        index += var_count(&var);
         vars.push(var);
     }
 
-    vars
-}
+    let mut base = quote! { #index };
 
-fn getters_ast(vars: &[Var]) -> TokenStream {
-    let mut getters = Vec::new();
+    for (name, ty) in types.iter() {
+        if let FieldType::Nested(ty) = ty {
+            let storage = storage_name(ty);
 
-    for var in vars.iter() {
-        let ast = getter_ast(var);
+            vars.push(Var::Nested {
+                name: name.clone(),
+                ty: ty.clone(),
+                base: base.clone(),
+            });
 
-        getters.push(ast);
+            base = quote! { #base + #storage::VARS };
+        }
     }
 
-    let ast = quote! {
-        #(#getters)*
-    };
+    Ok(vars)
+}
 
-    ast.into()
+/// The number of variables spanned by a (non-nested) `var`.
+fn var_count(var: &Var) -> u32 {
+    match var {
+        Var::Primitive { .. } => 1,
+        Var::Array { length, .. } => *length,
+        Var::Array2 { rows, cols, .. } => rows * cols,
+        Var::Blob { length, .. } => blob_chunks(*length),
+        Var::Nested { .. } => unreachable!(),
+    }
 }
 
-fn setters_ast(vars: &[Var]) -> TokenStream {
-    let mut setters = Vec::new();
+/// A `[u8; N]` blob is stored as `N / 8` (rounded up) `u64` variables (in Little-Endian).
+fn blob_chunks(length: u32) -> u32 {
+    length.div_ceil(8)
+}
 
-    for var in vars.iter() {
-        let ast = setter_ast(var);
+fn vars_count_ast(vars: &[Var]) -> TokenStream {
+    let mut count = 0;
+    let mut nested = Vec::new();
 
-        setters.push(ast);
+    for var in vars.iter() {
+        match var {
+            Var::Nested { ty, .. } => nested.push(storage_name(ty)),
+            var => count += var_count(var),
+        }
     }
 
-    let ast = quote! {
-        #(#setters)*
-    };
+    quote! { #count #(+ #nested::VARS)* }
+}
+
+fn accessors_ast(vars: &[Var], accessor: Accessor) -> TokenStream {
+    let mut accessors = Vec::new();
+
+    for var in vars.iter() {
+        let ast = match var {
+            Var::Nested { .. } => nested_ast(var, accessor),
+            _ => {
+                let getter = getter_ast(var, accessor);
+                let setter = setter_ast(var, accessor);
+
+                quote! {
+                    #getter
+
+                    #setter
+                }
+            }
+        };
+
+        accessors.push(ast);
+    }
 
-    ast.into()
+    quote! {
+        #(#accessors)*
+    }
 }
 
-fn field_as_var(id: VarId, field: &Field) -> Var {
-    match &field.ty {
-        Type::Array(array) => {
-            let ty = parse_array_elem_type(&array);
-            let length = parse_array_length(&array);
-            let name = field_ident(field);
+fn schema_ast(vars: &[Var]) -> TokenStream {
+    let mut fields = Vec::new();
 
-            Var::Array {
-                id,
-                name,
-                ty,
-                length,
+    for var in vars.iter() {
+        let field = match var {
+            Var::Nested { name, ty, base } => {
+                let name = name.to_string();
+                let storage = storage_name(ty);
+                let ty = ty.to_string();
+
+                quote! { (#base, #name, #ty, #storage::VARS) }
             }
-        }
-        Type::Path(path) => {
-            let name = field_ident(field);
-            let ty = parse_type_path(path);
+            _ => {
+                let (id, name, ty, count) = var_parts(var);
 
-            Var::Primitive { id, name, ty }
-        }
-        _ => panic!("Invalid Type"),
+                quote! { (#id, #name, #ty, #count) }
+            }
+        };
+
+        fields.push(field);
+    }
+
+    quote! {
+        /// The storage schema. Each item is a `(var_id, name, type, count)` tuple.
+        ///
+        /// A nested storage field is described by a single item (having its struct name as `type`).
+        #[allow(dead_code)]
+        const SCHEMA: &'static [(u32, &'static str, &'static str, u32)] = &[#(#fields),*];
     }
 }
 
-fn parse_array_elem_type(array: &TypeArray) -> Ident {
-    match *array.elem {
-        Type::Path(ref path) => parse_type_path(path),
-        _ => panic!("Invalid array type"),
+/// Returns the `(var_id, name, type, count)` of a (non-nested) `var`.
+///
+/// A blob is described as its `u64` variables.
+fn var_parts(var: &Var) -> (u32, String, String, u32) {
+    let count = var_count(var);
+
+    match var {
+        Var::Primitive { id, name, ty }
+        | Var::Array { id, name, ty, .. }
+        | Var::Array2 { id, name, ty, .. } => (id.0, name.to_string(), ty.to_string(), count),
+        Var::Blob { id, name, .. } => (id.0, name.to_string(), "u64".to_string(), count),
+        Var::Nested { .. } => unreachable!(),
     }
 }
 
-fn parse_array_length(array: &TypeArray) -> u32 {
-    match &array.len {
-        Expr::Lit(ExprLit { attrs, lit }) => {
-            assert!(attrs.is_empty());
+/// The storage's ABI record. Its variables types are named just like in `SCHEMA`.
+///
+/// A nested storage field is recorded as `{"name": .., "type": {"storage": "<struct name>"}}`
+/// (see `svm_codec::api::json::template_abi`).
+fn abi_ast(storage: &Ident, vars: &[Var]) -> TokenStream {
+    let vars: Vec<_> = vars
+        .iter()
+        .map(|var| match var {
+            Var::Nested { name, ty, .. } => json!({
+                "name": name.to_string(),
+                "type": { "storage": ty.to_string() },
+            }),
+            _ => {
+                let (id, name, ty, count) = var_parts(var);
+
+                json!({
+                    "id": id,
+                    "name": name,
+                    "type": ty,
+                    "count": count,
+                })
+            }
+        })
+        .collect();
+
+    let record = json!({
+        "storage": {
+            "name": storage.to_string(),
+            "vars": vars,
+        }
+    });
 
-            match lit {
-                Lit::Int(int) => {
-                    let int = int.base10_parse();
+    let static_name = format!("__SVM_ABI_STORAGE_{}", storage.to_string().to_uppercase());
 
-                    match int {
-                        Ok(int) => return int,
-                        Err(..) => panic!("Invalid array length"),
-                    }
+    abi::abi_section_ast(&static_name, record)
+}
+
+fn field_type(field: &Field) -> Result<FieldType> {
+    match &field.ty {
+        Type::Array(array) => match &*array.elem {
+            Type::Array(row) => {
+                let ty = parse_array_elem_type(row)?;
+                let rows = parse_array_length(array)?;
+                let cols = parse_array_length(row)?;
+
+                Ok(FieldType::Array2(ty, rows, cols))
+            }
+            _ => {
+                let ty = parse_array_elem_type(array)?;
+                let length = parse_array_length(array)?;
+
+                if ty == "u8" {
+                    Ok(FieldType::Blob(length))
+                } else {
+                    Ok(FieldType::Array(ty, length))
                 }
-                _ => panic!("Invalid array length"),
+            }
+        },
+        Type::Path(path) => {
+            let ty = parse_type_path(path)?;
+
+            if is_primitive(&ty) {
+                Ok(FieldType::Primitive(ty))
+            } else {
+                Ok(FieldType::Nested(ty))
             }
         }
-        _ => panic!("Invalid array length"),
+        ty => Err(Error::new_spanned(
+            ty,
+            "unsupported storage field type (expected a primitive, an array, `[u8; N]` or a `#[storage]` struct)",
+        )),
     }
 }
 
-fn parse_type_path(path: &TypePath) -> Ident {
-    assert!(path.qself.is_none());
+fn is_primitive(ty: &Ident) -> bool {
+    PRIMITIVES.contains(&ident_as_str!(ty))
+}
+
+fn parse_array_elem_type(array: &TypeArray) -> Result<Ident> {
+    let not_primitive = || {
+        Error::new_spanned(
+            &array.elem,
+            "the storage arrays items should be of a primitive type",
+        )
+    };
 
-    let path = &path.path;
-    let ty = segments_path_as_ident(&path);
+    match &*array.elem {
+        Type::Path(path) => {
+            let ty = parse_type_path(path)?;
 
-    match ident_as_str!(ty) {
-        #[rustfmt::skip]
-                "bool"    | 
-                "Amount"  |
-                "i8"      |
-                "u8"      |
-                "i16"     |
-                "u16"     |
-                "i32"     |
-                "u32"     |
-                "i64"     |
-                "u64"     |
-                "Address" => ty.clone(),
-        _ => panic!("Invalid Storage field type: {}", ty),
+            if is_primitive(&ty) {
+                Ok(ty)
+            } else {
+                Err(not_primitive())
+            }
+        }
+        _ => Err(not_primitive()),
     }
 }
 
-fn segments_path_as_ident(path: &Path) -> Ident {
-    match path.segments.len() {
-        1 => single_segment_path_ident(path),
-        _ => panic!("Invalid field type"),
+fn parse_array_length(array: &TypeArray) -> Result<u32> {
+    let invalid = || Error::new_spanned(&array.len, "invalid array length (expected an integer)");
+
+    let length = match &array.len {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().map_err(|_| invalid())?,
+        _ => return Err(invalid()),
+    };
+
+    if length == 0 {
+        return Err(Error::new_spanned(
+            &array.len,
+            "empty arrays aren't supported",
+        ));
     }
-}
 
-fn single_segment_path_ident(path: &Path) -> Ident {
-    path_segment_as_ident(path, 0)
+    Ok(length)
 }
 
-fn path_segment_as_ident(path: &Path, index: usize) -> Ident {
-    debug_assert!(path.segments.len() > index);
-
-    let segment = &path.segments[index];
-    assert!(matches!(segment.arguments, PathArguments::None));
+fn parse_type_path(path: &TypePath) -> Result<Ident> {
+    if path.qself.is_some() || path.path.segments.len() != 1 {
+        return Err(Error::new_spanned(
+            path,
+            "expected a type name (e.g `Amount` or a `#[storage]` struct name)",
+        ));
+    }
 
-    segment.ident.clone()
+    let segment = &path.path.segments[0];
+    let ty = segment.ident.clone();
+
+    match segment.arguments {
+        PathArguments::None => Ok(ty),
+        _ if ty == "Map" => Err(Error::new_spanned(
+            path,
+            "`Map` storage fields aren't supported yet (the SVM storage has no maps support)",
+        )),
+        _ => Err(Error::new_spanned(
+            path,
+            "generic storage field types aren't supported",
+        )),
+    }
 }
 
 fn getter_ident(var_name: &Ident) -> Ident {
@@ -241,239 +498,254 @@ fn field_ident(f: &Field) -> Ident {
 }
 
 fn storage_name(name: &Ident) -> Ident {
-    Ident::new(&format!("{}Storage", name), Span::call_site())
+    Ident::new(&format!("{}Storage", name), name.span())
+}
+
+fn storage_at_name(name: &Ident) -> Ident {
+    Ident::new(&format!("{}StorageAt", name), name.span())
 }
 
-fn getter_ast(var: &Var) -> TokenStream {
+/// The Rust type exposed by the getters and setters of the storage type `ty`.
+fn rust_type(ty: &Ident) -> TokenStream {
+    match ident_as_str!(ty) {
+        "Amount" => quote! { svm_sdk::Amount },
+        "Address" => quote! { svm_sdk::Address },
+        "U128" => quote! { svm_sdk::U128 },
+        "U256" => quote! { svm_sdk::U256 },
+        _ => quote! { #ty },
+    }
+}
+
+/// Returns the `svm_sdk::storage::ops` functions of the storage type `ty`.
+///
+/// The result is the `(getter, setter)` of a single variable when `array = false`
+/// and the `(getter, setter)` of an array item otherwise.
+fn ops_idents(ty: &Ident, array: bool) -> (Ident, Ident) {
+    let suffix = match ident_as_str!(ty) {
+        "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => "32",
+        "i64" | "u64" => "64",
+        "bool" => "_bool",
+        "Amount" => "_amount",
+        "Address" => "_addr",
+        "U128" => "_u128",
+        "U256" => "_u256",
+        _ => unreachable!(),
+    };
+
+    let prefix = if array { "array_" } else { "" };
+
+    let getter = format!("{}get{}", prefix, suffix);
+    let setter = format!("{}set{}", prefix, suffix);
+
+    (
+        Ident::new(&getter, Span::call_site()),
+        Ident::new(&setter, Span::call_site()),
+    )
+}
+
+/// Casting the value read using the getter of `ty` (see `ops_idents`).
+fn cast_from_ops(ty: &Ident, value: TokenStream) -> TokenStream {
+    match ident_as_str!(ty) {
+        "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" => quote! { #value as #ty },
+        _ => value,
+    }
+}
+
+/// Casting `value` before passing it to the setter of `ty` (see `ops_idents`).
+fn cast_to_ops(ty: &Ident, value: TokenStream) -> TokenStream {
+    match ident_as_str!(ty) {
+        "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => quote! { #value as u32 },
+        "i64" | "u64" => quote! { #value as u64 },
+        _ => value,
+    }
+}
+
+/// The type of the setter's `value` parameter (an `Address` is passed by reference).
+fn setter_value_type(ty: &Ident) -> TokenStream {
+    let rust_ty = rust_type(ty);
+
+    match ident_as_str!(ty) {
+        "Address" => quote! { &#rust_ty },
+        _ => rust_ty,
+    }
+}
+
+/// Returns the index parameters of an array getter / setter,
+/// and the code computing the array item `index` out of them.
+fn array_index_ast(var: &Var) -> (TokenStream, TokenStream, u32) {
+    match var {
+        Var::Array { length, .. } => (quote! { index: usize, }, quote! {}, *length),
+        Var::Array2 { rows, cols, .. } => {
+            let cols_usize = *cols as usize;
+
+            let params = quote! { row: usize, col: usize, };
+            let index = quote! {
+                assert!(col < #cols_usize);
+                let index = row * #cols_usize + col;
+            };
+
+            (params, index, rows * cols)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn getter_ast(var: &Var, accessor: Accessor) -> TokenStream {
     let includes = include_storage_ast();
+    let recv = accessor.receiver();
 
     match var {
         Var::Primitive { id, name, ty } => {
             let getter_name = getter_ident(name);
+            let id = accessor.var_id(quote! { #id });
+            let (get, _) = ops_idents(ty, false);
+            let rust_ty = rust_type(ty);
 
-            match ident_as_str!(ty) {
-                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
-                    quote! {
-                        fn #getter_name () -> #ty {
-                            #includes
+            let value = quote! { svm_sdk::storage::ops::#get::<StorageImpl>(#id) };
+            let value = cast_from_ops(ty, value);
 
-                            svm_sdk::storage::ops::get32::<StorageImpl>(#id) as #ty
-                        }
-                    }
-                }
-                "u64" | "i64" => {
-                    quote! {
-                        fn #getter_name () -> #ty {
-                            #includes
+            quote! {
+                fn #getter_name (#recv) -> #rust_ty {
+                    #includes
 
-                            svm_sdk::storage::ops::get64::<StorageImpl>(#id) as #ty
-                        }
-                    }
+                    #value
                 }
-                "bool" => {
-                    quote! {
-                        fn #getter_name () -> bool {
-                            #includes
+            }
+        }
+        Var::Array { id, name, ty, .. } | Var::Array2 { id, name, ty, .. } => {
+            let getter_name = getter_ident(name);
+            let id = accessor.var_id(quote! { #id });
+            let (get, _) = ops_idents(ty, true);
+            let rust_ty = rust_type(ty);
+            let (params, index, length) = array_index_ast(var);
 
-                            svm_sdk::storage::ops::get_bool::<StorageImpl>(#id) as #ty
-                        }
-                    }
-                }
-                "Amount" => {
-                    quote! {
-                        fn #getter_name () -> svm_sdk::Amount {
-                            #includes
+            let value = quote! { svm_sdk::storage::ops::#get::<StorageImpl>(#id, index, #length) };
+            let value = cast_from_ops(ty, value);
 
-                            svm_sdk::storage::ops::get_amount::<StorageImpl>(#id)
-                        }
-                    }
-                }
-                "Address" => {
-                    quote! {
-                        fn #getter_name () -> svm_sdk::Address {
-                            #includes
+            quote! {
+                fn #getter_name (#recv #params) -> #rust_ty {
+                    #includes
 
-                            svm_sdk::storage::ops::get_addr::<StorageImpl>(#id)
-                        }
-                    }
+                    #index
+
+                    #value
                 }
-                _ => unreachable!(),
             }
         }
-        Var::Array {
-            id,
-            name,
-            ty,
-            length,
-        } => {
+        Var::Blob { id, name, length } => {
             let getter_name = getter_ident(name);
+            let id = accessor.var_id(quote! { #id });
+            let chunks = blob_chunks(*length);
+            let length = *length as usize;
 
-            match ident_as_str!(ty) {
-                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
-                    quote! {
-                        fn #getter_name (index: usize) -> #ty {
-                            #includes
+            quote! {
+                fn #getter_name (#recv) -> [u8; #length] {
+                    #includes
 
-                            let value = svm_sdk::storage::ops::array_get32::<StorageImpl>(#id, index, #length);
-                            value as #ty
-                        }
-                    }
-                }
-                "u64" | "i64" => {
-                    quote! {
-                        fn #getter_name (index: usize) -> #ty {
-                            #includes
-
-                            let value = svm_sdk::storage::ops::array_get64::<StorageImpl>(#id, index, #length);
-                            value as #ty
-                        }
-                    }
-                }
-                "bool" => {
-                    quote! {
-                        fn #getter_name (index: usize) -> bool {
-                            #includes
+                    let mut bytes = [0u8; #length];
 
-                            svm_sdk::storage::ops::array_get_bool::<StorageImpl>(#id, index, #length)
-                        }
-                    }
-                }
-                "Amount" => quote! {
-                    fn #getter_name (index: usize) -> svm_sdk::Amount {
-                        #includes
+                    for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+                        let value = svm_sdk::storage::ops::array_get64::<StorageImpl>(#id, i, #chunks);
+                        let len = chunk.len();
 
-                        svm_sdk::storage::ops::array_get_amount::<StorageImpl>(#id, index, #length)
+                        chunk.copy_from_slice(&value.to_le_bytes()[..len]);
                     }
-                },
-                "Address" => quote! {
-                    fn #getter_name (index: usize) -> svm_sdk::Address {
-                        #includes
 
-                        svm_sdk::storage::ops::array_get_addr::<StorageImpl>(#id, index, #length)
-                    }
-                },
-                _ => unreachable!(),
+                    bytes
+                }
             }
         }
+        Var::Nested { .. } => unreachable!(),
     }
 }
 
-fn setter_ast(var: &Var) -> TokenStream {
+fn setter_ast(var: &Var, accessor: Accessor) -> TokenStream {
     let includes = include_storage_ast();
+    let recv = accessor.receiver();
 
     match var {
         Var::Primitive { id, name, ty } => {
             let setter_name = setter_ident(name);
+            let id = accessor.var_id(quote! { #id });
+            let (_, set) = ops_idents(ty, false);
+            let value_ty = setter_value_type(ty);
+            let value = cast_to_ops(ty, quote! { value });
 
-            match ident_as_str!(ty) {
-                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
-                    quote! {
-                        fn #setter_name (value: #ty) {
-                            #includes
-
-                            svm_sdk::storage::ops::set32::<StorageImpl>(#id, value as u32);
-                        }
-                    }
-                }
-                "u64" | "i64" => {
-                    quote! {
-                        fn #setter_name (value: #ty) {
-                            #includes
+            quote! {
+                fn #setter_name (#recv value: #value_ty) {
+                    #includes
 
-                            svm_sdk::storage::ops::set64::<StorageImpl>(#id, value as u64);
-                        }
-                    }
+                    svm_sdk::storage::ops::#set::<StorageImpl>(#id, #value);
                 }
-                "bool" => quote! {
-                    fn #setter_name (value: bool) {
-                        #includes
-
-                        svm_sdk::storage::ops::set_bool::<StorageImpl>(#id, value);
-                    }
-                },
-                "Amount" => quote! {
-                    fn #setter_name (value: svm_sdk::Amount) {
-                        #includes
-
-                        svm_sdk::storage::ops::set_amount::<StorageImpl>(#id, value);
-                    }
-                },
-                "Address" => quote! {
-                    fn #setter_name(value: &svm_sdk::Address) {
-                        #includes
-
-                        svm_sdk::storage::ops::set_addr::<StorageImpl>(#id, value);
-                    }
-                },
-                _ => unreachable!(),
             }
         }
-        Var::Array {
-            id,
-            name,
-            ty,
-            length,
-        } => {
+        Var::Array { id, name, ty, .. } | Var::Array2 { id, name, ty, .. } => {
             let setter_name = setter_ident(name);
+            let id = accessor.var_id(quote! { #id });
+            let (_, set) = ops_idents(ty, true);
+            let value_ty = setter_value_type(ty);
+            let value = cast_to_ops(ty, quote! { value });
+            let (params, index, length) = array_index_ast(var);
 
-            match ident_as_str!(ty) {
-                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
-                    quote! {
-                        fn #setter_name (index: usize, value: #ty) {
-                            #includes
+            quote! {
+                fn #setter_name (#recv #params value: #value_ty) {
+                    #includes
 
-                            svm_sdk::storage::ops::array_set32::<StorageImpl>(#id, index, #length, value as u32);
-                        }
-                    }
-                }
-                "u64" | "i64" => {
-                    quote! {
-                        fn #setter_name (index: usize, value: #ty) {
-                            #includes
+                    #index
 
-                            svm_sdk::storage::ops::array_set64::<StorageImpl>(#id, index, #length, value as u64);
-                        }
-                    }
+                    svm_sdk::storage::ops::#set::<StorageImpl>(#id, index, #length, #value);
                 }
-                "bool" => {
-                    quote! {
-                        fn #setter_name (index: usize, value: bool) {
-                            #includes
+            }
+        }
+        Var::Blob { id, name, length } => {
+            let setter_name = setter_ident(name);
+            let id = accessor.var_id(quote! { #id });
+            let chunks = blob_chunks(*length);
+            let length = *length as usize;
 
-                            svm_sdk::storage::ops::array_set_bool::<StorageImpl>(#id, index, #length, value);
-                        }
-                    }
-                }
-                "Amount" => {
-                    quote! {
-                        fn #setter_name (index: usize, value: Amount) {
-                            #includes
+            quote! {
+                fn #setter_name (#recv value: &[u8; #length]) {
+                    #includes
+
+                    for (i, chunk) in value.chunks(8).enumerate() {
+                        let mut bytes = [0u8; 8];
+                        bytes[..chunk.len()].copy_from_slice(chunk);
 
-                            svm_sdk::storage::ops::array_set_amount::<StorageImpl>(#id, index, #length, value);
-                        }
+                        let value = u64::from_le_bytes(bytes);
+                        svm_sdk::storage::ops::array_set64::<StorageImpl>(#id, i, #chunks, value);
                     }
                 }
-                "Address" => {
-                    quote! {
-                        fn #setter_name (index: usize, value: &svm_sdk::Address) {
-                            #includes
+            }
+        }
+        Var::Nested { .. } => unreachable!(),
+    }
+}
 
-                            svm_sdk::storage::ops::array_set_addr::<StorageImpl>(#id, index, #length, value);
-                        }
-                    }
+/// A nested storage field is accessed using a method named after the field,
+/// returning the nested storage's `{Name}StorageAt`.
+fn nested_ast(var: &Var, accessor: Accessor) -> TokenStream {
+    match var {
+        Var::Nested { name, ty, base } => {
+            let recv = accessor.receiver();
+            let base = accessor.var_id(base.clone());
+            let storage_at = storage_at_name(ty);
+
+            quote! {
+                fn #name (#recv) -> #storage_at {
+                    #storage_at(#base)
                 }
-                _ => unreachable!(),
             }
         }
+        _ => unreachable!(),
     }
 }
 
 fn include_storage_ast() -> TokenStream {
     quote! {
-        #[cfg(test)]
+        #[cfg(not(target_arch = "wasm32"))]
         use svm_sdk::storage::MockStorage as StorageImpl;
 
-        #[cfg(not(test))]
+        #[cfg(target_arch = "wasm32")]
         use svm_sdk::storage::ExtStorage as StorageImpl;
     }
 }
@@ -0,0 +1,308 @@
+use serde_json::{json, Value as Json};
+
+use svm_sdk::host::MockHost;
+use svm_sdk::traits::Encoder;
+use svm_sdk::{Address, Amount, AmountError, Bytes, CallData, U128, U256};
+use svm_sdk_macros::{ctor, endpoint, storage, AbiStruct};
+
+#[storage]
+struct Vars {
+    owner: Address,
+    balance: Amount,
+    scores: [u32; 3],
+}
+
+#[storage]
+struct Token {
+    supply: U256,
+    balances: [U128; 2],
+}
+
+#[endpoint(ctor)]
+fn init(owner: Address, balance: Amount) {
+    VarsStorage::set_owner(&owner);
+    VarsStorage::set_balance(balance);
+}
+
+#[ctor(payable)]
+fn open(_owner: Address) {}
+
+#[endpoint(payable)]
+fn fund() {}
+
+#[endpoint]
+fn work(a: Amount, to_double: bool) -> Amount {
+    if to_double {
+        a * Amount(2)
+    } else {
+        a
+    }
+}
+
+#[endpoint]
+fn stats(index: u8) -> (u32, [u16; 2], bool) {
+    (index as u32, [1, 2], true)
+}
+
+#[derive(AbiStruct, Debug, PartialEq)]
+struct Point {
+    x: u32,
+    y: i16,
+}
+
+#[derive(AbiStruct, Debug, PartialEq)]
+struct Shape {
+    name: String,
+    points: Vec<Point>,
+    tag: Bytes,
+}
+
+#[derive(AbiStruct, Debug, PartialEq)]
+struct Pair(bool, Amount);
+
+#[endpoint]
+fn draw(shape: Shape, scale: [u8; 2], memo: Vec<u64>) -> Point {
+    let origin = Point {
+        x: scale[0] as u32,
+        y: memo.len() as i16,
+    };
+
+    shape.points.into_iter().next().unwrap_or(origin)
+}
+
+#[endpoint]
+fn mint(amount: U128) -> U256 {
+    let supply = TokenStorage::get_supply() + amount.into();
+    TokenStorage::set_supply(supply);
+
+    supply
+}
+
+#[endpoint]
+fn withdraw(balance: Amount, amount: Amount) -> Result<Amount, AmountError> {
+    let left = balance.checked_sub(amount)?;
+
+    Ok(left)
+}
+
+fn record(bytes: &[u8]) -> Json {
+    assert_eq!(bytes.last(), Some(&b'\n'));
+
+    serde_json::from_slice(bytes).unwrap()
+}
+
+#[test]
+fn endpoint_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_ENDPOINT_WORK),
+        json!({
+            "endpoint": {
+                "name": "work",
+                "ctor": false,
+                "payable": false,
+                "params": [
+                    { "name": "a", "type": "amount" },
+                    { "name": "to_double", "type": "bool" }
+                ],
+                "returns": ["amount"]
+            }
+        })
+    );
+
+    assert_eq!(
+        record(&__SVM_ABI_ENDPOINT_STATS)["endpoint"]["returns"],
+        json!(["u32", ["u16"], "bool"])
+    );
+}
+
+#[test]
+fn ctor_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_ENDPOINT_INIT),
+        json!({
+            "endpoint": {
+                "name": "init",
+                "ctor": true,
+                "payable": false,
+                "params": [
+                    { "name": "owner", "type": "address" },
+                    { "name": "balance", "type": "amount" }
+                ],
+                "returns": []
+            }
+        })
+    );
+}
+
+#[test]
+fn payable_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_ENDPOINT_OPEN),
+        json!({
+            "endpoint": {
+                "name": "open",
+                "ctor": true,
+                "payable": true,
+                "params": [{ "name": "_owner", "type": "address" }],
+                "returns": []
+            }
+        })
+    );
+
+    let endpoint = &record(&__SVM_ABI_ENDPOINT_FUND)["endpoint"];
+
+    assert_eq!(endpoint["ctor"], json!(false));
+    assert_eq!(endpoint["payable"], json!(true));
+}
+
+#[test]
+fn storage_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_STORAGE_VARS),
+        json!({
+            "storage": {
+                "name": "Vars",
+                "vars": [
+                    { "id": 0, "name": "owner", "type": "Address", "count": 1 },
+                    { "id": 1, "name": "balance", "type": "Amount", "count": 1 },
+                    { "id": 2, "name": "scores", "type": "u32", "count": 3 }
+                ]
+            }
+        })
+    );
+}
+
+#[test]
+fn struct_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_STRUCT_SHAPE),
+        json!({
+            "struct": {
+                "name": "Shape",
+                "fields": [
+                    { "name": "name", "type": "string" },
+                    { "name": "points", "type": [{ "struct": "Point" }] },
+                    { "name": "tag", "type": "bytes" }
+                ]
+            }
+        })
+    );
+
+    assert_eq!(
+        record(&__SVM_ABI_STRUCT_PAIR),
+        json!({
+            "struct": {
+                "name": "Pair",
+                "fields": [
+                    { "name": "0", "type": "bool" },
+                    { "name": "1", "type": "amount" }
+                ]
+            }
+        })
+    );
+
+    assert_eq!(
+        record(&__SVM_ABI_ENDPOINT_DRAW),
+        json!({
+            "endpoint": {
+                "name": "draw",
+                "ctor": false,
+                "payable": false,
+                "params": [
+                    { "name": "shape", "type": { "struct": "Shape" } },
+                    { "name": "scale", "type": ["u8"] },
+                    { "name": "memo", "type": ["u64"] }
+                ],
+                "returns": [{ "struct": "Point" }]
+            }
+        })
+    );
+}
+
+#[test]
+fn struct_encode_decode() {
+    let shape = Shape {
+        name: "triangle".to_string(),
+        points: vec![Point { x: 1, y: -1 }, Point { x: 2, y: 3 }],
+        tag: Bytes(vec![0xAB, 0xCD]),
+    };
+    let pair = Pair(true, Amount(100));
+
+    let mut bytes = Vec::new();
+    shape.encode(&mut bytes);
+    pair.encode(&mut bytes);
+
+    let mut calldata = CallData::new(&bytes);
+    let (shape_, pair_): (Shape, Pair) = calldata.next_2();
+
+    assert_eq!(shape_, shape);
+    assert_eq!(pair_, pair);
+}
+
+#[test]
+fn wide_uints_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_STORAGE_TOKEN)["storage"]["vars"],
+        json!([
+            { "id": 0, "name": "supply", "type": "U256", "count": 1 },
+            { "id": 1, "name": "balances", "type": "U128", "count": 2 }
+        ])
+    );
+
+    let endpoint = &record(&__SVM_ABI_ENDPOINT_MINT)["endpoint"];
+
+    assert_eq!(
+        endpoint["params"],
+        json!([{ "name": "amount", "type": "u128" }])
+    );
+    assert_eq!(endpoint["returns"], json!(["u256"]));
+}
+
+#[test]
+fn wide_uints_storage() {
+    let big = U128(u128::MAX);
+
+    TokenStorage::set_balances(1, big);
+    assert_eq!(TokenStorage::get_balances(0), U128(0));
+    assert_eq!(TokenStorage::get_balances(1), big);
+
+    let supply = U256::from(big) * U256::from(big);
+    TokenStorage::set_supply(supply);
+    assert_eq!(TokenStorage::get_supply(), supply);
+}
+
+#[test]
+fn result_endpoint_abi() {
+    let endpoint = &record(&__SVM_ABI_ENDPOINT_WITHDRAW)["endpoint"];
+
+    assert_eq!(endpoint["returns"], json!(["amount"]));
+}
+
+#[test]
+fn result_endpoint_call() {
+    let host = MockHost::instance();
+
+    host.reset();
+    host.set_calldata((Amount(10), Amount(3)));
+
+    withdraw();
+
+    let mut expected = Vec::new();
+    Amount(7).encode(&mut expected);
+
+    assert_eq!(host.get_returndata(), Some(expected));
+    assert_eq!(host.get_revert_data(), None);
+
+    host.reset();
+    host.set_calldata((Amount(3), Amount(10)));
+
+    // reverting halts the endpoint (natively, it panics)
+    let result = std::panic::catch_unwind(withdraw);
+
+    assert!(result.is_err());
+    assert_eq!(host.get_returndata(), None);
+    assert_eq!(
+        host.get_revert_data(),
+        Some(svm_sdk::revert::encode_error(2, "amount underflow"))
+    );
+}
@@ -0,0 +1,129 @@
+use serde_json::{json, Value as Json};
+
+use svm_sdk_testing::TestApp;
+use svm_sdk::{Address, Amount};
+use svm_sdk_macros::storage;
+
+#[storage]
+struct Account {
+    owner: Address,
+    balance: Amount,
+    nonce: u32,
+}
+
+#[storage]
+struct Bank {
+    total: Amount,
+    alice: Account,
+    grid: [[u16; 3]; 2],
+    bob: Account,
+    hash: [u8; 20],
+}
+
+fn record(bytes: &[u8]) -> Json {
+    assert_eq!(bytes.last(), Some(&b'\n'));
+
+    serde_json::from_slice(bytes).unwrap()
+}
+
+#[test]
+fn nested_storage_layout() {
+    assert_eq!(AccountStorage::VARS, 3);
+    assert_eq!(BankStorage::VARS, 1 + 6 + 3 + 3 + 3);
+
+    assert_eq!(
+        BankStorage::SCHEMA,
+        &[
+            (0, "total", "Amount", 1),
+            (1, "grid", "u16", 6),
+            (7, "hash", "u64", 3),
+            (10, "alice", "Account", 3),
+            (13, "bob", "Account", 3),
+        ]
+    );
+}
+
+#[test]
+fn nested_storage_abi() {
+    assert_eq!(
+        record(&__SVM_ABI_STORAGE_BANK),
+        json!({
+            "storage": {
+                "name": "Bank",
+                "vars": [
+                    { "id": 0, "name": "total", "type": "Amount", "count": 1 },
+                    { "id": 1, "name": "grid", "type": "u16", "count": 6 },
+                    { "id": 7, "name": "hash", "type": "u64", "count": 3 },
+                    { "name": "alice", "type": { "storage": "Account" } },
+                    { "name": "bob", "type": { "storage": "Account" } }
+                ]
+            }
+        })
+    );
+}
+
+#[test]
+fn nested_storage_accessors() {
+    let _app = TestApp::new();
+
+    let alice: Address = [0x10; 20].into();
+    let bob: Address = [0x20; 20].into();
+
+    BankStorage::set_total(Amount(100));
+    BankStorage::alice().set_owner(&alice);
+    BankStorage::alice().set_balance(Amount(30));
+    BankStorage::bob().set_owner(&bob);
+    BankStorage::bob().set_balance(Amount(70));
+    BankStorage::bob().set_nonce(2);
+
+    assert_eq!(BankStorage::get_total(), Amount(100));
+
+    let account = BankStorage::alice();
+    assert_eq!(account.get_owner(), alice);
+    assert_eq!(account.get_balance(), Amount(30));
+    assert_eq!(account.get_nonce(), 0);
+
+    let account = BankStorage::bob();
+    assert_eq!(account.get_owner(), bob);
+    assert_eq!(account.get_balance(), Amount(70));
+    assert_eq!(account.get_nonce(), 2);
+}
+
+#[test]
+fn two_dim_array_storage() {
+    let _app = TestApp::new();
+
+    BankStorage::set_grid(0, 2, 10);
+    BankStorage::set_grid(1, 0, 20);
+
+    assert_eq!(BankStorage::get_grid(0, 2), 10);
+    assert_eq!(BankStorage::get_grid(1, 0), 20);
+    assert_eq!(BankStorage::get_grid(0, 0), 0);
+    assert_eq!(BankStorage::get_grid(1, 2), 0);
+}
+
+#[test]
+#[should_panic]
+fn two_dim_array_storage_col_out_of_bounds() {
+    let _app = TestApp::new();
+
+    BankStorage::get_grid(0, 3);
+}
+
+#[test]
+fn blob_storage() {
+    let _app = TestApp::new();
+
+    assert_eq!(BankStorage::get_hash(), [0; 20]);
+
+    let mut hash = [0u8; 20];
+
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = i as u8 + 1;
+    }
+
+    BankStorage::set_hash(&hash);
+
+    assert_eq!(BankStorage::get_hash(), hash);
+    assert_eq!(BankStorage::get_total(), Amount(0));
+}
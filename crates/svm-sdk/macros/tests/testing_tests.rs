@@ -0,0 +1,199 @@
+use svm_sdk_testing::TestApp;
+use svm_sdk::{ensure, Address, Amount, AmountError, LayerId};
+use svm_sdk_macros::{endpoint, storage};
+
+#[storage]
+struct Vault {
+    owner: Address,
+    balance: Amount,
+}
+
+#[endpoint(ctor)]
+fn init(balance: Amount) {
+    VaultStorage::set_owner(&Node.sender());
+    VaultStorage::set_balance(balance);
+}
+
+#[endpoint]
+fn deposit(amount: Amount) -> Amount {
+    let balance = VaultStorage::get_balance() + amount;
+    VaultStorage::set_balance(balance);
+
+    svm_sdk::log("deposit", 1);
+
+    balance
+}
+
+#[endpoint]
+fn withdraw(amount: Amount) -> Result<Amount, AmountError> {
+    let balance = VaultStorage::get_balance().checked_sub(amount)?;
+    VaultStorage::set_balance(balance);
+
+    Ok(balance)
+}
+
+#[endpoint]
+fn pay(to: Address, amount: Amount) {
+    ensure!(Node.sender() == VaultStorage::get_owner(), "not the owner");
+
+    Node.transfer(&to, amount);
+}
+
+#[endpoint(payable)]
+fn fund() -> Amount {
+    let balance = VaultStorage::get_balance() + Node.value();
+    VaultStorage::set_balance(balance);
+
+    balance
+}
+
+#[endpoint]
+fn layer() -> u64 {
+    let layer = Node.layer_id();
+
+    assert!(layer.0 > 0, "no layer");
+
+    layer.0
+}
+
+fn owner() -> Address {
+    [0x10; 20].into()
+}
+
+fn app_addr() -> Address {
+    [0x20; 20].into()
+}
+
+fn spawn_vault() -> TestApp {
+    let mut app = TestApp::new();
+
+    app.set_sender(owner());
+    app.set_app(app_addr());
+
+    let receipt = app.spawn(init, Amount(10));
+    assert!(receipt.success);
+
+    app
+}
+
+#[test]
+fn test_app_spawn() {
+    let _app = spawn_vault();
+
+    assert_eq!(VaultStorage::get_owner(), owner());
+    assert_eq!(VaultStorage::get_balance(), Amount(10));
+}
+
+#[test]
+fn test_app_call_returns_and_logs() {
+    let mut app = spawn_vault();
+
+    let receipt = app.call(deposit, Amount(5));
+
+    assert!(receipt.success);
+    assert_eq!(receipt.returns().next_1::<Amount>(), Amount(15));
+    assert_eq!(receipt.logs, vec![("deposit".to_string(), 1)]);
+    assert_eq!(VaultStorage::get_balance(), Amount(15));
+}
+
+#[test]
+fn test_app_call_err_reverts() {
+    let mut app = spawn_vault();
+
+    let receipt = app.call(withdraw, Amount(11));
+
+    assert!(receipt.is_reverted());
+    assert!(receipt.returndata.is_empty());
+    assert_eq!(receipt.error(), Some((2, "amount underflow".to_string())));
+    assert_eq!(VaultStorage::get_balance(), Amount(10));
+
+    let receipt = app.call(withdraw, Amount(4));
+
+    assert!(receipt.success);
+    assert_eq!(receipt.returns().next_1::<Amount>(), Amount(6));
+}
+
+#[test]
+fn test_app_ensure_reverts() {
+    let mut app = spawn_vault();
+
+    let other: Address = [0x30; 20].into();
+    app.set_balance(&app_addr(), Amount(100));
+
+    app.set_sender(other);
+    let receipt = app.call(pay, (other, Amount(30)));
+
+    assert_eq!(receipt.error(), Some((0, "not the owner".to_string())));
+    assert_eq!(app.balance_of(&other), Amount(0));
+
+    app.set_sender(owner());
+    let receipt = app.call(pay, (other, Amount(30)));
+
+    assert!(receipt.success);
+    assert_eq!(app.balance_of(&other), Amount(30));
+    assert_eq!(app.balance_of(&app_addr()), Amount(70));
+}
+
+#[test]
+fn test_app_payable() {
+    let mut app = spawn_vault();
+
+    app.set_value(Amount(5));
+    let receipt = app.call(fund, ());
+
+    assert_eq!(receipt.returns().next_1::<Amount>(), Amount(15));
+
+    let receipt = app.call(deposit, Amount(1));
+
+    assert_eq!(
+        receipt.error(),
+        Some((
+            svm_sdk::revert::NOT_PAYABLE_CODE,
+            "endpoint isn't payable".to_string()
+        ))
+    );
+    assert_eq!(VaultStorage::get_balance(), Amount(15));
+
+    app.set_value(Amount(0));
+    let receipt = app.call(deposit, Amount(1));
+
+    assert!(receipt.success);
+}
+
+#[test]
+fn test_app_panic_reverts() {
+    let mut app = spawn_vault();
+
+    app.set_layer(LayerId(0));
+    let receipt = app.call(layer, ());
+
+    assert_eq!(
+        receipt.error(),
+        Some((svm_sdk::revert::PANIC_CODE, "no layer".to_string()))
+    );
+
+    app.set_layer(LayerId(7));
+    let receipt = app.call(layer, ());
+
+    assert_eq!(receipt.returns().next_1::<u64>(), 7);
+}
+
+#[test]
+fn test_app_snapshot_restore() {
+    let mut app = spawn_vault();
+
+    let snapshot = app.snapshot();
+
+    app.call(deposit, Amount(5));
+    app.set_balance(&owner(), Amount(1));
+    assert_eq!(VaultStorage::get_balance(), Amount(15));
+
+    app.restore(snapshot);
+
+    assert_eq!(VaultStorage::get_balance(), Amount(10));
+    assert_eq!(app.balance_of(&owner()), Amount(0));
+
+    app.reset();
+
+    assert_eq!(VaultStorage::get_balance(), Amount(0));
+}
@@ -0,0 +1,64 @@
+use svm_sdk_host::traits::Host;
+use svm_sdk_types::Topic;
+
+#[cfg(target_arch = "wasm32")]
+use svm_sdk_host::ExtHost as Node;
+
+#[cfg(not(target_arch = "wasm32"))]
+use svm_sdk_host::MockHost as Node;
+
+/// Emits an event made of `topics` and its ABI-encoded `data` into the running App logs.
+///
+/// Prefer using the `emit!` macro, which builds the `topics` and encodes the `data`.
+/// When not compiled to Wasm the event is recorded by the `MockHost`.
+pub fn emit(topics: &[Topic], data: &[u8]) {
+    Node.emit(topics, data)
+}
+
+/// The `emit!` macro emits an event into the running App logs.
+///
+/// The first topic of the event is always its `name`. It must be a string literal
+/// of at most `TOPIC_SIZE` bytes, a longer `name` fails the compilation.
+/// The other `topics` can be of any type implementing `ToTopic`,
+/// and the `data` values of any type implementing the ABI `Encoder`.
+///
+/// ```rust,ignore
+/// use svm_sdk::{emit, Address, Amount};
+///
+/// fn transfer(from: Address, to: Address, amount: Amount) {
+///     emit!("Transfer", topics: [from, to], data: [amount]);
+/// }
+/// ```
+///
+/// Nodes index the events topics (see the receipt's bloom filter) so that one can ask
+/// which transactions have emitted a `Transfer` event involving `from`.
+///
+/// ```compile_fail
+/// svm_sdk::emit!("AnEventNameLongerThanThirtyTwoBytes");
+/// ```
+#[macro_export]
+macro_rules! emit {
+    ($name:literal) => {{
+        $crate::emit!($name, topics: [], data: [])
+    }};
+    ($name:literal, topics: [$($topic:expr),* $(,)?]) => {{
+        $crate::emit!($name, topics: [$($topic),*], data: [])
+    }};
+    ($name:literal, data: [$($data:expr),* $(,)?]) => {{
+        $crate::emit!($name, topics: [], data: [$($data),*])
+    }};
+    ($name:literal, topics: [$($topic:expr),* $(,)?], data: [$($data:expr),* $(,)?]) => {{
+        use $crate::traits::Encoder;
+        use $crate::ToTopic;
+
+        // overflows (and hence fails the compilation) when `$name` doesn't fit a `Topic`
+        const _: usize = $crate::TOPIC_SIZE - $name.len();
+
+        let topics = [$name.to_topic() $(, $topic.to_topic())*];
+
+        let mut data = Vec::new();
+        $( $data.encode(&mut data); )*
+
+        $crate::emit(&topics, &data);
+    }};
+}
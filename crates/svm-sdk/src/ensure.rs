@@ -1,24 +1,20 @@
 /// The `ensure!` macro is intended to be used within written SVM apps.
 /// The macro is very similar to the `assert` macro used for writing tests.
-/// Besides panicking in case the exercised expression isn't satisfied,
-/// the passed `msg` will be logged-in first.
 ///
-/// That log entry could be later retrieved and inspected since it will part of the
-/// logs associated with the SVM transaction receipt.
+/// When the exercised expression isn't satisfied, the running transaction reverts
+/// (see `svm_sdk::revert`) with error code `0` and the passed `msg`.
+///
+/// The revert data could be later retrieved and inspected since it will be part of
+/// the SVM transaction receipt.
 ///
 
 #[macro_export]
 macro_rules! ensure {
     ($expr:expr, $msg:expr) => {{
-        use $crate::log;
-
         let satisfied = $expr;
 
         if !satisfied {
-            log($msg, 0);
-
-            // should panic
-            let _ = 1 / 0;
+            $crate::revert::revert_with(0, $msg);
         }
     }};
 }
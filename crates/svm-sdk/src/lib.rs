@@ -11,15 +11,22 @@
 #![allow(dead_code)]
 #![allow(unreachable_code)]
 
+mod emit;
 mod log;
 
 /// Logging API
 pub use log::log;
 
+/// Events API
+pub use emit::emit;
+
 /// `ensure` macro
 #[macro_use]
 pub mod ensure;
 
+/// Revert API
+pub mod revert;
+
 use svm_sdk_alloc;
 use svm_sdk_storage;
 use svm_sdk_types;
@@ -32,14 +39,25 @@ pub mod host {
     pub use svm_sdk_host::{ExtHost, MockHost};
 }
 
+pub mod abi {
+    pub use svm_abi_encoder::encode_tuple;
+
+    /// The name of the Wasm custom section holding a template's ABI.
+    ///
+    /// Each `#[endpoint]`, `#[storage]` and `#[derive(AbiStruct)]` appends a single JSON record
+    /// terminated by a newline (the linker concatenates the custom sections sharing the same name).
+    pub const ABI_SECTION: &str = "svm_abi";
+}
+
 pub mod traits {
     pub use svm_abi_encoder::Encoder;
     pub use svm_sdk_host::traits::Host;
     pub use svm_sdk_storage::Storage;
+    pub use svm_sdk_types::AppError;
 }
 
 pub mod storage {
-    pub use svm_sdk_storage::{ExtStorage, MockStorage};
+    pub use svm_sdk_storage::{ExtStorage, MockStorage, StorageSnapshot};
 
     pub mod ops {
         #[rustfmt::skip]
@@ -62,6 +80,18 @@ pub mod storage {
             get_addr,
             set_addr,
 
+            load128,
+            store128,
+
+            load256,
+            store256,
+
+            get_u128,
+            set_u128,
+
+            get_u256,
+            set_u256,
+
             array_get_bool,
             array_set_bool,
 
@@ -75,7 +105,13 @@ pub mod storage {
             array_set_amount,
 
             array_get_addr,
-            array_set_addr
+            array_set_addr,
+
+            array_get_u128,
+            array_set_u128,
+
+            array_get_u256,
+            array_set_u256
         };
     }
 }
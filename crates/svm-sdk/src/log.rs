@@ -1,12 +1,14 @@
-extern "C" {
-    #[link_section = "svm"]
-    fn svm_log(msg_ptr: u32, msg_len: u32, code: u32);
-}
+use svm_sdk_host::traits::Host;
+
+#[cfg(target_arch = "wasm32")]
+use svm_sdk_host::ExtHost as Node;
+
+#[cfg(not(target_arch = "wasm32"))]
+use svm_sdk_host::MockHost as Node;
 
 /// Log the string `msg` along with code `code` into the running App logs.
+///
+/// When not compiled to Wasm the log is recorded by the `MockHost`.
 pub fn log(msg: &str, code: u8) {
-    let ptr = msg.as_ptr() as u32;
-    let len = msg.len() as u32;
-
-    unsafe { svm_log(ptr, len, code as u32) }
+    Node.log(msg, code)
 }
@@ -0,0 +1,72 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use svm_abi_encoder::Encoder;
+use svm_sdk_host::traits::Host;
+
+#[cfg(target_arch = "wasm32")]
+use svm_sdk_host::ExtHost as Node;
+
+#[cfg(not(target_arch = "wasm32"))]
+use svm_sdk_host::MockHost as Node;
+
+/// The error code of the revert data of a panicking App (see the `panic-handler` feature).
+pub const PANIC_CODE: u32 = u32::MAX;
+
+/// The error code of the revert data of a non-payable endpoint called along with transferred coins
+/// (see `Host::value`).
+pub const NOT_PAYABLE_CODE: u32 = u32::MAX - 1;
+
+/// Encodes an error as revert data: the ABI-encoded `code` (a `u32`) followed by the ABI-encoded `msg` (a `String`).
+///
+/// That's the revert data used by `ensure!`, the panic handler and endpoints returning an `Err`.
+pub fn encode_error(code: u32, msg: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    code.encode(&mut data);
+    msg.encode(&mut data);
+
+    data
+}
+
+/// Reverts the running transaction with revert data `data`.
+///
+/// The transaction's storage changes are discarded and its receipt holds the `data`.
+///
+/// When not compiled to Wasm the `data` is recorded by the `MockHost` and then `revert` panics.
+pub fn revert(data: &[u8]) -> ! {
+    Node.revert(data);
+
+    halt()
+}
+
+/// Reverts the running transaction with an error of code `code` and message `msg` (see `encode_error`).
+pub fn revert_with(code: u32, msg: &str) -> ! {
+    let data = encode_error(code, msg);
+
+    revert(&data)
+}
+
+/// Reverts the running transaction with `PANIC_CODE` and the panic message (and location).
+///
+/// A `#![no_std]` Template compiled to Wasm has to provide a `#[panic_handler]`,
+/// enabling the `panic-handler` feature makes the SDK provide this one.
+#[cfg(all(feature = "panic-handler", target_arch = "wasm32"))]
+#[panic_handler]
+fn panic_handler(info: &core::panic::PanicInfo) -> ! {
+    use alloc::string::ToString;
+
+    let msg = info.to_string();
+
+    revert_with(PANIC_CODE, &msg)
+}
+
+/// Halts the execution right after reverting (a trap when running as Wasm, otherwise a panic).
+fn halt() -> ! {
+    #[cfg(target_arch = "wasm32")]
+    core::arch::wasm32::unreachable();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    panic!("transaction reverted")
+}
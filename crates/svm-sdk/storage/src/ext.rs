@@ -13,6 +13,14 @@ extern "C" {
     fn svm_store160(mem_ptr: u32, var_id: u32);
 
     fn svm_load160(var_id: u32, mem_ptr: u32);
+
+    fn svm_store128(mem_ptr: u32, var_id: u32);
+
+    fn svm_load128(var_id: u32, mem_ptr: u32);
+
+    fn svm_store256(mem_ptr: u32, var_id: u32);
+
+    fn svm_load256(var_id: u32, mem_ptr: u32);
 }
 
 pub struct ExtStorage;
@@ -41,4 +49,20 @@ impl Storage for ExtStorage {
     fn load160(var_id: u32, offset: usize) {
         unsafe { svm_load160(var_id, offset as u32) }
     }
+
+    fn store128(var_id: u32, offset: usize) {
+        unsafe { svm_store128(offset as u32, var_id) }
+    }
+
+    fn load128(var_id: u32, offset: usize) {
+        unsafe { svm_load128(var_id, offset as u32) }
+    }
+
+    fn store256(var_id: u32, offset: usize) {
+        unsafe { svm_store256(offset as u32, var_id) }
+    }
+
+    fn load256(var_id: u32, offset: usize) {
+        unsafe { svm_load256(var_id, offset as u32) }
+    }
 }
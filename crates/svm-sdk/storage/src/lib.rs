@@ -16,10 +16,10 @@ mod mock;
 mod traits;
 
 pub use ext::ExtStorage;
-pub use mock::MockStorage;
+pub use mock::{MockStorage, StorageSnapshot};
 pub use traits::Storage;
 
-use svm_sdk_types::{Address, Amount};
+use svm_sdk_types::{Address, Amount, U128, U256};
 
 pub fn get32<S: Storage>(var_id: u32) -> u32 {
     S::get32(var_id)
@@ -96,6 +96,74 @@ pub fn set_addr<S: Storage>(var_id: u32, value: &Address) {
     store160::<S>(var_id, slice);
 }
 
+pub fn load128<S: Storage>(var_id: u32) -> &'static [u8] {
+    use svm_sdk_alloc::alloc;
+    let ptr = alloc(16);
+
+    S::load128(var_id, ptr.offset());
+
+    unsafe { core::slice::from_raw_parts(ptr.as_ptr(), 16) }
+}
+
+pub fn store128<S: Storage>(var_id: u32, slice: &[u8]) {
+    assert_eq!(slice.len(), 16);
+
+    let ptr: *const u8 = slice.as_ptr();
+    let offset = ptr as usize;
+
+    S::store128(var_id, offset);
+}
+
+pub fn load256<S: Storage>(var_id: u32) -> &'static [u8] {
+    use svm_sdk_alloc::alloc;
+    let ptr = alloc(32);
+
+    S::load256(var_id, ptr.offset());
+
+    unsafe { core::slice::from_raw_parts(ptr.as_ptr(), 32) }
+}
+
+pub fn store256<S: Storage>(var_id: u32, slice: &[u8]) {
+    assert_eq!(slice.len(), 32);
+
+    let ptr: *const u8 = slice.as_ptr();
+    let offset = ptr as usize;
+
+    S::store256(var_id, offset);
+}
+
+/// `U128` variables are stored as their Big-Endian representation.
+pub fn get_u128<S: Storage>(var_id: u32) -> U128 {
+    let slice = load128::<S>(var_id);
+
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(slice);
+
+    U128::from_be_bytes(bytes)
+}
+
+pub fn set_u128<S: Storage>(var_id: u32, value: U128) {
+    let bytes = value.to_be_bytes();
+
+    store128::<S>(var_id, &bytes);
+}
+
+/// `U256` variables are stored as their Big-Endian representation.
+pub fn get_u256<S: Storage>(var_id: u32) -> U256 {
+    let slice = load256::<S>(var_id);
+
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(slice);
+
+    U256::from_be_bytes(bytes)
+}
+
+pub fn set_u256<S: Storage>(var_id: u32, value: U256) {
+    let bytes = value.to_be_bytes();
+
+    store256::<S>(var_id, &bytes);
+}
+
 // Array
 
 pub fn array_get_bool<S: Storage>(var_id: u32, index: usize, length: u32) -> bool {
@@ -162,6 +230,30 @@ pub fn array_set_addr<S: Storage>(var_id: u32, index: usize, length: u32, value:
     store160::<S>(var_id, slice)
 }
 
+pub fn array_get_u128<S: Storage>(var_id: u32, index: usize, length: u32) -> U128 {
+    let var_id = cell_offset(var_id, index, length);
+
+    get_u128::<S>(var_id)
+}
+
+pub fn array_set_u128<S: Storage>(var_id: u32, index: usize, length: u32, value: U128) {
+    let var_id = cell_offset(var_id, index, length);
+
+    set_u128::<S>(var_id, value)
+}
+
+pub fn array_get_u256<S: Storage>(var_id: u32, index: usize, length: u32) -> U256 {
+    let var_id = cell_offset(var_id, index, length);
+
+    get_u256::<S>(var_id)
+}
+
+pub fn array_set_u256<S: Storage>(var_id: u32, index: usize, length: u32, value: U256) {
+    let var_id = cell_offset(var_id, index, length);
+
+    set_u256::<S>(var_id, value)
+}
+
 #[inline]
 fn cell_offset(var_id: u32, index: usize, length: u32) -> u32 {
     let index = index as u32;
@@ -28,6 +28,12 @@ pub struct InnerStorage {
     vars: HashMap<u32, Var>,
 }
 
+/// A copy of the `MockStorage` variables (see `MockStorage::snapshot` and `MockStorage::restore`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageSnapshot {
+    vars: HashMap<u32, Var>,
+}
+
 impl InnerStorage {
     pub fn new() -> Self {
         Self {
@@ -69,6 +75,22 @@ impl InnerStorage {
         self.load_vec(var_id, offset, 20)
     }
 
+    pub fn store128(&mut self, var_id: u32, offset: usize) {
+        self.store_vec(var_id, offset, 16);
+    }
+
+    pub fn load128(&self, var_id: u32, offset: usize) {
+        self.load_vec(var_id, offset, 16)
+    }
+
+    pub fn store256(&mut self, var_id: u32, offset: usize) {
+        self.store_vec(var_id, offset, 32);
+    }
+
+    pub fn load256(&self, var_id: u32, offset: usize) {
+        self.load_vec(var_id, offset, 32)
+    }
+
     fn get_var<F>(&self, var_id: u32, default: F) -> Var
     where
         F: Fn() -> Var,
@@ -106,9 +128,19 @@ impl InnerStorage {
         unsafe { core::slice::from_raw_parts(offset as *const u8, len) }
     }
 
-    fn clear(&mut self) {
+    pub fn clear(&mut self) {
         self.vars.clear();
     }
+
+    pub fn snapshot(&self) -> StorageSnapshot {
+        StorageSnapshot {
+            vars: self.vars.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: StorageSnapshot) {
+        self.vars = snapshot.vars;
+    }
 }
 
 pub struct MockStorage;
@@ -161,15 +193,51 @@ impl Storage for MockStorage {
 
         storage.load160(var_id, offset)
     }
+
+    fn store128(var_id: u32, offset: usize) {
+        let mut storage = Self::instance();
+
+        storage.store128(var_id, offset)
+    }
+
+    fn load128(var_id: u32, offset: usize) {
+        let mut storage = Self::instance();
+
+        storage.load128(var_id, offset)
+    }
+
+    fn store256(var_id: u32, offset: usize) {
+        let mut storage = Self::instance();
+
+        storage.store256(var_id, offset)
+    }
+
+    fn load256(var_id: u32, offset: usize) {
+        let mut storage = Self::instance();
+
+        storage.load256(var_id, offset)
+    }
 }
 
 impl MockStorage {
-    fn clear() {
+    pub fn clear() {
         let mut storage = Self::instance();
 
         storage.clear();
     }
 
+    pub fn snapshot() -> StorageSnapshot {
+        let storage = Self::instance();
+
+        storage.snapshot()
+    }
+
+    pub fn restore(snapshot: StorageSnapshot) {
+        let mut storage = Self::instance();
+
+        storage.restore(snapshot);
+    }
+
     fn from_raw_parts<'a>(offset: usize, len: usize) -> &'a [u8] {
         unsafe { core::slice::from_raw_parts(offset as *const u8, len) }
     }
@@ -237,12 +305,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn storage_mock_snapshot_restore() {
+        test(|| {
+            MockStorage::set32(1, 10);
+            MockStorage::set64(2, 20);
+
+            let snapshot = MockStorage::snapshot();
+
+            MockStorage::set32(1, 30);
+            MockStorage::set64(3, 40);
+
+            MockStorage::restore(snapshot);
+
+            assert_eq!(MockStorage::get32(1), 10);
+            assert_eq!(MockStorage::get64(2), 20);
+            assert_eq!(MockStorage::get64(3), 0);
+        });
+    }
+
     macro_rules! check_load_store {
         ($n:expr, $load_fn:ident, $store_fn:ident) => {{
             test(|| {
                 let var1 = 1;
                 let var2 = 2;
-                let n = 20;
+                let n = $n;
 
                 let addr1 = vec![0x10u8; n];
                 let addr2 = vec![0x20u8; n];
@@ -269,4 +356,14 @@ mod tests {
     fn storage_mock_load160_store160() {
         check_load_store!(20, load160, store160);
     }
+
+    #[test]
+    fn storage_mock_load128_store128() {
+        check_load_store!(16, load128, store128);
+    }
+
+    #[test]
+    fn storage_mock_load256_store256() {
+        check_load_store!(32, load256, store256);
+    }
 }
@@ -10,4 +10,12 @@ pub trait Storage {
     fn store160(var_id: u32, offset: usize);
 
     fn load160(var_id: u32, offset: usize);
+
+    fn store128(var_id: u32, offset: usize);
+
+    fn load128(var_id: u32, offset: usize);
+
+    fn store256(var_id: u32, offset: usize);
+
+    fn load256(var_id: u32, offset: usize);
 }
@@ -0,0 +1,267 @@
+//! A harness for unit-testing SVM apps natively (without compiling them to Wasm and without running a `Runtime`).
+//!
+//! When not compiled to Wasm, the `#[endpoint]`s run against the `MockHost` and the `#[storage]` against the `MockStorage`.
+//! A `TestApp` drives both: it sets the `calldata` and the transaction's environment (sender, layer, value, balances),
+//! calls an endpoint and collects its outcome into a `Receipt`.
+//!
+//! Since the `MockHost` and the `MockStorage` are global, each `TestApp` holds a lock throughout its lifetime.
+//! That way tests using a `TestApp` run one at a time (even when `cargo test` runs them on multiple threads).
+//!
+//! The crate is meant to be used as a dev-dependency (it requires the Rust stdlib, unlike `svm-sdk`).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use svm_sdk::{Amount, AmountError};
+//! use svm_sdk_testing::TestApp;
+//!
+//! #[storage]
+//! struct Vars {
+//!     balance: Amount,
+//! }
+//!
+//! #[endpoint(ctor)]
+//! fn init(balance: Amount) {
+//!     VarsStorage::set_balance(balance);
+//! }
+//!
+//! #[endpoint]
+//! fn withdraw(amount: Amount) -> Result<Amount, AmountError> {
+//!     let balance = VarsStorage::get_balance().checked_sub(amount)?;
+//!     VarsStorage::set_balance(balance);
+//!
+//!     Ok(balance)
+//! }
+//!
+//! let mut app = TestApp::new();
+//! app.set_sender([0x10; 20].into());
+//!
+//! let receipt = app.spawn(init, Amount(10));
+//! assert!(receipt.success);
+//!
+//! let receipt = app.call(withdraw, Amount(3));
+//! assert_eq!(receipt.returns().next_1::<Amount>(), Amount(7));
+//!
+//! let receipt = app.call(withdraw, Amount(8));
+//! assert_eq!(receipt.error(), Some((2, "amount underflow".to_string())));
+//! assert_eq!(VarsStorage::get_balance(), Amount(7));
+//! ```
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, MutexGuard};
+
+use lazy_static::lazy_static;
+
+use svm_abi_decoder::ReturnData;
+use svm_abi_encoder::Encoder;
+use svm_sdk_host::traits::Host;
+use svm_sdk_host::MockHost;
+use svm_sdk_storage::{MockStorage, StorageSnapshot};
+use svm_sdk_types::{Address, Amount, LayerId, Topic};
+
+use svm_sdk::revert::{encode_error, PANIC_CODE};
+
+lazy_static! {
+    static ref LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// The outcome of calling an endpoint using a `TestApp`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Receipt {
+    /// Whether the endpoint completed without reverting.
+    pub success: bool,
+
+    /// The encoded returned values (empty when the endpoint has no returns or has reverted).
+    pub returndata: Vec<u8>,
+
+    /// The revert data (see `svm_sdk::revert`), `None` when the endpoint completed.
+    pub revert_data: Option<Vec<u8>>,
+
+    /// The logs emitted by the endpoint.
+    pub logs: Vec<(String, u8)>,
+
+    /// The events emitted by the endpoint.
+    pub events: Vec<(Vec<Topic>, Vec<u8>)>,
+}
+
+impl Receipt {
+    /// Whether the endpoint has reverted (by an `Err`, `ensure!` or a panic).
+    pub fn is_reverted(&self) -> bool {
+        !self.success
+    }
+
+    /// Returns a decoder of the `returndata`.
+    pub fn returns(&self) -> ReturnData {
+        ReturnData::new(&self.returndata)
+    }
+
+    /// Decodes the revert data as an error's `(code, message)` (see `svm_sdk::revert::encode_error`).
+    ///
+    /// Returns `None` when the endpoint has completed.
+    pub fn error(&self) -> Option<(u32, String)> {
+        let data = self.revert_data.as_ref()?;
+
+        let mut data = ReturnData::new(data);
+        let code: u32 = data.next_1();
+        let msg: String = data.next_1();
+
+        Some((code, msg))
+    }
+}
+
+/// A copy of the app's state: its storage and the accounts balances.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    storage: StorageSnapshot,
+
+    accounts: HashMap<Address, Amount>,
+}
+
+/// Runs an app's endpoints natively (see the crate's documentation).
+pub struct TestApp {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// Creates a new `TestApp` having an empty storage and no accounts.
+    ///
+    /// Blocks while another `TestApp` is alive.
+    pub fn new() -> Self {
+        // a test panicking while holding the lock shouldn't fail the next tests
+        let guard = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+        let mut app = Self { _guard: guard };
+        app.reset();
+
+        app
+    }
+
+    /// Sets the transaction's sender (see `Host::sender`).
+    pub fn set_sender(&mut self, sender: Address) {
+        MockHost::instance().set_sender(sender);
+    }
+
+    /// Sets the address of the running app (see `Host::app`).
+    pub fn set_app(&mut self, app: Address) {
+        MockHost::instance().set_app(app);
+    }
+
+    /// Sets the current layer (see `Host::layer_id`).
+    pub fn set_layer(&mut self, layer: LayerId) {
+        MockHost::instance().set_layer_id(layer);
+    }
+
+    /// Sets the amount of coins transferred along with the next calls (see `Host::value`).
+    ///
+    /// Only the `MockHost` is updated, the balances are left untouched.
+    pub fn set_value(&mut self, value: Amount) {
+        MockHost::instance().set_value(value);
+    }
+
+    /// Sets the balance of account `addr`.
+    pub fn set_balance(&mut self, addr: &Address, amount: Amount) {
+        MockHost::instance().set_balance(addr, amount);
+    }
+
+    /// Returns the balance of account `addr`.
+    pub fn balance_of(&self, addr: &Address) -> Amount {
+        MockHost::instance().balance_of(addr)
+    }
+
+    /// Spawns the app: clears its storage and then calls its constructor `ctor` with `args`.
+    pub fn spawn<T: Encoder>(&mut self, ctor: fn(), args: T) -> Receipt {
+        MockStorage::clear();
+
+        self.call(ctor, args)
+    }
+
+    /// Calls `endpoint` with `args` (a single value or a tuple of values).
+    ///
+    /// When the endpoint reverts, the storage and the balances changes are discarded.
+    /// A panic is caught and reported as reverting with `PANIC_CODE` and the panic message.
+    pub fn call<T: Encoder>(&mut self, endpoint: fn(), args: T) -> Receipt {
+        let host = MockHost::instance();
+
+        host.set_calldata(args);
+        host.returndata = None;
+        host.revert_data = None;
+        host.logs.clear();
+        host.events.clear();
+
+        let snapshot = self.snapshot();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(endpoint));
+
+        let host = MockHost::instance();
+
+        let revert_data = match result {
+            Ok(()) => host.revert_data.take(),
+            Err(payload) => {
+                let data = host.revert_data.take().unwrap_or_else(|| {
+                    let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+                        msg.to_string()
+                    } else if let Some(msg) = payload.downcast_ref::<String>() {
+                        msg.clone()
+                    } else {
+                        "panic".to_string()
+                    };
+
+                    encode_error(PANIC_CODE, &msg)
+                });
+
+                Some(data)
+            }
+        };
+
+        let returndata = host.returndata.take().unwrap_or_default();
+
+        let receipt = Receipt {
+            success: revert_data.is_none(),
+            returndata: if revert_data.is_none() {
+                returndata
+            } else {
+                Vec::new()
+            },
+            revert_data,
+            logs: host.get_logs(),
+            events: host.get_events(),
+        };
+
+        if receipt.is_reverted() {
+            self.restore(snapshot);
+        }
+
+        receipt
+    }
+
+    /// Returns a copy of the app's storage and the accounts balances.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            storage: MockStorage::snapshot(),
+            accounts: MockHost::instance().accounts.clone(),
+        }
+    }
+
+    /// Restores the app's storage and the accounts balances to `snapshot`.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        MockStorage::restore(snapshot.storage);
+        MockHost::instance().accounts = snapshot.accounts;
+    }
+
+    /// Resets the transaction's environment, clears the app's storage and removes all the accounts.
+    pub fn reset(&mut self) {
+        let host = MockHost::instance();
+
+        host.reset();
+        host.accounts.clear();
+
+        MockStorage::clear();
+    }
+}
@@ -0,0 +1,55 @@
+use svm_sdk::host::MockHost;
+use svm_sdk::{emit, Address, Amount, ToTopic, TOPIC_SIZE};
+
+/// When running natively the events are recorded by the `MockHost`.
+#[test]
+fn emit_events() {
+    let from: Address = [0x10; 20].into();
+    let to: Address = [0x20; 20].into();
+    let amount = Amount(10);
+
+    emit!("Ping");
+    emit!("Transfer", topics: [from, to], data: [amount]);
+    emit!("Deposit", data: [amount, true]);
+
+    let mut amount_data = Vec::new();
+    svm_sdk::traits::Encoder::encode(&amount, &mut amount_data);
+
+    let mut deposit_data = amount_data.clone();
+    svm_sdk::traits::Encoder::encode(&true, &mut deposit_data);
+
+    let events = MockHost::instance().get_events();
+
+    assert_eq!(
+        events,
+        vec![
+            (vec!["Ping".to_topic()], Vec::new()),
+            (
+                vec!["Transfer".to_topic(), from.to_topic(), to.to_topic()],
+                amount_data
+            ),
+            (vec!["Deposit".to_topic()], deposit_data)
+        ]
+    );
+}
+
+#[test]
+fn to_topic() {
+    let mut expected = [0; TOPIC_SIZE];
+    expected[..8].copy_from_slice(b"Transfer");
+    assert_eq!("Transfer".to_topic(), expected);
+
+    let addr: Address = [0x10; 20].into();
+    let mut expected = [0; TOPIC_SIZE];
+    expected[..20].copy_from_slice(&[0x10; 20]);
+    assert_eq!(addr.to_topic(), expected);
+
+    let mut expected = [0; TOPIC_SIZE];
+    expected[TOPIC_SIZE - 1] = 10;
+    assert_eq!(Amount(10).to_topic(), expected);
+    assert_eq!(10u32.to_topic(), expected);
+
+    let mut expected = [0; TOPIC_SIZE];
+    expected[TOPIC_SIZE - 1] = 1;
+    assert_eq!(true.to_topic(), expected);
+}
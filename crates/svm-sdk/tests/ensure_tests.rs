@@ -1,16 +1,12 @@
 use svm_sdk::ensure;
-
-/// We stub the `svm_log` external function defined at `log.rs`
-#[no_mangle]
-fn svm_log(_msg_ptr: u32, _msg_len: u32, _code: u32) {
-    //
-}
+use svm_sdk::host::MockHost;
 
 #[test]
 fn ensure_true() {
     ensure!(1 < 2, "one is smaller than two");
 }
 
+/// When running natively the revert data is recorded by the `MockHost` (and then `ensure!` panics).
 #[test]
 fn ensure_false() {
     let result = std::panic::catch_unwind(|| {
@@ -18,4 +14,21 @@ fn ensure_false() {
     });
 
     assert!(result.is_err());
+
+    let data = svm_sdk::revert::encode_error(0, "one is smaller than two");
+    assert_eq!(MockHost::instance().get_revert_data(), Some(data));
+}
+
+#[test]
+fn revert_data_encoding() {
+    use svm_sdk::ReturnData;
+
+    let data = svm_sdk::revert::encode_error(7, "Not enough funds");
+
+    let mut data = ReturnData::new(&data);
+    let code: u32 = data.next_1();
+    let msg: String = data.next_1();
+
+    assert_eq!(code, 7);
+    assert_eq!(msg, "Not enough funds");
 }
@@ -1,108 +1,143 @@
 use core::cmp::{Ordering, PartialOrd};
 use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
+use crate::AppError;
+
 #[derive(Debug, PartialEq, Copy, Clone, Hash)]
 #[repr(transparent)]
 pub struct Amount(pub u64);
 
-impl From<i64> for Amount {
-    fn from(v: i64) -> Amount {
-        Amount(v as _)
-    }
-}
-
-impl Add for Amount {
-    type Output = Self;
+/// The error returned by `Amount`'s checked arithmetic.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum AmountError {
+    /// The result is larger than `u64::MAX`.
+    Overflow,
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0)
-    }
+    /// The result is negative.
+    Underflow,
 }
 
-impl Add<u64> for Amount {
-    type Output = Self;
-
-    fn add(self, rhs: u64) -> Self::Output {
-        Self(self.0 + rhs)
+impl AppError for AmountError {
+    fn code(&self) -> u32 {
+        match self {
+            AmountError::Overflow => 1,
+            AmountError::Underflow => 2,
+        }
     }
-}
 
-impl Sub for Amount {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        assert!(self.0 >= rhs.0);
-
-        Self(self.0 - rhs.0)
+    fn message(&self) -> &str {
+        match self {
+            AmountError::Overflow => "amount overflow",
+            AmountError::Underflow => "amount underflow",
+        }
     }
 }
 
-impl Sub<u64> for Amount {
-    type Output = Self;
-
-    fn sub(self, rhs: u64) -> Self::Output {
-        assert!(self.0 >= rhs);
-
-        Self(self.0 - rhs)
+impl Amount {
+    /// Returns `self + rhs` or `AmountError::Overflow`.
+    pub fn checked_add(self, rhs: Amount) -> Result<Amount, AmountError> {
+        match self.0.checked_add(rhs.0) {
+            Some(v) => Ok(Amount(v)),
+            None => Err(AmountError::Overflow),
+        }
     }
-}
-
-impl Mul for Amount {
-    type Output = Self;
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        Amount(self.0 * rhs.0)
+    /// Returns `self - rhs` or `AmountError::Underflow`.
+    pub fn checked_sub(self, rhs: Amount) -> Result<Amount, AmountError> {
+        match self.0.checked_sub(rhs.0) {
+            Some(v) => Ok(Amount(v)),
+            None => Err(AmountError::Underflow),
+        }
     }
-}
-
-impl Mul<u64> for Amount {
-    type Output = Self;
 
-    fn mul(self, rhs: u64) -> Self::Output {
-        Amount(self.0 * rhs)
+    /// Returns `self * rhs` or `AmountError::Overflow`.
+    pub fn checked_mul(self, rhs: Amount) -> Result<Amount, AmountError> {
+        match self.0.checked_mul(rhs.0) {
+            Some(v) => Ok(Amount(v)),
+            None => Err(AmountError::Overflow),
+        }
     }
-}
 
-impl AddAssign for Amount {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = Amount(self.0 + rhs.0)
+    /// Returns `self + rhs`, clamped at `u64::MAX`.
+    pub fn saturating_add(self, rhs: Amount) -> Amount {
+        Amount(self.0.saturating_add(rhs.0))
     }
-}
 
-impl AddAssign<u64> for Amount {
-    fn add_assign(&mut self, rhs: u64) {
-        *self = Amount(self.0 + rhs)
+    /// Returns `self - rhs`, clamped at zero.
+    pub fn saturating_sub(self, rhs: Amount) -> Amount {
+        Amount(self.0.saturating_sub(rhs.0))
     }
-}
-
-impl SubAssign for Amount {
-    fn sub_assign(&mut self, rhs: Self) {
-        assert!(self.0 >= rhs.0);
 
-        *self = Amount(self.0 - rhs.0)
+    /// Returns `self * rhs`, clamped at `u64::MAX`.
+    pub fn saturating_mul(self, rhs: Amount) -> Amount {
+        Amount(self.0.saturating_mul(rhs.0))
     }
 }
 
-impl SubAssign<u64> for Amount {
-    fn sub_assign(&mut self, rhs: u64) {
-        assert!(self.0 >= rhs);
-
-        *self = Amount(self.0 - rhs)
+impl From<i64> for Amount {
+    fn from(v: i64) -> Amount {
+        Amount(v as _)
     }
 }
 
-impl MulAssign for Amount {
-    fn mul_assign(&mut self, rhs: Self) {
-        *self = Amount(self.0 * rhs.0)
+#[inline]
+fn unwrap(res: Result<Amount, AmountError>) -> Amount {
+    match res {
+        Ok(amount) => amount,
+        Err(AmountError::Overflow) => panic!("amount overflow"),
+        Err(AmountError::Underflow) => panic!("amount underflow"),
     }
 }
 
-impl MulAssign<u64> for Amount {
-    fn mul_assign(&mut self, rhs: u64) {
-        *self = Amount(self.0 * rhs)
-    }
+macro_rules! impl_ops {
+    ($rhs:ty, $amount:expr) => {
+        impl Add<$rhs> for Amount {
+            type Output = Self;
+
+            fn add(self, rhs: $rhs) -> Self::Output {
+                unwrap(self.checked_add($amount(rhs)))
+            }
+        }
+
+        impl Sub<$rhs> for Amount {
+            type Output = Self;
+
+            fn sub(self, rhs: $rhs) -> Self::Output {
+                unwrap(self.checked_sub($amount(rhs)))
+            }
+        }
+
+        impl Mul<$rhs> for Amount {
+            type Output = Self;
+
+            fn mul(self, rhs: $rhs) -> Self::Output {
+                unwrap(self.checked_mul($amount(rhs)))
+            }
+        }
+
+        impl AddAssign<$rhs> for Amount {
+            fn add_assign(&mut self, rhs: $rhs) {
+                *self = *self + rhs
+            }
+        }
+
+        impl SubAssign<$rhs> for Amount {
+            fn sub_assign(&mut self, rhs: $rhs) {
+                *self = *self - rhs
+            }
+        }
+
+        impl MulAssign<$rhs> for Amount {
+            fn mul_assign(&mut self, rhs: $rhs) {
+                *self = *self * rhs
+            }
+        }
+    };
 }
 
+impl_ops!(Amount, core::convert::identity);
+impl_ops!(u64, Amount);
+
 impl PartialOrd for Amount {
     #[inline]
     fn partial_cmp(&self, other: &Amount) -> Option<Ordering> {
@@ -189,4 +224,55 @@ mod tests {
         assert!(b <= a);
         assert!(b < a);
     }
+
+    #[test]
+    fn amount_checked() {
+        let max = Amount(u64::MAX);
+
+        assert_eq!(Amount(10).checked_add(Amount(20)), Ok(Amount(30)));
+        assert_eq!(max.checked_add(Amount(1)), Err(AmountError::Overflow));
+
+        assert_eq!(Amount(20).checked_sub(Amount(5)), Ok(Amount(15)));
+        assert_eq!(
+            Amount(5).checked_sub(Amount(20)),
+            Err(AmountError::Underflow)
+        );
+
+        assert_eq!(Amount(2).checked_mul(Amount(3)), Ok(Amount(6)));
+        assert_eq!(max.checked_mul(Amount(2)), Err(AmountError::Overflow));
+    }
+
+    #[test]
+    fn amount_saturating() {
+        let max = Amount(u64::MAX);
+
+        assert_eq!(Amount(10).saturating_add(Amount(20)), Amount(30));
+        assert_eq!(max.saturating_add(Amount(1)), max);
+
+        assert_eq!(Amount(20).saturating_sub(Amount(5)), Amount(15));
+        assert_eq!(Amount(5).saturating_sub(Amount(20)), Amount(0));
+
+        assert_eq!(Amount(2).saturating_mul(Amount(3)), Amount(6));
+        assert_eq!(max.saturating_mul(Amount(2)), max);
+    }
+
+    #[test]
+    fn amount_error_code() {
+        assert_eq!(AmountError::Overflow.code(), 1);
+        assert_eq!(AmountError::Underflow.code(), 2);
+        assert_eq!(AmountError::Underflow.message(), "amount underflow");
+    }
+
+    #[test]
+    #[should_panic(expected = "amount overflow")]
+    fn amount_add_overflow_panics() {
+        let _ = Amount(u64::MAX) + 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "amount underflow")]
+    fn amount_sub_underflow_panics() {
+        let mut a = Amount(1);
+        a -= Amount(2);
+    }
 }
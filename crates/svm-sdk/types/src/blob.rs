@@ -3,6 +3,7 @@ macro_rules! impl_blob_type {
         use core::char;
         use core::cmp::{Eq, PartialEq};
         use core::fmt::{self, Debug};
+        use core::hash::{Hash, Hasher};
 
         extern crate alloc;
 
@@ -11,7 +12,7 @@ macro_rules! impl_blob_type {
 
         #[allow(missing_docs)]
         #[repr(transparent)]
-        #[derive(core::fmt::Debug, Copy, Clone, Hash)]
+        #[derive(core::fmt::Debug, Copy, Clone)]
         pub struct $ty(*const u8);
 
         impl $crate::types::PrimitiveMarker for $ty {}
@@ -117,6 +118,13 @@ macro_rules! impl_blob_type {
         }
 
         impl Eq for $ty {}
+
+        /// Hashing the bytes (and not the pointer) in order to agree with `PartialEq`.
+        impl Hash for $ty {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.as_slice().hash(state);
+            }
+        }
     };
 }
 
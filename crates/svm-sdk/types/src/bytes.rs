@@ -0,0 +1,52 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+/// A variable-length byte-string.
+///
+/// A `Vec<u8>` is encoded as an `Array` of `u8` items, whereas `Bytes` is encoded
+/// as the ABI `Bytes` (its length followed by the raw bytes).
+#[derive(Debug, PartialEq, Clone, Default, Hash)]
+#[repr(transparent)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    /// Returns the underlying bytes.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the number of bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    #[inline]
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
@@ -0,0 +1,29 @@
+/// An error returned by an `#[endpoint]` function (as the `Err` of its `Result`).
+///
+/// On `Err` the transaction reverts with the `code` and `message` as its revert data
+/// and the storage changes made by the endpoint are discarded.
+pub trait AppError {
+    /// The error code (chosen by the Template's author).
+    fn code(&self) -> u32;
+
+    /// A human-readable description of the error.
+    fn message(&self) -> &str {
+        ""
+    }
+}
+
+impl AppError for u32 {
+    fn code(&self) -> u32 {
+        *self
+    }
+}
+
+impl AppError for &str {
+    fn code(&self) -> u32 {
+        0
+    }
+
+    fn message(&self) -> &str {
+        self
+    }
+}
@@ -18,10 +18,22 @@ pub mod types;
 pub mod value;
 
 mod amount;
-pub use amount::Amount;
+pub use amount::{Amount, AmountError};
+
+mod error;
+pub use error::AppError;
 
 mod layer_id;
 pub use layer_id::LayerId;
 
 mod blob;
 pub use blob::Address;
+
+mod bytes;
+pub use bytes::Bytes;
+
+mod uint;
+pub use uint::{U128, U256};
+
+mod topic;
+pub use topic::{ToTopic, Topic, TOPIC_SIZE};
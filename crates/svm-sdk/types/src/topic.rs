@@ -0,0 +1,101 @@
+use crate::{Address, Amount, U128, U256};
+
+/// The byte-size of an event's `Topic`.
+pub const TOPIC_SIZE: usize = 32;
+
+/// An indexed field of an emitted event (used by nodes for filtering events).
+pub type Topic = [u8; TOPIC_SIZE];
+
+/// Types which can be used as an event's `Topic`.
+///
+/// Byte-strings are left-aligned while numbers are right-aligned (big-endian).
+/// The remaining bytes are zeroed.
+pub trait ToTopic {
+    /// Returns `self` as a `Topic`.
+    fn to_topic(&self) -> Topic;
+}
+
+impl<T> ToTopic for &T
+where
+    T: ToTopic + ?Sized,
+{
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        (**self).to_topic()
+    }
+}
+
+/// # Panics
+///
+/// Panics when the byte-string is longer than `TOPIC_SIZE` bytes
+/// (the `emit!` macro rejects longer event names at compile-time).
+impl ToTopic for [u8] {
+    fn to_topic(&self) -> Topic {
+        assert!(self.len() <= TOPIC_SIZE);
+
+        let mut topic = [0; TOPIC_SIZE];
+        topic[..self.len()].copy_from_slice(self);
+
+        topic
+    }
+}
+
+impl ToTopic for str {
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        self.as_bytes().to_topic()
+    }
+}
+
+impl ToTopic for Address {
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        self.as_slice().to_topic()
+    }
+}
+
+impl ToTopic for bool {
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        (*self as u64).to_topic()
+    }
+}
+
+impl ToTopic for Amount {
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        self.0.to_topic()
+    }
+}
+
+impl ToTopic for u32 {
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        (*self as u64).to_topic()
+    }
+}
+
+impl ToTopic for u64 {
+    fn to_topic(&self) -> Topic {
+        let mut topic = [0; TOPIC_SIZE];
+        topic[TOPIC_SIZE - 8..].copy_from_slice(&self.to_be_bytes());
+
+        topic
+    }
+}
+
+impl ToTopic for U128 {
+    fn to_topic(&self) -> Topic {
+        let mut topic = [0; TOPIC_SIZE];
+        topic[TOPIC_SIZE - U128::BYTES..].copy_from_slice(&self.to_be_bytes());
+
+        topic
+    }
+}
+
+impl ToTopic for U256 {
+    #[inline]
+    fn to_topic(&self) -> Topic {
+        self.to_be_bytes()
+    }
+}
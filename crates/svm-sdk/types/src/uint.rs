@@ -0,0 +1,479 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// An unsigned 128-bit integer.
+///
+/// Arithmetic operators panic on overflow (and underflow) regardless of the build profile.
+/// Use the `checked_*` methods in order to handle these cases.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Default, Hash)]
+#[repr(transparent)]
+pub struct U128(pub u128);
+
+impl U128 {
+    /// The number of bytes of an encoded `U128`.
+    pub const BYTES: usize = 16;
+
+    /// Zero.
+    pub const ZERO: U128 = U128(0);
+
+    /// The largest `U128` value.
+    pub const MAX: U128 = U128(u128::MAX);
+
+    /// Checked addition. Returns `None` on overflow.
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Checked subtraction. Returns `None` on underflow.
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Checked multiplication. Returns `None` on overflow.
+    #[inline]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).map(Self)
+    }
+
+    /// Returns the Big-Endian representation.
+    #[inline]
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    /// Creates a value from its Big-Endian representation.
+    #[inline]
+    pub fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    /// Parses a decimal string. Returns `None` if it's invalid or out-of-range.
+    pub fn from_dec_str(s: &str) -> Option<Self> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        s.parse().ok().map(Self)
+    }
+}
+
+/// An unsigned 256-bit integer.
+///
+/// Arithmetic operators panic on overflow (and underflow) regardless of the build profile.
+/// Use the `checked_*` methods in order to handle these cases.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, Hash)]
+pub struct U256 {
+    /// Little-Endian 64-bit limbs (`limbs[0]` is the least significant).
+    limbs: [u64; 4],
+}
+
+impl U256 {
+    /// The number of bytes of an encoded `U256`.
+    pub const BYTES: usize = 32;
+
+    /// Zero.
+    pub const ZERO: U256 = U256 { limbs: [0; 4] };
+
+    /// The largest `U256` value.
+    pub const MAX: U256 = U256 {
+        limbs: [u64::MAX; 4],
+    };
+
+    /// Returns whether the value is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.limbs == [0; 4]
+    }
+
+    /// Checked addition. Returns `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let mut limbs = [0; 4];
+        let mut carry = false;
+
+        for i in 0..4 {
+            let (sum, c1) = self.limbs[i].overflowing_add(rhs.limbs[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+
+            limbs[i] = sum;
+            carry = c1 || c2;
+        }
+
+        if carry {
+            None
+        } else {
+            Some(Self { limbs })
+        }
+    }
+
+    /// Checked subtraction. Returns `None` on underflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let mut limbs = [0; 4];
+        let mut borrow = false;
+
+        for i in 0..4 {
+            let (diff, b1) = self.limbs[i].overflowing_sub(rhs.limbs[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+
+            limbs[i] = diff;
+            borrow = b1 || b2;
+        }
+
+        if borrow {
+            None
+        } else {
+            Some(Self { limbs })
+        }
+    }
+
+    /// Checked multiplication. Returns `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+
+        for i in 0..4 {
+            let mut carry = 0u128;
+
+            for j in 0..4 {
+                let product = self.limbs[i] as u128 * rhs.limbs[j] as u128;
+
+                if i + j >= 4 {
+                    if product != 0 {
+                        return None;
+                    }
+                    continue;
+                }
+
+                let acc = limbs[i + j] as u128 + product + carry;
+
+                limbs[i + j] = acc as u64;
+                carry = acc >> 64;
+            }
+
+            // A carry out of the 4th limb means the product doesn't fit.
+            if carry != 0 {
+                return None;
+            }
+        }
+
+        Some(Self { limbs })
+    }
+
+    /// Returns the Big-Endian representation.
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+
+        for (i, limb) in self.limbs.iter().rev().enumerate() {
+            bytes[i * 8..(i + 1) * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+
+        bytes
+    }
+
+    /// Creates a value from its Big-Endian representation.
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0; 4];
+
+        for (i, limb) in limbs.iter_mut().rev().enumerate() {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(&bytes[i * 8..(i + 1) * 8]);
+
+            *limb = u64::from_be_bytes(buf);
+        }
+
+        Self { limbs }
+    }
+
+    /// Parses a decimal string. Returns `None` if it's invalid or out-of-range.
+    pub fn from_dec_str(s: &str) -> Option<Self> {
+        if s.is_empty() {
+            return None;
+        }
+
+        let ten = Self::from(10u64);
+
+        s.bytes().try_fold(Self::ZERO, |acc, b| {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+
+            let digit = Self::from((b - b'0') as u64);
+
+            acc.checked_mul(ten)?.checked_add(digit)
+        })
+    }
+
+    /// Divides by `divisor`, returning the quotient and the remainder.
+    fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+        let mut limbs = [0; 4];
+        let mut rem = 0u128;
+
+        for i in (0..4).rev() {
+            let acc = (rem << 64) | self.limbs[i] as u128;
+
+            limbs[i] = (acc / divisor as u128) as u64;
+            rem = acc % divisor as u128;
+        }
+
+        (Self { limbs }, rem as u64)
+    }
+}
+
+impl From<u64> for U256 {
+    #[inline]
+    fn from(v: u64) -> Self {
+        Self {
+            limbs: [v, 0, 0, 0],
+        }
+    }
+}
+
+impl From<u128> for U256 {
+    #[inline]
+    fn from(v: u128) -> Self {
+        Self {
+            limbs: [v as u64, (v >> 64) as u64, 0, 0],
+        }
+    }
+}
+
+impl From<U128> for U256 {
+    #[inline]
+    fn from(v: U128) -> Self {
+        v.0.into()
+    }
+}
+
+impl From<u64> for U128 {
+    #[inline]
+    fn from(v: u64) -> Self {
+        Self(v as u128)
+    }
+}
+
+impl From<u128> for U128 {
+    #[inline]
+    fn from(v: u128) -> Self {
+        Self(v)
+    }
+}
+
+impl PartialOrd for U256 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+impl fmt::Display for U128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `2^256 - 1` has 78 decimal digits.
+        let mut digits = [0u8; 78];
+        let mut pos = digits.len();
+        let mut n = *self;
+
+        loop {
+            let (quotient, digit) = n.div_rem_u64(10);
+
+            pos -= 1;
+            digits[pos] = b'0' + digit as u8;
+            n = quotient;
+
+            if n.is_zero() {
+                break;
+            }
+        }
+
+        let s = core::str::from_utf8(&digits[pos..]).unwrap();
+
+        f.pad_integral(true, "", s)
+    }
+}
+
+macro_rules! impl_ops {
+    ($ty:ident) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                self.checked_add(rhs).expect("integer overflow")
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.checked_sub(rhs).expect("integer underflow")
+            }
+        }
+
+        impl Mul for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                self.checked_mul(rhs).expect("integer overflow")
+            }
+        }
+
+        impl AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl MulAssign for $ty {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs
+            }
+        }
+    };
+}
+
+impl_ops!(U128);
+impl_ops!(U256);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+
+    fn pow2(exp: u32) -> U256 {
+        let mut bytes = [0; 32];
+        bytes[31 - (exp / 8) as usize] = 1 << (exp % 8);
+
+        U256::from_be_bytes(bytes)
+    }
+
+    #[test]
+    fn u128_checked_arithmetic() {
+        assert_eq!(U128(10).checked_add(U128(20)), Some(U128(30)));
+        assert_eq!(U128::MAX.checked_add(U128(1)), None);
+
+        assert_eq!(U128(20).checked_sub(U128(5)), Some(U128(15)));
+        assert_eq!(U128(5).checked_sub(U128(20)), None);
+
+        assert_eq!(U128(6).checked_mul(U128(7)), Some(U128(42)));
+        assert_eq!(U128::MAX.checked_mul(U128(2)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "integer underflow")]
+    fn u128_sub_underflow_panics() {
+        let _ = U128(1) - U128(2);
+    }
+
+    #[test]
+    fn u128_dec_str() {
+        assert_eq!(U128::from_dec_str("0"), Some(U128(0)));
+        assert_eq!(U128::from_dec_str(&u128::MAX.to_string()), Some(U128::MAX));
+        assert_eq!(
+            U128::from_dec_str("340282366920938463463374607431768211456"),
+            None
+        );
+        assert_eq!(U128::from_dec_str("+1"), None);
+        assert_eq!(U128::from_dec_str(""), None);
+    }
+
+    #[test]
+    fn u256_checked_add_carries_across_limbs() {
+        let a = U256::from(u64::MAX);
+        let b = U256::from(1u64);
+
+        assert_eq!(a + b, U256::from(1u128 << 64));
+        assert_eq!(U256::MAX.checked_add(b), None);
+    }
+
+    #[test]
+    fn u256_checked_sub_borrows_across_limbs() {
+        let a = U256::from(1u128 << 64);
+        let b = U256::from(1u64);
+
+        assert_eq!(a - b, U256::from(u64::MAX));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(U256::MAX - U256::MAX, U256::ZERO);
+    }
+
+    #[test]
+    fn u256_checked_mul() {
+        let a = U256::from(u128::MAX);
+
+        assert_eq!(a * U256::from(1u64), a);
+        assert_eq!(pow2(128) * pow2(127), pow2(255));
+        assert_eq!(pow2(128).checked_mul(pow2(128)), None);
+        assert_eq!(pow2(255).checked_mul(U256::from(2u64)), None);
+        assert_eq!(U256::MAX.checked_mul(U256::ZERO), Some(U256::ZERO));
+
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        let expected = (U256::MAX - pow2(129)) + U256::from(2u64);
+        assert_eq!(a * a, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "integer overflow")]
+    fn u256_add_overflow_panics() {
+        let _ = U256::MAX + U256::from(1u64);
+    }
+
+    #[test]
+    fn u256_ordering() {
+        assert!(pow2(200) > U256::from(u128::MAX));
+        assert!(U256::from(1u64) < U256::from(2u64));
+        assert!(U256::MAX > pow2(255));
+    }
+
+    #[test]
+    fn u256_be_bytes() {
+        let mut bytes = [0; 32];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+
+        let v = U256::from_be_bytes(bytes);
+        assert_eq!(v.to_be_bytes(), bytes);
+
+        assert_eq!(U256::from(1u64).to_be_bytes()[31], 1);
+    }
+
+    #[test]
+    fn u256_dec_str() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+        assert_eq!(U256::ZERO.to_string(), "0");
+        assert_eq!(U256::from(1234567890u64).to_string(), "1234567890");
+        assert_eq!(U256::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(U256::MAX.to_string(), max);
+
+        assert_eq!(U256::from_dec_str(max), Some(U256::MAX));
+        assert_eq!(U256::from_dec_str("0"), Some(U256::ZERO));
+        assert_eq!(
+            U256::from_dec_str(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+            ),
+            None
+        );
+        assert_eq!(U256::from_dec_str("12a"), None);
+    }
+}
@@ -1,10 +1,11 @@
 use core::cmp::PartialEq;
 use core::fmt::{self, Debug};
 
-use crate::{Address, Amount};
+use crate::{Address, Amount, Bytes, U128, U256};
 
 extern crate alloc;
 
+use alloc::string::String;
 use alloc::vec::Vec;
 
 /// Primitive value
@@ -33,6 +34,10 @@ pub enum Primitive {
     I64(i64),
 
     U64(u64),
+
+    U128(U128),
+
+    U256(U256),
 }
 
 /// Composite value
@@ -43,6 +48,15 @@ pub enum Composite<'a> {
 
     /// An owned `Array`
     ArrayOwned(Vec<Value<'a>>),
+
+    /// A `Tuple` (a struct is encoded as the `Tuple` of its fields)
+    Tuple(Vec<Value<'a>>),
+
+    /// A variable-length byte-string
+    Bytes(Vec<u8>),
+
+    /// An UTF-8 string
+    String(String),
 }
 
 /// An ABI Value
@@ -187,6 +201,9 @@ impl_from_rust_to_value!(U32, u32);
 impl_from_rust_to_value!(I64, i64);
 impl_from_rust_to_value!(U64, u64);
 
+impl_from_rust_to_value!(U128, U128);
+impl_from_rust_to_value!(U256, U256);
+
 impl_from_rust_to_value!(Address, Address);
 
 /// Array value
@@ -237,6 +254,80 @@ impl<'a> From<Vec<Value<'a>>> for Value<'a> {
     }
 }
 
+/// Takes `Bytes` and turns it into a single (`Composite`) Value (of kind `Bytes`)
+///
+/// # Example
+///
+/// ```rust
+/// use svm_sdk_types::Bytes;
+/// use svm_sdk_types::value::Value;
+///
+/// let value: Value = Bytes(vec![0x10, 0x20]).into();
+/// let bytes: Bytes = value.into();
+///
+/// assert_eq!(bytes, Bytes(vec![0x10, 0x20]));
+/// ```
+impl From<Bytes> for Value<'_> {
+    fn from(bytes: Bytes) -> Self {
+        let comp = Composite::Bytes(bytes.0);
+        Value::Composite(comp)
+    }
+}
+
+/// Takes a `String` and turns it into a single (`Composite`) Value (of kind `String`)
+///
+/// # Example
+///
+/// ```rust
+/// use svm_sdk_types::value::Value;
+///
+/// let value: Value = "Hello".into();
+/// let s: String = value.into();
+///
+/// assert_eq!(s, "Hello");
+/// ```
+impl From<String> for Value<'_> {
+    fn from(s: String) -> Self {
+        let comp = Composite::String(s);
+        Value::Composite(comp)
+    }
+}
+
+impl From<&str> for Value<'_> {
+    fn from(s: &str) -> Self {
+        String::from(s).into()
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Returns a `Value` of kind `Tuple` holding `fields`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use svm_sdk_types::value::Value;
+    ///
+    /// let value: Value = Value::tuple(vec![10u32.into(), true.into()]);
+    /// let fields = value.into_fields();
+    ///
+    /// assert_eq!(fields, vec![10u32.into(), true.into()]);
+    /// ```
+    pub fn tuple(fields: Vec<Value<'a>>) -> Self {
+        let comp = Composite::Tuple(fields);
+        Value::Composite(comp)
+    }
+
+    /// Consumes a `Value` of kind `Tuple` and returns its fields.
+    ///
+    /// Panics if the `Value` isn't a `Tuple`.
+    pub fn into_fields(self) -> Vec<Value<'a>> {
+        match self {
+            Value::Composite(Composite::Tuple(fields)) => fields,
+            _ => unreachable!(),
+        }
+    }
+}
+
 macro_rules! impl_from_value_to_rust {
     ($prim_ident:ident, $T:ty) => {
         impl From<Value<'_>> for $T {
@@ -275,8 +366,66 @@ impl_from_value_to_rust!(U32, u32);
 impl_from_value_to_rust!(I64, i64);
 impl_from_value_to_rust!(U64, u64);
 
+impl_from_value_to_rust!(U128, U128);
+impl_from_value_to_rust!(U256, U256);
+
 impl_from_value_to_rust!(Address, Address);
 
+macro_rules! impl_from_composite_to_rust {
+    ($comp_ident:ident, $T:ty) => {
+        impl From<Value<'_>> for $T {
+            fn from(value: Value) -> Self {
+                match value {
+                    Value::Composite(Composite::$comp_ident(v)) => v.into(),
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        impl From<Value<'_>> for Option<$T> {
+            fn from(value: Value) -> Self {
+                match value {
+                    Value::Primitive(Primitive::None) => None,
+                    Value::Composite(Composite::$comp_ident(v)) => Some(v.into()),
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+impl_from_composite_to_rust!(Bytes, Bytes);
+impl_from_composite_to_rust!(String, String);
+
+/// Takes a (`Composite`) Value of kind `ArrayOwned` and turns it into a `Vec`
+/// (of any length, unlike the fixed-size arrays conversions below)
+///
+/// # Example
+///
+/// ```rust
+/// use svm_sdk_types::value::Value;
+///
+/// let values: Vec<Value> = (0..300u32).map(|i| i.into()).collect();
+/// let value: Value = values.into();
+///
+/// let vec: Vec<u32> = value.into();
+///
+/// assert_eq!(vec, (0..300u32).collect::<Vec<_>>());
+/// ```
+impl<'a, T> From<Value<'a>> for Vec<T>
+where
+    Value<'a>: Into<T>,
+{
+    fn from(value: Value<'a>) -> Self {
+        match value {
+            Value::Composite(Composite::ArrayOwned(values)) => {
+                values.into_iter().map(|v| v.into()).collect()
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
 macro_rules! impl_value_to_rust_array {
     ([] => $($tt:tt)*) => {};
     ([$T:tt $($T_tail:tt)*] => $($tt:tt)*) => {
@@ -326,4 +475,5 @@ impl_value_to_rust_array!([
     i16 u16
     i32 u32
     i64 u64
+    U128 U256
 ] => 1 2 3 4 5 6 7 8 9 10);
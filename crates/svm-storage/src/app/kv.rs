@@ -1,7 +1,6 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
 
-use crate::kv::StatefulKV;
+use crate::kv::{ExternKVError, StatefulKV};
 
 use svm_common::{DefaultKeyHasher, KeyHasher};
 use svm_types::{Address, State};
@@ -13,51 +12,68 @@ use svm_types::{Address, State};
 pub struct AppKVStore {
     pub(crate) app_addr: Address,
 
-    pub(crate) kv: Rc<RefCell<dyn StatefulKV>>,
+    pub(crate) kv: Arc<Mutex<dyn StatefulKV + Send>>,
 }
 
 impl StatefulKV for AppKVStore {
     #[inline]
-    #[must_use]
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ExternKVError> {
         let key = self.build_key(key);
 
-        self.kv.borrow().get(&key)
+        self.kv().get(&key)
+    }
+
+    fn get_many(&self, keys: &[&[u8]]) -> Result<Vec<Option<Vec<u8>>>, ExternKVError> {
+        let keys: Vec<Vec<u8>> = keys.iter().map(|key| self.build_key(key)).collect();
+        let keys: Vec<&[u8]> = keys.iter().map(|key| &key[..]).collect();
+
+        self.kv().get_many(&keys)
     }
 
     #[inline]
-    fn set(&mut self, key: &[u8], value: &[u8]) {
+    fn set(&mut self, key: &[u8], value: &[u8]) -> Result<(), ExternKVError> {
         let key = self.build_key(key);
 
-        self.kv.borrow_mut().set(&key, value);
+        self.kv().set(&key, value)
+    }
+
+    fn set_many(&mut self, changes: &[(&[u8], &[u8])]) -> Result<(), ExternKVError> {
+        let keys: Vec<Vec<u8>> = changes.iter().map(|(key, _)| self.build_key(key)).collect();
+
+        let changes: Vec<(&[u8], &[u8])> = keys
+            .iter()
+            .zip(changes.iter())
+            .map(|(key, (_, value))| (&key[..], *value))
+            .collect();
+
+        self.kv().set_many(&changes)
     }
 
     #[inline]
-    fn discard(&mut self) {
-        self.kv.borrow_mut().discard();
+    fn discard(&mut self) -> Result<(), ExternKVError> {
+        self.kv().discard()
     }
 
     #[inline]
     fn flush(&mut self) {
-        self.kv.borrow_mut().flush();
+        self.kv().flush();
     }
 
     #[inline]
-    #[must_use]
-    fn checkpoint(&mut self) -> State {
-        self.kv.borrow_mut().checkpoint()
+    fn checkpoint(&mut self) -> Result<State, ExternKVError> {
+        self.kv().checkpoint()
     }
 
     #[inline]
     #[must_use]
     fn rewind(&mut self, state: &State) {
-        self.kv.borrow_mut().rewind(state);
+        self.kv().rewind(state);
     }
 
     #[inline]
     #[must_use]
     fn head(&self) -> State {
-        self.kv.borrow().head()
+        self.kv().head()
     }
 }
 
@@ -65,12 +81,39 @@ impl AppKVStore {
     /// Create a new `AppKVStore` instance for application `app_addr`.
     ///
     /// Delegates work to raw key-value store `kv`.
-    pub fn new(app_addr: Address, kv: &Rc<RefCell<dyn StatefulKV>>) -> Self {
-        let kv = Rc::clone(&kv);
+    pub fn new(app_addr: Address, kv: &Arc<Mutex<dyn StatefulKV + Send>>) -> Self {
+        let kv = Arc::clone(&kv);
 
         Self { app_addr, kv }
     }
 
+    /// Sets `changes`, creates a new checkpoint and flushes it.
+    ///
+    /// The underlying key-value store is locked for the whole operation,
+    /// so other apps sharing it can't interleave their own changes.
+    pub fn commit_many(&mut self, changes: &[(&[u8], &[u8])]) -> Result<State, ExternKVError> {
+        let keys: Vec<Vec<u8>> = changes.iter().map(|(key, _)| self.build_key(key)).collect();
+
+        let changes: Vec<(&[u8], &[u8])> = keys
+            .iter()
+            .zip(changes.iter())
+            .map(|(key, (_, value))| (&key[..], *value))
+            .collect();
+
+        let mut kv = self.kv();
+
+        kv.set_many(&changes)?;
+        let state = kv.checkpoint()?;
+        kv.flush();
+
+        Ok(state)
+    }
+
+    #[inline]
+    fn kv(&self) -> MutexGuard<dyn StatefulKV + Send + 'static> {
+        self.kv.lock().unwrap()
+    }
+
     #[inline]
     fn build_key(&self, key: &[u8]) -> Vec<u8> {
         debug_assert_eq!(key.len(), 4);
@@ -93,7 +136,7 @@ impl Clone for AppKVStore {
     fn clone(&self) -> Self {
         Self {
             app_addr: self.app_addr.clone(),
-            kv: Rc::clone(&self.kv),
+            kv: Arc::clone(&self.kv),
         }
     }
 }
@@ -7,7 +7,9 @@ mod kv;
 pub use kv::AppKVStore;
 
 use svm_layout::{DataLayout, VarId};
-use svm_types::State;
+use svm_types::{Snapshot, SnapshotError, State};
+
+use crate::kv::ExternKVError;
 
 ///
 /// The `AppStorage` manages a running app's storage.
@@ -61,18 +63,20 @@ impl AppStorage {
     }
 
     /// Reads variable `var_id`.
-    pub fn read_var(&self, var_id: VarId) -> Vec<u8> {
-        let var = self.uncommitted.get(&var_id).cloned();
+    ///
+    /// Returns an `ExternKVError` when the underlying key-value store has failed.
+    pub fn read_var(&self, var_id: VarId) -> Result<Vec<u8>, ExternKVError> {
+        if let Some(var) = self.uncommitted.get(&var_id) {
+            return Ok(var.clone());
+        }
 
-        var.unwrap_or_else(|| {
-            let (off, len) = self.var_layout(var_id);
+        let (off, len) = self.var_layout(var_id);
 
-            let bytes = self.raw_storage.read(off, len);
+        let bytes = self.raw_storage.read(off, len)?;
 
-            debug_assert_eq!(bytes.len(), len as usize);
+        debug_assert_eq!(bytes.len(), len as usize);
 
-            bytes
-        })
+        Ok(bytes)
     }
 
     /// Marks variable as `dirty`. Upon `commit` will persist the variable.
@@ -91,9 +95,81 @@ impl AppStorage {
         self.layout.get_var(var_id)
     }
 
+    /// Exports the app's storage as it was at `state` into a `Snapshot`.
+    ///
+    /// After exporting, the storage is rewound back to its current `head`.
+    ///
+    /// Returns `SnapshotError::UncommittedChanges` when there are uncommitted changes.
+    pub fn export(&mut self, state: &State) -> Result<Snapshot, SnapshotError> {
+        if !self.uncommitted.is_empty() {
+            return Err(SnapshotError::UncommittedChanges);
+        }
+
+        let head = self.head();
+
+        self.rewind(state);
+
+        let vars: Result<Vec<_>, _> = self
+            .layout
+            .iter()
+            .map(|(var_id, _off, _len)| self.read_var(var_id))
+            .collect();
+
+        self.rewind(&head);
+
+        let vars = vars?;
+
+        let snapshot = Snapshot {
+            layout: self.layout.clone(),
+            state: state.clone(),
+            vars,
+        };
+
+        Ok(snapshot)
+    }
+
+    /// Imports `snapshot` variables into the app's storage and commits them.
+    ///
+    /// Returns the new `State`. Since `State` computation is up to the underlying key-value store,
+    /// it won't necessarily equal the `state` the snapshot has been taken at.
+    ///
+    /// Nothing is written when the `snapshot` doesn't match the app's layout.
+    pub fn import(&mut self, snapshot: &Snapshot) -> Result<State, SnapshotError> {
+        if !self.layout.iter().eq(snapshot.layout.iter()) {
+            return Err(SnapshotError::LayoutMismatch);
+        }
+
+        if self.layout.len() != snapshot.vars.len() {
+            return Err(SnapshotError::VarsCountMismatch {
+                expected: self.layout.len(),
+                actual: snapshot.vars.len(),
+            });
+        }
+
+        for ((var_id, _off, len), value) in self.layout.iter().zip(snapshot.vars.iter()) {
+            if len as usize != value.len() {
+                return Err(SnapshotError::VarLengthMismatch {
+                    var_id: var_id.0,
+                    expected: len,
+                    actual: value.len(),
+                });
+            }
+        }
+
+        for (i, value) in snapshot.vars.iter().enumerate() {
+            self.write_var(VarId(i as u32), value.clone());
+        }
+
+        let state = self.commit()?;
+
+        Ok(state)
+    }
+
     /// Commits modified variables into the raw storage.
-    #[must_use]
-    pub fn commit(&mut self) -> State {
+    ///
+    /// Returns an `ExternKVError` when the underlying key-value store has failed.
+    /// The modified variables are dropped either way.
+    pub fn commit(&mut self) -> Result<State, ExternKVError> {
         let var_offset: HashMap<VarId, u32> = self
             .uncommitted
             .keys()
@@ -114,10 +190,16 @@ impl AppStorage {
             })
             .collect::<Vec<_>>();
 
-        self.raw_storage.write(&changes);
+        let state = self.raw_storage.write(&changes);
 
         debug_assert!(self.uncommitted.is_empty());
 
-        self.raw_storage.head()
+        state
+    }
+}
+
+impl From<ExternKVError> for SnapshotError {
+    fn from(err: ExternKVError) -> Self {
+        SnapshotError::StorageFailed(err.to_string())
     }
 }
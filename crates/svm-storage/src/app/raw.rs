@@ -4,7 +4,7 @@ use svm_types::State;
 
 use super::AppKVStore;
 
-use crate::kv::StatefulKV;
+use crate::kv::{ExternKVError, StatefulKV};
 
 /// Interface against the key-value store.
 /// Data is manipulated using `offset` and `length`.
@@ -52,49 +52,52 @@ impl RawStorage {
 
     /// Reads the raw data under `offset, offset + 1, ..., offset + length - 1`
     /// In case there is no stored blob, returns a zeros vector of length `length`.
-    pub fn read(&self, offset: u32, length: u32) -> Vec<u8> {
+    pub fn read(&self, offset: u32, length: u32) -> Result<Vec<u8>, ExternKVError> {
         assert!(length <= self.kv_value_size);
 
         let key = self.offset_length_key(offset, length);
-        let value = self.do_read_key(key);
+        let value = self.do_read_key(key)?;
 
         let slice = self.value_slice(&value[..], offset, length);
-        slice.to_vec()
+        Ok(slice.to_vec())
     }
 
     /// Write a batch of changes into underlying key-value store.
-    pub fn write(&mut self, changes: &[RawChange]) {
+    /// Returns the `State` of the created checkpoint.
+    pub fn write(&mut self, changes: &[RawChange]) -> Result<State, ExternKVError> {
         let changes = self.group_changes_by_key(changes);
 
-        let mut raw_changes = Vec::with_capacity(changes.len());
+        let raw_keys: Vec<[u8; 4]> = changes.keys().map(|key| key.to_be_bytes()).collect();
+        let keys: Vec<&[u8]> = raw_keys.iter().map(|key| &key[..]).collect();
 
-        for (key, value_changes) in changes.iter() {
-            let raw_key = key.to_be_bytes();
+        let values = self.app_kv.get_many(&keys)?;
 
-            let mut raw_value = self.do_read_key(*key);
+        let mut raw_values = Vec::with_capacity(changes.len());
+
+        for (value, value_changes) in values.into_iter().zip(changes.values()) {
+            let mut raw_value = value.unwrap_or(vec![0; self.kv_value_size as usize]);
             debug_assert_eq!(raw_value.len(), self.kv_value_size as usize);
 
             self.patch_value(&mut raw_value, &value_changes[..]);
 
-            raw_changes.push((raw_key, raw_value));
-        }
-
-        for (k, v) in raw_changes.iter() {
-            self.app_kv.set(k, v);
+            raw_values.push(raw_value);
         }
 
-        let _state = self.app_kv.checkpoint();
+        let raw_changes: Vec<(&[u8], &[u8])> = keys
+            .iter()
+            .zip(raw_values.iter())
+            .map(|(k, v)| (*k, &v[..]))
+            .collect();
 
-        self.app_kv.flush();
+        self.app_kv.commit_many(&raw_changes)
     }
 
     #[inline]
-    fn do_read_key(&self, key: u32) -> Vec<u8> {
+    fn do_read_key(&self, key: u32) -> Result<Vec<u8>, ExternKVError> {
         let key = key.to_be_bytes();
+        let value = self.app_kv.get(&key[..])?;
 
-        self.app_kv
-            .get(&key[..])
-            .unwrap_or(vec![0; self.kv_value_size as usize])
+        Ok(value.unwrap_or(vec![0; self.kv_value_size as usize]))
     }
 
     #[inline]
@@ -175,7 +178,7 @@ mod tests {
         let len = 20;
 
         let storage = RawStorage::new(kv, KV_VALUE_SIZE);
-        let bytes = storage.read(off, len);
+        let bytes = storage.read(off, len).unwrap();
 
         assert_eq!(bytes, vec![0; len as usize]);
     }
@@ -198,12 +201,12 @@ mod tests {
         let changes = vec![var1.clone(), var2.clone()];
 
         let mut storage = RawStorage::new(kv, KV_VALUE_SIZE);
-        storage.write(&changes);
+        let _state = storage.write(&changes).unwrap();
 
-        let data1 = storage.read(var1.offset, var1.len());
+        let data1 = storage.read(var1.offset, var1.len()).unwrap();
         assert_eq!(data1, vec![0x10, 0x20, 0x30]);
 
-        let data2 = storage.read(var2.offset, var2.len());
+        let data2 = storage.read(var2.offset, var2.len()).unwrap();
         assert_eq!(data2, vec![0x40, 0x50]);
     }
 }
@@ -1,54 +1,119 @@
+use std::fmt;
+
 use super::StatefulKV;
 
+use svm_ffi::svm_byte_array;
 use svm_types::State;
 
-const BUF_SIZE: usize = 1024;
-static mut BUF: [u8; BUF_SIZE] = [0; BUF_SIZE];
+/// The initial capacity of each value buffer passed to `GetManyFn`.
+/// Values exceeding it are fetched again using a large-enough buffer.
+const VALUE_INITIAL_CAPACITY: usize = 64;
+
+/// The capacity of the error message buffer passed to each callback.
+const ERROR_CAPACITY: usize = 256;
+
+/// The `length` set by the `Host` for a key having no value (see `GetManyFn`).
+///
+/// A `length` of zero stands for an empty value.
+pub const MISSING_VALUE: u32 = u32::MAX;
+
+/// The maximum byte-length of a value returned by the `Host`.
+/// A longer value fails the `get_many` call (see `GetManyFn`).
+pub const MAX_VALUE_LEN: u32 = 1 << 16;
 
-/// # Get a Key's Value
+/// # Get Many Keys' Values
 ///
-/// Gets a key's matching value.
-/// The value buffer to copy the value to is allocated by `SVM`.
-/// The buffer maximum size is 1024 bytes. (This value should be more than enough).
+/// Gets the values of a batch of keys.
+/// All the buffers are allocated by `SVM` (the caller), and they are valid only during the call.
 ///
-/// * key_ptr   - a raw pointer to the key's first byte
-/// * key_len   - key's byte-length
-/// * value_ptr - a raw pointer to the value's buffer first byte
-/// * value_len - a pointer
-pub type GetFn = unsafe extern "C" fn(*const u8, u32, *mut u8, *mut u32);
+/// * keys   - a raw pointer to the first of `nkeys` keys.
+/// * values - a raw pointer to the first of `nkeys` value buffers. For each `values[i]`:
+///   - When `keys[i]` has no value, the `Host` should set `length` to `MISSING_VALUE`.
+///   - When the value fits into `capacity` bytes, the `Host` should copy it to `bytes` and set `length`
+///     (an empty value has a `length` of zero).
+///   - Otherwise, the `Host` should only set `length` to the value's byte-length.
+///     (`SVM` will ask for that key again using a large-enough buffer).
+///   - Values can't be longer than `MAX_VALUE_LEN` bytes.
+/// * nkeys  - the number of keys.
+/// * error  - an error message buffer (see `ExternKVError`).
+///
+/// Returns whether the call has succeeded.
+pub type GetManyFn = unsafe extern "C" fn(
+    *const svm_byte_array,
+    *mut svm_byte_array,
+    u32,
+    *mut svm_byte_array,
+) -> bool;
 
-/// # Sets a Key's Value
+/// # Set Many Keys' Values
+///
+/// Sets the values of a batch of keys.
 ///
-/// Sets a value for a key.
+/// * keys   - a raw pointer to the first of `nkeys` keys.
+/// * values - a raw pointer to the first of `nkeys` values. (`values[i]` is the new value of `keys[i]`).
+/// * nkeys  - the number of keys.
+/// * error  - an error message buffer (see `ExternKVError`).
 ///
-/// * key_ptr   - a raw pointer to the key's first byte
-/// * key_len   - key's byte-length
-/// * value_ptr - a raw pointer to the value's first byte
-/// * value_len - a raw pointer to the value's first byte
-pub type SetFn = unsafe extern "C" fn(*const u8, u32, *const u8, u32);
+/// Returns whether the call has succeeded.
+pub type SetManyFn = unsafe extern "C" fn(
+    *const svm_byte_array,
+    *const svm_byte_array,
+    u32,
+    *mut svm_byte_array,
+) -> bool;
 
 /// # Discard
 ///
 /// Notifies the `Host` to discard the current executed transaction changes.
-pub type DiscardFn = unsafe extern "C" fn();
+///
+/// * error - an error message buffer (see `ExternKVError`).
+///
+/// Returns whether the call has succeeded.
+pub type DiscardFn = unsafe extern "C" fn(*mut svm_byte_array) -> bool;
 
 /// # Checkpoint
 ///
 /// Notifies the `Host` to checkpoint key-value `State`.
-/// Returns the `State` derived for the checkpoint.
+/// The `Host` should copy the `State` derived for the checkpoint into the given `State::len()` bytes buffer.
 ///
 /// Computing a checkpoint doesn't guarantee that the pendind changes and checkpoint
 /// have been persisted. It's up to the `Host` to determine when to save data for long-term usage.
-pub type CheckpointFn = unsafe extern "C" fn(*mut u8);
+///
+/// * state - a `State::len()` bytes buffer.
+/// * error - an error message buffer (see `ExternKVError`).
+///
+/// Returns whether the call has succeeded.
+pub type CheckpointFn = unsafe extern "C" fn(*mut svm_byte_array, *mut svm_byte_array) -> bool;
+
+/// An error reported by the `Host`.
+///
+/// On failure, the `Host` may copy a UTF-8 error message into the error buffer
+/// it has been given (truncated to its `capacity`) and set its `length`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternKVError {
+    /// The failed operation
+    pub op: &'static str,
+
+    /// The `Host` error message
+    pub msg: String,
+}
+
+impl fmt::Display for ExternKVError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`ExternKV` `{}` has failed: {}", self.op, self.msg)
+    }
+}
 
 /// `ExternKV` holds pointers to FFI functions for an external key-value store.
 /// It implements the `StatefulKV` traits by delegation to the FFI functions.
+///
+/// Errors returned by the `Host` are reported as `ExternKVError`.
 pub struct ExternKV {
-    /// A function-pointer for a key-value store `Get`
-    pub get_fn: GetFn,
+    /// A function-pointer for a key-value store batched `Get`
+    pub get_many_fn: GetManyFn,
 
-    /// A function-pointer for a key-value store `Set`
-    pub set_fn: SetFn,
+    /// A function-pointer for a key-value store batched `Set`
+    pub set_many_fn: SetManyFn,
 
     /// A function-pointer for a key-value store `Discard`
     pub discard_fn: DiscardFn,
@@ -61,42 +126,188 @@ pub struct ExternKV {
     pub head: Option<State>,
 }
 
-impl StatefulKV for ExternKV {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
-        let key_ptr = key.as_ptr();
-        let key_len = key.len() as u32;
+impl ExternKV {
+    /// Gets the values of `keys` using a single `Host` call
+    /// (plus another one in case some values don't fit into their initial buffers).
+    ///
+    /// Fails when the `Host` reports a value longer than `MAX_VALUE_LEN` bytes.
+    pub fn try_get_many(&self, keys: &[&[u8]]) -> Result<Vec<Option<Vec<u8>>>, ExternKVError> {
+        let mut buffers = vec![vec![0; VALUE_INITIAL_CAPACITY]; keys.len()];
+        let lengths = self.call_get_many(keys, &mut buffers)?;
 
-        let mut value_len = 0;
+        let mut values = Vec::with_capacity(keys.len());
+        let mut refetch = Vec::new();
 
-        unsafe {
-            (self.get_fn)(key_ptr, key_len, BUF.as_mut_ptr(), &mut value_len);
+        for (i, (buf, len)) in buffers.iter_mut().zip(lengths.iter()).enumerate() {
+            if *len == MISSING_VALUE {
+                values.push(None);
+                continue;
+            }
 
-            if value_len > 0 {
-                let value = std::slice::from_raw_parts(BUF.as_ptr(), value_len as usize);
+            check_value_len(i, *len)?;
 
-                Some(value.to_vec())
+            let len = *len as usize;
+
+            if len <= buf.len() {
+                buf.truncate(len);
+                values.push(Some(std::mem::take(buf)));
             } else {
-                None
+                values.push(None);
+                refetch.push((i, len));
             }
         }
-    }
 
-    fn set(&mut self, key: &[u8], value: &[u8]) {
-        let key_ptr = key.as_ptr();
-        let val_ptr = value.as_ptr();
+        if !refetch.is_empty() {
+            let keys: Vec<&[u8]> = refetch.iter().map(|(i, _)| keys[*i]).collect();
+            let mut buffers: Vec<Vec<u8>> = refetch.iter().map(|(_, len)| vec![0; *len]).collect();
 
-        let key_len = key.len() as u32;
-        let val_len = value.len() as u32;
+            let lengths = self.call_get_many(&keys, &mut buffers)?;
 
-        unsafe {
-            (self.set_fn)(key_ptr, key_len, val_ptr, val_len);
+            for (((i, expected), len), mut buf) in refetch.iter().zip(lengths).zip(buffers) {
+                if len as usize != *expected {
+                    let msg = format!("value of key #{} has changed its length", i);
+
+                    return Err(ExternKVError {
+                        op: "get_many",
+                        msg,
+                    });
+                }
+
+                buf.truncate(len as usize);
+                values[*i] = Some(buf);
+            }
         }
+
+        Ok(values)
     }
 
-    fn discard(&mut self) {
-        unsafe {
-            (self.discard_fn)();
-        }
+    /// Sets the values of many keys using a single `Host` call.
+    pub fn try_set_many(&mut self, changes: &[(&[u8], &[u8])]) -> Result<(), ExternKVError> {
+        let keys: Vec<svm_byte_array> = changes.iter().map(|(k, _)| byte_array_view(k)).collect();
+        let values: Vec<svm_byte_array> = changes.iter().map(|(_, v)| byte_array_view(v)).collect();
+        let nkeys = changes.len() as u32;
+
+        call_host("set_many", |err| unsafe {
+            (self.set_many_fn)(keys.as_ptr(), values.as_ptr(), nkeys, err)
+        })
+    }
+
+    /// Discards the pending changes.
+    pub fn try_discard(&mut self) -> Result<(), ExternKVError> {
+        call_host("discard", |err| unsafe { (self.discard_fn)(err) })
+    }
+
+    /// Creates a new checkpoint and returns its `State`.
+    pub fn try_checkpoint(&mut self) -> Result<State, ExternKVError> {
+        let mut buf = vec![0; State::len()];
+        let mut state = byte_array_buffer(&mut buf);
+
+        call_host("checkpoint", |err| unsafe {
+            (self.checkpoint_fn)(&mut state, err)
+        })?;
+
+        Ok(State::from(&buf[..]))
+    }
+
+    /// Calls `get_many_fn` with `buffers` as the value buffers.
+    /// Returns the `length` set by the `Host` for each value.
+    fn call_get_many(
+        &self,
+        keys: &[&[u8]],
+        buffers: &mut [Vec<u8>],
+    ) -> Result<Vec<u32>, ExternKVError> {
+        debug_assert_eq!(keys.len(), buffers.len());
+
+        let keys: Vec<svm_byte_array> = keys.iter().map(|k| byte_array_view(k)).collect();
+        let mut values: Vec<svm_byte_array> = buffers
+            .iter_mut()
+            .map(|buf| byte_array_buffer(buf))
+            .collect();
+        let nkeys = keys.len() as u32;
+
+        call_host("get_many", |err| unsafe {
+            (self.get_many_fn)(keys.as_ptr(), values.as_mut_ptr(), nkeys, err)
+        })?;
+
+        Ok(values.iter().map(|v| v.length).collect())
+    }
+}
+
+/// Fails when the `Host` reports that the value of key #`index` is longer than `MAX_VALUE_LEN` bytes.
+fn check_value_len(index: usize, len: u32) -> Result<(), ExternKVError> {
+    if len > MAX_VALUE_LEN {
+        let msg = format!(
+            "value of key #{} is too long ({} bytes, at most {} bytes are allowed)",
+            index, len, MAX_VALUE_LEN
+        );
+
+        return Err(ExternKVError {
+            op: "get_many",
+            msg,
+        });
+    }
+
+    Ok(())
+}
+
+/// Calls the `Host` passing it an error message buffer.
+/// Turns a failure into an `ExternKVError` holding the `Host` error message.
+fn call_host<F>(op: &'static str, f: F) -> Result<(), ExternKVError>
+where
+    F: FnOnce(*mut svm_byte_array) -> bool,
+{
+    let mut buf = vec![0; ERROR_CAPACITY];
+    let mut err = byte_array_buffer(&mut buf);
+
+    if f(&mut err) {
+        return Ok(());
+    }
+
+    let len = (err.length as usize).min(buf.len());
+    let msg = String::from_utf8_lossy(&buf[..len]).to_string();
+
+    Err(ExternKVError { op, msg })
+}
+
+/// A read-only `svm_byte_array` borrowing `bytes`.
+fn byte_array_view(bytes: &[u8]) -> svm_byte_array {
+    svm_byte_array {
+        bytes: bytes.as_ptr(),
+        length: bytes.len() as u32,
+        capacity: bytes.len() as u32,
+    }
+}
+
+/// An empty `svm_byte_array` borrowing `buf` for the `Host` to write to.
+fn byte_array_buffer(buf: &mut [u8]) -> svm_byte_array {
+    svm_byte_array {
+        bytes: buf.as_mut_ptr(),
+        length: 0,
+        capacity: buf.len() as u32,
+    }
+}
+
+impl StatefulKV for ExternKV {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ExternKVError> {
+        let mut values = self.get_many(&[key])?;
+
+        Ok(values.pop().unwrap())
+    }
+
+    fn get_many(&self, keys: &[&[u8]]) -> Result<Vec<Option<Vec<u8>>>, ExternKVError> {
+        self.try_get_many(keys)
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) -> Result<(), ExternKVError> {
+        self.set_many(&[(key, value)])
+    }
+
+    fn set_many(&mut self, changes: &[(&[u8], &[u8])]) -> Result<(), ExternKVError> {
+        self.try_set_many(changes)
+    }
+
+    fn discard(&mut self) -> Result<(), ExternKVError> {
+        self.try_discard()
     }
 
     fn flush(&mut self) {
@@ -107,12 +318,8 @@ impl StatefulKV for ExternKV {
         // on when to actually persist data.
     }
 
-    fn checkpoint(&mut self) -> State {
-        unsafe {
-            (self.checkpoint_fn)(BUF.as_mut_ptr());
-
-            State::from(BUF.as_ptr())
-        }
+    fn checkpoint(&mut self) -> Result<State, ExternKVError> {
+        self.try_checkpoint()
     }
 
     fn rewind(&mut self, _state: &State) {
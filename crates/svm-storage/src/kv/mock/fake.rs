@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::fmt;
 
-use super::super::StatefulKV;
+use super::super::{ExternKVError, StatefulKV};
 
 use svm_common::{fmt::fmt_hex, DefaultKeyHasher, KeyHasher};
 use svm_types::State;
@@ -78,25 +78,30 @@ impl Node {
 }
 
 impl StatefulKV for FakeKV {
-    #[must_use]
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
-        self.get_journal(key).or_else(|| self.get_flushed(key))
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ExternKVError> {
+        let value = self.get_journal(key).or_else(|| self.get_flushed(key));
+
+        Ok(value)
     }
 
-    fn set(&mut self, key: &[u8], value: &[u8]) {
+    fn set(&mut self, key: &[u8], value: &[u8]) -> Result<(), ExternKVError> {
         let key = key.to_vec();
         let value = value.to_vec();
         let change = Change(key, value);
 
         let (_, changes) = self.journal.last_mut().unwrap();
         changes.push(change);
+
+        Ok(())
     }
 
-    fn discard(&mut self) {
+    fn discard(&mut self) -> Result<(), ExternKVError> {
         let (maybe_state, changes) = self.journal.last_mut().unwrap();
         matches!(maybe_state, None);
 
         changes.clear();
+
+        Ok(())
     }
 
     fn flush(&mut self) {
@@ -124,8 +129,7 @@ impl StatefulKV for FakeKV {
         self.assert_journal_empty();
     }
 
-    #[must_use]
-    fn checkpoint(&mut self) -> State {
+    fn checkpoint(&mut self) -> Result<State, ExternKVError> {
         let (_, changes) = self.journal.last().unwrap();
         let new_state = self.compute_state(&changes);
 
@@ -136,7 +140,7 @@ impl StatefulKV for FakeKV {
         self.head = new_state.clone();
         self.journal.push((None, Vec::new()));
 
-        new_state
+        Ok(new_state)
     }
 
     fn rewind(&mut self, state: &State) {
@@ -331,10 +335,10 @@ mod tests {
             let changes = vec![$( (&$k[..], &$v[..]), )*];
 
             for (k, v) in changes.iter() {
-                $kv.set(k, v);
+                $kv.set(k, v).unwrap();
             }
 
-            let state = $kv.checkpoint();
+            let state = $kv.checkpoint().unwrap();
 
             $kv.flush();
 
@@ -345,7 +349,7 @@ mod tests {
     macro_rules! assert_no_keys {
         ($kv:ident, $($k:expr), *) => {{
             $(
-                let v = $kv.get(&$k[..]);
+                let v = $kv.get(&$k[..]).unwrap();
                 assert!(v.is_none());
              )*
         }};
@@ -354,7 +358,7 @@ mod tests {
     macro_rules! assert_keys {
         ($kv:ident, $( ($k:expr => $v:expr), )* ) => {{
             $(
-                let v = $kv.get(&$k[..]);
+                let v = $kv.get(&$k[..]).unwrap();
                 assert_eq!(v.unwrap(), $v);
              )*
         }};
@@ -1,19 +1,19 @@
 use super::FakeKV;
-use crate::kv::{ExternKV, StatefulKV};
+use crate::kv::{ExternKV, StatefulKV, MISSING_VALUE};
 
 use std::slice;
 use std::sync::Mutex;
 
-use svm_types::State;
+use svm_ffi::svm_byte_array;
 
 use lazy_static::lazy_static;
 
 // This file contains a mock implementation from the `Host`'s angle.
 //
 // The `Host` (i.e `go-spacemesh` but theoretically other Full-Node)
-// exposes to `ExternKV` (the `FFI_KV` below) the following functions pointers:
-// * `get`
-// * `set`
+// exposes to `ExternKV` (see `extern_kv` below) the following functions pointers:
+// * `get_many`
+// * `set_many`
 // * `discard`
 // * `checkpoint`
 //
@@ -23,7 +23,8 @@ use lazy_static::lazy_static;
 // +------------------------------------------------+
 // |                                                |
 // |     `Host` (mock for `go-spacemesh`)           |
-// |  implements: `get, set, discard, checkpoint`   |
+// |  implements: `get_many, set_many,              |
+// |               discard, checkpoint`             |
 // |                                                |
 // |    ^                                           |
 // +----|-------------------------------------------+
@@ -38,13 +39,6 @@ use lazy_static::lazy_static;
 
 lazy_static! {
     static ref KV: Mutex<FakeKV> = Mutex::new(FakeKV::new());
-    static ref FFI_KV: ExternKV = ExternKV {
-        get_fn: get,
-        set_fn: set,
-        discard_fn: discard,
-        checkpoint_fn: checkpoint,
-        head: None,
-    };
 }
 
 macro_rules! kv {
@@ -53,42 +47,226 @@ macro_rules! kv {
     }};
 }
 
-pub unsafe extern "C" fn get(
-    key_ptr: *const u8,
-    key_len: u32,
-    value_ptr: *mut u8,
-    value_len: *mut u32,
-) {
-    let key = slice::from_raw_parts(key_ptr, key_len as usize);
-    let value = kv!().get(key);
-
-    match value {
-        Some(value) => {
-            std::ptr::copy(value.as_ptr(), value_ptr, value.len());
-            *value_len = value.len() as u32;
+/// Returns an `ExternKV` delegating to the mock `Host`.
+pub fn extern_kv() -> ExternKV {
+    ExternKV {
+        get_many_fn: get_many,
+        set_many_fn: set_many,
+        discard_fn: discard,
+        checkpoint_fn: checkpoint,
+        head: None,
+    }
+}
+
+unsafe fn as_slice<'a>(bytes: &svm_byte_array) -> &'a [u8] {
+    slice::from_raw_parts(bytes.bytes, bytes.length as usize)
+}
+
+unsafe fn copy_to(src: &[u8], dst: &mut svm_byte_array) {
+    if src.len() <= dst.capacity as usize {
+        std::ptr::copy(src.as_ptr(), dst.bytes as *mut u8, src.len());
+    }
+
+    dst.length = src.len() as u32;
+}
+
+pub unsafe extern "C" fn get_many(
+    keys: *const svm_byte_array,
+    values: *mut svm_byte_array,
+    nkeys: u32,
+    _error: *mut svm_byte_array,
+) -> bool {
+    let keys = slice::from_raw_parts(keys, nkeys as usize);
+    let values = slice::from_raw_parts_mut(values, nkeys as usize);
+
+    let kv = kv!();
+
+    for (key, value) in keys.iter().zip(values.iter_mut()) {
+        match kv.get(as_slice(key)).unwrap() {
+            Some(v) => copy_to(&v, value),
+            None => value.length = MISSING_VALUE,
         }
-        None => *value_len = 0,
     }
+
+    true
 }
 
-pub unsafe extern "C" fn set(
-    key_ptr: *const u8,
-    key_len: u32,
-    value_ptr: *const u8,
-    value_len: u32,
-) {
-    let key = slice::from_raw_parts(key_ptr, key_len as usize);
-    let value = slice::from_raw_parts(value_ptr, value_len as usize);
+pub unsafe extern "C" fn set_many(
+    keys: *const svm_byte_array,
+    values: *const svm_byte_array,
+    nkeys: u32,
+    _error: *mut svm_byte_array,
+) -> bool {
+    let keys = slice::from_raw_parts(keys, nkeys as usize);
+    let values = slice::from_raw_parts(values, nkeys as usize);
 
-    kv!().set(key, value);
+    let mut kv = kv!();
+
+    for (key, value) in keys.iter().zip(values.iter()) {
+        kv.set(as_slice(key), as_slice(value)).unwrap();
+    }
+
+    true
 }
 
-pub unsafe extern "C" fn discard() {
-    kv!().discard()
+pub unsafe extern "C" fn discard(_error: *mut svm_byte_array) -> bool {
+    kv!().discard().unwrap();
+
+    true
 }
 
-pub unsafe extern "C" fn checkpoint(state_ptr: *mut u8) {
-    let state = kv!().checkpoint();
+pub unsafe extern "C" fn checkpoint(
+    state: *mut svm_byte_array,
+    _error: *mut svm_byte_array,
+) -> bool {
+    let s = kv!().checkpoint().unwrap();
+
+    copy_to(s.as_slice(), &mut *state);
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::app::{AppKVStore, AppStorage};
+    use crate::kv::{ExternKVError, MAX_VALUE_LEN};
+
+    use svm_layout::{DataLayout, VarId};
+    use svm_types::{Address, State};
+
+    unsafe extern "C" fn failing_discard(error: *mut svm_byte_array) -> bool {
+        copy_to(b"Host is unavailable", &mut *error);
+
+        false
+    }
+
+    unsafe extern "C" fn failing_get_many(
+        _keys: *const svm_byte_array,
+        _values: *mut svm_byte_array,
+        _nkeys: u32,
+        error: *mut svm_byte_array,
+    ) -> bool {
+        copy_to(b"Host is unavailable", &mut *error);
+
+        false
+    }
+
+    unsafe extern "C" fn failing_set_many(
+        _keys: *const svm_byte_array,
+        _values: *const svm_byte_array,
+        _nkeys: u32,
+        error: *mut svm_byte_array,
+    ) -> bool {
+        copy_to(b"Host is unavailable", &mut *error);
+
+        false
+    }
+
+    unsafe extern "C" fn oversize_get_many(
+        _keys: *const svm_byte_array,
+        values: *mut svm_byte_array,
+        _nkeys: u32,
+        _error: *mut svm_byte_array,
+    ) -> bool {
+        (*values).length = MAX_VALUE_LEN + 1;
+
+        true
+    }
+
+    fn app_storage(kv: ExternKV) -> AppStorage {
+        let kv: Arc<Mutex<dyn StatefulKV + Send>> = Arc::new(Mutex::new(kv));
+        let app_kv = AppKVStore::new(Address::of("my-app"), &kv);
+        let layout = DataLayout::from(&[4, 2][..]);
+
+        AppStorage::new(layout, app_kv)
+    }
+
+    #[test]
+    fn extern_kv_get_many_and_set_many() {
+        let mut kv = extern_kv();
+
+        let small = vec![0x10; 4];
+        let large = vec![0x20; 1000];
+
+        kv.set_many(&[(b"ffi-key-1", &small), (b"ffi-key-2", &large)])
+            .unwrap();
 
-    std::ptr::copy(state.as_ptr(), state_ptr, State::len());
+        let values = kv
+            .get_many(&[b"ffi-key-1", b"ffi-key-2", b"ffi-key-3"])
+            .unwrap();
+        assert_eq!(values, vec![Some(small), Some(large), None]);
+
+        let state = kv.checkpoint().unwrap();
+        assert_ne!(state, State::empty());
+    }
+
+    #[test]
+    fn extern_kv_get_empty_value() {
+        let mut kv = extern_kv();
+
+        kv.set(b"ffi-empty-key", &[]).unwrap();
+
+        let values = kv.get_many(&[b"ffi-empty-key", b"ffi-no-key"]).unwrap();
+        assert_eq!(values, vec![Some(Vec::new()), None]);
+    }
+
+    #[test]
+    fn extern_kv_get_value_too_long() {
+        let mut kv = extern_kv();
+        kv.get_many_fn = oversize_get_many;
+
+        let err = kv.get(b"ffi-key").unwrap_err();
+
+        assert_eq!(err.op, "get_many");
+        assert_eq!(
+            err.msg,
+            "value of key #0 is too long (65537 bytes, at most 65536 bytes are allowed)"
+        );
+    }
+
+    #[test]
+    fn extern_kv_host_error() {
+        let mut kv = extern_kv();
+        kv.discard_fn = failing_discard;
+
+        let err = kv.discard().unwrap_err();
+
+        assert_eq!(
+            err,
+            ExternKVError {
+                op: "discard",
+                msg: "Host is unavailable".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn app_storage_read_var_host_error() {
+        let mut kv = extern_kv();
+        kv.get_many_fn = failing_get_many;
+
+        let storage = app_storage(kv);
+        let err = storage.read_var(VarId(0)).unwrap_err();
+
+        assert_eq!(err.op, "get_many");
+        assert_eq!(err.msg, "Host is unavailable");
+    }
+
+    #[test]
+    fn app_storage_commit_host_error() {
+        let mut kv = extern_kv();
+        kv.set_many_fn = failing_set_many;
+
+        let mut storage = app_storage(kv);
+        storage.write_var(VarId(1), vec![0x10, 0x20]);
+
+        let err = storage.commit().unwrap_err();
+
+        assert_eq!(err.op, "set_many");
+        assert_eq!(err.msg, "Host is unavailable");
+    }
 }
@@ -2,4 +2,4 @@ mod fake;
 mod ffi;
 
 pub use fake::FakeKV;
-pub use ffi::{checkpoint, discard, get, set};
+pub use ffi::{checkpoint, discard, extern_kv, get_many, set_many};
@@ -2,6 +2,9 @@ mod ffi;
 mod mock;
 mod traits;
 
-pub use ffi::ExternKV;
+pub use ffi::{
+    CheckpointFn, DiscardFn, ExternKV, ExternKVError, GetManyFn, SetManyFn, MAX_VALUE_LEN,
+    MISSING_VALUE,
+};
 pub use mock::FakeKV;
 pub use traits::StatefulKV;
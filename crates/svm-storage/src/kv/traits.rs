@@ -1,10 +1,15 @@
 use svm_types::State;
 
+use super::ExternKVError;
+
 /// This trait should be implemented by `State`-aware key-value stores.
 ///
 /// Implementations of this trait will probably want to use a raw-key value store
 /// for implementating primitive operations.
 ///
+/// Operations which may reach the underlying store return an `ExternKVError` on failure
+/// (in-memory implementations never fail).
+///
 pub trait StatefulKV {
     /// Gets the `value` pointed by by `key`.
     ///
@@ -12,8 +17,15 @@ pub trait StatefulKV {
     /// Otherwise, should proceed looking for `key -> value` under the persisted data.
     ///
     /// In case there is no matching `value`, `None` should be returned.
-    #[must_use]
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ExternKVError>;
+
+    /// Gets the values pointed by `keys` (the `i-th` item is the value of `keys[i]`).
+    ///
+    /// Implementations backed by a remote store (see `ExternKV`) should override it
+    /// in order to fetch all values at once.
+    fn get_many(&self, keys: &[&[u8]]) -> Result<Vec<Option<Vec<u8>>>, ExternKVError> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
 
     /// Sets a new pending change.
     ///
@@ -23,7 +35,18 @@ pub trait StatefulKV {
     /// Subsequent `get` calls on `key` should return the new set `value`.
     ///
     /// See also: `flush`
-    fn set(&mut self, key: &[u8], value: &[u8]);
+    fn set(&mut self, key: &[u8], value: &[u8]) -> Result<(), ExternKVError>;
+
+    /// Sets a batch of new pending changes. Each change is a `(key, value)` pair.
+    ///
+    /// See also: `set` and `get_many`
+    fn set_many(&mut self, changes: &[(&[u8], &[u8])]) -> Result<(), ExternKVError> {
+        for (key, value) in changes.iter() {
+            self.set(key, value)?;
+        }
+
+        Ok(())
+    }
 
     /// Discards all pending changes
     ///
@@ -35,7 +58,7 @@ pub trait StatefulKV {
     /// Any other pending changes associated with a checkpoint won't be discarded,
     /// even if `flush` has not been called on them.
     ///
-    fn discard(&mut self);
+    fn discard(&mut self) -> Result<(), ExternKVError>;
 
     /// Persists all pending changes since last `flush` call.
     ///
@@ -53,8 +76,7 @@ pub trait StatefulKV {
     /// Creates a new checkpoint and returns its `State`.
     ///
     /// In order to persist the pending changes, `flush` should be called.
-    #[must_use]
-    fn checkpoint(&mut self) -> State;
+    fn checkpoint(&mut self) -> Result<State, ExternKVError>;
 
     /// Rewinds the current pointed-to `State`.
     ///
@@ -1,14 +1,13 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use svm_types::Address;
 
 use crate::app::AppKVStore;
 use crate::kv::{FakeKV, StatefulKV};
 
-/// Creates an in-memory stateful key-value store and returns it wrapped within `Rc<RefCell<..>>`
-pub fn create_kv() -> Rc<RefCell<dyn StatefulKV>> {
-    Rc::new(RefCell::new(FakeKV::new()))
+/// Creates an in-memory stateful key-value store and returns it wrapped within `Arc<Mutex<..>>`
+pub fn create_kv() -> Arc<Mutex<dyn StatefulKV + Send>> {
+    Arc::new(Mutex::new(FakeKV::new()))
 }
 
 /// Creates an `AppKVStore` for app having `Address = app_addr`.
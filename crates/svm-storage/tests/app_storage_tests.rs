@@ -1,11 +1,11 @@
 use svm_layout::{DataLayout, VarId};
 use svm_storage::{app::AppStorage, testing};
-use svm_types::Address;
+use svm_types::{Address, Snapshot, SnapshotError, State};
 
 macro_rules! assert_vars {
         ($app:expr, $($var_id:expr => $expected:expr), *) => {{
             $(
-                let actual = $app.read_var(VarId($var_id));
+                let actual = $app.read_var(VarId($var_id)).unwrap();
                 assert_eq!(actual, $expected);
              )*
         }};
@@ -44,7 +44,7 @@ fn app_storage_vars_are_persisted_only_on_commit() {
     assert_vars!(app2, 0 => [0, 0, 0, 0], 1 => [0, 0]);
 
     // now, we'll commit the dirty changes
-    let _state = app.commit();
+    let _state = app.commit().unwrap();
 
     // we'll spin a new app with no caching
     let app3 = AppStorage::new(layout.clone(), kv.clone());
@@ -67,3 +67,81 @@ fn app_storage_write_var_value_should_match_layout_length() {
     // calling `write_var` with 2-byte value (expected variable's to value to be 4 bytes)
     app.write_var(VarId(0), vec![0, 0]);
 }
+
+#[test]
+fn app_storage_export_and_import_snapshot() {
+    let layout = DataLayout::from(vec![4, 2].as_slice());
+
+    let addr = Address::of("my-app");
+    let kv = testing::create_app_kv(addr.clone());
+
+    let mut app = AppStorage::new(layout.clone(), kv.clone());
+
+    write_vars!(app, 0 => [10, 20, 30, 40], 1 => [50, 60]);
+    let state1 = app.commit().unwrap();
+
+    write_vars!(app, 0 => [11, 21, 31, 41], 1 => [51, 61]);
+    let state2 = app.commit().unwrap();
+
+    // exporting an older `State` doesn't affect the current one
+    let snapshot = app.export(&state1).unwrap();
+
+    assert_eq!(snapshot.state, state1);
+    assert_eq!(snapshot.layout, layout);
+    assert_eq!(snapshot.vars, vec![vec![10, 20, 30, 40], vec![50, 60]]);
+    assert_eq!(app.head(), state2);
+
+    // seeding a fresh storage (i.e on another node)
+    let other_kv = testing::create_app_kv(addr);
+    let mut other = AppStorage::new(layout, other_kv);
+
+    let _state = other.import(&snapshot).unwrap();
+
+    assert_vars!(other, 0 => [10, 20, 30, 40], 1 => [50, 60]);
+}
+
+#[test]
+fn app_storage_export_with_uncommitted_changes() {
+    let layout: DataLayout = vec![4].into();
+    let addr = Address::of("my-app");
+    let kv = testing::create_app_kv(addr);
+
+    let mut app = AppStorage::new(layout, kv);
+    let state = app.head();
+
+    write_vars!(app, 0 => [10, 20, 30, 40]);
+
+    assert_eq!(app.export(&state), Err(SnapshotError::UncommittedChanges));
+}
+
+#[test]
+fn app_storage_import_mismatching_snapshot() {
+    let addr = Address::of("my-app");
+    let kv = testing::create_app_kv(addr);
+
+    let mut app = AppStorage::new(vec![4, 2].into(), kv);
+    let head = app.head();
+
+    let snapshot = Snapshot {
+        layout: vec![4].into(),
+        state: State::empty(),
+        vars: vec![vec![10, 20, 30, 40]],
+    };
+    assert_eq!(app.import(&snapshot), Err(SnapshotError::LayoutMismatch));
+
+    let snapshot = Snapshot {
+        layout: vec![4, 2].into(),
+        state: State::empty(),
+        vars: vec![vec![10, 20, 30, 40], vec![50]],
+    };
+    assert_eq!(
+        app.import(&snapshot),
+        Err(SnapshotError::VarLengthMismatch {
+            var_id: 1,
+            expected: 2,
+            actual: 1
+        })
+    );
+
+    assert_eq!(app.head(), head);
+}
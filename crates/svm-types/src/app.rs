@@ -4,6 +4,11 @@ use crate::{Address, TemplateAddr};
 
 /// An in-memory representation of an app.
 #[derive(PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct App {
     /// `App` version.
     pub version: u32,
@@ -4,6 +4,11 @@ use crate::{Address, AppAddr, WasmValue};
 
 /// An in-memory representation of an exec-app transaction.
 #[derive(PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct AppTransaction {
     /// The app-transaction version.
     pub version: u32,
@@ -15,6 +20,7 @@ pub struct AppTransaction {
     pub func_name: String,
 
     /// Transaction's calldata
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
     pub calldata: Vec<u8>,
 }
 
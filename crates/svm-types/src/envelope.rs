@@ -0,0 +1,148 @@
+use std::fmt;
+
+use svm_common::{DefaultKeyHasher, KeyHasher};
+
+use crate::impl_bytes_primitive;
+use crate::Address;
+
+impl_bytes_primitive!(PublicKey, 32);
+
+impl_bytes_primitive!(Signature, 64);
+
+/// The kind of the transaction carried as the `payload` of an `Envelope`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate", rename_all = "kebab-case")
+)]
+pub enum TxKind {
+    /// A `deploy-template` transaction
+    DeployTemplate = 0,
+
+    /// A `spawn-app` transaction
+    SpawnApp = 1,
+
+    /// An `exec-app` transaction
+    ExecApp = 2,
+
+    /// An `upgrade-app` transaction
+    UpgradeApp = 3,
+}
+
+impl TxKind {
+    /// Returns the `TxKind` matching `kind` (if any).
+    pub fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            0 => Some(TxKind::DeployTemplate),
+            1 => Some(TxKind::SpawnApp),
+            2 => Some(TxKind::ExecApp),
+            3 => Some(TxKind::UpgradeApp),
+            _ => None,
+        }
+    }
+}
+
+/// An in-memory representation of a transaction envelope.
+///
+/// The envelope wraps an encoded SVM transaction (the `payload`) with the account-level
+/// data every host needs: the sender, its nonce, gas and value.
+///
+/// The `signature` is an `ed25519` signature made by the `public_key` owner
+/// over all the other fields (see `svm_codec::api::raw::envelope_signing_bytes`).
+#[derive(Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Envelope {
+    /// The envelope version.
+    pub version: u32,
+
+    /// The kind of the `payload` transaction
+    pub kind: TxKind,
+
+    /// The transaction sender
+    pub sender: Address,
+
+    /// The sender's nonce
+    pub nonce: u64,
+
+    /// The maximum amount of gas the transaction may consume
+    pub gas_limit: u64,
+
+    /// The price the sender pays per unit of gas
+    pub gas_price: u64,
+
+    /// The amount of coins transferred along with the transaction
+    pub value: u64,
+
+    /// The encoded SVM transaction
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
+    pub payload: Vec<u8>,
+
+    /// The sender's `ed25519` public key
+    pub public_key: PublicKey,
+
+    /// The sender's `ed25519` signature
+    pub signature: Signature,
+}
+
+impl Envelope {
+    /// Derives the `Address` owned by `public_key`.
+    ///
+    /// The address is made of the last 20 bytes of the `keccak256` hash of the key.
+    pub fn sender_of(public_key: &PublicKey) -> Address {
+        let hash = DefaultKeyHasher::hash(public_key.as_slice());
+        let skip = hash.len() - Address::len();
+
+        Address::from(&hash[skip..])
+    }
+
+    fn fmt_address(addr: &Address) -> String {
+        addr.fmt(4, 4, " ")
+    }
+}
+
+impl fmt::Debug for Envelope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Envelope")
+            .field("version", &self.version)
+            .field("kind", &self.kind)
+            .field("sender", &Envelope::fmt_address(&self.sender))
+            .field("nonce", &self.nonce)
+            .field("gas_limit", &self.gas_limit)
+            .field("gas_price", &self.gas_price)
+            .field("value", &self.value)
+            .field("payload", &self.payload.iter().take(4).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_kind_from_u8() {
+        assert_eq!(TxKind::from_u8(0), Some(TxKind::DeployTemplate));
+        assert_eq!(TxKind::from_u8(1), Some(TxKind::SpawnApp));
+        assert_eq!(TxKind::from_u8(2), Some(TxKind::ExecApp));
+        assert_eq!(TxKind::from_u8(3), Some(TxKind::UpgradeApp));
+        assert_eq!(TxKind::from_u8(4), None);
+    }
+
+    #[test]
+    fn sender_of_public_key() {
+        let key1 = PublicKey::from([0x10; 32]);
+        let key2 = PublicKey::from([0x20; 32]);
+
+        let addr1 = Envelope::sender_of(&key1);
+        let addr2 = Envelope::sender_of(&key2);
+
+        assert_eq!(addr1, Envelope::sender_of(&key1));
+        assert_ne!(addr1, addr2);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::Envelope;
+
+/// The environment of a running transaction which isn't part of its encoded payload.
+///
+/// The `Host` builds it out of the transaction's `Envelope` and passes it to each `spawn_app` / `exec_app`
+/// call of the `Runtime`, which exposes it to the running app (see the `svm_value` vmcall).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct HostCtx {
+    /// The amount of coins transferred along with the transaction
+    pub value: u64,
+}
+
+impl HostCtx {
+    /// Creates a `HostCtx` of a transaction transferring `value` coins.
+    pub fn with_value(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl From<&Envelope> for HostCtx {
+    fn from(envelope: &Envelope) -> Self {
+        Self::with_value(envelope.value)
+    }
+}
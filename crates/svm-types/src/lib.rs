@@ -11,20 +11,31 @@ mod address;
 mod address_of;
 mod app;
 mod app_tx;
+mod envelope;
+mod host_ctx;
+mod snapshot;
 mod spawn_app;
 mod state;
 mod template;
+mod upgrade_app;
 mod wasm_type;
 mod wasm_value;
 
+#[cfg(feature = "serde")]
+mod serde_hex;
+
 pub mod gas;
 pub mod receipt;
 pub use address::{Address, AppAddr, AuthorAddr, CreatorAddr, TemplateAddr};
 pub use address_of::AddressOf;
 pub use app::App;
 pub use app_tx::AppTransaction;
+pub use envelope::{Envelope, PublicKey, Signature, TxKind};
+pub use host_ctx::HostCtx;
+pub use snapshot::{Snapshot, SnapshotError};
 pub use spawn_app::SpawnApp;
 pub use state::State;
 pub use template::AppTemplate;
+pub use upgrade_app::UpgradeApp;
 pub use wasm_type::{WasmType, WasmTypeError};
 pub use wasm_value::WasmValue;
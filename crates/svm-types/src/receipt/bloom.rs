@@ -0,0 +1,149 @@
+use std::fmt;
+
+use svm_common::{DefaultKeyHasher, KeyHasher};
+
+use super::Log;
+
+const BLOOM_SIZE: usize = 256;
+
+/// A 2048-bit bloom filter over the `topics` of a receipt's logs.
+///
+/// Each topic sets 3 bits, each one chosen by a pair of bytes of the topic's `keccak256` hash.
+/// Asking whether a topic `may be contained` has no false negatives (but may have false positives).
+#[derive(Clone)]
+pub struct Bloom([u8; BLOOM_SIZE]);
+
+impl Bloom {
+    /// The bloom size in bytes.
+    pub const BYTES: usize = BLOOM_SIZE;
+
+    /// The number of bits set for each topic.
+    const BITS_PER_TOPIC: usize = 3;
+
+    /// An empty bloom (no bits set).
+    pub fn empty() -> Self {
+        Self([0; Self::BYTES])
+    }
+
+    /// Computes the bloom of the `topics` of all `logs`.
+    pub fn from_logs(logs: &[Log]) -> Self {
+        let mut bloom = Self::empty();
+
+        for topic in logs.iter().flat_map(|log| log.topics().iter()) {
+            bloom.accrue(topic);
+        }
+
+        bloom
+    }
+
+    /// Adds `topic` to the bloom.
+    pub fn accrue(&mut self, topic: &[u8]) {
+        for (byte, mask) in Self::positions(topic).iter() {
+            self.0[*byte] |= *mask;
+        }
+    }
+
+    /// Returns whether `topic` may have been added to the bloom.
+    pub fn contains(&self, topic: &[u8]) -> bool {
+        Self::positions(topic)
+            .iter()
+            .all(|(byte, mask)| self.0[*byte] & *mask != 0)
+    }
+
+    /// Returns whether no bits are set.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|byte| *byte == 0)
+    }
+
+    /// Borrows the bloom's underlying bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+
+    fn positions(topic: &[u8]) -> [(usize, u8); Self::BITS_PER_TOPIC] {
+        let hash = DefaultKeyHasher::hash(topic);
+
+        let mut positions = [(0, 0); Self::BITS_PER_TOPIC];
+
+        for (i, pos) in positions.iter_mut().enumerate() {
+            // the 11 least-significant bits of each bytes pair select a bit within the 2048 bits.
+            let bit = ((hash[2 * i] as usize) << 8 | hash[2 * i + 1] as usize) & 0x7FF;
+
+            let byte = Self::BYTES - 1 - bit / 8;
+            let mask = 1 << (bit % 8);
+
+            *pos = (byte, mask);
+        }
+
+        positions
+    }
+}
+
+impl From<&[u8]> for Bloom {
+    fn from(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), Self::BYTES);
+
+        let mut buf = [0; Self::BYTES];
+        buf.copy_from_slice(bytes);
+
+        Self(buf)
+    }
+}
+
+impl PartialEq for Bloom {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl fmt::Debug for Bloom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let set_bits: u32 = self.0.iter().map(|byte| byte.count_ones()).sum();
+
+        write!(f, "Bloom({} bits set)", set_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(topics: &[&[u8]]) -> Log {
+        let topics = topics.iter().map(|t| t.to_vec()).collect();
+
+        Log::event(topics, Vec::new())
+    }
+
+    #[test]
+    fn bloom_empty() {
+        let bloom = Bloom::from_logs(&[]);
+
+        assert!(bloom.is_empty());
+        assert!(!bloom.contains(b"Transfer"));
+    }
+
+    #[test]
+    fn bloom_contains_logs_topics() {
+        let logs = vec![
+            event(&[b"Transfer", b"alice"]),
+            Log::new(b"no topics".to_vec(), 1),
+            event(&[b"Approval"]),
+        ];
+
+        let bloom = Bloom::from_logs(&logs);
+
+        assert!(bloom.contains(b"Transfer"));
+        assert!(bloom.contains(b"alice"));
+        assert!(bloom.contains(b"Approval"));
+
+        assert!(!bloom.contains(b"Burn"));
+    }
+
+    #[test]
+    fn bloom_from_bytes() {
+        let bloom = Bloom::from_logs(&[event(&[b"Transfer"])]);
+        let copy = Bloom::from(bloom.as_slice());
+
+        assert_eq!(bloom, copy);
+    }
+}
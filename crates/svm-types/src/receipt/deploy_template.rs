@@ -5,17 +5,34 @@ use crate::TemplateAddr;
 
 /// Returned Receipt after deploying a Template.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct TemplateReceipt {
     /// whether spawn succedded or not
     pub success: bool,
 
     /// the error in case spawning failed
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub error: Option<ReceiptError>,
 
     /// The deployed template `Address`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "template", default, skip_serializing_if = "Option::is_none")
+    )]
     pub addr: Option<TemplateAddr>,
 
     /// The amount of gas used for template deployment
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "MaybeGas::new", skip_serializing_if = "MaybeGas::is_none")
+    )]
     pub gas_used: MaybeGas,
 
     pub logs: Vec<Log>,
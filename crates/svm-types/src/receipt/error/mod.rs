@@ -4,7 +4,16 @@ mod spawn_app;
 
 use crate::{AppAddr, TemplateAddr};
 
+/// The error of a failed transaction.
+///
+/// With the `serde` feature, an error is represented as an object tagged by its `type`
+/// (i.e `{ type: 'app-not-found', app: '1020...' }`).
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate", into = "ErrorRepr", from = "ErrorRepr")
+)]
 pub enum ReceiptError {
     OOG,
     TemplateNotFound(TemplateAddr),
@@ -30,4 +39,206 @@ pub enum ReceiptError {
         func: String,
         msg: String,
     },
+    UpgradeNotAuthorized {
+        app_addr: AppAddr,
+    },
+    IncompatibleLayout {
+        app_addr: AppAddr,
+        template_addr: TemplateAddr,
+    },
+    Reverted {
+        data: Vec<u8>,
+    },
+    StorageFailed {
+        app_addr: AppAddr,
+        msg: String,
+    },
+    InvalidTransaction {
+        msg: String,
+    },
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde_crate::Serialize, serde_crate::Deserialize)]
+#[serde(crate = "serde_crate", tag = "type", rename_all = "kebab-case")]
+enum ErrorRepr {
+    Oog,
+    TemplateNotFound {
+        template: TemplateAddr,
+    },
+    AppNotFound {
+        app: AppAddr,
+    },
+    CompilationFailed {
+        template: TemplateAddr,
+        app: AppAddr,
+        message: String,
+    },
+    InstantiationFailed {
+        template: TemplateAddr,
+        app: AppAddr,
+        message: String,
+    },
+    #[serde(rename = "function-not-found")]
+    FuncNotFound {
+        template: TemplateAddr,
+        app: AppAddr,
+        func: String,
+    },
+    #[serde(rename = "function-failed")]
+    FuncFailed {
+        template: TemplateAddr,
+        app: AppAddr,
+        func: String,
+        message: String,
+    },
+    UpgradeNotAuthorized {
+        app: AppAddr,
+    },
+    IncompatibleLayout {
+        template: TemplateAddr,
+        app: AppAddr,
+    },
+    Reverted {
+        #[serde(with = "crate::serde_hex::bytes")]
+        data: Vec<u8>,
+    },
+    StorageFailed {
+        app: AppAddr,
+        message: String,
+    },
+    InvalidTransaction {
+        message: String,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl From<ReceiptError> for ErrorRepr {
+    fn from(err: ReceiptError) -> Self {
+        match err {
+            ReceiptError::OOG => ErrorRepr::Oog,
+            ReceiptError::TemplateNotFound(template) => ErrorRepr::TemplateNotFound { template },
+            ReceiptError::AppNotFound(app) => ErrorRepr::AppNotFound { app },
+            ReceiptError::CompilationFailed {
+                app_addr,
+                template_addr,
+                msg,
+            } => ErrorRepr::CompilationFailed {
+                template: template_addr,
+                app: app_addr,
+                message: msg,
+            },
+            ReceiptError::InstantiationFailed {
+                app_addr,
+                template_addr,
+                msg,
+            } => ErrorRepr::InstantiationFailed {
+                template: template_addr,
+                app: app_addr,
+                message: msg,
+            },
+            ReceiptError::FuncNotFound {
+                app_addr,
+                template_addr,
+                func,
+            } => ErrorRepr::FuncNotFound {
+                template: template_addr,
+                app: app_addr,
+                func,
+            },
+            ReceiptError::FuncFailed {
+                app_addr,
+                template_addr,
+                func,
+                msg,
+            } => ErrorRepr::FuncFailed {
+                template: template_addr,
+                app: app_addr,
+                func,
+                message: msg,
+            },
+            ReceiptError::UpgradeNotAuthorized { app_addr } => {
+                ErrorRepr::UpgradeNotAuthorized { app: app_addr }
+            }
+            ReceiptError::IncompatibleLayout {
+                app_addr,
+                template_addr,
+            } => ErrorRepr::IncompatibleLayout {
+                template: template_addr,
+                app: app_addr,
+            },
+            ReceiptError::Reverted { data } => ErrorRepr::Reverted { data },
+            ReceiptError::StorageFailed { app_addr, msg } => ErrorRepr::StorageFailed {
+                app: app_addr,
+                message: msg,
+            },
+            ReceiptError::InvalidTransaction { msg } => {
+                ErrorRepr::InvalidTransaction { message: msg }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ErrorRepr> for ReceiptError {
+    fn from(repr: ErrorRepr) -> Self {
+        match repr {
+            ErrorRepr::Oog => ReceiptError::OOG,
+            ErrorRepr::TemplateNotFound { template } => ReceiptError::TemplateNotFound(template),
+            ErrorRepr::AppNotFound { app } => ReceiptError::AppNotFound(app),
+            ErrorRepr::CompilationFailed {
+                template,
+                app,
+                message,
+            } => ReceiptError::CompilationFailed {
+                app_addr: app,
+                template_addr: template,
+                msg: message,
+            },
+            ErrorRepr::InstantiationFailed {
+                template,
+                app,
+                message,
+            } => ReceiptError::InstantiationFailed {
+                app_addr: app,
+                template_addr: template,
+                msg: message,
+            },
+            ErrorRepr::FuncNotFound {
+                template,
+                app,
+                func,
+            } => ReceiptError::FuncNotFound {
+                app_addr: app,
+                template_addr: template,
+                func,
+            },
+            ErrorRepr::FuncFailed {
+                template,
+                app,
+                func,
+                message,
+            } => ReceiptError::FuncFailed {
+                app_addr: app,
+                template_addr: template,
+                func,
+                msg: message,
+            },
+            ErrorRepr::UpgradeNotAuthorized { app } => {
+                ReceiptError::UpgradeNotAuthorized { app_addr: app }
+            }
+            ErrorRepr::IncompatibleLayout { template, app } => ReceiptError::IncompatibleLayout {
+                app_addr: app,
+                template_addr: template,
+            },
+            ErrorRepr::Reverted { data } => ReceiptError::Reverted { data },
+            ErrorRepr::StorageFailed { app, message } => ReceiptError::StorageFailed {
+                app_addr: app,
+                msg: message,
+            },
+            ErrorRepr::InvalidTransaction { message } => {
+                ReceiptError::InvalidTransaction { msg: message }
+            }
+        }
+    }
 }
@@ -3,20 +3,45 @@ use crate::{gas::MaybeGas, State, WasmValue};
 
 /// Runtime transaction execution receipt
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct ExecReceipt {
     /// Whether transaction succedded or not.
     pub success: bool,
 
     /// The execution error in case execution failed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub error: Option<ReceiptError>,
 
     /// The new app `State` if execution succedded.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub new_state: Option<State>,
 
     /// Returned the data
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::serde_hex::option_bytes"
+        )
+    )]
     pub returndata: Option<Vec<u8>>,
 
     /// The amount of gas used.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "MaybeGas::new", skip_serializing_if = "MaybeGas::is_none")
+    )]
     pub gas_used: MaybeGas,
 
     /// logged entries during execution of app's transaction
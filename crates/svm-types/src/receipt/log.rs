@@ -1,23 +1,146 @@
 use std::fmt;
 
+/// A log entry emitted by an executed App.
+///
+/// With the `serde` feature, a log is represented as `{ msg, code, topics }`
+/// and an event as `{ data, topics }` (`msg`, `data` and each topic are hex strings).
 #[derive(PartialEq, Clone)]
-pub struct Log {
-    pub msg: Vec<u8>,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate", into = "LogRepr", try_from = "LogRepr")
+)]
+pub enum Log {
+    /// A message logged by the App (see the `svm_log` vmcall).
+    Message {
+        /// The log's message (an arbitrary blob of bytes).
+        msg: Vec<u8>,
 
-    pub code: u8,
+        /// The log's code.
+        code: u8,
+
+        /// Indexed fields of the log entry, used for filtering logs.
+        topics: Vec<Vec<u8>>,
+    },
+
+    /// An event emitted by the App (see the `svm_emit` vmcall).
+    Event {
+        /// Indexed fields of the event (i.e its name), used for filtering logs.
+        topics: Vec<Vec<u8>>,
+
+        /// The event's ABI-encoded data.
+        data: Vec<u8>,
+    },
+}
+
+impl Log {
+    /// New `Log::Message` without any `topics`.
+    pub fn new(msg: Vec<u8>, code: u8) -> Self {
+        Log::Message {
+            msg,
+            code,
+            topics: Vec::new(),
+        }
+    }
+
+    /// New `Log::Event`.
+    pub fn event(topics: Vec<Vec<u8>>, data: Vec<u8>) -> Self {
+        Log::Event { topics, data }
+    }
+
+    /// Returns whether the `Log` is an event.
+    pub fn is_event(&self) -> bool {
+        matches!(self, Log::Event { .. })
+    }
+
+    /// Borrows the log's `topics`.
+    pub fn topics(&self) -> &[Vec<u8>] {
+        match self {
+            Log::Message { topics, .. } | Log::Event { topics, .. } => topics,
+        }
+    }
 }
 
 impl fmt::Debug for Log {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Log")
-            .field("message", &fmt_msg(self))
-            .field("code", &self.code)
-            .finish()
+        match self {
+            Log::Message { msg, code, topics } => f
+                .debug_struct("Log")
+                .field("message", &String::from_utf8_lossy(msg))
+                .field("code", code)
+                .field("topics", topics)
+                .finish(),
+            Log::Event { topics, data } => f
+                .debug_struct("Event")
+                .field("topics", topics)
+                .field("data", data)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde_crate::Serialize, serde_crate::Deserialize)]
+#[serde(crate = "serde_crate")]
+struct LogRepr {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_hex::option_bytes"
+    )]
+    msg: Option<Vec<u8>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_hex::option_bytes"
+    )]
+    data: Option<Vec<u8>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code: Option<u8>,
+
+    #[serde(with = "crate::serde_hex::vec_bytes")]
+    topics: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Log> for LogRepr {
+    fn from(log: Log) -> Self {
+        match log {
+            Log::Message { msg, code, topics } => Self {
+                msg: Some(msg),
+                data: None,
+                code: Some(code),
+                topics,
+            },
+            Log::Event { topics, data } => Self {
+                msg: None,
+                data: Some(data),
+                code: None,
+                topics,
+            },
+        }
     }
 }
 
-fn fmt_msg(log: &Log) -> String {
-    let bytes = log.msg.clone();
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<LogRepr> for Log {
+    type Error = String;
 
-    unsafe { String::from_utf8_unchecked(bytes) }
+    fn try_from(repr: LogRepr) -> Result<Self, Self::Error> {
+        match (repr.msg, repr.code, repr.data) {
+            (Some(msg), Some(code), None) => Ok(Log::Message {
+                msg,
+                code,
+                topics: repr.topics,
+            }),
+            (None, None, Some(data)) => Ok(Log::Event {
+                topics: repr.topics,
+                data,
+            }),
+            (_, _, Some(..)) => Err("an event must have only a `data` field".into()),
+            (_, _, None) => Err("a log must have only a `msg` and a `code` field".into()),
+        }
+    }
 }
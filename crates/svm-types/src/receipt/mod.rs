@@ -1,15 +1,19 @@
+mod bloom;
 mod deploy_template;
 mod exec_app;
 mod log;
 mod spawn_app;
+mod upgrade_app;
 
 mod error;
 pub use error::ReceiptError;
 
+pub use bloom::Bloom;
 pub use deploy_template::TemplateReceipt;
 pub use exec_app::ExecReceipt;
 pub use log::Log;
 pub use spawn_app::{make_spawn_app_receipt, SpawnAppReceipt};
+pub use upgrade_app::{make_upgrade_app_receipt, UpgradeAppReceipt};
 
 use crate::{gas::MaybeGas, WasmValue};
 
@@ -23,6 +27,9 @@ pub enum Receipt<'a> {
 
     /// Borrows a `ExecReceipt`.
     ExecApp(&'a ExecReceipt),
+
+    /// Borrows a `UpgradeAppReceipt`.
+    UpgradeApp(&'a UpgradeAppReceipt),
 }
 
 impl<'a> Receipt<'a> {
@@ -32,6 +39,7 @@ impl<'a> Receipt<'a> {
             Self::DeployTemplate(r) => r.success,
             Self::SpawnApp(r) => r.success,
             Self::ExecApp(r) => r.success,
+            Self::UpgradeApp(r) => r.success,
         }
     }
 
@@ -41,6 +49,7 @@ impl<'a> Receipt<'a> {
             Self::DeployTemplate(..) => unreachable!(),
             Self::SpawnApp(r) => r.get_returndata(),
             Self::ExecApp(r) => r.get_returndata(),
+            Self::UpgradeApp(r) => r.get_returndata(),
         }
     }
 
@@ -50,6 +59,7 @@ impl<'a> Receipt<'a> {
             Self::DeployTemplate(r) => r.gas_used,
             Self::SpawnApp(r) => r.gas_used,
             Self::ExecApp(r) => r.gas_used,
+            Self::UpgradeApp(r) => r.gas_used,
         }
     }
 
@@ -59,18 +69,30 @@ impl<'a> Receipt<'a> {
             Self::DeployTemplate(r) => r.error.as_ref().unwrap(),
             Self::SpawnApp(r) => r.error.as_ref().unwrap(),
             Self::ExecApp(r) => r.error.as_ref().unwrap(),
+            Self::UpgradeApp(r) => r.error.as_ref().unwrap(),
         }
     }
 }
 
 /// Owned Receipt
+///
+/// With the `serde` feature, a receipt is represented as an object tagged by its `type`
+/// (i.e `{ type: 'exec-app', success: true, new_state: 'A0A0...', ... }`).
+/// Fields without a value (such as the `error` of a successful receipt) are omitted.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate", tag = "type", rename_all = "kebab-case")
+)]
 pub enum ReceiptOwned {
     DeployTemplate(TemplateReceipt),
 
     SpawnApp(SpawnAppReceipt),
 
     ExecApp(ExecReceipt),
+
+    UpgradeApp(UpgradeAppReceipt),
 }
 
 impl ReceiptOwned {
@@ -79,6 +101,7 @@ impl ReceiptOwned {
             ReceiptOwned::DeployTemplate(receipt) => receipt.success,
             ReceiptOwned::SpawnApp(receipt) => receipt.success,
             ReceiptOwned::ExecApp(receipt) => receipt.success,
+            ReceiptOwned::UpgradeApp(receipt) => receipt.success,
         }
     }
 
@@ -103,19 +126,33 @@ impl ReceiptOwned {
         }
     }
 
+    pub fn into_upgrade_app(self) -> UpgradeAppReceipt {
+        match self {
+            ReceiptOwned::UpgradeApp(r) => r,
+            _ => unreachable!(),
+        }
+    }
+
     pub fn get_logs(&self) -> &[Log] {
         match self {
             ReceiptOwned::DeployTemplate(receipt) => receipt.get_logs(),
             ReceiptOwned::SpawnApp(receipt) => receipt.get_logs(),
             ReceiptOwned::ExecApp(receipt) => receipt.get_logs(),
+            ReceiptOwned::UpgradeApp(receipt) => receipt.get_logs(),
         }
     }
 
+    /// Returns the bloom filter of the receipt's logs `topics`.
+    pub fn bloom(&self) -> Bloom {
+        Bloom::from_logs(self.get_logs())
+    }
+
     pub fn get_error(&self) -> &ReceiptError {
         match self {
             ReceiptOwned::DeployTemplate(receipt) => receipt.get_error(),
             ReceiptOwned::SpawnApp(receipt) => receipt.get_error(),
             ReceiptOwned::ExecApp(receipt) => receipt.get_error(),
+            ReceiptOwned::UpgradeApp(receipt) => receipt.get_error(),
         }
     }
 }
@@ -3,23 +3,52 @@ use crate::{gas::MaybeGas, AppAddr, State};
 
 /// Returned Receipt after spawning an App.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct SpawnAppReceipt {
     /// whether spawn succedded or not
     pub success: bool,
 
     /// the error in case spawning failed
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub error: Option<ReceiptError>,
 
     /// the spawned app `Address`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "app", default, skip_serializing_if = "Option::is_none")
+    )]
     pub app_addr: Option<AppAddr>,
 
     /// the spawned app initial state (after executing its ctor)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "state", default, skip_serializing_if = "Option::is_none")
+    )]
     pub init_state: Option<State>,
 
     /// returned ctor data
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::serde_hex::option_bytes"
+        )
+    )]
     pub returndata: Option<Vec<u8>>,
 
     /// The amount of gas used
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "MaybeGas::new", skip_serializing_if = "MaybeGas::is_none")
+    )]
     pub gas_used: MaybeGas,
 
     /// logged entries during spawn-app's ctor running
@@ -0,0 +1,132 @@
+use crate::receipt::{ExecReceipt, Log, ReceiptError};
+use crate::{gas::MaybeGas, State, TemplateAddr};
+
+/// Returned Receipt after upgrading an App.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct UpgradeAppReceipt {
+    /// whether upgrade succedded or not
+    pub success: bool,
+
+    /// the error in case upgrading failed
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<ReceiptError>,
+
+    /// the `Address` of the `AppTemplate` the app is now bound to
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "template", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub template_addr: Option<TemplateAddr>,
+
+    /// the app new state (after running its migration)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub new_state: Option<State>,
+
+    /// returned migration data
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::serde_hex::option_bytes"
+        )
+    )]
+    pub returndata: Option<Vec<u8>>,
+
+    /// The amount of gas used
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "MaybeGas::new", skip_serializing_if = "MaybeGas::is_none")
+    )]
+    pub gas_used: MaybeGas,
+
+    /// logged entries during upgrade-app's migration running
+    pub logs: Vec<Log>,
+}
+
+impl UpgradeAppReceipt {
+    /// Creates a `UpgradeAppReceipt` for reaching reaching `Out-of-Gas`.
+    pub fn new_oog(logs: Vec<Log>) -> Self {
+        Self::from_err(ReceiptError::OOG, logs)
+    }
+
+    pub fn from_err(error: ReceiptError, logs: Vec<Log>) -> Self {
+        Self {
+            success: false,
+            error: Some(error),
+            template_addr: None,
+            new_state: None,
+            returndata: None,
+            gas_used: MaybeGas::new(),
+            logs,
+        }
+    }
+
+    /// Returns upgraded-app `Error`. Panics if upgrading has *not* failed.
+    pub fn get_error(&self) -> &ReceiptError {
+        self.error.as_ref().unwrap()
+    }
+
+    /// Returns upgraded-app new template `Address`. Panics if upgrading has failed.
+    pub fn get_template_addr(&self) -> &TemplateAddr {
+        self.template_addr.as_ref().unwrap()
+    }
+
+    /// Returns upgraded-app new `State`. Panics if upgrading has failed.
+    pub fn get_new_state(&self) -> &State {
+        self.new_state.as_ref().unwrap()
+    }
+
+    /// Returns migration results. Panics if upgrading has failed.
+    pub fn get_returndata(&self) -> &Vec<u8> {
+        self.returndata.as_ref().unwrap()
+    }
+
+    /// Returns upgraded-app gas-used
+    pub fn get_gas_used(&self) -> MaybeGas {
+        self.gas_used
+    }
+
+    pub fn get_logs(&self) -> &[Log] {
+        &self.logs
+    }
+
+    pub fn take_logs(&mut self) -> Vec<Log> {
+        std::mem::take(&mut self.logs)
+    }
+}
+
+#[allow(missing_docs)]
+pub fn make_upgrade_app_receipt(
+    mut migration_receipt: ExecReceipt,
+    template_addr: &TemplateAddr,
+) -> UpgradeAppReceipt {
+    let logs = migration_receipt.take_logs();
+
+    if migration_receipt.success {
+        UpgradeAppReceipt {
+            success: true,
+            error: None,
+            template_addr: Some(template_addr.clone()),
+            new_state: migration_receipt.new_state,
+            returndata: migration_receipt.returndata,
+            gas_used: migration_receipt.gas_used,
+            logs,
+        }
+    } else {
+        let error = migration_receipt.error.unwrap();
+
+        UpgradeAppReceipt::from_err(error, logs)
+    }
+}
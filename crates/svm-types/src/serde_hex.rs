@@ -0,0 +1,419 @@
+//! `serde` support for `svm-types` (enabled by the `serde` feature).
+//!
+//! Every binary value (addresses, states, keys, signatures, code, calldata, etc.)
+//! is serialized as an upper-case hex string without a `0x` prefix.
+//! This is the same representation used by `svm_codec::api::json`.
+
+use serde_crate::de::{self, Deserializer};
+use serde_crate::{Deserialize, Serialize, Serializer};
+
+use crate::gas::MaybeGas;
+use crate::receipt::Bloom;
+use crate::{Address, AddressOf, PublicKey, Signature, State};
+
+/// Encodes `bytes` as an upper-case hex string.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    svm_common::fmt::fmt_hex(bytes, "")
+}
+
+/// Decodes a hex string (upper-case or lower-case) into bytes.
+pub(crate) fn decode(s: &str) -> Result<Vec<u8>, String> {
+    if !s.is_ascii() || s.len() % 2 != 0 {
+        return Err(format!("invalid hex string `{}`", s));
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| format!("invalid hex string `{}`", s))
+}
+
+fn deserialize_hex<'de, D>(de: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(de)?;
+
+    decode(&s).map_err(de::Error::custom)
+}
+
+fn deserialize_fixed<'de, D>(de: D, len: usize) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let bytes = deserialize_hex(de)?;
+
+    if bytes.len() != len {
+        let expected = format!("{} bytes", len);
+
+        return Err(de::Error::invalid_length(bytes.len(), &expected.as_str()));
+    }
+
+    Ok(bytes)
+}
+
+/// `#[serde(with = "crate::serde_hex::bytes")]` for `Vec<u8>` fields.
+pub(crate) mod bytes {
+    use super::*;
+
+    pub fn serialize<S>(bytes: &[u8], ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.serialize_str(&encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_hex(de)
+    }
+}
+
+/// `#[serde(with = "crate::serde_hex::option_bytes")]` for `Option<Vec<u8>>` fields.
+pub(crate) mod option_bytes {
+    use super::*;
+
+    pub fn serialize<S>(bytes: &Option<Vec<u8>>, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match bytes {
+            Some(bytes) => ser.serialize_some(&encode(bytes)),
+            None => ser.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<Option<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: Option<String> = Deserialize::deserialize(de)?;
+
+        match s {
+            Some(s) => decode(&s).map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+/// `#[serde(with = "crate::serde_hex::vec_bytes")]` for `Vec<Vec<u8>>` fields.
+pub(crate) mod vec_bytes {
+    use super::*;
+
+    pub fn serialize<S>(items: &[Vec<u8>], ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.collect_seq(items.iter().map(|bytes| encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<Vec<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let items: Vec<String> = Deserialize::deserialize(de)?;
+
+        items
+            .iter()
+            .map(|s| decode(s).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+macro_rules! impl_hex_serde {
+    ($primitive: ident, $byte_count: expr) => {
+        impl Serialize for $primitive {
+            fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                ser.serialize_str(&encode(self.as_slice()))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $primitive {
+            fn deserialize<D>(de: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let bytes = deserialize_fixed(de, $byte_count)?;
+
+                Ok($primitive::from(&bytes[..]))
+            }
+        }
+    };
+}
+
+impl_hex_serde!(Address, Address::len());
+impl_hex_serde!(State, State::len());
+impl_hex_serde!(PublicKey, PublicKey::len());
+impl_hex_serde!(Signature, Signature::len());
+impl_hex_serde!(Bloom, Bloom::BYTES);
+
+/// An `AddressOf<T>` is serialized just like its inner `Address`.
+impl<T> Serialize for AddressOf<T> {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner().serialize(ser)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for AddressOf<T> {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let addr = Address::deserialize(de)?;
+
+        Ok(AddressOf::new(addr))
+    }
+}
+
+/// A `MaybeGas` is serialized as a number (`-1` when there is no gas).
+impl Serialize for MaybeGas {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let gas = if self.is_some() {
+            self.unwrap() as i64
+        } else {
+            -1
+        };
+
+        gas.serialize(ser)
+    }
+}
+
+impl<'de> Deserialize<'de> for MaybeGas {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let gas: i64 = Deserialize::deserialize(de)?;
+
+        match gas {
+            -1 => Ok(MaybeGas::new()),
+            gas if gas >= 0 => Ok(MaybeGas::with(gas as u64)),
+            _ => {
+                let msg = format!("value `{}` should be `-1` or non-negative", gas);
+
+                Err(de::Error::custom(msg))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn hex_decode() {
+        assert_eq!(decode("10aB").unwrap(), vec![0x10, 0xAB]);
+        assert_eq!(decode("").unwrap(), Vec::<u8>::new());
+
+        assert!(decode("1").is_err());
+        assert!(decode("ZZ").is_err());
+        assert!(decode("ñ1").is_err());
+    }
+
+    #[test]
+    fn address_serde() {
+        let addr = Address::from([0x10; 20]);
+
+        let json = serde_json::to_value(&addr).unwrap();
+        assert_eq!(json, json!("1010101010101010101010101010101010101010"));
+
+        let decoded: Address = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, addr);
+
+        let too_short = json!("1010");
+        assert!(serde_json::from_value::<Address>(too_short).is_err());
+    }
+
+    #[test]
+    fn address_of_serde() {
+        let addr = crate::TemplateAddr::new(Address::from([0xAB; 20]));
+
+        let json = serde_json::to_value(&addr).unwrap();
+        assert_eq!(json, json!("ABABABABABABABABABABABABABABABABABABABAB"));
+
+        let decoded: crate::TemplateAddr = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, addr);
+    }
+
+    #[test]
+    fn state_serde() {
+        let state = State::from([0xFF; 32]);
+
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json, json!("FF".repeat(32)));
+
+        let decoded: State = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, state);
+    }
+
+    #[test]
+    fn maybe_gas_serde() {
+        let gas = MaybeGas::with(100);
+        assert_eq!(serde_json::to_value(&gas).unwrap(), json!(100));
+
+        let gas = MaybeGas::new();
+        assert_eq!(serde_json::to_value(&gas).unwrap(), json!(-1));
+
+        let decoded: MaybeGas = serde_json::from_value(json!(100)).unwrap();
+        assert_eq!(decoded, MaybeGas::with(100));
+
+        let decoded: MaybeGas = serde_json::from_value(json!(-1)).unwrap();
+        assert!(decoded.is_none());
+
+        assert!(serde_json::from_value::<MaybeGas>(json!(-2)).is_err());
+    }
+
+    #[test]
+    fn exec_receipt_serde() {
+        use crate::receipt::{ExecReceipt, Log, ReceiptError};
+
+        let receipt = ExecReceipt {
+            success: true,
+            error: None,
+            new_state: Some(State::from([0x10; 32])),
+            returndata: Some(vec![0xAB, 0xCD]),
+            gas_used: MaybeGas::with(10),
+            logs: vec![Log::new(b"Hello".to_vec(), 200)],
+        };
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "success": true,
+                "new_state": "10".repeat(32),
+                "returndata": "ABCD",
+                "gas_used": 10,
+                "logs": [{ "msg": "48656C6C6F", "code": 200, "topics": [] }]
+            })
+        );
+
+        let decoded: ExecReceipt = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, receipt);
+
+        let receipt = ExecReceipt::new_oog(Vec::new());
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["error"], json!({ "type": "oog" }));
+
+        assert_eq!(
+            json,
+            json!({ "success": false, "error": { "type": "oog" }, "logs": [] })
+        );
+
+        let decoded: ExecReceipt = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.error, Some(ReceiptError::OOG));
+    }
+
+    #[test]
+    fn receipt_owned_serde() {
+        use crate::receipt::{ReceiptOwned, TemplateReceipt};
+
+        let addr = crate::TemplateAddr::new(Address::from([0x10; 20]));
+        let receipt = ReceiptOwned::DeployTemplate(TemplateReceipt::new(addr, MaybeGas::with(10)));
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "type": "deploy-template",
+                "success": true,
+                "template": "10".repeat(20),
+                "gas_used": 10,
+                "logs": []
+            })
+        );
+
+        let decoded: ReceiptOwned = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, receipt);
+    }
+
+    #[test]
+    fn event_serde() {
+        use crate::receipt::Log;
+
+        let event = Log::event(vec![vec![0xAB; 2]], vec![0xFF, 0x00]);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json, json!({ "data": "FF00", "topics": ["ABAB"] }));
+
+        let decoded: Log = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, event);
+
+        let invalid = json!({ "data": "FF00", "code": 255, "topics": ["ABAB"] });
+        assert!(serde_json::from_value::<Log>(invalid).is_err());
+
+        let invalid = json!({ "msg": "48656C6C6F", "topics": ["ABAB"] });
+        assert!(serde_json::from_value::<Log>(invalid).is_err());
+    }
+
+    #[test]
+    fn receipt_error_serde() {
+        use crate::receipt::ReceiptError;
+        use crate::{AppAddr, TemplateAddr};
+
+        let err = ReceiptError::FuncFailed {
+            app_addr: AppAddr::new(Address::from([0x10; 20])),
+            template_addr: TemplateAddr::new(Address::from([0x20; 20])),
+            func: "run".to_string(),
+            msg: "trapped".to_string(),
+        };
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "type": "function-failed",
+                "app": "10".repeat(20),
+                "template": "20".repeat(20),
+                "func": "run",
+                "message": "trapped"
+            })
+        );
+
+        let decoded: ReceiptError = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, err);
+
+        let json = json!({ "type": "template-not-found", "template": "20".repeat(20) });
+        let decoded: ReceiptError = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            decoded,
+            ReceiptError::TemplateNotFound(TemplateAddr::new(Address::from([0x20; 20])))
+        );
+    }
+
+    #[test]
+    fn app_template_serde() {
+        use svm_layout::DataLayout;
+
+        use crate::AppTemplate;
+
+        let template = AppTemplate {
+            version: 0,
+            name: "My Template".to_string(),
+            code: vec![0x0C, 0x00, 0x0D, 0x0E],
+            data: DataLayout::from(&[4, 8][..]),
+        };
+
+        let json = serde_json::to_value(&template).unwrap();
+        assert_eq!(json["code"], json!("0C000D0E"));
+        assert_eq!(json["data"]["vars"], json!([[0, 4], [4, 8]]));
+
+        let decoded: AppTemplate = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, template);
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+use svm_layout::DataLayout;
+
+use crate::State;
+
+/// A portable snapshot of an App's storage at a given `State`.
+///
+/// The `i-th` item of `vars` holds the raw value of variable `i` of `layout`.
+#[derive(PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct Snapshot {
+    /// The App's storage `DataLayout`
+    pub layout: DataLayout,
+
+    /// The `State` the snapshot has been taken at
+    pub state: State,
+
+    /// The variables raw values (ordered by `VarId`)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec_bytes"))]
+    pub vars: Vec<Vec<u8>>,
+}
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("state", &self.state.fmt(4, 4, " "))
+            .field("layout", &self.layout)
+            .field("#vars", &self.vars.len())
+            .finish()
+    }
+}
+
+/// Errors raised while exporting, importing or encoding a `Snapshot`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SnapshotError {
+    /// The storage has uncommitted changes (export requires a clean storage)
+    UncommittedChanges,
+
+    /// The snapshot `DataLayout` doesn't match the App's `DataLayout`
+    LayoutMismatch,
+
+    /// The number of variable values doesn't match the number of layout variables
+    VarsCountMismatch {
+        /// The number of variables declared by the layout
+        expected: usize,
+
+        /// The number of values carried by the snapshot
+        actual: usize,
+    },
+
+    /// A variable value length doesn't match its layout length
+    VarLengthMismatch {
+        /// The variable index
+        var_id: u32,
+
+        /// The variable length declared by the layout
+        expected: u32,
+
+        /// The length of the variable value
+        actual: usize,
+    },
+
+    /// The snapshot has more variables than the wire format can encode
+    TooManyVars {
+        /// The number of variables
+        count: usize,
+
+        /// The maximum number of variables allowed
+        max: usize,
+    },
+
+    /// A variable is longer than the wire format can encode
+    VarTooLong {
+        /// The variable index
+        var_id: u32,
+
+        /// The variable length
+        len: u32,
+
+        /// The maximum variable length allowed
+        max: u32,
+    },
+
+    /// The underlying key-value store has failed (holds its error message)
+    StorageFailed(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::UncommittedChanges => {
+                write!(fmt, "Storage has uncommitted changes")
+            }
+            SnapshotError::LayoutMismatch => {
+                write!(fmt, "Snapshot layout doesn't match the App's layout")
+            }
+            SnapshotError::VarsCountMismatch { expected, actual } => write!(
+                fmt,
+                "Snapshot has {} variables values (expected: {})",
+                actual, expected
+            ),
+            SnapshotError::VarLengthMismatch {
+                var_id,
+                expected,
+                actual,
+            } => write!(
+                fmt,
+                "Variable #{} value is of {} bytes (expected: {})",
+                var_id, actual, expected
+            ),
+            SnapshotError::TooManyVars { count, max } => write!(
+                fmt,
+                "Snapshot has too many variables ({}, max: {})",
+                count, max
+            ),
+            SnapshotError::VarTooLong { var_id, len, max } => write!(
+                fmt,
+                "Variable #{} is too long ({} bytes, max: {})",
+                var_id, len, max
+            ),
+            SnapshotError::StorageFailed(msg) => write!(fmt, "Storage has failed: {}", msg),
+        }
+    }
+}
@@ -4,14 +4,21 @@ use crate::App;
 
 /// Struct representation of the parsed raw Spawn-App.
 #[derive(PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct SpawnApp {
     /// Holds all `SpawnApp` non-ctor_name related data.
+    #[cfg_attr(feature = "serde", serde(flatten))]
     pub app: App,
 
     /// ctor function name
     pub ctor_name: String,
 
     /// calldata
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
     pub calldata: Vec<u8>,
 }
 
@@ -5,9 +5,15 @@ use svm_layout::DataLayout;
 /// An in-memory representation of an app-template.
 #[allow(missing_docs)]
 #[derive(PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
 pub struct AppTemplate {
     pub version: u32,
     pub name: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
     pub code: Vec<u8>,
     pub data: DataLayout,
 }
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::{Address, AppAddr, TemplateAddr};
+
+/// An in-memory representation of an upgrade-app transaction.
+///
+/// Upgrading binds an existing `App` to a new `AppTemplate`.
+/// The `App` storage is kept, so the new template's `DataLayout` must be compatible with the current one.
+#[derive(PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize, serde_crate::Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct UpgradeApp {
+    /// The upgrade-app transaction version.
+    pub version: u32,
+
+    /// The `App` account address
+    pub app: AppAddr,
+
+    /// The `Address` of the `AppTemplate` the `App` is being upgraded to
+    pub template: TemplateAddr,
+
+    /// Migration function to run against the `App` storage (exported by the new template)
+    pub migration: Option<String>,
+
+    /// Migration function calldata
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
+    pub calldata: Vec<u8>,
+}
+
+impl fmt::Debug for UpgradeApp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let version = self.fmt_version();
+        let app = self.fmt_app();
+        let template = self.fmt_template();
+        let migration = self.fmt_migration();
+        let calldata = self.fmt_calldata();
+
+        let msg = [version, app, template, migration, calldata];
+
+        write!(f, "{}", msg.join("\n"))
+    }
+}
+
+impl UpgradeApp {
+    fn fmt_version(&self) -> String {
+        format!("Version: {}", self.version)
+    }
+
+    fn fmt_app(&self) -> String {
+        format!("App: {}", UpgradeApp::fmt_address(self.app.inner()))
+    }
+
+    fn fmt_template(&self) -> String {
+        format!("Template: {}", UpgradeApp::fmt_address(self.template.inner()))
+    }
+
+    fn fmt_migration(&self) -> String {
+        match self.migration {
+            Some(ref func) => format!("migration: {}", func),
+            None => "migration: None".to_string(),
+        }
+    }
+
+    fn fmt_calldata(&self) -> String {
+        format!(
+            "calldata: {:?}",
+            self.calldata.iter().take(4).collect::<Vec<_>>()
+        )
+    }
+
+    fn fmt_address(addr: &Address) -> String {
+        addr.fmt(4, 4, " ")
+    }
+}
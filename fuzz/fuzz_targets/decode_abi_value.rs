@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use svm_abi_decoder::{Cursor, Decoder};
+
+fuzz_target!(|data: &[u8]| {
+    let decoder = Decoder::new();
+    let mut cursor = Cursor::new(data);
+
+    while !cursor.is_eof() {
+        if decoder.decode_value(&mut cursor).is_err() {
+            break;
+        }
+    }
+});
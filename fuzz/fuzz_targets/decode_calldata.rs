@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use serde_json::json;
+
+fuzz_target!(|data: &[u8]| {
+    let calldata: String = data.iter().map(|byte| format!("{:02X}", byte)).collect();
+    let json = json!({ "calldata": calldata });
+
+    let _ = svm_codec::api::json::decode_calldata(&json);
+});
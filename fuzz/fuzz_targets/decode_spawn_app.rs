@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use svm_codec::api::raw;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = svm_fuzz::with_iter(data, raw::decode_spawn_app);
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use svm_abi_decoder::{Cursor, Decoder};
+use svm_fuzz::ArbitraryValue;
+
+fuzz_target!(|values: Vec<ArbitraryValue>| {
+    let bytes = svm_fuzz::encode_values(&values);
+
+    let decoder = Decoder::new();
+    let mut cursor = Cursor::new(&bytes);
+
+    for expected in values.iter() {
+        let actual = decoder.decode_value(&mut cursor).unwrap();
+
+        assert_eq!(&actual, &expected.0);
+    }
+
+    assert!(cursor.is_eof());
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use svm_codec::api::raw;
+use svm_fuzz::ArbitraryTemplate;
+
+fuzz_target!(|input: ArbitraryTemplate| {
+    let ArbitraryTemplate(expected) = input;
+
+    let bytes = svm_fuzz::encode_with(|w| raw::encode_deploy_template(&expected, w));
+    let actual = svm_fuzz::with_iter(&bytes, raw::decode_deploy_template).unwrap();
+
+    assert_eq!(actual, expected);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use svm_codec::api::raw;
+use svm_fuzz::ArbitraryTransaction;
+
+fuzz_target!(|input: ArbitraryTransaction| {
+    let ArbitraryTransaction(expected) = input;
+
+    let bytes = svm_fuzz::encode_with(|w| raw::encode_exec_app(&expected, w));
+    let actual = svm_fuzz::with_iter(&bytes, raw::decode_exec_app).unwrap();
+
+    assert_eq!(actual, expected);
+});
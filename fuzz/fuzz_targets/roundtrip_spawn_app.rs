@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use svm_codec::api::raw;
+use svm_fuzz::ArbitrarySpawnApp;
+
+fuzz_target!(|input: ArbitrarySpawnApp| {
+    let ArbitrarySpawnApp(expected) = input;
+
+    let bytes = svm_fuzz::encode_with(|w| raw::encode_spawn_app(&expected, w));
+    let actual = svm_fuzz::with_iter(&bytes, raw::decode_spawn_app).unwrap();
+
+    assert_eq!(actual, expected);
+});
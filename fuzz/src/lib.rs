@@ -0,0 +1,303 @@
+//! Structured `arbitrary` generators used by the fuzz targets (see `fuzz_targets/`).
+//!
+//! Each generator outputs only values the encoders accept (i.e lengths fit a `varuint14`,
+//! versions are supported by the codec, etc.), so that `encode` followed by `decode`
+//! is expected to return the original value.
+
+#![allow(missing_docs)]
+
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use svm_layout::{DataLayout, DataLayoutBuilder, VarType};
+use svm_nibble::{NibbleIter, NibbleWriter};
+use svm_sdk_types::value::{Composite, Primitive, Value};
+use svm_types::{
+    Address, App, AppAddr, AppTemplate, AppTransaction, SpawnApp, TemplateAddr, TxKind,
+};
+
+/// The maximum length of names (template, app, function, schema variables).
+pub const MAX_NAME_LEN: usize = 64;
+
+/// The maximum length of a `varuint14` encoded length.
+pub const MAX_VARUINT14: usize = (1 << 14) - 1;
+
+/// The maximum number of variables of a generated `DataLayout`.
+pub const MAX_LAYOUT_VARS: usize = 32;
+
+/// The maximum number of items of a generated ABI array (or fields of a tuple).
+/// It's above 255 so that arrays using the `ARR_N` marker are generated as well.
+pub const MAX_ARRAY_ITEMS: usize = 300;
+
+/// The maximum nesting level of generated ABI arrays and tuples.
+pub const MAX_ARRAY_DEPTH: usize = 3;
+
+/// The maximum length of generated ABI `Bytes` and `String` values.
+pub const MAX_BLOB_LEN: usize = 300;
+
+/// Feeds `data` into a `NibbleIter` and calls `f` with it.
+pub fn with_iter<T>(data: &[u8], f: impl FnOnce(&mut NibbleIter) -> T) -> T {
+    let mut iter = NibbleIter::new(data);
+
+    f(&mut iter)
+}
+
+/// Runs `encode` over a fresh `NibbleWriter` and returns the written bytes.
+pub fn encode_with(encode: impl FnOnce(&mut NibbleWriter)) -> Vec<u8> {
+    let mut w = NibbleWriter::new();
+
+    encode(&mut w);
+
+    w.into_bytes()
+}
+
+fn arbitrary_version(u: &mut Unstructured, kind: TxKind) -> Result<u32> {
+    let versions = svm_codec::api::raw::supported_versions(kind);
+
+    u.choose(&versions).map(|v| *v)
+}
+
+/// Generates a name. Names can't be empty (the decoders reject empty strings).
+fn arbitrary_name(u: &mut Unstructured) -> Result<String> {
+    let name: String = u.arbitrary()?;
+
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(Error::IncorrectFormat);
+    }
+
+    Ok(name)
+}
+
+fn arbitrary_bytes(u: &mut Unstructured, max_len: usize) -> Result<Vec<u8>> {
+    let len = u.int_in_range(0..=max_len.min(u.len()))?;
+
+    u.bytes(len).map(|bytes| bytes.to_vec())
+}
+
+fn arbitrary_address(u: &mut Unstructured) -> Result<Address> {
+    let bytes: [u8; 20] = u.arbitrary()?;
+
+    Ok(Address::from(bytes))
+}
+
+fn arbitrary_var_type(u: &mut Unstructured) -> Result<VarType> {
+    let types = [
+        VarType::Bool,
+        VarType::I8,
+        VarType::U8,
+        VarType::I16,
+        VarType::U16,
+        VarType::I32,
+        VarType::U32,
+        VarType::I64,
+        VarType::U64,
+        VarType::U128,
+        VarType::U256,
+        VarType::Amount,
+        VarType::Address,
+    ];
+
+    u.choose(&types).map(|ty| *ty)
+}
+
+/// Generates a `DataLayout` with an optional schema.
+///
+/// When there's a schema, its fields span exactly all the layout's variables
+/// (otherwise the decoder would reject it).
+fn arbitrary_layout(u: &mut Unstructured) -> Result<DataLayout> {
+    let nvars = u.int_in_range(0..=MAX_LAYOUT_VARS)?;
+
+    let mut builder = DataLayoutBuilder::with_capacity(nvars);
+
+    for _ in 0..nvars {
+        let len = u.int_in_range(0..=MAX_VARUINT14 as u32)?;
+
+        builder.add_var(len);
+    }
+
+    let has_schema: bool = u.arbitrary()?;
+
+    if has_schema {
+        let mut remaining = nvars as u32;
+
+        while remaining > 0 {
+            let name = arbitrary_name(u)?;
+            let ty = arbitrary_var_type(u)?;
+            let count = u.int_in_range(1..=remaining)?;
+
+            builder.add_symbolic_var(&name, ty, count);
+
+            remaining -= count;
+        }
+    }
+
+    Ok(builder.build())
+}
+
+/// An `AppTemplate` accepted by `encode_deploy_template`.
+#[derive(Debug)]
+pub struct ArbitraryTemplate(pub AppTemplate);
+
+impl<'a> Arbitrary<'a> for ArbitraryTemplate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let template = AppTemplate {
+            version: arbitrary_version(u, TxKind::DeployTemplate)?,
+            name: arbitrary_name(u)?,
+            code: arbitrary_bytes(u, u16::MAX as usize)?,
+            data: arbitrary_layout(u)?,
+        };
+
+        Ok(Self(template))
+    }
+}
+
+/// A `SpawnApp` accepted by `encode_spawn_app`.
+#[derive(Debug)]
+pub struct ArbitrarySpawnApp(pub SpawnApp);
+
+impl<'a> Arbitrary<'a> for ArbitrarySpawnApp {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let app = App {
+            version: arbitrary_version(u, TxKind::SpawnApp)?,
+            name: arbitrary_name(u)?,
+            template: TemplateAddr::new(arbitrary_address(u)?),
+        };
+
+        let spawn = SpawnApp {
+            app,
+            ctor_name: arbitrary_name(u)?,
+            calldata: arbitrary_calldata(u)?,
+        };
+
+        Ok(Self(spawn))
+    }
+}
+
+/// An `AppTransaction` accepted by `encode_exec_app`.
+#[derive(Debug)]
+pub struct ArbitraryTransaction(pub AppTransaction);
+
+impl<'a> Arbitrary<'a> for ArbitraryTransaction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let tx = AppTransaction {
+            version: arbitrary_version(u, TxKind::ExecApp)?,
+            app: AppAddr::new(arbitrary_address(u)?),
+            func_name: arbitrary_name(u)?,
+            calldata: arbitrary_calldata(u)?,
+        };
+
+        Ok(Self(tx))
+    }
+}
+
+/// Generates `calldata`. It's either ABI-encoded values or opaque bytes
+/// (the codec doesn't look into the `calldata`).
+fn arbitrary_calldata(u: &mut Unstructured) -> Result<Vec<u8>> {
+    let abi_encoded: bool = u.arbitrary()?;
+
+    if abi_encoded {
+        let values: Vec<ArbitraryValue> = u.arbitrary()?;
+        let calldata = encode_values(&values);
+
+        if calldata.len() > MAX_VARUINT14 {
+            return Err(Error::IncorrectFormat);
+        }
+
+        Ok(calldata)
+    } else {
+        arbitrary_bytes(u, MAX_VARUINT14)
+    }
+}
+
+/// ABI-encodes `values` one after the other.
+pub fn encode_values(values: &[ArbitraryValue]) -> Vec<u8> {
+    use svm_abi_encoder::Encoder;
+
+    let mut buf = Vec::new();
+
+    for value in values.iter() {
+        value.0.encode(&mut buf);
+    }
+
+    buf
+}
+
+/// An ABI `Value` (a `Primitive`, `Bytes`, a `String` or a possibly nested `Array` / `Tuple`).
+///
+/// Arrays are always generated as `Composite::ArrayOwned` since that's what the decoder outputs.
+#[derive(Debug, PartialEq)]
+pub struct ArbitraryValue(pub Value<'static>);
+
+impl<'a> Arbitrary<'a> for ArbitraryValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_value(u, 0).map(Self)
+    }
+}
+
+fn arbitrary_value(u: &mut Unstructured, depth: usize) -> Result<Value<'static>> {
+    let is_nested = depth < MAX_ARRAY_DEPTH && u.ratio(1, 4)?;
+
+    if is_nested {
+        let nitems = u.int_in_range(0..=MAX_ARRAY_ITEMS.min(u.len()))?;
+
+        let items = (0..nitems)
+            .map(|_| arbitrary_value(u, depth + 1))
+            .collect::<Result<Vec<_>>>()?;
+
+        let is_tuple: bool = u.arbitrary()?;
+
+        let comp = if is_tuple {
+            Composite::Tuple(items)
+        } else {
+            Composite::ArrayOwned(items)
+        };
+
+        return Ok(Value::Composite(comp));
+    }
+
+    match u.int_in_range(0..=7)? {
+        0 => {
+            let bytes = arbitrary_bytes(u, MAX_BLOB_LEN)?;
+
+            Ok(Value::Composite(Composite::Bytes(bytes)))
+        }
+        1 => {
+            let s: String = u.arbitrary()?;
+
+            if s.len() > MAX_BLOB_LEN {
+                return Err(Error::IncorrectFormat);
+            }
+
+            Ok(Value::Composite(Composite::String(s)))
+        }
+        _ => arbitrary_primitive(u).map(Value::Primitive),
+    }
+}
+
+fn arbitrary_primitive(u: &mut Unstructured) -> Result<Primitive> {
+    let prim = match u.int_in_range(0..=13)? {
+        0 => Primitive::None,
+        1 => Primitive::Bool(u.arbitrary()?),
+        2 => {
+            let bytes: [u8; 20] = u.arbitrary()?;
+
+            Primitive::Address(bytes.into())
+        }
+        3 => Primitive::Amount(svm_sdk_types::Amount(u.arbitrary()?)),
+        4 => Primitive::I8(u.arbitrary()?),
+        5 => Primitive::U8(u.arbitrary()?),
+        6 => Primitive::I16(u.arbitrary()?),
+        7 => Primitive::U16(u.arbitrary()?),
+        8 => Primitive::I32(u.arbitrary()?),
+        9 => Primitive::U32(u.arbitrary()?),
+        10 => Primitive::I64(u.arbitrary()?),
+        11 => Primitive::U64(u.arbitrary()?),
+        12 => Primitive::U128(svm_sdk_types::U128(u.arbitrary()?)),
+        _ => {
+            let bytes: [u8; 32] = u.arbitrary()?;
+
+            Primitive::U256(svm_sdk_types::U256::from_be_bytes(bytes))
+        }
+    };
+
+    Ok(prim)
+}